@@ -0,0 +1,18 @@
+#!/bin/bash
+# Claude Code hook script: tell AMF an agent session is up and ready for
+# input, so prompts queued while it launched can be sent.
+
+INPUT=$(cat)
+
+SESSION_ID="${AMF_SESSION:-$(echo "$INPUT" | jq -r '.session_id // empty' 2>/dev/null)}"
+CWD=$(echo "$INPUT" | jq -r '.cwd // empty' 2>/dev/null)
+
+if [ -z "$SESSION_ID" ]; then
+    exit 0
+fi
+
+MSG="{\"type\":\"session-start\",\"session_id\":\"$SESSION_ID\",\"cwd\":\"$CWD\"}"
+
+if command -v amf >/dev/null 2>&1; then
+    echo "$MSG" | amf notify 2>/dev/null
+fi
@@ -1,32 +1,294 @@
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use anyhow::{Result, bail};
 
 use super::*;
 use crate::automation::{
-    AutomationHookPrompt, BatchFeatureAutomationResult, CreateBatchFeaturesRequest,
-    CreateBatchFeaturesResponse, CreateFeatureRequest, CreateFeatureResponse, CreateProjectRequest,
-    CreateProjectResponse,
+    ARCHIVE_FEATURE_ACTION, AddSessionRequest, AddSessionResponse, AutomationError,
+    AutomationErrorCode, AutomationHookPrompt, BatchFeatureAutomationResult, BatchFeatureSpec,
+    CreateBatchFeaturesRequest, CreateBatchFeaturesResponse, CreateFeatureRequest,
+    CreateFeatureResponse, CreateProjectRequest, CreateProjectResponse, DELETE_FEATURE_ACTION,
+    FeatureLifecycleRequest, FeatureLifecycleResponse, FeatureState, ForkFeatureRequest,
+    ForkFeatureResponse, HelloRequest, HelloResponse, InputDecision, LandFeatureRequest,
+    LandFeatureResponse, ListInputsRequest, ListInputsResponse, ListStateRequest,
+    ListStateResponse, PendingInputDetail, PendingInputState, ProjectState, RENAME_FEATURE_ACTION,
+    RESTORE_FEATURE_ACTION, ReadDiffRequest, ReadDiffResponse, RenameFeatureRequest,
+    ResolveInputRequest, ResolveInputResponse, RestackFeatureRequest, RestackFeatureResponse,
+    RestackedFeature, SET_FEATURE_READY_ACTION, SET_FEATURE_TAGS_ACTION, START_FEATURE_ACTION,
+    STOP_FEATURE_ACTION, SendPromptRequest, SendPromptResponse, SessionState,
+    SetFeatureReadyRequest, SetFeatureTagsRequest, WaitForIdleRequest, WaitForIdleResponse,
+    WaitOutcome,
 };
-use crate::extension::merge_project_extension_config;
+use crate::extension::{HookConfig, merge_project_extension_config};
+use crate::project::FeatureRepo;
+
+fn is_review_notification(notification_type: &str) -> bool {
+    matches!(notification_type, "diff-review" | "change-reason")
+}
+
+fn is_agent_session(kind: &SessionKind) -> bool {
+    matches!(
+        kind,
+        SessionKind::Claude | SessionKind::Opencode | SessionKind::Codex | SessionKind::Pi
+    )
+}
+
+/// Whether an input raised for `feature` belongs to `session`. Inputs whose
+/// hook session id names none of the feature's sessions apply to all of them.
+fn input_targets_session(feature: &Feature, session: &FeatureSession, input_session: &str) -> bool {
+    if session.claude_session_id.as_deref() == Some(input_session) {
+        return true;
+    }
+    !feature
+        .sessions
+        .iter()
+        .any(|s| s.claude_session_id.as_deref() == Some(input_session))
+}
+
+/// A resolved `create_batch_features` entry: the reply row plus the
+/// settings that are only needed while creating the feature.
+struct BatchFeaturePlan {
+    result: BatchFeatureAutomationResult,
+    review: bool,
+    plan_mode: bool,
+    enable_chrome: bool,
+    prompt: Option<String>,
+}
+
+/// A `wait_for_idle` request parked until its feature goes idle. The
+/// reply is sent from the main loop, so the dashboard keeps running.
+pub(crate) struct AutomationWait {
+    pub(crate) request: WaitForIdleRequest,
+    pub(crate) request_id: Option<String>,
+    pub(crate) reply_socket: Option<String>,
+    started_at: Instant,
+    idle_since: Option<Instant>,
+}
+
+impl AutomationWait {
+    pub(crate) fn new(
+        request: WaitForIdleRequest,
+        request_id: Option<String>,
+        reply_socket: Option<String>,
+        now: Instant,
+    ) -> Self {
+        Self {
+            request,
+            request_id,
+            reply_socket,
+            started_at: now,
+            idle_since: None,
+        }
+    }
+}
+
+/// How long an agent without a startup hook gets to come up before a
+/// queued prompt is typed into it.
+const AGENT_STARTUP_DELAY: Duration = Duration::from_secs(5);
+
+/// How long a prompt waits for Claude's `SessionStart` hook before it is
+/// dropped.
+const QUEUED_PROMPT_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// A prompt held back until the agent it is meant for has started. Typing
+/// it any earlier would land in the shell or the agent's startup screen.
+pub(crate) struct QueuedPrompt {
+    pub(crate) tmux_session: String,
+    tmux_window: String,
+    workdir: PathBuf,
+    prompt: String,
+    /// Claude reports that it is ready through the `SessionStart` hook;
+    /// other agents get `AGENT_STARTUP_DELAY` instead.
+    waits_for_hook: bool,
+    queued_at: Instant,
+}
 
 impl App {
-    fn planned_batch_feature_results(
+    fn plan_batch_features(
+        &self,
         request: &CreateBatchFeaturesRequest,
         project_repo: &Path,
-    ) -> Vec<BatchFeatureAutomationResult> {
-        (1..=request.feature_count)
-            .map(|i| {
-                let branch = format!("{}{}", request.feature_prefix, i);
-                BatchFeatureAutomationResult {
+    ) -> Result<Vec<BatchFeaturePlan>> {
+        let specs = if request.features.is_empty() {
+            vec![BatchFeatureSpec::default(); request.feature_count]
+        } else {
+            request.features.clone()
+        };
+        let presets = self.allowed_feature_presets_for_repo(project_repo);
+
+        let mut plans: Vec<BatchFeaturePlan> = Vec::with_capacity(specs.len());
+        for (i, spec) in specs.into_iter().enumerate() {
+            let preset = match spec.preset.as_deref() {
+                Some(name) => Some(presets.iter().find(|p| p.name == name).ok_or_else(|| {
+                    AutomationError::validation(format!("Unknown feature preset '{}'", name))
+                })?),
+                None => None,
+            };
+
+            let branch = match spec.branch.as_deref().map(str::trim) {
+                Some("") => bail!(AutomationError::validation(format!(
+                    "Branch name for feature {} cannot be empty",
+                    i + 1
+                ))),
+                Some(branch) => branch.to_string(),
+                None => {
+                    let prefix = preset
+                        .and_then(|p| p.branch_prefix.as_deref())
+                        .unwrap_or(&request.feature_prefix);
+                    format!("{}{}", prefix, i + 1)
+                }
+            };
+            if plans.iter().any(|plan| plan.result.branch == branch) {
+                bail!(AutomationError::validation(format!(
+                    "Branch '{}' appears more than once in the batch",
+                    branch
+                )));
+            }
+
+            let agent = spec
+                .agent
+                .or_else(|| preset.map(|p| p.agent.clone()))
+                .unwrap_or_else(|| request.agent.clone());
+            let mode = spec
+                .mode
+                .or_else(|| preset.map(|p| p.mode.clone()))
+                .unwrap_or_else(|| request.mode.clone());
+            if !self.allows_agent_for_repo(project_repo, &agent) {
+                bail!(AutomationError::validation(format!(
+                    "Harness '{}' is not allowed for this workspace",
+                    agent.display_name()
+                )));
+            }
+            self.ensure_agent_mode_supported(&agent, &mode)?;
+
+            let base_ref = spec
+                .base_ref
+                .map(|base| base.trim().to_string())
+                .filter(|base| !base.is_empty());
+            let prompt = spec
+                .prompt
+                .map(|prompt| prompt.trim().to_string())
+                .filter(|prompt| !prompt.is_empty());
+
+            plans.push(BatchFeaturePlan {
+                result: BatchFeatureAutomationResult {
                     name: branch.clone(),
                     branch: branch.clone(),
                     workdir: project_repo.join(".worktrees").join(&branch),
                     tmux_session: format!("amf-{}", branch),
+                    agent,
+                    mode,
+                    preset: preset.map(|p| p.name.clone()),
+                    base_ref,
                     started: !request.dry_run,
-                }
-            })
-            .collect()
+                    prompt_sent: false,
+                    prompt_queued: false,
+                },
+                review: spec
+                    .review
+                    .or_else(|| preset.map(|p| p.review))
+                    .unwrap_or(request.review),
+                plan_mode: preset.is_some_and(|p| p.plan_mode),
+                enable_chrome: preset.map_or(request.enable_chrome, |p| p.enable_chrome),
+                prompt,
+            });
+        }
+        Ok(plans)
+    }
+
+    /// Paste a prompt into an agent window and submit it, keeping a copy
+    /// in `.claude/latest-prompt.txt` like the steering prompt does.
+    fn deliver_prompt(
+        &mut self,
+        workdir: &Path,
+        session: &str,
+        window: &str,
+        prompt: &str,
+    ) -> Result<()> {
+        self.persist_startup_prompt(workdir, prompt);
+        self.tmux.paste_text(session, window, prompt)?;
+        self.tmux.send_key_name(session, window, "Enter")
+    }
+
+    /// Hold `prompt` for a feature whose agent was just launched until
+    /// `deliver_queued_prompts` or `poll_queued_prompts` can send it.
+    fn queue_prompt(&mut self, pi: usize, fi: usize, window: &str, prompt: &str, now: Instant) {
+        let feature = &self.store.projects[pi].features[fi];
+        let waits_for_hook = feature
+            .sessions
+            .iter()
+            .find(|session| session.tmux_window == window)
+            .is_some_and(|session| session.kind == SessionKind::Claude);
+        self.queued_prompts.push(QueuedPrompt {
+            tmux_session: feature.tmux_session.clone(),
+            tmux_window: window.to_string(),
+            workdir: feature.workdir.clone(),
+            prompt: prompt.to_string(),
+            waits_for_hook,
+            queued_at: now,
+        });
+    }
+
+    /// Send every prompt queued for `tmux_session`, whose agent has just
+    /// reported that it is ready.
+    pub(crate) fn deliver_queued_prompts(&mut self, tmux_session: &str) {
+        let (ready, waiting) = std::mem::take(&mut self.queued_prompts)
+            .into_iter()
+            .partition(|queued| queued.tmux_session == tmux_session);
+        self.queued_prompts = waiting;
+        for queued in ready {
+            self.deliver_queued_prompt(queued);
+        }
+    }
+
+    /// Send queued prompts to agents without a startup hook once they have
+    /// had time to come up, and drop those whose agent never reported in.
+    pub(crate) fn poll_queued_prompts(&mut self, now: Instant) {
+        if self.queued_prompts.is_empty() {
+            return;
+        }
+        let mut due = Vec::new();
+        let mut expired = Vec::new();
+        for queued in std::mem::take(&mut self.queued_prompts) {
+            let waited = now.saturating_duration_since(queued.queued_at);
+            if !queued.waits_for_hook && waited >= AGENT_STARTUP_DELAY {
+                due.push(queued);
+            } else if waited >= QUEUED_PROMPT_TIMEOUT {
+                expired.push(queued);
+            } else {
+                self.queued_prompts.push(queued);
+            }
+        }
+        for queued in due {
+            self.deliver_queued_prompt(queued);
+        }
+        for queued in expired {
+            self.log_error(
+                "automation",
+                format!(
+                    "Dropped prompt for {}:{}; the agent never reported that it started",
+                    queued.tmux_session, queued.tmux_window
+                ),
+            );
+        }
+    }
+
+    fn deliver_queued_prompt(&mut self, queued: QueuedPrompt) {
+        if let Err(err) = self.deliver_prompt(
+            &queued.workdir,
+            &queued.tmux_session,
+            &queued.tmux_window,
+            &queued.prompt,
+        ) {
+            self.log_error(
+                "automation",
+                format!(
+                    "Failed to send queued prompt to {}:{}: {err}",
+                    queued.tmux_session, queued.tmux_window
+                ),
+            );
+        }
     }
 
     fn resolve_sync_hook_script(script: &str) -> String {
@@ -71,6 +333,55 @@ impl App {
         }
     }
 
+    /// Run the `on_worktree_created` hook for an automation request in the
+    /// new worktree. Returns `None` when no hook is configured, otherwise
+    /// whether the hook succeeded.
+    fn run_worktree_created_hook_for_request(
+        &mut self,
+        project_repo: &Path,
+        workdir: &Path,
+        feature_name: &str,
+        hook_choice: Option<&str>,
+    ) -> Result<Option<bool>> {
+        let ext = merge_project_extension_config(&self.config.extension, project_repo);
+        let Some(ref hook_cfg) = ext.lifecycle_hooks.on_worktree_created else {
+            return Ok(None);
+        };
+
+        let choice = match hook_cfg.prompt() {
+            Some(prompt_cfg) => {
+                let choice = hook_choice.ok_or_else(|| {
+                    AutomationError::validation(format!(
+                        "Worktree hook requires a choice; provide `hook_choice` from [{}]",
+                        prompt_cfg.options.join(", ")
+                    ))
+                })?;
+                if !prompt_cfg.options.iter().any(|option| option == choice) {
+                    bail!(AutomationError::validation(format!(
+                        "Invalid hook_choice '{}'; expected one of [{}]",
+                        choice,
+                        prompt_cfg.options.join(", ")
+                    )));
+                }
+                Some(choice)
+            }
+            None => None,
+        };
+
+        let (success, detail) = Self::run_worktree_hook_sync(hook_cfg.script(), workdir, choice);
+        if !success {
+            self.log_warn(
+                "automation",
+                format!(
+                    "Worktree hook failed for feature '{}': {}",
+                    feature_name,
+                    detail.unwrap_or_else(|| "unknown error".to_string())
+                ),
+            );
+        }
+        Ok(Some(success))
+    }
+
     fn worktree_hook_prompt_for_repo(&self, project_repo: &Path) -> Option<AutomationHookPrompt> {
         let ext = merge_project_extension_config(&self.config.extension, project_repo);
         ext.lifecycle_hooks
@@ -83,24 +394,31 @@ impl App {
             })
     }
 
+    pub fn hello_from_request(&self, _request: &HelloRequest) -> Result<HelloResponse> {
+        Ok(HelloResponse::new(self.headless))
+    }
+
     pub fn create_project_from_request(
         &mut self,
         request: &CreateProjectRequest,
     ) -> Result<CreateProjectResponse> {
         if request.project_name.trim().is_empty() {
-            bail!("Project name cannot be empty");
+            bail!(AutomationError::validation("Project name cannot be empty"));
         }
 
         if request.path.as_os_str().is_empty() {
-            bail!("Path cannot be empty");
+            bail!(AutomationError::validation("Path cannot be empty"));
         }
 
         if !request.path.exists() {
-            bail!("Path does not exist: {}", request.path.display());
+            bail!(AutomationError::validation(format!(
+                "Path does not exist: {}",
+                request.path.display()
+            )));
         }
 
         if self.store.find_project(&request.project_name).is_some() {
-            bail!("Project '{}' already exists", request.project_name);
+            bail!(AutomationError::project_exists(&request.project_name));
         }
 
         let (project_path, is_git) = match self.worktree.repo_root(&request.path) {
@@ -147,12 +465,16 @@ impl App {
         request: &CreateFeatureRequest,
     ) -> Result<CreateFeatureResponse> {
         if request.project_name.trim().is_empty() {
-            bail!("Project name cannot be empty");
+            bail!(AutomationError::validation("Project name cannot be empty"));
         }
 
         if request.branch.trim().is_empty() {
-            bail!("Branch name cannot be empty");
+            bail!(AutomationError::validation("Branch name cannot be empty"));
         }
+        let prompt = match request.prompt.as_deref().map(str::trim) {
+            Some("") => bail!(AutomationError::validation("Prompt cannot be empty")),
+            prompt => prompt,
+        };
 
         let (
             project_repo,
@@ -160,38 +482,74 @@ impl App {
             has_non_worktree_feature,
             has_any_features,
             project_collapsed,
+            parent,
         ) = {
             let project = self
                 .store
                 .find_project(&request.project_name)
-                .ok_or_else(|| anyhow::anyhow!("Project '{}' not found", request.project_name))?;
+                .ok_or_else(|| AutomationError::project_not_found(&request.project_name))?;
 
             if project.features.iter().any(|f| f.name == request.branch) {
-                bail!(
-                    "Feature '{}' already exists in '{}'",
-                    request.branch,
-                    request.project_name
-                );
+                bail!(AutomationError::new(
+                    AutomationErrorCode::BranchExists,
+                    format!(
+                        "Feature '{}' already exists in '{}'",
+                        request.branch, request.project_name
+                    ),
+                ));
             }
 
+            let parent = match request.parent_feature.as_deref() {
+                Some(name) => {
+                    let parent = project
+                        .features
+                        .iter()
+                        .find(|f| f.name == name)
+                        .ok_or_else(|| {
+                            AutomationError::feature_not_found(&request.project_name, name)
+                        })?;
+                    Some((parent.id.clone(), parent.branch.clone()))
+                }
+                None => None,
+            };
+
             (
                 project.repo.clone(),
                 project.is_git,
                 project.features.iter().any(|f| !f.is_worktree),
                 !project.features.is_empty(),
                 project.collapsed,
+                parent,
             )
         };
 
         if !self.allows_agent_for_repo(&project_repo, &request.agent) {
-            bail!(
+            bail!(AutomationError::validation(format!(
                 "Harness '{}' is not allowed for this workspace",
                 request.agent.display_name()
-            );
+            )));
         }
         self.ensure_agent_mode_supported(&request.agent, &request.mode)?;
 
-        let use_worktree = request.use_worktree.unwrap_or(has_any_features);
+        let multi_repo = !request.extra_projects.is_empty();
+        let use_worktree = request
+            .use_worktree
+            .unwrap_or(has_any_features || parent.is_some() || multi_repo);
+        if parent.is_some() && !use_worktree {
+            bail!(AutomationError::validation(
+                "Stacked features require a worktree",
+            ));
+        }
+        if multi_repo && !use_worktree {
+            bail!(AutomationError::validation(
+                "Multi-repo features require worktrees",
+            ));
+        }
+        if multi_repo && parent.is_some() {
+            bail!(AutomationError::validation(
+                "Stacked features cannot span several repositories",
+            ));
+        }
         let is_git = stored_is_git || self.worktree.repo_root(&project_repo).is_ok();
 
         if is_git && !stored_is_git {
@@ -202,11 +560,16 @@ impl App {
         }
 
         if use_worktree && !is_git {
-            bail!("Worktrees require a git repository");
+            bail!(AutomationError::new(
+                AutomationErrorCode::NotGit,
+                "Worktrees require a git repository",
+            ));
         }
 
         if !use_worktree && has_non_worktree_feature {
-            bail!("Only one non-worktree feature allowed per project");
+            bail!(AutomationError::validation(
+                "Only one non-worktree feature allowed per project",
+            ));
         }
 
         let hook_prompt = if use_worktree {
@@ -219,19 +582,27 @@ impl App {
         let workdir = if use_worktree {
             let planned_workdir = project_repo.join(".worktrees").join(&request.branch);
             if planned_workdir.exists() {
-                bail!(
-                    "Worktree path already exists: {}",
-                    planned_workdir.display()
-                );
+                bail!(AutomationError::new(
+                    AutomationErrorCode::BranchExists,
+                    format!(
+                        "Worktree path already exists: {}",
+                        planned_workdir.display()
+                    ),
+                ));
             }
             planned_workdir
         } else {
             project_repo.clone()
         };
+        let repos = if multi_repo {
+            self.plan_feature_repos(&request.project_name, &request.extra_projects, &workdir)?
+        } else {
+            Vec::new()
+        };
 
         if request.dry_run {
             let message = format!("Dry run: would create feature '{}'", request.branch);
-            return Ok(CreateFeatureResponse::success(
+            let mut response = CreateFeatureResponse::success(
                 request,
                 workdir,
                 use_worktree,
@@ -241,67 +612,61 @@ impl App {
                 hook_succeeded,
                 hook_prompt.clone(),
                 message,
-            ));
+            );
+            response.repos = repos.iter().map(|repo| repo.workdir.clone()).collect();
+            return Ok(response);
         }
 
-        let final_workdir = if use_worktree {
-            let workdir = self
-                .worktree
-                .create(&project_repo, &request.branch, &request.branch)?;
-
-            let ext = merge_project_extension_config(&self.config.extension, &project_repo);
-            if let Some(ref hook_cfg) = ext.lifecycle_hooks.on_worktree_created {
-                hook_ran = true;
-                let prompt = hook_cfg.prompt();
-                if let Some(prompt_cfg) = prompt {
-                    let choice = request.hook_choice.as_deref().ok_or_else(|| {
-                        anyhow::anyhow!(
-                            "Worktree hook requires a choice; provide `hook_choice` from [{}]",
-                            prompt_cfg.options.join(", ")
-                        )
-                    })?;
-                    if !prompt_cfg.options.iter().any(|option| option == choice) {
-                        bail!(
-                            "Invalid hook_choice '{}'; expected one of [{}]",
-                            choice,
-                            prompt_cfg.options.join(", ")
-                        );
-                    }
-                    let (success, detail) =
-                        Self::run_worktree_hook_sync(hook_cfg.script(), &workdir, Some(choice));
-                    hook_succeeded = Some(success);
-                    if !success {
-                        self.log_warn(
-                            "automation",
-                            format!(
-                                "Worktree hook failed for feature '{}': {}",
-                                request.branch,
-                                detail.unwrap_or_else(|| "unknown error".to_string())
-                            ),
-                        );
-                    }
-                } else {
-                    let (success, detail) =
-                        Self::run_worktree_hook_sync(hook_cfg.script(), &workdir, None);
-                    hook_succeeded = Some(success);
-                    if !success {
-                        self.log_warn(
-                            "automation",
-                            format!(
-                                "Worktree hook failed for feature '{}': {}",
-                                request.branch,
-                                detail.unwrap_or_else(|| "unknown error".to_string())
-                            ),
-                        );
-                    }
-                }
+        let final_workdir = if multi_repo {
+            self.create_feature_repo_worktrees(&repos, &request.branch)?;
+            let mut results = Vec::new();
+            for member in &repos {
+                results.push(self.run_worktree_created_hook_for_request(
+                    &member.repo,
+                    &member.workdir,
+                    &request.branch,
+                    request.hook_choice.as_deref(),
+                )?);
             }
+            hook_succeeded = results.into_iter().flatten().reduce(|all, ok| all && ok);
+            hook_ran = hook_succeeded.is_some();
+
+            workdir
+        } else if use_worktree {
+            let workdir = match &parent {
+                Some((_, parent_branch)) => self.worktree.create_from(
+                    &project_repo,
+                    &request.branch,
+                    &request.branch,
+                    parent_branch,
+                )?,
+                None => self
+                    .worktree
+                    .create(&project_repo, &request.branch, &request.branch)?,
+            };
+
+            hook_succeeded = self.run_worktree_created_hook_for_request(
+                &project_repo,
+                &workdir,
+                &request.branch,
+                request.hook_choice.as_deref(),
+            )?;
+            hook_ran = hook_succeeded.is_some();
 
             workdir
         } else {
             project_repo.clone()
         };
 
+        if let Some(success) = hook_succeeded {
+            self.emit_hook_completed(
+                &request.project_name,
+                &request.branch,
+                "on_worktree_created",
+                success,
+            );
+        }
+
         let feature = Feature::new(
             request.branch.clone(),
             request.branch.clone(),
@@ -314,6 +679,9 @@ impl App {
             request.enable_chrome,
         );
         let mut feature = feature;
+        feature.parent_id = parent.map(|(id, _)| id);
+        feature.repos = repos;
+        let member_workdirs = feature.member_workdirs();
         Self::initialize_feature_sessions(&mut feature, request.create_terminal);
 
         self.store.add_feature(&request.project_name, feature);
@@ -335,6 +703,15 @@ impl App {
         self.ensure_feature_running(pi, fi)?;
         self.save()?;
 
+        let mut prompt_queued = false;
+        if let Some(prompt) = prompt {
+            let window = Self::automation_agent_window(&self.store.projects[pi].features[fi])?;
+            // The agent has only just been launched, so the prompt waits
+            // until it is ready to read it.
+            self.queue_prompt(pi, fi, &window, prompt, Instant::now());
+            prompt_queued = true;
+        }
+
         let message = match hook_succeeded {
             Some(true) => format!(
                 "Created and started feature '{}' (hook succeeded)",
@@ -347,7 +724,7 @@ impl App {
             None => format!("Created and started feature '{}'", request.branch),
         };
 
-        Ok(CreateFeatureResponse::success(
+        let mut response = CreateFeatureResponse::success(
             request,
             final_workdir,
             use_worktree,
@@ -357,7 +734,79 @@ impl App {
             hook_succeeded,
             hook_prompt,
             message,
-        ))
+        );
+        response.repos = member_workdirs;
+        response.prompt_queued = prompt_queued;
+        Ok(response)
+    }
+
+    /// Resolve the member repos of a multi-repo feature: the owning project
+    /// first, then `extra_projects`, each with a worktree directory named
+    /// after its project under `parent_dir`.
+    fn plan_feature_repos(
+        &self,
+        project_name: &str,
+        extra_projects: &[String],
+        parent_dir: &Path,
+    ) -> Result<Vec<FeatureRepo>> {
+        let mut names = vec![project_name.to_string()];
+        for name in extra_projects {
+            let name = name.trim();
+            if names.iter().any(|taken| taken == name) {
+                bail!(AutomationError::validation(format!(
+                    "Project '{}' is listed more than once",
+                    name
+                )));
+            }
+            names.push(name.to_string());
+        }
+
+        let mut repos = Vec::new();
+        for name in names {
+            let (project_id, repo, stored_is_git) = {
+                let project = self
+                    .store
+                    .find_project(&name)
+                    .ok_or_else(|| AutomationError::project_not_found(&name))?;
+                (project.id.clone(), project.repo.clone(), project.is_git)
+            };
+            if !stored_is_git && self.worktree.repo_root(&repo).is_err() {
+                bail!(AutomationError::new(
+                    AutomationErrorCode::NotGit,
+                    format!("Project '{}' is not a git repository", name),
+                ));
+            }
+            let workdir = parent_dir.join(&name);
+            if workdir.exists() {
+                bail!(AutomationError::new(
+                    AutomationErrorCode::BranchExists,
+                    format!("Worktree path already exists: {}", workdir.display()),
+                ));
+            }
+            repos.push(FeatureRepo {
+                project_id,
+                repo,
+                workdir,
+            });
+        }
+        Ok(repos)
+    }
+
+    /// Create every member worktree on `branch`, removing the ones already
+    /// made if a later one fails.
+    fn create_feature_repo_worktrees(&self, repos: &[FeatureRepo], branch: &str) -> Result<()> {
+        for (i, member) in repos.iter().enumerate() {
+            if let Err(err) = self
+                .worktree
+                .create_at(&member.repo, &member.workdir, branch)
+            {
+                for created in &repos[..i] {
+                    let _ = self.worktree.remove(&created.repo, &created.workdir);
+                }
+                return Err(err);
+            }
+        }
+        Ok(())
     }
 
     pub fn create_batch_features_from_request(
@@ -365,23 +814,27 @@ impl App {
         request: &CreateBatchFeaturesRequest,
     ) -> Result<CreateBatchFeaturesResponse> {
         if request.workspace_path.as_os_str().is_empty() || !request.workspace_path.exists() {
-            bail!("Workspace path is invalid");
+            bail!(AutomationError::validation("Workspace path is invalid"));
         }
 
         if request.project_name.trim().is_empty() {
-            bail!("Project name cannot be empty");
+            bail!(AutomationError::validation("Project name cannot be empty"));
         }
 
         if self.store.find_project(&request.project_name).is_some() {
-            bail!("Project '{}' already exists", request.project_name);
+            bail!(AutomationError::project_exists(&request.project_name));
         }
 
-        if request.feature_count == 0 {
-            bail!("Feature count must be at least 1");
+        if request.features.is_empty() && request.feature_count == 0 {
+            bail!(AutomationError::validation(
+                "Feature count must be at least 1"
+            ));
         }
 
         if request.feature_prefix.trim().is_empty() {
-            bail!("Feature prefix cannot be empty");
+            bail!(AutomationError::validation(
+                "Feature prefix cannot be empty"
+            ));
         }
 
         let (project_repo, is_git) = match self.worktree.repo_root(&request.workspace_path) {
@@ -390,36 +843,35 @@ impl App {
         };
 
         if !is_git {
-            bail!("Batch features require a git repository");
-        }
-
-        if !self.allows_agent_for_repo(&project_repo, &request.agent) {
-            bail!(
-                "Harness '{}' is not allowed for this workspace",
-                request.agent.display_name()
-            );
+            bail!(AutomationError::new(
+                AutomationErrorCode::NotGit,
+                "Batch features require a git repository",
+            ));
         }
-        self.ensure_agent_mode_supported(&request.agent, &request.mode)?;
 
-        let planned_features = Self::planned_batch_feature_results(request, &project_repo);
-        for feature in &planned_features {
-            if feature.workdir.exists() {
-                bail!(
-                    "Worktree path already exists: {}",
-                    feature.workdir.display()
-                );
+        let plans = self.plan_batch_features(request, &project_repo)?;
+        for plan in &plans {
+            if plan.result.workdir.exists() {
+                bail!(AutomationError::new(
+                    AutomationErrorCode::BranchExists,
+                    format!(
+                        "Worktree path already exists: {}",
+                        plan.result.workdir.display()
+                    ),
+                ));
             }
         }
 
         if request.dry_run {
             let message = format!(
                 "Dry run: would create project '{}' with {} features",
-                request.project_name, request.feature_count
+                request.project_name,
+                plans.len()
             );
             return Ok(CreateBatchFeaturesResponse::success(
                 request,
                 project_repo,
-                planned_features,
+                plans.into_iter().map(|plan| plan.result).collect(),
                 message,
             ));
         }
@@ -434,50 +886,68 @@ impl App {
         self.save()?;
 
         let pi = self.store.projects.len().saturating_sub(1);
-        let mut created_feature_names = Vec::new();
-        let mut response_features = Vec::new();
+        let mut created = Vec::new();
 
-        for planned in planned_features {
-            let workdir = self
-                .worktree
-                .create(&project_repo, &planned.branch, &planned.branch)?;
+        for mut plan in plans {
+            let workdir = match plan.result.base_ref.as_deref() {
+                Some(base) => self.worktree.create_from(
+                    &project_repo,
+                    &plan.result.branch,
+                    &plan.result.branch,
+                    base,
+                )?,
+                None => {
+                    self.worktree
+                        .create(&project_repo, &plan.result.branch, &plan.result.branch)?
+                }
+            };
 
             let feature = Feature::new(
-                planned.name.clone(),
-                planned.branch.clone(),
+                plan.result.name.clone(),
+                plan.result.branch.clone(),
                 workdir.clone(),
                 true,
-                request.mode.clone(),
-                request.review,
-                false,
-                request.agent.clone(),
-                request.enable_chrome,
+                plan.result.mode.clone(),
+                plan.review,
+                plan.plan_mode,
+                plan.result.agent.clone(),
+                plan.enable_chrome,
             );
 
             self.store.add_feature(&request.project_name, feature);
-            created_feature_names.push(planned.name.clone());
-            response_features.push(BatchFeatureAutomationResult {
-                workdir,
-                started: true,
-                ..planned
-            });
+            plan.result.workdir = workdir;
+            created.push(plan);
             self.save()?;
         }
 
-        for branch in &created_feature_names {
+        let mut response_features = Vec::new();
+        for plan in created {
+            let mut result = plan.result;
             let fi = self.store.projects[pi]
                 .features
                 .iter()
-                .position(|f| f.name == *branch)
-                .ok_or_else(|| anyhow::anyhow!("Feature '{}' missing after creation", branch))?;
+                .position(|f| f.name == result.name)
+                .ok_or_else(|| {
+                    anyhow::anyhow!("Feature '{}' missing after creation", result.name)
+                })?;
 
             self.ensure_feature_running(pi, fi)?;
             self.save()?;
+
+            if let Some(prompt) = plan.prompt {
+                let window = Self::automation_agent_window(&self.store.projects[pi].features[fi])?;
+                // The agent has only just been launched, so the prompt
+                // waits until it is ready to read it.
+                self.queue_prompt(pi, fi, &window, &prompt, Instant::now());
+                result.prompt_queued = true;
+            }
+            response_features.push(result);
         }
 
         let message = format!(
             "Created project '{}' with {} features",
-            request.project_name, request.feature_count
+            request.project_name,
+            response_features.len()
         );
 
         Ok(CreateBatchFeaturesResponse::success(
@@ -487,4 +957,1329 @@ impl App {
             message,
         ))
     }
+
+    /// Resolve a project/feature pair named in an automation request.
+    pub(crate) fn automation_feature_indices(
+        &self,
+        project_name: &str,
+        feature_name: &str,
+    ) -> Result<(usize, usize)> {
+        let pi = self
+            .store
+            .projects
+            .iter()
+            .position(|p| p.name == project_name)
+            .ok_or_else(|| AutomationError::project_not_found(project_name))?;
+        let fi = self.store.projects[pi]
+            .features
+            .iter()
+            .position(|f| f.name == feature_name)
+            .ok_or_else(|| AutomationError::feature_not_found(project_name, feature_name))?;
+        Ok((pi, fi))
+    }
+
+    /// The tmux window of the feature's agent session.
+    fn automation_agent_window(feature: &Feature) -> Result<String> {
+        Self::get_agent_window(feature).ok_or_else(|| {
+            AutomationError::new(
+                AutomationErrorCode::SessionNotFound,
+                format!(
+                    "Feature '{}' has no {} session",
+                    feature.name,
+                    feature.agent.display_name()
+                ),
+            )
+            .into()
+        })
+    }
+
+    /// Resolve a `session` selector (session id, label, or tmux window) to
+    /// one of the feature's sessions.
+    fn automation_session<'a>(feature: &'a Feature, selector: &str) -> Result<&'a FeatureSession> {
+        feature
+            .sessions
+            .iter()
+            .find(|s| s.id == selector || s.label == selector || s.tmux_window == selector)
+            .ok_or_else(|| {
+                AutomationError::new(
+                    AutomationErrorCode::SessionNotFound,
+                    format!(
+                        "Session '{}' not found in feature '{}'",
+                        selector, feature.name
+                    ),
+                )
+                .into()
+            })
+    }
+
+    pub fn send_prompt_from_request(
+        &mut self,
+        request: &SendPromptRequest,
+    ) -> Result<SendPromptResponse> {
+        let prompt = request.prompt.trim();
+        if prompt.is_empty() {
+            bail!(AutomationError::validation("Prompt cannot be empty"));
+        }
+
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        let feature = &self.store.projects[pi].features[fi];
+        let tmux_session = feature.tmux_session.clone();
+        let workdir = feature.workdir.clone();
+        let tmux_window = match request.session.as_deref() {
+            Some(selector) => Self::automation_session(feature, selector)?
+                .tmux_window
+                .clone(),
+            None => Self::automation_agent_window(feature)?,
+        };
+
+        let running = self.tmux.session_exists(&tmux_session);
+        if !running && !request.start {
+            bail!(AutomationError::invalid_state(format!(
+                "Feature '{}' is not running; set `start` to launch it first",
+                request.feature_name
+            )));
+        }
+
+        if request.dry_run {
+            let message = format!(
+                "Dry run: would send prompt to {}:{}",
+                tmux_session, tmux_window
+            );
+            return Ok(SendPromptResponse::success(
+                request,
+                tmux_session,
+                tmux_window,
+                false,
+                false,
+                message,
+            ));
+        }
+
+        if !running {
+            self.ensure_feature_running(pi, fi)?;
+            self.save()?;
+            self.queue_prompt(pi, fi, &tmux_window, prompt, Instant::now());
+            let message = format!(
+                "Started {} and queued the prompt for {} until the agent is ready",
+                tmux_session, tmux_window
+            );
+            return Ok(SendPromptResponse::success(
+                request,
+                tmux_session,
+                tmux_window,
+                true,
+                false,
+                message,
+            ));
+        }
+
+        self.deliver_prompt(&workdir, &tmux_session, &tmux_window, prompt)?;
+
+        let message = format!("Sent prompt to {}:{}", tmux_session, tmux_window);
+        Ok(SendPromptResponse::success(
+            request,
+            tmux_session,
+            tmux_window,
+            false,
+            true,
+            message,
+        ))
+    }
+
+    /// Check `hook_choice` against a prompted lifecycle hook. Plain script
+    /// hooks ignore the choice.
+    fn lifecycle_hook_choice<'a>(
+        hook: &HookConfig,
+        choice: Option<&'a str>,
+    ) -> Result<Option<&'a str>> {
+        let Some(prompt) = hook.prompt() else {
+            return Ok(None);
+        };
+        let choice = choice.ok_or_else(|| {
+            AutomationError::validation(format!(
+                "Lifecycle hook requires a choice; provide `hook_choice` from [{}]",
+                prompt.options.join(", ")
+            ))
+        })?;
+        if !prompt.options.iter().any(|option| option == choice) {
+            bail!(AutomationError::validation(format!(
+                "Invalid hook_choice '{}'; expected one of [{}]",
+                choice,
+                prompt.options.join(", ")
+            )));
+        }
+        Ok(Some(choice))
+    }
+
+    fn lifecycle_response(
+        &self,
+        action: &'static str,
+        pi: usize,
+        fi: usize,
+        dry_run: bool,
+        hook_prompt: Option<AutomationHookPrompt>,
+        message: String,
+    ) -> FeatureLifecycleResponse {
+        let project = &self.store.projects[pi];
+        FeatureLifecycleResponse::success(
+            action,
+            &project.name,
+            &project.features[fi],
+            dry_run,
+            hook_prompt,
+            message,
+        )
+    }
+
+    pub fn start_feature_from_request(
+        &mut self,
+        request: &FeatureLifecycleRequest,
+    ) -> Result<FeatureLifecycleResponse> {
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        let repo = self.store.projects[pi].repo.clone();
+        let feature = &self.store.projects[pi].features[fi];
+        if feature.pending_worktree_script {
+            bail!(AutomationError::invalid_state(format!(
+                "Feature '{}' is still running its worktree setup script",
+                feature.name
+            )));
+        }
+        if feature.is_archived() {
+            bail!(AutomationError::invalid_state(format!(
+                "Feature '{}' is archived; restore it first",
+                feature.name
+            )));
+        }
+        if feature.status != ProjectStatus::Stopped {
+            bail!(AutomationError::invalid_state(format!(
+                "Feature '{}' is already running",
+                feature.name
+            )));
+        }
+        let workdir = feature.workdir.clone();
+
+        let ext = merge_project_extension_config(&self.config.extension, &repo);
+        let on_start = ext.lifecycle_hooks.on_start;
+        let hook_prompt = on_start
+            .as_ref()
+            .and_then(|hook| hook.prompt())
+            .map(|prompt| AutomationHookPrompt {
+                title: prompt.title.clone(),
+                options: prompt.options.clone(),
+            });
+
+        if request.dry_run {
+            let message = format!("Dry run: would start feature '{}'", request.feature_name);
+            return Ok(self.lifecycle_response(
+                START_FEATURE_ACTION,
+                pi,
+                fi,
+                true,
+                hook_prompt,
+                message,
+            ));
+        }
+
+        let choice = match on_start.as_ref() {
+            Some(hook) => Self::lifecycle_hook_choice(hook, request.hook_choice.as_deref())?,
+            None => None,
+        };
+        self.do_start_feature(pi, fi)?;
+        if let Some(ref hook) = on_start {
+            self.run_lifecycle_hook(hook.script(), &workdir, choice);
+        }
+
+        let message = format!("Started feature '{}'", request.feature_name);
+        Ok(self.lifecycle_response(START_FEATURE_ACTION, pi, fi, false, hook_prompt, message))
+    }
+
+    pub fn stop_feature_from_request(
+        &mut self,
+        request: &FeatureLifecycleRequest,
+    ) -> Result<FeatureLifecycleResponse> {
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        let repo = self.store.projects[pi].repo.clone();
+        let feature = &self.store.projects[pi].features[fi];
+        if feature.pending_worktree_script {
+            bail!(AutomationError::invalid_state(format!(
+                "Feature '{}' is still running its worktree setup script",
+                feature.name
+            )));
+        }
+        if feature.status == ProjectStatus::Stopped {
+            bail!(AutomationError::invalid_state(format!(
+                "Feature '{}' is already stopped",
+                feature.name
+            )));
+        }
+        let workdir = feature.workdir.clone();
+
+        let ext = merge_project_extension_config(&self.config.extension, &repo);
+        let on_stop = ext.lifecycle_hooks.on_stop;
+        let hook_prompt = on_stop
+            .as_ref()
+            .and_then(|hook| hook.prompt())
+            .map(|prompt| AutomationHookPrompt {
+                title: prompt.title.clone(),
+                options: prompt.options.clone(),
+            });
+
+        if request.dry_run {
+            let message = format!("Dry run: would stop feature '{}'", request.feature_name);
+            return Ok(self.lifecycle_response(
+                STOP_FEATURE_ACTION,
+                pi,
+                fi,
+                true,
+                hook_prompt,
+                message,
+            ));
+        }
+
+        if let Some(ref hook) = on_stop {
+            let choice = Self::lifecycle_hook_choice(hook, request.hook_choice.as_deref())?;
+            self.run_lifecycle_hook(hook.script(), &workdir, choice);
+        }
+        self.do_stop_feature(pi, fi)?;
+
+        let message = format!("Stopped feature '{}'", request.feature_name);
+        Ok(self.lifecycle_response(STOP_FEATURE_ACTION, pi, fi, false, hook_prompt, message))
+    }
+
+    pub fn set_feature_ready_from_request(
+        &mut self,
+        request: &SetFeatureReadyRequest,
+    ) -> Result<FeatureLifecycleResponse> {
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        let verb = if request.ready { "ready" } else { "not ready" };
+
+        if request.dry_run {
+            let message = format!(
+                "Dry run: would mark feature '{}' as {}",
+                request.feature_name, verb
+            );
+            return Ok(self.lifecycle_response(
+                SET_FEATURE_READY_ACTION,
+                pi,
+                fi,
+                true,
+                None,
+                message,
+            ));
+        }
+
+        self.store.projects[pi].features[fi].ready = request.ready;
+        self.save()?;
+
+        let message = if request.ready {
+            let dirty = Self::uncommitted_member_repos(&self.store.projects[pi].features[fi]);
+            Self::ready_message(&request.feature_name, &dirty)
+        } else {
+            format!("Marked '{}' as {}", request.feature_name, verb)
+        };
+        Ok(self.lifecycle_response(SET_FEATURE_READY_ACTION, pi, fi, false, None, message))
+    }
+
+    pub fn rename_feature_from_request(
+        &mut self,
+        request: &RenameFeatureRequest,
+    ) -> Result<FeatureLifecycleResponse> {
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        let nickname = request
+            .nickname
+            .as_deref()
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string);
+
+        if request.dry_run {
+            let message = match nickname {
+                Some(ref name) => format!("Dry run: would rename to '{}'", name),
+                None => "Dry run: would clear nickname".to_string(),
+            };
+            return Ok(self.lifecycle_response(RENAME_FEATURE_ACTION, pi, fi, true, None, message));
+        }
+
+        let message = match nickname {
+            Some(ref name) => format!("Renamed to '{}'", name),
+            None => "Nickname cleared".to_string(),
+        };
+        self.store.projects[pi].features[fi].nickname = nickname;
+        self.save()?;
+
+        Ok(self.lifecycle_response(RENAME_FEATURE_ACTION, pi, fi, false, None, message))
+    }
+
+    pub fn set_feature_tags_from_request(
+        &mut self,
+        request: &SetFeatureTagsRequest,
+    ) -> Result<FeatureLifecycleResponse> {
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        let tags = crate::project::normalize_tags(&request.tags)
+            .map_err(|err| AutomationError::validation(err.to_string()))?;
+
+        let described = if tags.is_empty() {
+            "no tags".to_string()
+        } else {
+            tags.join(", ")
+        };
+        if request.dry_run {
+            let message = format!("Dry run: would set tags to {}", described);
+            return Ok(self.lifecycle_response(
+                SET_FEATURE_TAGS_ACTION,
+                pi,
+                fi,
+                true,
+                None,
+                message,
+            ));
+        }
+
+        self.store.projects[pi].features[fi].tags = tags;
+        self.save()?;
+
+        let message = format!("Set tags of '{}' to {}", request.feature_name, described);
+        Ok(self.lifecycle_response(SET_FEATURE_TAGS_ACTION, pi, fi, false, None, message))
+    }
+
+    pub fn archive_feature_from_request(
+        &mut self,
+        request: &FeatureLifecycleRequest,
+    ) -> Result<FeatureLifecycleResponse> {
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        if let Some(reason) = self.archive_blocker(pi, fi) {
+            bail!(AutomationError::invalid_state(reason));
+        }
+
+        if request.dry_run {
+            let feature = &self.store.projects[pi].features[fi];
+            let message = if feature.is_worktree {
+                format!(
+                    "Dry run: would archive feature '{}' and remove worktree {}",
+                    feature.name,
+                    feature.workdir.display()
+                )
+            } else {
+                format!("Dry run: would archive feature '{}'", feature.name)
+            };
+            return Ok(self.lifecycle_response(
+                ARCHIVE_FEATURE_ACTION,
+                pi,
+                fi,
+                true,
+                None,
+                message,
+            ));
+        }
+
+        self.archive_feature(pi, fi)?;
+
+        let message = format!("Archived feature '{}'", request.feature_name);
+        Ok(self.lifecycle_response(ARCHIVE_FEATURE_ACTION, pi, fi, false, None, message))
+    }
+
+    pub fn restore_feature_from_request(
+        &mut self,
+        request: &FeatureLifecycleRequest,
+    ) -> Result<FeatureLifecycleResponse> {
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        if !self.store.projects[pi].features[fi].is_archived() {
+            bail!(AutomationError::invalid_state(format!(
+                "Feature '{}' is not archived",
+                request.feature_name
+            )));
+        }
+
+        if request.dry_run {
+            let message = format!(
+                "Dry run: would restore feature '{}' from branch '{}'",
+                request.feature_name, self.store.projects[pi].features[fi].branch
+            );
+            return Ok(self.lifecycle_response(
+                RESTORE_FEATURE_ACTION,
+                pi,
+                fi,
+                true,
+                None,
+                message,
+            ));
+        }
+
+        self.restore_feature(pi, fi)?;
+
+        let message = format!("Restored feature '{}'", request.feature_name);
+        Ok(self.lifecycle_response(RESTORE_FEATURE_ACTION, pi, fi, false, None, message))
+    }
+
+    /// Start deleting a feature in the background. The tmux session is
+    /// killed and the worktree removed by `poll_background_deletions`; the
+    /// feature disappears from the store once that finishes.
+    pub fn delete_feature_from_request(
+        &mut self,
+        request: &FeatureLifecycleRequest,
+    ) -> Result<FeatureLifecycleResponse> {
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        let deleting_in_foreground = matches!(
+            &self.mode,
+            AppMode::DeletingFeatureInProgress(state)
+                if state.project_name == request.project_name
+                    && state.feature_name == request.feature_name
+        );
+        if deleting_in_foreground
+            || self.is_feature_being_deleted(&request.project_name, &request.feature_name)
+        {
+            bail!(AutomationError::invalid_state(format!(
+                "Feature '{}' is already being deleted",
+                request.feature_name
+            )));
+        }
+
+        if request.dry_run {
+            let feature = &self.store.projects[pi].features[fi];
+            let message = if feature.is_worktree {
+                format!(
+                    "Dry run: would delete feature '{}' and remove worktree {}",
+                    feature.name,
+                    feature.workdir.display()
+                )
+            } else {
+                format!("Dry run: would delete feature '{}'", feature.name)
+            };
+            return Ok(self.lifecycle_response(DELETE_FEATURE_ACTION, pi, fi, true, None, message));
+        }
+
+        let response = self.lifecycle_response(
+            DELETE_FEATURE_ACTION,
+            pi,
+            fi,
+            false,
+            None,
+            format!(
+                "Deleting feature '{}' in the background",
+                request.feature_name
+            ),
+        );
+        if let Some(state) =
+            self.begin_feature_deletion(&request.project_name, &request.feature_name)?
+        {
+            self.background_deletions.insert(state.key(), state);
+        }
+
+        Ok(response)
+    }
+
+    pub fn fork_feature_from_request(
+        &mut self,
+        request: &ForkFeatureRequest,
+    ) -> Result<ForkFeatureResponse> {
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        let project = &self.store.projects[pi];
+        let source = &project.features[fi];
+        let project_repo = project.repo.clone();
+        let source_id = source.id.clone();
+        let source_branch = source.branch.clone();
+        let source_workdir = source.workdir.clone();
+
+        let branch = match request.branch.as_deref().map(str::trim) {
+            Some("") => bail!(AutomationError::validation("Branch name cannot be empty")),
+            Some(branch) => branch.to_string(),
+            None => format!("{}-fork", source_branch),
+        };
+        if project.features.iter().any(|f| f.name == branch) {
+            bail!(AutomationError::new(
+                AutomationErrorCode::BranchExists,
+                format!(
+                    "Feature '{}' already exists in '{}'",
+                    branch, request.project_name
+                ),
+            ));
+        }
+        if !project.is_git {
+            bail!(AutomationError::new(
+                AutomationErrorCode::NotGit,
+                "Forking requires a git repository",
+            ));
+        }
+        if source.is_multi_repo() {
+            bail!(AutomationError::invalid_state(
+                "Multi-repo features cannot be forked",
+            ));
+        }
+
+        let agent = match &request.agent {
+            Some(agent) => agent.clone(),
+            None => {
+                self.normalize_agent_for_repo(&project_repo, &source.agent)
+                    .0
+            }
+        };
+        let mode = request.mode.clone().unwrap_or_else(|| source.mode.clone());
+        let review = request.review.unwrap_or(source.review);
+        let enable_chrome = request.enable_chrome.unwrap_or(source.enable_chrome);
+
+        if !self.allows_agent_for_repo(&project_repo, &agent) {
+            bail!(AutomationError::validation(format!(
+                "Harness '{}' is not allowed for this workspace",
+                agent.display_name()
+            )));
+        }
+        self.ensure_agent_mode_supported(&agent, &mode)?;
+
+        let planned_workdir = project_repo.join(".worktrees").join(&branch);
+        if planned_workdir.exists() {
+            bail!(AutomationError::new(
+                AutomationErrorCode::BranchExists,
+                format!(
+                    "Worktree path already exists: {}",
+                    planned_workdir.display()
+                ),
+            ));
+        }
+        let hook_prompt = self.worktree_hook_prompt_for_repo(&project_repo);
+
+        if request.dry_run {
+            let message = format!("Dry run: would fork '{}' -> '{}'", source_branch, branch);
+            return Ok(ForkFeatureResponse::success(
+                request,
+                source_branch,
+                branch,
+                planned_workdir,
+                agent,
+                mode,
+                false,
+                false,
+                false,
+                None,
+                hook_prompt,
+                message,
+            ));
+        }
+
+        let workdir = self
+            .worktree
+            .create_from(&project_repo, &branch, &branch, &source_branch)?;
+        let (copied, included_context) = Self::seed_forked_worktree(
+            &source_workdir,
+            &workdir,
+            request.copy_uncommitted_changes,
+            request.include_context,
+        );
+
+        let hook_succeeded = self.run_worktree_created_hook_for_request(
+            &project_repo,
+            &workdir,
+            &branch,
+            request.hook_choice.as_deref(),
+        )?;
+        if let Some(success) = hook_succeeded {
+            self.emit_hook_completed(
+                &request.project_name,
+                &branch,
+                "on_worktree_created",
+                success,
+            );
+        }
+
+        let mut feature = Feature::new(
+            branch.clone(),
+            branch.clone(),
+            workdir.clone(),
+            true,
+            mode.clone(),
+            review,
+            false,
+            agent.clone(),
+            enable_chrome,
+        );
+        feature.parent_id = Some(source_id);
+        Self::initialize_feature_sessions(&mut feature, false);
+        self.store.add_feature(&request.project_name, feature);
+        self.save()?;
+
+        let fi = self.store.projects[pi].features.len().saturating_sub(1);
+        self.ensure_feature_running(pi, fi)?;
+        self.save()?;
+
+        let message = format!("Forked '{}' -> '{}'", source_branch, branch);
+        Ok(ForkFeatureResponse::success(
+            request,
+            source_branch,
+            branch,
+            workdir,
+            agent,
+            mode,
+            true,
+            copied,
+            included_context,
+            hook_succeeded,
+            hook_prompt,
+            message,
+        ))
+    }
+
+    pub fn add_session_from_request(
+        &mut self,
+        request: &AddSessionRequest,
+    ) -> Result<AddSessionResponse> {
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        let repo = self.store.projects[pi].repo.clone();
+        let feature = &self.store.projects[pi].features[fi];
+        if feature.is_archived() {
+            bail!(AutomationError::invalid_state(format!(
+                "Feature '{}' is archived; restore it first",
+                feature.name
+            )));
+        }
+        let running = self.tmux.session_exists(&feature.tmux_session);
+        let feature_workdir = feature.workdir.clone();
+
+        let custom = match (&request.kind, request.custom.as_deref()) {
+            (Some(_), Some(_)) | (None, None) => {
+                bail!(AutomationError::validation(
+                    "Set exactly one of `kind` or `custom`"
+                ));
+            }
+            (Some(kind), None) => {
+                match kind {
+                    SessionKind::Terminal | SessionKind::Nvim => {}
+                    SessionKind::Claude | SessionKind::Opencode | SessionKind::Codex => {
+                        let agent =
+                            super::session_ops::agent_for_session_kind(kind).unwrap_or_default();
+                        if !self.allows_agent_for_repo(&repo, &agent) {
+                            bail!(AutomationError::validation(format!(
+                                "Harness '{}' is not allowed for this workspace",
+                                agent.display_name()
+                            )));
+                        }
+                    }
+                    _ => bail!(AutomationError::validation(format!(
+                        "Session kind '{}' cannot be added over automation",
+                        serde_json::to_value(kind)
+                            .ok()
+                            .and_then(|v| v.as_str().map(str::to_string))
+                            .unwrap_or_default()
+                    ))),
+                }
+                if !running {
+                    bail!(AutomationError::invalid_state(format!(
+                        "Feature '{}' is not running; start it before adding a built-in session",
+                        request.feature_name
+                    )));
+                }
+                None
+            }
+            (None, Some(name)) => {
+                let config = self
+                    .extension_for_repo(&repo)
+                    .custom_sessions
+                    .into_iter()
+                    .find(|config| config.name == name)
+                    .ok_or_else(|| {
+                        AutomationError::validation(format!("Unknown custom session '{}'", name))
+                    })?;
+                let check_dir = config
+                    .working_dir
+                    .as_ref()
+                    .map(|rel| feature_workdir.join(rel))
+                    .unwrap_or_else(|| feature_workdir.clone());
+                if let Err(reason) = config.run_pre_check(&check_dir) {
+                    bail!(AutomationError::invalid_state(format!(
+                        "{}: {}",
+                        config.name, reason
+                    )));
+                }
+                Some(config)
+            }
+        };
+        let kind = request.kind.clone().unwrap_or(SessionKind::Custom);
+
+        if request.dry_run {
+            let label = match &custom {
+                Some(config) => config.name.clone(),
+                None => self.store.projects[pi].features[fi].next_label(&kind),
+            };
+            let message = format!(
+                "Dry run: would add '{}' to '{}'",
+                label, request.feature_name
+            );
+            return Ok(AddSessionResponse::success(
+                request, kind, None, label, None, false, message,
+            ));
+        }
+
+        let launched = match &custom {
+            Some(config) => {
+                self.add_custom_session_type(pi, fi, config)?;
+                running
+            }
+            None => {
+                self.add_builtin_session(pi, fi, kind.clone())?;
+                true
+            }
+        };
+
+        let session = self.store.projects[pi].features[fi]
+            .sessions
+            .last()
+            .ok_or_else(|| anyhow::anyhow!("Session missing after add"))?;
+        let message = format!("Added '{}' to '{}'", session.label, request.feature_name);
+        Ok(AddSessionResponse::success(
+            request,
+            kind,
+            Some(session.id.clone()),
+            session.label.clone(),
+            Some(session.tmux_window.clone()),
+            launched,
+            message,
+        ))
+    }
+
+    /// The project, feature, and optional session a `wait_for_idle`
+    /// request waits on.
+    pub(crate) fn wait_for_idle_target(
+        &self,
+        request: &WaitForIdleRequest,
+    ) -> Result<(&Project, &Feature, Option<&FeatureSession>)> {
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        let project = &self.store.projects[pi];
+        let feature = &project.features[fi];
+        let session = request
+            .session
+            .as_deref()
+            .map(|selector| Self::automation_session(feature, selector))
+            .transpose()?;
+        Ok((project, feature, session))
+    }
+
+    /// Check a parked `wait_for_idle` request. Returns the reply once the
+    /// wait is over, or `None` to keep waiting.
+    pub(crate) fn poll_wait_for_idle(
+        &self,
+        wait: &mut AutomationWait,
+        now: Instant,
+    ) -> Option<Result<WaitForIdleResponse>> {
+        let request = &wait.request;
+        let (project, feature, session) = match self.wait_for_idle_target(request) {
+            Ok(target) => target,
+            Err(err) => return Some(Err(err)),
+        };
+        let state = self.feature_state(project, feature);
+        let waited = now.saturating_duration_since(wait.started_at);
+
+        // Thinking is tracked per tmux session, so a single session only
+        // counts as thinking when it runs an agent. Input requests raised by
+        // another session's hooks do not end its wait.
+        let (input_requested, thinking) = match session {
+            None => (!state.pending_inputs.is_empty(), state.thinking),
+            Some(session) => (
+                self.automation_inputs().iter().any(|input| {
+                    input.project_name.as_deref() == Some(project.name.as_str())
+                        && input.feature_name.as_deref() == Some(feature.name.as_str())
+                        && input_targets_session(feature, session, &input.session_id)
+                }),
+                state.thinking && is_agent_session(&session.kind),
+            ),
+        };
+
+        let outcome = if input_requested {
+            Some(WaitOutcome::InputRequested)
+        } else if feature.status == ProjectStatus::Stopped {
+            Some(WaitOutcome::Stopped)
+        } else if thinking {
+            wait.idle_since = None;
+            None
+        } else {
+            let idle_since = *wait.idle_since.get_or_insert(now);
+            (now.saturating_duration_since(idle_since) >= Duration::from_millis(request.settle_ms))
+                .then_some(WaitOutcome::Idle)
+        };
+        let outcome = outcome.or_else(|| {
+            (waited >= Duration::from_millis(request.timeout_ms)).then_some(WaitOutcome::TimedOut)
+        })?;
+
+        Some(Ok(WaitForIdleResponse::success(
+            request,
+            outcome,
+            waited.as_millis() as u64,
+            state,
+        )))
+    }
+
+    pub fn read_diff_from_request(&self, request: &ReadDiffRequest) -> Result<ReadDiffResponse> {
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        let project = &self.store.projects[pi];
+        if !project.is_git {
+            bail!(AutomationError::new(
+                AutomationErrorCode::NotGit,
+                "Reading a diff requires a git repository",
+            ));
+        }
+
+        let feature = &project.features[fi];
+        let parent_branch = project
+            .parent_of(feature)
+            .map(|parent| parent.branch.as_str());
+        let snapshot = crate::diff::load_workspace_snapshot(
+            &feature.workdir,
+            &feature.member_workdirs(),
+            parent_branch,
+        )?;
+        Ok(ReadDiffResponse::success(request, snapshot))
+    }
+
+    pub fn restack_feature_from_request(
+        &mut self,
+        request: &RestackFeatureRequest,
+    ) -> Result<RestackFeatureResponse> {
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        if !self.store.projects[pi].is_git {
+            bail!(AutomationError::new(
+                AutomationErrorCode::NotGit,
+                "Restacking requires a git repository",
+            ));
+        }
+
+        let below = self.stack_below(pi, fi);
+        let planned: Vec<(usize, RestackedFeature)> = {
+            let features = &self.store.projects[pi].features;
+            below
+                .iter()
+                .map(|&(child, parent)| {
+                    (
+                        child,
+                        RestackedFeature {
+                            feature_name: features[child].name.clone(),
+                            branch: features[child].branch.clone(),
+                            onto: features[parent].branch.clone(),
+                        },
+                    )
+                })
+                .collect()
+        };
+
+        if request.dry_run {
+            let planned: Vec<_> = planned.into_iter().map(|(_, entry)| entry).collect();
+            let message = format!(
+                "Dry run: would restack {} feature(s) onto '{}'",
+                planned.len(),
+                request.feature_name
+            );
+            return Ok(RestackFeatureResponse::success(request, planned, message));
+        }
+
+        let done = self.restack_feature(pi, fi)?;
+        let restacked: Vec<_> = planned
+            .into_iter()
+            .filter(|(child, _)| done.contains(child))
+            .map(|(_, entry)| entry)
+            .collect();
+        let message = if restacked.is_empty() {
+            format!("No features are stacked on '{}'", request.feature_name)
+        } else {
+            format!(
+                "Restacked {} feature(s) onto '{}'",
+                restacked.len(),
+                request.feature_name
+            )
+        };
+        Ok(RestackFeatureResponse::success(request, restacked, message))
+    }
+
+    pub fn land_feature_from_request(
+        &mut self,
+        request: &LandFeatureRequest,
+    ) -> Result<LandFeatureResponse> {
+        let (pi, fi) =
+            self.automation_feature_indices(&request.project_name, &request.feature_name)?;
+        if !self.store.projects[pi].is_git {
+            bail!(AutomationError::new(
+                AutomationErrorCode::NotGit,
+                "Landing requires a git repository",
+            ));
+        }
+        if let Some(reason) = self.land_blocker(pi, fi) {
+            bail!(AutomationError::invalid_state(reason));
+        }
+
+        let preview = self.land_preview(pi, fi)?;
+        if request.dry_run {
+            let message = if preview.conflicts.is_empty() {
+                format!(
+                    "Dry run: '{}' merges cleanly into '{}'",
+                    preview.branch, preview.target
+                )
+            } else {
+                format!(
+                    "Dry run: '{}' conflicts with '{}' in {} file(s)",
+                    preview.branch,
+                    preview.target,
+                    preview.conflicts.len()
+                )
+            };
+            return Ok(LandFeatureResponse::success(
+                request, &preview, None, message,
+            ));
+        }
+
+        if !self.store.projects[pi].features[fi].ready {
+            bail!(AutomationError::invalid_state(format!(
+                "Mark '{}' ready before landing it",
+                request.feature_name
+            )));
+        }
+        if !preview.conflicts.is_empty() {
+            bail!(AutomationError::invalid_state(format!(
+                "'{}' conflicts with '{}' in {}",
+                preview.branch,
+                preview.target,
+                preview.conflict_paths().join(", ")
+            )));
+        }
+
+        let landed = self.land_feature(pi, fi, request.strategy)?;
+        let message = format!("Landed '{}' on '{}'", request.feature_name, landed.target);
+        Ok(LandFeatureResponse::success(
+            request,
+            &preview,
+            Some(landed.commit),
+            message,
+        ))
+    }
+
+    pub fn list_inputs_from_request(
+        &self,
+        request: &ListInputsRequest,
+    ) -> Result<ListInputsResponse> {
+        match (
+            request.project_name.as_deref(),
+            request.feature_name.as_deref(),
+        ) {
+            (Some(project_name), Some(feature_name)) => {
+                self.automation_feature_indices(project_name, feature_name)?;
+            }
+            (Some(project_name), None) if self.store.find_project(project_name).is_none() => {
+                bail!(AutomationError::project_not_found(project_name));
+            }
+            _ => {}
+        }
+
+        let inputs = self
+            .automation_inputs()
+            .into_iter()
+            .filter(|input| {
+                request
+                    .project_name
+                    .as_ref()
+                    .is_none_or(|name| input.project_name.as_ref() == Some(name))
+                    && request
+                        .feature_name
+                        .as_ref()
+                        .is_none_or(|name| input.feature_name.as_ref() == Some(name))
+            })
+            .collect();
+        Ok(ListInputsResponse::success(inputs))
+    }
+
+    pub fn resolve_input_from_request(
+        &mut self,
+        request: &ResolveInputRequest,
+    ) -> Result<ResolveInputResponse> {
+        let Some(decision) = request.decision else {
+            bail!(AutomationError::validation(
+                "decision is required: approve, reject, or dismiss"
+            ));
+        };
+        let input_id = request.input_id.trim();
+        if input_id.is_empty() {
+            bail!(AutomationError::validation("input_id cannot be empty"));
+        }
+        let not_found = || {
+            AutomationError::new(
+                AutomationErrorCode::InputNotFound,
+                format!("No pending input with request_id '{}'", input_id),
+            )
+        };
+        let input = self
+            .automation_inputs()
+            .into_iter()
+            .find(|input| input.request_id.as_deref() == Some(input_id))
+            .ok_or_else(not_found)?;
+        if decision != InputDecision::Dismiss && !input.reviewable {
+            bail!(AutomationError::invalid_state(format!(
+                "'{}' is not a diff review and can only be dismissed",
+                input.notification_type
+            )));
+        }
+
+        let reason = request
+            .reason
+            .as_deref()
+            .map(str::trim)
+            .filter(|reason| !reason.is_empty() && decision != InputDecision::Dismiss);
+        let target = input
+            .relative_path
+            .clone()
+            .filter(|path| !path.is_empty())
+            .unwrap_or_else(|| input.notification_type.clone());
+        let (verb, done, review_decision) = match decision {
+            InputDecision::Approve => ("approve", "Approved", "proceed"),
+            InputDecision::Reject => ("reject", "Rejected", "reject"),
+            InputDecision::Dismiss => ("dismiss", "Dismissed", "cancel"),
+        };
+
+        if request.dry_run {
+            let message = format!("Dry run: would {} '{}'", verb, target);
+            return Ok(ResolveInputResponse::success(
+                request,
+                decision,
+                &input,
+                input.reviewable,
+                message,
+            ));
+        }
+
+        let response = review_response(review_decision, reason);
+        if input.open_in_dashboard {
+            // Another client may have closed or replaced the prompt since
+            // the input was listed.
+            let state = match std::mem::replace(&mut self.mode, AppMode::Normal) {
+                AppMode::DiffReviewPrompt(state)
+                    if state.request_id.as_deref() == Some(input_id) =>
+                {
+                    state
+                }
+                other => {
+                    self.mode = other;
+                    bail!(AutomationError::invalid_state(format!(
+                        "The review for '{}' is no longer open",
+                        target
+                    )));
+                }
+            };
+            self.send_review_response(
+                state.request_id.as_deref(),
+                state.reply_socket.as_deref(),
+                &state.response_file,
+                &state.proceed_signal,
+                &response,
+            );
+            self.emit_diff_review_decision(
+                &state.workdir,
+                &state.change_id,
+                &state.relative_path,
+                review_decision,
+                reason.map(str::to_string),
+            );
+            self.mode = match state.return_to_view {
+                Some(view) => AppMode::Viewing(view),
+                None => AppMode::Normal,
+            };
+        } else {
+            let idx = self
+                .pending_inputs
+                .iter()
+                .position(|pending| pending.request_id.as_deref() == Some(input_id))
+                .ok_or_else(not_found)?;
+            let pending = self.pending_inputs.remove(idx);
+            if !pending.file_path.as_os_str().is_empty() {
+                let _ = std::fs::remove_file(&pending.file_path);
+            }
+            if input.reviewable {
+                self.send_review_response(
+                    pending.request_id.as_deref(),
+                    pending.reply_socket.as_deref(),
+                    Path::new(pending.response_file.as_deref().unwrap_or_default()),
+                    Path::new(pending.proceed_signal.as_deref().unwrap_or_default()),
+                    &response,
+                );
+                self.emit_diff_review_decision(
+                    Path::new(&pending.cwd),
+                    pending.change_id.as_deref().unwrap_or_default(),
+                    pending.relative_path.as_deref().unwrap_or_default(),
+                    review_decision,
+                    reason.map(str::to_string),
+                );
+            }
+            if let AppMode::NotificationPicker(selected, _) = &mut self.mode {
+                *selected = (*selected).min(self.pending_inputs.len().saturating_sub(1));
+            }
+        }
+
+        self.log_info(
+            "automation",
+            format!("{} pending input '{}' ({})", done, input_id, target),
+        );
+        let message = format!("{} '{}'", done, target);
+        Ok(ResolveInputResponse::success(
+            request,
+            decision,
+            &input,
+            input.reviewable,
+            message,
+        ))
+    }
+
+    /// Every pending input, plus the diff review open in the prompt dialog,
+    /// which has already left `pending_inputs`.
+    fn automation_inputs(&self) -> Vec<PendingInputDetail> {
+        let mut inputs: Vec<PendingInputDetail> = self
+            .pending_inputs
+            .iter()
+            .map(|input| PendingInputDetail {
+                request_id: input.request_id.clone(),
+                project_name: input.project_name.clone(),
+                feature_name: input.feature_name.clone(),
+                notification_type: input.notification_type.clone(),
+                message: input.message.clone(),
+                session_id: input.session_id.clone(),
+                change_id: input.change_id.clone(),
+                tool: input.tool.clone(),
+                file_path: input.target_file_path.clone(),
+                relative_path: input.relative_path.clone(),
+                old_snippet: input.old_snippet.clone(),
+                new_snippet: input.new_snippet.clone(),
+                is_new_file: input.is_new_file,
+                reason: input.reason.clone(),
+                reviewable: is_review_notification(&input.notification_type),
+                open_in_dashboard: false,
+            })
+            .collect();
+
+        if let AppMode::DiffReviewPrompt(state) = &self.mode {
+            let (project_name, feature_name, _, _) = self.project_feature_for_cwd(&state.workdir);
+            let non_empty = |value: &str| Some(value.to_string()).filter(|v| !v.is_empty());
+            inputs.push(PendingInputDetail {
+                request_id: state.request_id.clone(),
+                project_name,
+                feature_name,
+                notification_type: "diff-review".to_string(),
+                message: format!("Review: {}", state.relative_path),
+                session_id: state.session_id.clone(),
+                change_id: non_empty(&state.change_id),
+                tool: non_empty(&state.tool),
+                file_path: non_empty(&state.file_path),
+                relative_path: non_empty(&state.relative_path),
+                old_snippet: non_empty(&state.old_snippet),
+                new_snippet: non_empty(&state.new_snippet),
+                is_new_file: None,
+                reason: None,
+                reviewable: true,
+                open_in_dashboard: true,
+            });
+        }
+        inputs
+    }
+
+    pub fn list_state_from_request(&self, request: &ListStateRequest) -> Result<ListStateResponse> {
+        if let Some(name) = request.project_name.as_deref()
+            && self.store.find_project(name).is_none()
+        {
+            bail!(AutomationError::project_not_found(name));
+        }
+
+        let projects = self
+            .store
+            .projects
+            .iter()
+            .filter(|project| {
+                request
+                    .project_name
+                    .as_deref()
+                    .is_none_or(|name| project.name == name)
+            })
+            .map(|project| ProjectState {
+                name: project.name.clone(),
+                repo: project.repo.clone(),
+                is_git: project.is_git,
+                preferred_agent: project.preferred_agent.clone(),
+                features: project
+                    .features
+                    .iter()
+                    .filter(|feature| {
+                        request
+                            .tag
+                            .as_deref()
+                            .is_none_or(|tag| feature.has_tag(tag))
+                    })
+                    .map(|feature| self.feature_state(project, feature))
+                    .collect(),
+            })
+            .collect();
+
+        Ok(ListStateResponse::success(projects))
+    }
+
+    fn feature_state(&self, project: &Project, feature: &Feature) -> FeatureState {
+        let project_name = project.name.as_str();
+        let pending_inputs = self
+            .automation_inputs()
+            .into_iter()
+            .filter(|input| {
+                input.project_name.as_deref() == Some(project_name)
+                    && input.feature_name.as_deref() == Some(feature.name.as_str())
+            })
+            .map(|input| PendingInputState {
+                notification_type: input.notification_type,
+                message: input.message,
+                request_id: input.request_id,
+                tool: input.tool,
+                relative_path: input.relative_path,
+            })
+            .collect();
+
+        let sessions = feature
+            .sessions
+            .iter()
+            .map(|session| SessionState {
+                id: session.id.clone(),
+                kind: session.kind.clone(),
+                label: session.label.clone(),
+                tmux_window: session.tmux_window.clone(),
+                claude_session_id: session.claude_session_id.clone(),
+                status_text: session.status_text.clone(),
+                token_usage: session
+                    .token_usage_source
+                    .as_ref()
+                    .and_then(|source| self.token_tracker.cached_usage(source)),
+            })
+            .collect();
+
+        FeatureState {
+            name: feature.name.clone(),
+            branch: feature.branch.clone(),
+            workdir: feature.workdir.clone(),
+            is_worktree: feature.is_worktree,
+            tmux_session: feature.tmux_session.clone(),
+            agent: feature.agent.clone(),
+            mode: feature.mode.clone(),
+            review: feature.review,
+            status: feature.status.clone(),
+            ready: feature.ready,
+            thinking: self.is_feature_thinking(&feature.tmux_session),
+            deleting: self.is_feature_being_deleted(project_name, &feature.name),
+            nickname: feature.nickname.clone(),
+            parent_feature: project.parent_of(feature).map(|parent| parent.name.clone()),
+            tags: feature.tags.clone(),
+            repos: feature.member_workdirs(),
+            archived_at: feature.archived_at,
+            summary: feature.summary.clone(),
+            summary_updated_at: feature.summary_updated_at,
+            pending_inputs,
+            sessions,
+        }
+    }
 }
@@ -0,0 +1,226 @@
+use super::*;
+use crate::checkpoint::Checkpoint;
+
+/// A finished background snapshot for one feature.
+pub struct CheckpointResult {
+    pub feature_id: String,
+    pub result: anyhow::Result<Option<Checkpoint>>,
+}
+
+impl App {
+    /// Snapshot feature `fi` of project `pi` into a checkpoint in a
+    /// background thread. Results are applied by [`App::poll_checkpoints`].
+    /// Features that are not plain git worktrees, or already being
+    /// snapshotted, are skipped.
+    pub fn checkpoint_feature_background(&mut self, pi: usize, fi: usize, reason: &str) {
+        let Some(project) = self.store.projects.get(pi) else {
+            return;
+        };
+        let Some(feature) = project.features.get(fi) else {
+            return;
+        };
+        if !project.is_git
+            || feature.is_archived()
+            || feature.is_multi_repo()
+            || !feature.workdir.exists()
+            || !self.checkpoints_inflight.insert(feature.id.clone())
+        {
+            return;
+        }
+
+        let feature_id = feature.id.clone();
+        let workdir = feature.workdir.clone();
+        let reason = reason.to_string();
+        let tx = self.checkpoint_tx.clone();
+        std::thread::spawn(move || {
+            let result = crate::checkpoint::snapshot(&workdir, &feature_id, &reason);
+            let _ = tx.send(CheckpointResult { feature_id, result });
+        });
+    }
+
+    /// Apply finished snapshots. Returns `true` when the open checkpoint
+    /// dialog gained an entry.
+    pub fn poll_checkpoints(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok(CheckpointResult { feature_id, result }) = self.checkpoint_rx.try_recv() {
+            self.checkpoints_inflight.remove(&feature_id);
+            match result {
+                Ok(Some(checkpoint)) => {
+                    self.log_debug(
+                        "checkpoint",
+                        format!(
+                            "Checkpoint #{} ({}) for feature {feature_id}",
+                            checkpoint.number, checkpoint.reason
+                        ),
+                    );
+                    if matches!(&self.mode, AppMode::ReviewingCheckpoints(state) if state.feature_id == feature_id)
+                    {
+                        self.reload_checkpoint_dialog();
+                        changed = true;
+                    }
+                }
+                Ok(None) => {}
+                Err(err) => self.log_warn(
+                    "checkpoint",
+                    format!("Checkpoint failed for feature {feature_id}: {err}"),
+                ),
+            }
+        }
+        changed
+    }
+
+    pub fn open_checkpoint_dialog(&mut self) {
+        let (pi, fi) = match &self.selection {
+            Selection::Feature(pi, fi) | Selection::Session(pi, fi, _) => (*pi, *fi),
+            _ => return,
+        };
+        let Some(project) = self.store.projects.get(pi) else {
+            return;
+        };
+        let Some(feature) = project.features.get(fi) else {
+            return;
+        };
+        if !project.is_git || feature.is_multi_repo() {
+            self.message = Some("Checkpoints need a single git worktree".into());
+            return;
+        }
+
+        self.mode = AppMode::ReviewingCheckpoints(CheckpointDialogState {
+            project_name: project.name.clone(),
+            feature_name: feature.name.clone(),
+            feature_id: feature.id.clone(),
+            workdir: feature.workdir.clone(),
+            checkpoints: Vec::new(),
+            selected: 0,
+        });
+        self.message = None;
+        self.reload_checkpoint_dialog();
+    }
+
+    pub fn checkpoint_dialog_select_next(&mut self) {
+        if let AppMode::ReviewingCheckpoints(state) = &mut self.mode
+            && !state.checkpoints.is_empty()
+        {
+            state.selected = (state.selected + 1) % state.checkpoints.len();
+        }
+    }
+
+    pub fn checkpoint_dialog_select_prev(&mut self) {
+        if let AppMode::ReviewingCheckpoints(state) = &mut self.mode
+            && !state.checkpoints.is_empty()
+        {
+            let count = state.checkpoints.len();
+            state.selected = (state.selected + count - 1) % count;
+        }
+    }
+
+    /// Show what changed in the worktree since the highlighted checkpoint.
+    /// Closing the viewer returns to the dialog.
+    pub fn open_checkpoint_diff(&mut self) {
+        let state = match &self.mode {
+            AppMode::ReviewingCheckpoints(state) => state.clone(),
+            _ => return,
+        };
+        let Some(checkpoint) = state.selected_checkpoint() else {
+            return;
+        };
+
+        let files = match crate::checkpoint::diff(&state.workdir, checkpoint) {
+            Ok(files) => files,
+            Err(err) => {
+                self.message = Some(format!("Error: {}", err));
+                return;
+            }
+        };
+        if files.is_empty() {
+            self.message = Some(format!(
+                "No changes since checkpoint #{}",
+                checkpoint.number
+            ));
+            return;
+        }
+        let mut viewer = DiffViewerState::new(None, state.workdir.clone());
+        viewer.branch = state.feature_name.clone();
+        viewer.base_ref = format!("checkpoint #{}", checkpoint.number);
+        viewer.base_commit = checkpoint.commit.clone();
+        viewer.files = files;
+        viewer.layout = self.preferred_diff_viewer_layout();
+        viewer.return_to = Some(DiffViewerReturn::Checkpoints(state));
+        self.mode = AppMode::DiffViewer(viewer);
+    }
+
+    /// Roll the worktree back to the highlighted checkpoint. The state it
+    /// replaces is checkpointed first, so the rollback can be undone.
+    pub fn restore_selected_checkpoint(&mut self) {
+        let (workdir, feature_id, checkpoint) = match &self.mode {
+            AppMode::ReviewingCheckpoints(state) => match state.selected_checkpoint() {
+                Some(checkpoint) => (
+                    state.workdir.clone(),
+                    state.feature_id.clone(),
+                    checkpoint.clone(),
+                ),
+                None => return,
+            },
+            _ => return,
+        };
+
+        self.message = Some(
+            match crate::checkpoint::restore(&workdir, &feature_id, &checkpoint) {
+                Ok(Some(saved)) => format!(
+                    "Restored checkpoint #{} (previous state saved as #{})",
+                    checkpoint.number, saved.number
+                ),
+                Ok(None) => format!("Restored checkpoint #{}", checkpoint.number),
+                Err(err) => format!("Error: {}", err),
+            },
+        );
+        self.reload_checkpoint_dialog();
+    }
+
+    /// Open the fork dialog seeded with the highlighted checkpoint.
+    pub fn fork_from_selected_checkpoint(&mut self) {
+        let (workdir, checkpoint) = match &self.mode {
+            AppMode::ReviewingCheckpoints(state) => match state.selected_checkpoint() {
+                Some(checkpoint) => (state.workdir.clone(), checkpoint.clone()),
+                None => return,
+            },
+            _ => return,
+        };
+        if let Err(err) = crate::checkpoint::base_commit(&workdir, &checkpoint) {
+            self.message = Some(format!("Error: {}", err));
+            return;
+        }
+
+        self.start_fork_feature();
+        if let AppMode::ForkingFeature(state) = &mut self.mode {
+            state.new_branch = format!("{}-checkpoint-{}", state.source_branch, checkpoint.number);
+            state.checkpoint = Some(checkpoint);
+        }
+    }
+
+    pub fn cancel_checkpoint_dialog(&mut self) {
+        self.mode = AppMode::Normal;
+    }
+
+    fn reload_checkpoint_dialog(&mut self) {
+        let AppMode::ReviewingCheckpoints(state) = &mut self.mode else {
+            return;
+        };
+        match crate::checkpoint::list(&state.workdir, &state.feature_id) {
+            Ok(mut checkpoints) => {
+                checkpoints.reverse();
+                let selected = state
+                    .selected_checkpoint()
+                    .and_then(|current| {
+                        checkpoints
+                            .iter()
+                            .position(|checkpoint| checkpoint.number == current.number)
+                    })
+                    .unwrap_or(0);
+                state.checkpoints = checkpoints;
+                state.selected = selected;
+            }
+            Err(err) => self.message = Some(format!("Error: {}", err)),
+        }
+    }
+}
@@ -34,7 +34,9 @@ impl App {
                     p.and_then(|p| p.features.get(*fi).map(|f| f.workdir.clone())),
                 )
             }
-            Selection::Project(pi) => (self.store.projects.get(*pi).map(|p| p.repo.clone()), None),
+            Selection::Project(pi) | Selection::ArchivedFeatures(pi) => {
+                (self.store.projects.get(*pi).map(|p| p.repo.clone()), None)
+            }
         };
 
         let mut commands = if self
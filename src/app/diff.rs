@@ -20,12 +20,14 @@ impl App {
     }
 
     pub fn open_diff_viewer(&mut self) -> Result<()> {
-        let Some((view, workdir)) = self.current_view_and_workdir() else {
+        let Some((view, workdir, members, parent_branch)) = self.current_view_and_workdir() else {
             self.message = Some("No active feature diff available".to_string());
             return Ok(());
         };
 
-        let mut state = DiffViewerState::new(view, workdir);
+        let mut state = DiffViewerState::new(Some(view), workdir);
+        state.members = members;
+        state.parent_branch = parent_branch;
         state.layout = self.preferred_diff_viewer_layout();
         self.populate_diff_viewer_state(&mut state);
         self.mode = AppMode::DiffViewer(state);
@@ -33,32 +35,55 @@ impl App {
     }
 
     pub fn close_diff_viewer(&mut self) {
-        let view = match std::mem::replace(&mut self.mode, AppMode::Normal) {
-            AppMode::DiffViewer(state) => state.from_view,
+        let state = match std::mem::replace(&mut self.mode, AppMode::Normal) {
+            AppMode::DiffViewer(state) => state,
             other => {
                 self.mode = other;
                 return;
             }
         };
-        self.mode = AppMode::Viewing(view);
+        self.mode = match (state.from_view, state.return_to) {
+            (Some(view), _) => AppMode::Viewing(view),
+            (None, Some(DiffViewerReturn::Land(land))) => AppMode::LandingFeature(land),
+            (None, Some(DiffViewerReturn::Overlaps(overlaps))) => {
+                AppMode::ReviewingOverlaps(overlaps)
+            }
+            (None, Some(DiffViewerReturn::Checkpoints(checkpoints))) => {
+                AppMode::ReviewingCheckpoints(checkpoints)
+            }
+            (None, None) => AppMode::Normal,
+        };
     }
 
     pub fn refresh_diff_viewer(&mut self) {
-        let Some((workdir, selected_path, selected_index)) = (match &self.mode {
-            AppMode::DiffViewer(state) => Some((
-                state.workdir.clone(),
-                state
-                    .files
-                    .get(state.selected_file)
-                    .map(|file| file.path.clone()),
-                state.selected_file,
-            )),
-            _ => None,
-        }) else {
+        if matches!(&self.mode, AppMode::DiffViewer(state) if state.staging.is_some()) {
+            self.reload_staging();
+            return;
+        }
+        let Some((workdir, members, parent_branch, selected_path, selected_index)) =
+            (match &self.mode {
+                // Land conflicts come from a trial merge that no longer
+                // exists, overlaps compare two worktrees, not a base, and
+                // checkpoint diffs are against a snapshot.
+                AppMode::DiffViewer(state) if state.return_to.is_some() => None,
+                AppMode::DiffViewer(state) => Some((
+                    state.workdir.clone(),
+                    state.members.clone(),
+                    state.parent_branch.clone(),
+                    state
+                        .files
+                        .get(state.selected_file)
+                        .map(|file| file.path.clone()),
+                    state.selected_file,
+                )),
+                _ => None,
+            })
+        else {
             return;
         };
 
-        let snapshot = crate::diff::load_snapshot(&workdir);
+        let snapshot =
+            crate::diff::load_workspace_snapshot(&workdir, &members, parent_branch.as_deref());
         if let AppMode::DiffViewer(state) = &mut self.mode {
             match snapshot {
                 Ok(snapshot) => {
@@ -91,6 +116,9 @@ impl App {
         {
             state.selected_file += 1;
             state.patch_scroll = 0;
+            if let Some(staging) = &mut state.staging {
+                *staging = StagingState::new(staging.side);
+            }
         }
     }
 
@@ -100,6 +128,9 @@ impl App {
         {
             state.selected_file -= 1;
             state.patch_scroll = 0;
+            if let Some(staging) = &mut state.staging {
+                *staging = StagingState::new(staging.side);
+            }
         }
     }
 
@@ -188,6 +219,10 @@ impl App {
         }
     }
 
+    pub fn diff_viewer_is_staging(&self) -> bool {
+        matches!(&self.mode, AppMode::DiffViewer(state) if state.staging.is_some())
+    }
+
     pub fn diff_viewer_selected_file_is_new(&self) -> bool {
         match &self.mode {
             AppMode::DiffViewer(state) => state
@@ -201,11 +236,13 @@ impl App {
 
     pub fn diff_viewer_layout(&self) -> Option<DiffViewerLayout> {
         match &self.mode {
-            AppMode::DiffViewer(state) => Some(if self.diff_viewer_selected_file_is_new() {
-                DiffViewerLayout::Unified
-            } else {
-                state.layout.clone()
-            }),
+            AppMode::DiffViewer(state) => Some(
+                if self.diff_viewer_selected_file_is_new() || state.staging.is_some() {
+                    DiffViewerLayout::Unified
+                } else {
+                    state.layout.clone()
+                },
+            ),
             _ => None,
         }
     }
@@ -247,30 +284,39 @@ impl App {
         Ok(())
     }
 
-    fn current_view_and_workdir(&self) -> Option<(ViewState, std::path::PathBuf)> {
+    #[allow(clippy::type_complexity)]
+    fn current_view_and_workdir(
+        &self,
+    ) -> Option<(ViewState, PathBuf, Vec<PathBuf>, Option<String>)> {
         let view = match &self.mode {
             AppMode::Viewing(view) => view.clone(),
             _ => return None,
         };
 
-        let workdir = self
+        let project = self
             .store
             .projects
             .iter()
-            .find(|project| project.name == view.project_name)
-            .and_then(|project| {
-                project
-                    .features
-                    .iter()
-                    .find(|feature| feature.name == view.feature_name)
-            })
-            .map(|feature| feature.workdir.clone())?;
-
-        Some((view, workdir))
+            .find(|project| project.name == view.project_name)?;
+        let feature = project
+            .features
+            .iter()
+            .find(|feature| feature.name == view.feature_name)?;
+        let workdir = feature.workdir.clone();
+        let members = feature.member_workdirs();
+        let parent_branch = project
+            .parent_of(feature)
+            .map(|parent| parent.branch.clone());
+
+        Some((view, workdir, members, parent_branch))
     }
 
     fn populate_diff_viewer_state(&self, state: &mut DiffViewerState) {
-        match crate::diff::load_snapshot(&state.workdir) {
+        match crate::diff::load_workspace_snapshot(
+            &state.workdir,
+            &state.members,
+            state.parent_branch.as_deref(),
+        ) {
             Ok(snapshot) => {
                 state.branch = snapshot.branch;
                 state.base_ref = snapshot.base_ref;
@@ -0,0 +1,199 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+
+use super::*;
+use crate::automation::AutomationEvent;
+
+type FeatureKey = (String, String);
+
+struct FeatureSnapshot {
+    status: ProjectStatus,
+    branch: String,
+    workdir: PathBuf,
+}
+
+/// The slice of dashboard state that `amf automation watch` reports on.
+/// Events are produced by diffing two snapshots, so every code path that
+/// changes statuses, thinking, or pending inputs is covered without having
+/// to emit from each one.
+pub(crate) struct EventSnapshot {
+    features: HashMap<FeatureKey, FeatureSnapshot>,
+    order: Vec<FeatureKey>,
+    thinking: HashSet<FeatureKey>,
+    pending_inputs: Vec<PendingInput>,
+}
+
+impl EventSnapshot {
+    pub(super) fn capture(app: &App) -> Self {
+        let mut features = HashMap::new();
+        let mut order = Vec::new();
+        let mut thinking = HashSet::new();
+        for project in &app.store.projects {
+            for feature in &project.features {
+                let key = (project.name.clone(), feature.name.clone());
+                if app.is_feature_thinking(&feature.tmux_session) {
+                    thinking.insert(key.clone());
+                }
+                features.insert(
+                    key.clone(),
+                    FeatureSnapshot {
+                        status: feature.status.clone(),
+                        branch: feature.branch.clone(),
+                        workdir: feature.workdir.clone(),
+                    },
+                );
+                order.push(key);
+            }
+        }
+        Self {
+            features,
+            order,
+            thinking,
+            pending_inputs: app.pending_inputs.clone(),
+        }
+    }
+
+    pub(super) fn diff(&self, next: &EventSnapshot) -> Vec<AutomationEvent> {
+        let mut events = Vec::new();
+
+        for key in &next.order {
+            let (project_name, feature_name) = key.clone();
+            let current = &next.features[key];
+            match self.features.get(key) {
+                None => events.push(AutomationEvent::FeatureCreated {
+                    project_name,
+                    feature_name,
+                    branch: current.branch.clone(),
+                    workdir: current.workdir.clone(),
+                }),
+                Some(previous) if previous.status != current.status => {
+                    events.push(AutomationEvent::FeatureStatusChanged {
+                        project_name,
+                        feature_name,
+                        from: previous.status.clone(),
+                        to: current.status.clone(),
+                    })
+                }
+                Some(_) => {}
+            }
+        }
+
+        for key in &next.order {
+            let (project_name, feature_name) = key.clone();
+            match (self.thinking.contains(key), next.thinking.contains(key)) {
+                (false, true) => events.push(AutomationEvent::ThinkingStarted {
+                    project_name,
+                    feature_name,
+                }),
+                (true, false) => events.push(AutomationEvent::ThinkingStopped {
+                    project_name,
+                    feature_name,
+                }),
+                _ => {}
+            }
+        }
+
+        for input in &next.pending_inputs {
+            if !self.pending_inputs.contains(input) {
+                events.push(AutomationEvent::PendingInputRaised {
+                    project_name: input.project_name.clone(),
+                    feature_name: input.feature_name.clone(),
+                    notification_type: input.notification_type.clone(),
+                    message: input.message.clone(),
+                    request_id: input.request_id.clone(),
+                });
+            }
+        }
+        for input in &self.pending_inputs {
+            if !next.pending_inputs.contains(input) {
+                events.push(AutomationEvent::PendingInputCleared {
+                    project_name: input.project_name.clone(),
+                    feature_name: input.feature_name.clone(),
+                    notification_type: input.notification_type.clone(),
+                    request_id: input.request_id.clone(),
+                });
+            }
+        }
+
+        for key in &self.order {
+            if !next.features.contains_key(key) {
+                let (project_name, feature_name) = key.clone();
+                events.push(AutomationEvent::FeatureDeleted {
+                    project_name,
+                    feature_name,
+                });
+            }
+        }
+
+        events
+    }
+}
+
+impl App {
+    /// Broadcast a single event to `amf automation watch` subscribers.
+    pub(crate) fn emit_automation_event(&self, event: AutomationEvent) {
+        if let Some(ipc) = self.ipc.as_ref()
+            && ipc.has_subscribers()
+        {
+            ipc.broadcast(&event.to_message(Utc::now()));
+        }
+    }
+
+    /// Diff the current state against what subscribers last saw and
+    /// broadcast the changes. Called periodically from the main loop.
+    pub fn publish_automation_events(&mut self) {
+        let subscribed = self.ipc.as_ref().is_some_and(|ipc| ipc.has_subscribers());
+        if !subscribed {
+            self.event_snapshot = None;
+            return;
+        }
+
+        let next = EventSnapshot::capture(self);
+        let events = self
+            .event_snapshot
+            .as_ref()
+            .map(|previous| previous.diff(&next))
+            .unwrap_or_default();
+        self.event_snapshot = Some(next);
+
+        for event in events {
+            self.emit_automation_event(event);
+        }
+    }
+
+    pub(crate) fn emit_diff_review_decision(
+        &self,
+        workdir: &Path,
+        change_id: &str,
+        relative_path: &str,
+        decision: &str,
+        reason: Option<String>,
+    ) {
+        let (project_name, feature_name, _, _) = self.project_feature_for_cwd(workdir);
+        self.emit_automation_event(AutomationEvent::DiffReviewDecision {
+            project_name,
+            feature_name,
+            change_id: change_id.to_string(),
+            relative_path: relative_path.to_string(),
+            decision: decision.to_string(),
+            reason,
+        });
+    }
+
+    pub(crate) fn emit_hook_completed(
+        &self,
+        project_name: &str,
+        feature_name: &str,
+        hook: &str,
+        success: bool,
+    ) {
+        self.emit_automation_event(AutomationEvent::HookCompleted {
+            project_name: project_name.to_string(),
+            feature_name: feature_name.to_string(),
+            hook: hook.to_string(),
+            success,
+        });
+    }
+}
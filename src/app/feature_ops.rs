@@ -5,12 +5,12 @@ use super::setup::{
     ensure_notification_hooks, ensure_plan_mode_claude_md, ensure_review_claude_md,
 };
 use super::*;
-use crate::app::util::read_latest_prompt;
+use crate::app::util::{latest_prompt_path, read_latest_prompt};
 use crate::automation::CreateBatchFeaturesRequest;
 use crate::extension::{load_global_extension_config, merge_project_extension_config};
 use crate::tmux::TmuxManager;
 use crate::worktree::WorktreeManager;
-use state::{BackgroundDeletion, DeleteStage, ForkFeatureState, ForkFeatureStep};
+use state::{DeleteStage, ForkFeatureState, ForkFeatureStep};
 
 impl App {
     fn drain_background_command_output(
@@ -65,9 +65,10 @@ impl App {
         feature.ready = !feature.ready;
         let name = feature.name.clone();
         let ready = feature.ready;
+        let dirty = Self::uncommitted_member_repos(feature);
         self.save()?;
         self.message = Some(if ready {
-            format!("Marked '{}' as ready", name)
+            Self::ready_message(&name, &dirty)
         } else {
             format!("Marked '{}' as not ready", name)
         });
@@ -75,11 +76,105 @@ impl App {
         Ok(())
     }
 
+    /// Labels of the member repos of a multi-repo feature that still have
+    /// uncommitted changes to tracked files.
+    pub(crate) fn uncommitted_member_repos(feature: &Feature) -> Vec<String> {
+        feature
+            .repos
+            .iter()
+            .filter(|member| {
+                member.workdir.exists()
+                    && WorktreeManager::has_tracked_changes(&member.workdir).unwrap_or(false)
+            })
+            .map(|member| member.label())
+            .collect()
+    }
+
+    /// Status line for marking a feature ready, naming member repos that
+    /// still have uncommitted changes.
+    pub(crate) fn ready_message(name: &str, dirty: &[String]) -> String {
+        if dirty.is_empty() {
+            format!("Marked '{}' as ready", name)
+        } else {
+            format!(
+                "Marked '{}' as ready (uncommitted changes in {})",
+                name,
+                dirty.join(", ")
+            )
+        }
+    }
+
+    pub fn restack_selected_feature(&mut self) -> Result<()> {
+        let (pi, fi) = match &self.selection {
+            Selection::Feature(pi, fi) | Selection::Session(pi, fi, _) => (*pi, *fi),
+            _ => return Ok(()),
+        };
+        let Some(feature) = self.store.projects.get(pi).and_then(|p| p.features.get(fi)) else {
+            return Ok(());
+        };
+        let name = feature.name.clone();
+
+        self.message = Some(match self.restack_feature(pi, fi) {
+            Ok(restacked) if restacked.is_empty() => {
+                format!("No features are stacked on '{}'", name)
+            }
+            Ok(restacked) => format!("Restacked {} feature(s) onto '{}'", restacked.len(), name),
+            Err(err) => format!("Error: {}", err),
+        });
+        Ok(())
+    }
+
+    /// Features stacked below `fi`, parents before children, each paired
+    /// with the index of the feature it sits on.
+    pub(crate) fn stack_below(&self, pi: usize, fi: usize) -> Vec<(usize, usize)> {
+        let project = &self.store.projects[pi];
+        let mut below = Vec::new();
+        let mut queue = vec![fi];
+        while let Some(parent) = queue.pop() {
+            for child in project.stacked_children(&project.features[parent].id) {
+                if child != fi && !below.iter().any(|&(c, _)| c == child) {
+                    below.push((child, parent));
+                    queue.insert(0, child);
+                }
+            }
+        }
+        below
+    }
+
+    /// Rebase every feature stacked below `fi` onto its parent's branch,
+    /// parents before children. Stops at the first rebase that fails;
+    /// features rebased before it stay rebased. Returns the features that
+    /// were rebased.
+    pub fn restack_feature(&mut self, pi: usize, fi: usize) -> Result<Vec<usize>> {
+        let mut restacked = Vec::new();
+        for (child, parent) in self.stack_below(pi, fi) {
+            let project = &self.store.projects[pi];
+            let onto = &project.features[parent].branch;
+            let feature = &project.features[child];
+            if feature.is_archived() {
+                continue;
+            }
+            if let Err(err) = self.worktree.rebase(&feature.workdir, onto) {
+                anyhow::bail!(
+                    "Could not restack '{}' onto '{}': {}",
+                    feature.name,
+                    onto,
+                    err
+                );
+            }
+            restacked.push(child);
+        }
+        Ok(restacked)
+    }
+
     pub fn start_create_feature(&mut self) {
         let (project_name, project_repo, preferred_agent, is_first, used_workdirs) = match &self
             .selection
         {
-            Selection::Project(pi) | Selection::Feature(pi, _) | Selection::Session(pi, _, _) => {
+            Selection::Project(pi)
+            | Selection::ArchivedFeatures(pi)
+            | Selection::Feature(pi, _)
+            | Selection::Session(pi, _, _) => {
                 if let Some(p) = self.store.projects.get(*pi) {
                     let used: Vec<PathBuf> = p.features.iter().map(|f| f.workdir.clone()).collect();
                     (
@@ -226,6 +321,7 @@ impl App {
                             create_terminal,
                             enable_chrome,
                             steering_enabled,
+                            parent_id: None,
                         },
                     );
                 } else {
@@ -389,7 +485,7 @@ impl App {
         }
     }
 
-    fn persist_startup_prompt(&mut self, workdir: &std::path::Path, prompt: &str) {
+    pub(crate) fn persist_startup_prompt(&mut self, workdir: &std::path::Path, prompt: &str) {
         let claude_dir = workdir.join(".claude");
         if let Err(err) = std::fs::create_dir_all(&claude_dir) {
             self.log_warn(
@@ -516,6 +612,9 @@ impl App {
         let repo = self.store.projects[pi].repo.clone();
         let viewport = self.viewport_size();
         let (agent, mode) = match self.store.projects.get(pi).and_then(|p| p.features.get(fi)) {
+            Some(feature) if feature.is_archived() => {
+                anyhow::bail!("'{}' is archived; restore it first", feature.name)
+            }
             Some(feature) => (feature.agent.clone(), feature.mode.clone()),
             None => return Ok(()),
         };
@@ -703,6 +802,14 @@ impl App {
             return Ok(());
         }
 
+        if self.store.projects[pi]
+            .features
+            .get(fi)
+            .is_some_and(Feature::is_archived)
+        {
+            return self.toggle_archive_selected_feature();
+        }
+
         let status = self
             .store
             .projects
@@ -901,20 +1008,48 @@ impl App {
             _ => return Ok(()),
         };
 
-        let (tmux_session, is_worktree, repo, workdir) = if let Some(project) =
-            self.store.find_project(&project_name)
-            && let Some(feature) = project.features.iter().find(|f| f.name == feature_name)
-        {
-            // Run on_stop for custom sessions before killing.
-            Self::run_custom_session_on_stop(feature, self.db.as_ref());
-            (
-                feature.tmux_session.clone(),
-                feature.is_worktree,
-                project.repo.clone(),
-                feature.workdir.clone(),
-            )
+        if let Some(state) = self.begin_feature_deletion(&project_name, &feature_name)? {
+            self.mode = AppMode::DeletingFeatureInProgress(state);
+        }
+
+        Ok(())
+    }
+
+    /// Run custom-session teardown and spawn the tmux kill that starts a
+    /// feature deletion. Returns `None` when the feature does not exist.
+    pub(crate) fn begin_feature_deletion(
+        &mut self,
+        project_name: &str,
+        feature_name: &str,
+    ) -> Result<Option<DeletingFeatureState>> {
+        let (tmux_session, is_worktree, repo, workdir, mut pending_worktrees, workspace_dir) =
+            if let Some(project) = self.store.find_project(project_name)
+                && let Some(feature) = project.features.iter().find(|f| f.name == feature_name)
+            {
+                // Run on_stop for custom sessions before killing.
+                Self::run_custom_session_on_stop(feature, self.db.as_ref());
+                let members: Vec<(PathBuf, PathBuf)> = feature
+                    .repos
+                    .iter()
+                    .map(|member| (member.repo.clone(), member.workdir.clone()))
+                    .collect();
+                (
+                    feature.tmux_session.clone(),
+                    // Archived features have no worktree left to remove.
+                    feature.is_worktree && !feature.is_archived(),
+                    project.repo.clone(),
+                    feature.workdir.clone(),
+                    members,
+                    feature.is_multi_repo().then(|| feature.workdir.clone()),
+                )
+            } else {
+                return Ok(None);
+            };
+        // A multi-repo feature removes each member worktree in turn.
+        let (repo, workdir) = if pending_worktrees.is_empty() {
+            (repo, workdir)
         } else {
-            return Ok(());
+            pending_worktrees.remove(0)
         };
 
         let spawned = TmuxManager::spawn_kill_session(&tmux_session)?;
@@ -923,21 +1058,21 @@ impl App {
             None => (None, None),
         };
 
-        self.mode = AppMode::DeletingFeatureInProgress(DeletingFeatureState {
-            project_name,
-            feature_name,
+        Ok(Some(DeletingFeatureState {
+            project_name: project_name.to_string(),
+            feature_name: feature_name.to_string(),
             tmux_session,
             is_worktree,
             repo,
             workdir,
+            pending_worktrees,
+            workspace_dir,
             stage: DeleteStage::KillingTmux,
             child,
             output: String::new(),
             output_rx,
             error: None,
-        });
-
-        Ok(())
+        }))
     }
 
     pub fn poll_deleting_feature(&mut self) -> Result<()> {
@@ -975,6 +1110,14 @@ impl App {
             }
         }
 
+        Self::advance_deletion(state);
+        Ok(())
+    }
+
+    /// Move a deletion whose last command has exited on to its next stage:
+    /// remove the worktree once tmux is gone, then each pending member
+    /// worktree of a multi-repo feature, then complete.
+    fn advance_deletion(state: &mut DeletingFeatureState) {
         match state.stage {
             DeleteStage::KillingTmux => {
                 if state.error.is_some() {
@@ -996,21 +1139,65 @@ impl App {
                 }
             }
             DeleteStage::RemovingWorktree => {
-                state.stage = DeleteStage::Completed;
+                if state.error.is_none() && !state.pending_worktrees.is_empty() {
+                    let (repo, workdir) = state.pending_worktrees.remove(0);
+                    match WorktreeManager::spawn_remove(&repo, &workdir) {
+                        Ok(spawned) => {
+                            state.child = Some(spawned.child);
+                            state.output.clear();
+                            state.output_rx = Some(spawned.output_rx);
+                            state.repo = repo;
+                            state.workdir = workdir;
+                        }
+                        Err(e) => {
+                            state.error = Some(e.to_string());
+                        }
+                    }
+                } else {
+                    state.stage = DeleteStage::Completed;
+                }
             }
             DeleteStage::Completed => {}
         }
+    }
 
-        Ok(())
+    /// Drop a feature whose tmux session and worktrees are gone from the
+    /// store, along with what it left behind outside them.
+    fn finish_feature_deletion(
+        &mut self,
+        project_name: &str,
+        feature_name: &str,
+        tmux_session: &str,
+        workspace_dir: Option<&Path>,
+    ) -> Result<()> {
+        if let Some(dir) = workspace_dir {
+            let _ = std::fs::remove_dir_all(dir);
+        }
+        if let Some(project) = self.store.find_project(project_name)
+            && let Some(feature) = project.features.iter().find(|f| f.name == feature_name)
+        {
+            Self::clear_feature_checkpoints(project, feature);
+        }
+        self.clear_sidebar_state_for_session(tmux_session);
+        self.store.remove_feature(project_name, feature_name);
+        self.save()
+    }
+
+    /// Delete the checkpoint refs a removed feature kept in its repo.
+    pub(crate) fn clear_feature_checkpoints(project: &Project, feature: &Feature) {
+        if project.is_git && !feature.is_multi_repo() {
+            let _ = crate::checkpoint::clear(&project.repo, &feature.id);
+        }
     }
 
     pub fn complete_deleting_feature(&mut self) -> Result<()> {
-        let (project_name, feature_name, tmux_session, had_error, error_msg) = {
+        let (project_name, feature_name, tmux_session, workspace_dir, had_error, error_msg) = {
             match &self.mode {
                 AppMode::DeletingFeatureInProgress(s) => (
                     s.project_name.clone(),
                     s.feature_name.clone(),
                     s.tmux_session.clone(),
+                    s.workspace_dir.clone(),
                     s.error.is_some(),
                     s.error.clone(),
                 ),
@@ -1031,9 +1218,12 @@ impl App {
             return Ok(());
         }
 
-        self.clear_sidebar_state_for_session(&tmux_session);
-        self.store.remove_feature(&project_name, &feature_name);
-        self.save()?;
+        self.finish_feature_deletion(
+            &project_name,
+            &feature_name,
+            &tmux_session,
+            workspace_dir.as_deref(),
+        )?;
 
         if let Some(pi) = self
             .store
@@ -1057,9 +1247,7 @@ impl App {
         if let AppMode::DeletingFeatureInProgress(state) =
             std::mem::replace(&mut self.mode, AppMode::Normal)
         {
-            let key = state.key();
-            let bg = BackgroundDeletion::from_deleting_state(state);
-            self.background_deletions.insert(key, bg);
+            self.background_deletions.insert(state.key(), state);
             self.message = Some("Deletion moved to background".to_string());
         }
     }
@@ -1102,35 +1290,7 @@ impl App {
                 }
             }
 
-            match deletion.stage {
-                DeleteStage::KillingTmux => {
-                    if deletion.child.is_none() {
-                        if deletion.error.is_some() {
-                            deletion.stage = DeleteStage::Completed;
-                        } else if deletion.is_worktree {
-                            match WorktreeManager::spawn_remove(&deletion.repo, &deletion.workdir) {
-                                Ok(spawned) => {
-                                    deletion.child = Some(spawned.child);
-                                    deletion.output.clear();
-                                    deletion.output_rx = Some(spawned.output_rx);
-                                    deletion.stage = DeleteStage::RemovingWorktree;
-                                }
-                                Err(e) => {
-                                    deletion.error = Some(e.to_string());
-                                }
-                            }
-                        } else {
-                            deletion.stage = DeleteStage::Completed;
-                        }
-                    }
-                }
-                DeleteStage::RemovingWorktree => {
-                    if deletion.child.is_none() {
-                        deletion.stage = DeleteStage::Completed;
-                    }
-                }
-                DeleteStage::Completed => {}
-            }
+            Self::advance_deletion(deletion);
 
             if deletion.stage == DeleteStage::Completed && deletion.child.is_none() {
                 completed.push(key.clone());
@@ -1151,10 +1311,12 @@ impl App {
                         ),
                     );
                 } else {
-                    self.clear_sidebar_state_for_session(&deletion.tmux_session);
-                    self.store
-                        .remove_feature(&deletion.project_name, &deletion.feature_name);
-                    let _ = self.save();
+                    let _ = self.finish_feature_deletion(
+                        &deletion.project_name,
+                        &deletion.feature_name,
+                        &deletion.tmux_session,
+                        deletion.workspace_dir.as_deref(),
+                    );
                     self.message = Some(format!("Deleted feature '{}'", deletion.feature_name));
                 }
             }
@@ -1163,6 +1325,176 @@ impl App {
         Ok(())
     }
 
+    pub fn toggle_archive_selected_feature(&mut self) -> Result<()> {
+        let (pi, fi) = match &self.selection {
+            Selection::Feature(pi, fi) | Selection::Session(pi, fi, _) => (*pi, *fi),
+            _ => return Ok(()),
+        };
+        if self.block_if_feature_pending_worktree_script(pi, fi) {
+            return Ok(());
+        }
+        let Some(feature) = self.store.projects.get(pi).and_then(|p| p.features.get(fi)) else {
+            return Ok(());
+        };
+        let name = feature.name.clone();
+
+        if feature.is_archived() {
+            self.message = Some(match self.restore_feature(pi, fi) {
+                Ok(()) => format!("Restored '{}'", name),
+                Err(err) => format!("Error: {}", err),
+            });
+            return Ok(());
+        }
+
+        match self.archive_feature(pi, fi) {
+            Ok(()) => {
+                self.selection = Selection::Feature(pi, fi);
+                if self.selection_index().is_none() {
+                    self.selection = Selection::ArchivedFeatures(pi);
+                }
+                self.message = Some(format!("Archived '{}'", name));
+            }
+            Err(err) => self.message = Some(format!("Error: {}", err)),
+        }
+        Ok(())
+    }
+
+    /// Why `fi` cannot be archived right now, if anything stops it.
+    pub(crate) fn archive_blocker(&self, pi: usize, fi: usize) -> Option<String> {
+        let feature = &self.store.projects[pi].features[fi];
+        if feature.is_archived() {
+            return Some(format!("'{}' is already archived", feature.name));
+        }
+        if feature.pending_worktree_script {
+            return Some(format!(
+                "'{}' is still running its worktree setup script",
+                feature.name
+            ));
+        }
+        if feature.is_multi_repo() {
+            return Some(format!(
+                "'{}' spans several repositories and cannot be archived",
+                feature.name
+            ));
+        }
+        if !feature.is_worktree || !feature.workdir.exists() {
+            return None;
+        }
+        // Archiving force-removes the worktree, so a git failure here must
+        // block rather than be read as a clean tree.
+        let unchecked = |err: anyhow::Error| {
+            Some(format!(
+                "could not check '{}' for changes: {}",
+                feature.name, err
+            ))
+        };
+        match WorktreeManager::has_tracked_changes(&feature.workdir) {
+            Ok(true) => {
+                return Some(format!(
+                    "'{}' has uncommitted changes; commit or stash them before archiving",
+                    feature.name
+                ));
+            }
+            Ok(false) => {}
+            Err(err) => return unchecked(err),
+        }
+        let untracked = match WorktreeManager::untracked_files(&feature.workdir) {
+            Ok(untracked) => untracked,
+            Err(err) => return unchecked(err),
+        };
+        if let Some(first) = untracked.first() {
+            return Some(format!(
+                "'{}' has {} untracked file(s) such as {}; commit or remove them before archiving",
+                feature.name,
+                untracked.len(),
+                first
+            ));
+        }
+        None
+    }
+
+    /// Kill the feature's tmux session and remove its worktree, keeping
+    /// the branch, the feature metadata, and each session's resume id so
+    /// `restore_feature` can bring it back.
+    pub(crate) fn archive_feature(&mut self, pi: usize, fi: usize) -> Result<()> {
+        if let Some(reason) = self.archive_blocker(pi, fi) {
+            anyhow::bail!(reason);
+        }
+        let repo = self.store.projects[pi].repo.clone();
+        let feature = &self.store.projects[pi].features[fi];
+        let tmux_session = feature.tmux_session.clone();
+        let workdir = feature.workdir.clone();
+        let remove_worktree = feature.is_worktree && workdir.exists();
+
+        Self::run_custom_session_on_stop(feature, self.db.as_ref());
+        if self.tmux.session_exists(&tmux_session) {
+            self.tmux.kill_session(&tmux_session)?;
+        }
+
+        // The latest prompt lives inside the worktree, so keep a copy.
+        let archived_prompt = if remove_worktree {
+            read_latest_prompt(&workdir)
+        } else {
+            None
+        };
+        if remove_worktree {
+            self.worktree.remove(&repo, &workdir)?;
+        }
+
+        self.clear_sidebar_state_for_session(&tmux_session);
+        let feature = &mut self.store.projects[pi].features[fi];
+        feature.status = ProjectStatus::Stopped;
+        feature.collapsed = true;
+        feature.archived_at = Some(chrono::Utc::now());
+        feature.archived_prompt = archived_prompt;
+        self.save()
+    }
+
+    /// Recreate an archived feature's worktree from its branch and relaunch
+    /// its sessions. Claude sessions resume from their stored
+    /// `claude_session_id`.
+    pub(crate) fn restore_feature(&mut self, pi: usize, fi: usize) -> Result<()> {
+        let repo = self.store.projects[pi].repo.clone();
+        let feature = &self.store.projects[pi].features[fi];
+        if !feature.is_archived() {
+            anyhow::bail!("'{}' is not archived", feature.name);
+        }
+
+        // Recreate the worktree where it was: Claude only resumes a session
+        // from the directory it was started in.
+        let workdir = if feature.is_worktree && !feature.workdir.exists() {
+            // `create_at` would quietly start a new branch from HEAD.
+            let branch_ref = format!("refs/heads/{}", feature.branch);
+            let verify = ["show-ref", "--verify", "--quiet", branch_ref.as_str()];
+            if !crate::diff::git_succeeds(&repo, &verify)? {
+                anyhow::bail!(
+                    "Cannot restore '{}': branch '{}' no longer exists",
+                    feature.name,
+                    feature.branch
+                );
+            }
+            self.worktree
+                .create_at(&repo, &feature.workdir, &feature.branch)?
+        } else {
+            feature.workdir.clone()
+        };
+
+        let feature = &mut self.store.projects[pi].features[fi];
+        if let Some(prompt) = feature.archived_prompt.take() {
+            let path = latest_prompt_path(&workdir);
+            if let Some(dir) = path.parent() {
+                let _ = std::fs::create_dir_all(dir);
+            }
+            let _ = std::fs::write(path, prompt);
+        }
+        feature.workdir = workdir;
+        feature.archived_at = None;
+        self.save()?;
+
+        self.ensure_feature_running(pi, fi)?;
+        self.save()
+    }
+
     pub fn start_fork_feature(&mut self) {
         let (pi, fi) = match &self.selection {
             Selection::Feature(pi, fi) => (*pi, *fi),
@@ -1177,6 +1509,10 @@ impl App {
             },
             None => return,
         };
+        if feature.is_multi_repo() {
+            self.message = Some("Multi-repo features cannot be forked".into());
+            return;
+        }
 
         let (agent, agent_index) = self.normalize_agent_for_repo(&project_repo, &feature.agent);
         self.active_extension = self.extension_for_repo(&project_repo);
@@ -1195,6 +1531,7 @@ impl App {
             review: feature.review,
             enable_chrome: feature.enable_chrome,
             include_context: true,
+            checkpoint: None,
         };
 
         self.mode = AppMode::ForkingFeature(state);
@@ -1216,12 +1553,14 @@ impl App {
         let agent = state.agent.clone();
         let enable_chrome = state.enable_chrome;
         let include_context = state.include_context;
-        let source_workdir = self
+        let checkpoint = state.checkpoint.clone();
+        let source = self
             .store
             .projects
             .get(state.source_pi)
-            .and_then(|p| p.features.get(state.source_fi))
-            .map(|f| f.workdir.clone());
+            .and_then(|p| p.features.get(state.source_fi));
+        let source_workdir = source.map(|f| f.workdir.clone());
+        let source_id = source.map(|f| f.id.clone());
 
         if new_branch.is_empty() {
             self.message = Some("Error: Branch name cannot be empty".into());
@@ -1251,25 +1590,27 @@ impl App {
             return Ok(());
         }
 
-        // Create worktree from source branch
-        let workdir =
-            self.worktree
-                .create_from(&project_repo, &new_branch, &new_branch, &source_branch)?;
+        // Create worktree from source branch, or from the commit a
+        // checkpoint was taken on
+        let base = match &checkpoint {
+            Some(checkpoint) => match crate::checkpoint::base_commit(&project_repo, checkpoint) {
+                Ok(commit) => commit,
+                Err(err) => {
+                    self.message = Some(format!("Error: {}", err));
+                    return Ok(());
+                }
+            },
+            None => source_branch.clone(),
+        };
+        let workdir = self
+            .worktree
+            .create_from(&project_repo, &new_branch, &new_branch, &base)?;
 
-        // Copy uncommitted changes from source worktree
-        if let Some(ref src_wd) = source_workdir {
-            let _ = WorktreeManager::copy_uncommitted_changes(src_wd, &workdir);
+        if let Some(checkpoint) = &checkpoint {
+            crate::checkpoint::write_tree(&workdir, &checkpoint.commit)?;
         }
-
-        // Export transcript context from source session
-        if include_context
-            && let Some(ref src_wd) = source_workdir
-            && let Some(jsonl) = crate::transcript::find_latest_transcript(src_wd)
-            && let Ok(md) = crate::transcript::export_transcript_markdown(&jsonl)
-        {
-            let claude_dir = workdir.join(".claude");
-            let _ = std::fs::create_dir_all(&claude_dir);
-            let _ = std::fs::write(claude_dir.join("context.md"), md);
+        if let Some(ref src_wd) = source_workdir {
+            Self::seed_forked_worktree(src_wd, &workdir, checkpoint.is_none(), include_context);
         }
 
         // Check for lifecycle hooks
@@ -1293,6 +1634,7 @@ impl App {
                         create_terminal: false,
                         enable_chrome,
                         steering_enabled: false,
+                        parent_id: source_id,
                     },
                 );
                 return Ok(());
@@ -1312,10 +1654,11 @@ impl App {
                 false,
                 None,
             );
+            self.set_feature_parent(&project_name, &new_branch, source_id);
             return Ok(());
         }
 
-        let feature = Feature::new(
+        let mut feature = Feature::new(
             new_branch.clone(),
             new_branch.clone(),
             workdir,
@@ -1326,6 +1669,7 @@ impl App {
             agent,
             enable_chrome,
         );
+        feature.parent_id = source_id;
 
         self.store.add_feature(&project_name, feature);
         self.save()?;
@@ -1359,6 +1703,46 @@ impl App {
         Ok(())
     }
 
+    /// Record which feature `branch` is stacked on once it is in the store.
+    pub(crate) fn set_feature_parent(
+        &mut self,
+        project_name: &str,
+        branch: &str,
+        parent_id: Option<String>,
+    ) {
+        if let Some(feature) = self
+            .store
+            .find_project_mut(project_name)
+            .and_then(|project| project.features.iter_mut().find(|f| f.name == branch))
+        {
+            feature.parent_id = parent_id;
+        }
+    }
+
+    /// Carry a source feature's state into a freshly created fork worktree:
+    /// its uncommitted changes, and its latest transcript exported to
+    /// `.claude/context.md`. Returns whether each step happened.
+    pub(crate) fn seed_forked_worktree(
+        source_workdir: &Path,
+        workdir: &Path,
+        copy_changes: bool,
+        include_context: bool,
+    ) -> (bool, bool) {
+        let copied = copy_changes
+            && WorktreeManager::copy_uncommitted_changes(source_workdir, workdir).is_ok();
+
+        let context_written = include_context
+            && crate::transcript::find_latest_transcript(source_workdir)
+                .and_then(|jsonl| crate::transcript::export_transcript_markdown(&jsonl).ok())
+                .is_some_and(|md| {
+                    let claude_dir = workdir.join(".claude");
+                    let _ = std::fs::create_dir_all(&claude_dir);
+                    std::fs::write(claude_dir.join("context.md"), md).is_ok()
+                });
+
+        (copied, context_written)
+    }
+
     pub fn start_rename_feature(&mut self) {
         let (pi, fi) = match &self.selection {
             Selection::Feature(pi, fi) => (*pi, *fi),
@@ -1414,6 +1798,122 @@ impl App {
         self.mode = AppMode::Normal;
     }
 
+    pub fn start_edit_feature_tags(&mut self) {
+        let (pi, fi) = match &self.selection {
+            Selection::Feature(pi, fi) => (*pi, *fi),
+            _ => return,
+        };
+
+        let current_tags = match self.store.projects.get(pi).and_then(|p| p.features.get(fi)) {
+            Some(f) => f.tags.join(", "),
+            None => return,
+        };
+
+        self.mode = AppMode::EditingFeatureTags(state::EditFeatureTagsState {
+            project_idx: pi,
+            feature_idx: fi,
+            input: current_tags,
+        });
+    }
+
+    pub fn apply_feature_tags(&mut self) -> Result<()> {
+        let (pi, fi, input) = match &self.mode {
+            AppMode::EditingFeatureTags(state) => {
+                (state.project_idx, state.feature_idx, state.input.clone())
+            }
+            _ => return Ok(()),
+        };
+
+        let tags = match crate::project::parse_tags(&input) {
+            Ok(tags) => tags,
+            Err(err) => {
+                // Keep the dialog open so the input can be fixed.
+                self.message = Some(format!("Error: {}", err));
+                return Ok(());
+            }
+        };
+
+        if let Some(feature) = self
+            .store
+            .projects
+            .get_mut(pi)
+            .and_then(|p| p.features.get_mut(fi))
+        {
+            feature.tags = tags.clone();
+        }
+
+        self.save()?;
+        self.mode = AppMode::Normal;
+
+        self.message = if tags.is_empty() {
+            Some("Tags cleared".into())
+        } else {
+            Some(format!("Tags: {}", tags.join(", ")))
+        };
+
+        Ok(())
+    }
+
+    pub fn cancel_edit_feature_tags(&mut self) {
+        self.mode = AppMode::Normal;
+    }
+
+    pub fn start_edit_feature_notes(&mut self) {
+        let (pi, fi) = match &self.selection {
+            Selection::Feature(pi, fi) | Selection::Session(pi, fi, _) => (*pi, *fi),
+            _ => return,
+        };
+
+        let Some(feature) = self.store.projects.get(pi).and_then(|p| p.features.get(fi)) else {
+            return;
+        };
+
+        self.mode = AppMode::EditingFeatureNotes(FeatureNotesState::new(
+            pi,
+            fi,
+            feature.name.clone(),
+            feature.notes.clone().unwrap_or_default(),
+        ));
+    }
+
+    pub fn save_feature_notes(&mut self) -> Result<()> {
+        let (pi, fi, text) = match &self.mode {
+            AppMode::EditingFeatureNotes(state) => (
+                state.project_idx,
+                state.feature_idx,
+                state.editor.text().trim_end().to_string(),
+            ),
+            _ => return Ok(()),
+        };
+
+        if let Some(feature) = self
+            .store
+            .projects
+            .get_mut(pi)
+            .and_then(|p| p.features.get_mut(fi))
+        {
+            feature.notes = if text.trim().is_empty() {
+                None
+            } else {
+                Some(text.clone())
+            };
+        }
+
+        self.save()?;
+        self.mode = AppMode::Normal;
+        self.message = Some(if text.trim().is_empty() {
+            "Notes cleared".into()
+        } else {
+            "Notes saved".into()
+        });
+
+        Ok(())
+    }
+
+    pub fn cancel_edit_feature_notes(&mut self) {
+        self.mode = AppMode::Normal;
+    }
+
     pub fn create_batch_features(&mut self) -> Result<()> {
         let state = match &self.mode {
             AppMode::CreatingBatchFeatures(s) => s.clone(),
@@ -1429,6 +1929,7 @@ impl App {
             mode: state.mode.clone(),
             review: state.review,
             enable_chrome: state.enable_chrome,
+            features: Vec::new(),
             dry_run: false,
         };
 
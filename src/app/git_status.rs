@@ -0,0 +1,79 @@
+use super::*;
+use crate::git_status::GitSummary;
+
+/// One feature worktree to summarise.
+struct GitSummaryJob {
+    feature_id: String,
+    workdir: PathBuf,
+    parent_branch: Option<String>,
+}
+
+impl App {
+    /// Read the git state of every active worktree feature in a background
+    /// thread. Results are applied by [`App::poll_git_summaries`].
+    pub fn refresh_git_summaries_background(&mut self) {
+        let jobs = git_summary_jobs(&self.store);
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.git_summary_bg = Some(rx);
+
+        std::thread::spawn(move || {
+            let summaries = jobs
+                .into_iter()
+                .filter_map(|job| {
+                    let summary =
+                        crate::git_status::load(&job.workdir, job.parent_branch.as_deref()).ok()?;
+                    Some((job.feature_id, summary))
+                })
+                .collect();
+            let _ = tx.send(summaries);
+        });
+    }
+
+    /// Apply a finished git summary refresh. Returns `true` when any
+    /// summary changed.
+    pub fn poll_git_summaries(&mut self) -> bool {
+        let Some(rx) = &self.git_summary_bg else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(summaries) => {
+                self.git_summary_bg = None;
+                if summaries == self.git_summaries {
+                    return false;
+                }
+                self.git_summaries = summaries;
+                true
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => false,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.git_summary_bg = None;
+                false
+            }
+        }
+    }
+
+    pub fn git_summary(&self, feature_id: &str) -> Option<&GitSummary> {
+        self.git_summaries.get(feature_id)
+    }
+}
+
+fn git_summary_jobs(store: &ProjectStore) -> Vec<GitSummaryJob> {
+    store
+        .projects
+        .iter()
+        .filter(|project| project.is_git)
+        .flat_map(|project| {
+            project
+                .features
+                .iter()
+                .filter(|feature| {
+                    !feature.is_archived() && !feature.is_multi_repo() && feature.workdir.exists()
+                })
+                .map(move |feature| GitSummaryJob {
+                    feature_id: feature.id.clone(),
+                    workdir: feature.workdir.clone(),
+                    parent_branch: project.parent_of(feature).map(|p| p.branch.clone()),
+                })
+        })
+        .collect()
+}
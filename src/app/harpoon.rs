@@ -159,7 +159,7 @@ impl App {
                 feature.sessions.get(si)?;
                 Some((pi, fi, si))
             }
-            Selection::Project(_) => None,
+            Selection::Project(_) | Selection::ArchivedFeatures(_) => None,
         }
     }
 
@@ -307,12 +307,13 @@ impl App {
                 create_terminal,
                 enable_chrome,
                 steering_enabled,
+                parent_id,
             } => {
                 self.start_worktree_hook(
                     &state.script,
                     state.workdir,
-                    project_name,
-                    branch,
+                    project_name.clone(),
+                    branch.clone(),
                     mode,
                     review,
                     plan_mode,
@@ -322,6 +323,9 @@ impl App {
                     steering_enabled,
                     Some(choice),
                 );
+                if parent_id.is_some() {
+                    self.set_feature_parent(&project_name, &branch, parent_id);
+                }
             }
             HookNext::StartFeature { pi, fi } => {
                 self.run_lifecycle_hook(&state.script, &state.workdir, Some(&choice));
@@ -512,6 +516,10 @@ impl App {
             true,
         )?;
 
+        if let Some(success) = success {
+            self.emit_hook_completed(&project_name, &branch, "on_worktree_created", success);
+        }
+
         match success {
             Some(true) => {
                 self.message = Some(format!(
@@ -576,6 +584,12 @@ impl App {
 
         for key in completed {
             if let Some(hook) = self.background_hooks.remove(&key) {
+                self.emit_hook_completed(
+                    &hook.project_name,
+                    &hook.branch,
+                    "on_worktree_created",
+                    hook.success.unwrap_or(false),
+                );
                 if let Err(err) = self.finalize_worktree_hook_feature(
                     hook.workdir.clone(),
                     hook.project_name.clone(),
@@ -0,0 +1,316 @@
+use std::collections::HashSet;
+
+use super::*;
+use crate::token_tracking::{TokenUsageProvider, TokenUsageSource};
+
+impl App {
+    pub fn open_import_picker(&mut self) {
+        let Some(pi) = self.selected_project_index() else {
+            self.message = Some("Select a project first".into());
+            return;
+        };
+
+        let candidates = self.import_candidates(pi);
+        let project_name = self.store.projects[pi].name.clone();
+        if candidates.is_empty() {
+            self.message = Some(format!(
+                "No untracked worktrees or tmux sessions for '{}'",
+                project_name
+            ));
+            return;
+        }
+
+        self.mode = AppMode::ImportPicker(ImportPickerState {
+            project_idx: pi,
+            project_name,
+            checked: vec![false; candidates.len()],
+            candidates,
+            selected: 0,
+        });
+        self.message = None;
+    }
+
+    pub fn cancel_import_picker(&mut self) {
+        self.mode = AppMode::Normal;
+    }
+
+    pub fn toggle_import_candidate(&mut self) {
+        if let AppMode::ImportPicker(state) = &mut self.mode
+            && let Some(checked) = state.checked.get_mut(state.selected)
+        {
+            *checked = !*checked;
+        }
+    }
+
+    /// Adopt the checked candidates, or the highlighted one when none are
+    /// checked, as features of the picker's project.
+    pub fn import_checked_candidates(&mut self) -> Result<()> {
+        let state = match std::mem::replace(&mut self.mode, AppMode::Normal) {
+            AppMode::ImportPicker(state) => state,
+            other => {
+                self.mode = other;
+                return Ok(());
+            }
+        };
+
+        let chosen: Vec<ImportCandidate> = if state.checked.iter().any(|checked| *checked) {
+            state
+                .candidates
+                .into_iter()
+                .zip(state.checked)
+                .filter_map(|(candidate, checked)| checked.then_some(candidate))
+                .collect()
+        } else {
+            state
+                .candidates
+                .into_iter()
+                .nth(state.selected)
+                .into_iter()
+                .collect()
+        };
+
+        let pi = state.project_idx;
+        let Some(project) = self.store.projects.get(pi) else {
+            return Ok(());
+        };
+        let preferred_agent = project.preferred_agent.clone();
+        let count = chosen.len();
+        for candidate in chosen {
+            if let Some(session) = &candidate.tmux_session {
+                // Hooks fired from agents started in the session later on
+                // need this to find their feature.
+                let _ = self.tmux.set_session_env(session, "AMF_SESSION", session);
+            }
+            let feature = imported_feature(candidate, &preferred_agent);
+            self.store.projects[pi].features.push(feature);
+        }
+
+        let project = &mut self.store.projects[pi];
+        project.collapsed = false;
+        self.selection = Selection::Feature(pi, project.features.len().saturating_sub(1));
+        self.message = Some(format!("Imported {} feature(s)", count));
+        self.save()
+    }
+
+    fn selected_project_index(&self) -> Option<usize> {
+        match &self.selection {
+            Selection::Project(pi)
+            | Selection::ArchivedFeatures(pi)
+            | Selection::Feature(pi, _)
+            | Selection::Session(pi, _, _) => (*pi < self.store.projects.len()).then_some(*pi),
+        }
+    }
+
+    /// Worktrees of project `pi` and tmux sessions started inside them (or
+    /// inside the repo itself) that no feature tracks yet.
+    pub(crate) fn import_candidates(&self, pi: usize) -> Vec<ImportCandidate> {
+        let project = &self.store.projects[pi];
+        let repo = project.repo.clone();
+        let tracked_sessions: HashSet<&str> = self
+            .store
+            .projects
+            .iter()
+            .flat_map(|p| p.features.iter())
+            .map(|f| f.tmux_session.as_str())
+            .collect();
+        // Multi-repo features owned by any project may hold a worktree here.
+        let member_workdirs = self
+            .store
+            .projects
+            .iter()
+            .flat_map(|p| p.features.iter())
+            .flat_map(|f| f.repos.iter())
+            .filter(|member| member.project_id == project.id)
+            .map(|member| &member.workdir);
+        let tracked_workdirs: Vec<&PathBuf> = project
+            .features
+            .iter()
+            .map(|f| &f.workdir)
+            .chain(member_workdirs)
+            .collect();
+        let mut taken: HashSet<String> = project.features.iter().map(|f| f.name.clone()).collect();
+
+        let mut sessions: Vec<(String, PathBuf)> = self
+            .tmux
+            .list_session_paths()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|(name, _)| !tracked_sessions.contains(name.as_str()))
+            .collect();
+
+        let mut candidates = Vec::new();
+        let worktrees = self.worktree.list(&repo).unwrap_or_default();
+        for wt in worktrees {
+            if wt.path == repo || tracked_workdirs.contains(&&wt.path) {
+                continue;
+            }
+            let branch = wt.branch.clone().unwrap_or_else(|| {
+                wt.path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_default()
+            });
+            let session = take_session_in(&mut sessions, &wt.path);
+            let name = unique_name(&branch, &mut taken);
+            candidates.push(self.import_candidate(name, branch, wt.path, true, session));
+        }
+
+        // Sessions started in a tracked worktree belong to that feature.
+        sessions.retain(|(_, path)| {
+            !tracked_workdirs
+                .iter()
+                .any(|workdir| **workdir != repo && path.starts_with(workdir))
+        });
+        let has_repo_feature = project.features.iter().any(|f| !f.is_worktree);
+        if !has_repo_feature && let Some(session) = take_session_in(&mut sessions, &repo) {
+            let branch = WorktreeManager::current_branch(&repo)
+                .ok()
+                .flatten()
+                .unwrap_or_else(|| session.clone());
+            let name = unique_name(&branch, &mut taken);
+            candidates.push(self.import_candidate(name, branch, repo, false, Some(session)));
+        }
+
+        candidates
+    }
+
+    fn import_candidate(
+        &self,
+        name: String,
+        branch: String,
+        workdir: PathBuf,
+        is_worktree: bool,
+        tmux_session: Option<String>,
+    ) -> ImportCandidate {
+        let windows = tmux_session
+            .as_deref()
+            .map(|session| {
+                self.tmux
+                    .list_window_commands(session)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|(window, command)| {
+                        let kind = detect_window_kind(&window, &command);
+                        (window, kind)
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        ImportCandidate {
+            name,
+            branch,
+            workdir,
+            is_worktree,
+            tmux_session,
+            windows,
+        }
+    }
+}
+
+fn take_session_in(sessions: &mut Vec<(String, PathBuf)>, dir: &Path) -> Option<String> {
+    let index = sessions
+        .iter()
+        .position(|(_, path)| path.starts_with(dir))?;
+    Some(sessions.remove(index).0)
+}
+
+fn unique_name(base: &str, taken: &mut HashSet<String>) -> String {
+    let mut name = base.to_string();
+    let mut n = 2u32;
+    while taken.contains(&name) {
+        name = format!("{}-{}", base, n);
+        n += 1;
+    }
+    taken.insert(name.clone());
+    name
+}
+
+/// Guess what runs in a tmux window from its name and the command in its
+/// active pane.
+pub(crate) fn detect_window_kind(window: &str, command: &str) -> SessionKind {
+    let window = window.to_ascii_lowercase();
+    let command = command.to_ascii_lowercase();
+    let has = |needle: &str| window.contains(needle) || command.contains(needle);
+    if has("claude") {
+        SessionKind::Claude
+    } else if has("opencode") {
+        SessionKind::Opencode
+    } else if has("codex") {
+        SessionKind::Codex
+    } else if has("vim") {
+        SessionKind::Nvim
+    } else {
+        SessionKind::Terminal
+    }
+}
+
+fn imported_feature(candidate: ImportCandidate, preferred_agent: &AgentKind) -> Feature {
+    let agent = candidate
+        .windows
+        .iter()
+        .find_map(|(_, kind)| match kind {
+            SessionKind::Claude => Some(AgentKind::Claude),
+            SessionKind::Opencode => Some(AgentKind::Opencode),
+            SessionKind::Codex => Some(AgentKind::Codex),
+            _ => None,
+        })
+        .unwrap_or_else(|| preferred_agent.clone());
+
+    let mut feature = Feature::new(
+        candidate.name,
+        candidate.branch,
+        candidate.workdir,
+        candidate.is_worktree,
+        VibeMode::default(),
+        false,
+        false,
+        agent,
+        false,
+    );
+    if let Some(tmux_session) = candidate.tmux_session {
+        feature.tmux_session = tmux_session;
+        feature.status = ProjectStatus::Idle;
+        for (window, kind) in candidate.windows {
+            feature.add_session(kind).tmux_window = window;
+        }
+    }
+    App::initialize_feature_sessions(&mut feature, false);
+    match_resume_ids(&mut feature);
+    feature
+}
+
+/// Give each agent session the newest unused resume id recorded for the
+/// feature's workdir.
+fn match_resume_ids(feature: &mut Feature) {
+    let mut claude_ids = claude_sessions::fetch_claude_sessions(&feature.workdir)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|session| session.id);
+    let mut codex_ids = codex_sessions::fetch_codex_sessions(&feature.workdir)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|session| session.id);
+
+    for session in &mut feature.sessions {
+        match session.kind {
+            SessionKind::Claude => {
+                if let Some(id) = claude_ids.next() {
+                    session.claude_session_id = Some(id.clone());
+                    session.set_token_usage_source_exact(TokenUsageSource {
+                        provider: TokenUsageProvider::Claude,
+                        id,
+                    });
+                }
+            }
+            SessionKind::Codex => {
+                if let Some(id) = codex_ids.next() {
+                    session.set_token_usage_source_exact(TokenUsageSource {
+                        provider: TokenUsageProvider::Codex,
+                        id,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,197 @@
+use anyhow::Result;
+
+use super::*;
+use crate::land::{LandPreview, LandStrategy, Landed};
+
+impl App {
+    pub fn open_land_dialog(&mut self) {
+        let (pi, fi) = match &self.selection {
+            Selection::Feature(pi, fi) | Selection::Session(pi, fi, _) => (*pi, *fi),
+            _ => return,
+        };
+        let Some(feature) = self.store.projects.get(pi).and_then(|p| p.features.get(fi)) else {
+            return;
+        };
+        let project_name = self.store.projects[pi].name.clone();
+        let feature_name = feature.name.clone();
+
+        match self.land_preview(pi, fi) {
+            Ok(preview) => {
+                self.mode = AppMode::LandingFeature(LandFeatureState {
+                    project_name,
+                    feature_name,
+                    preview,
+                    selected: 0,
+                    landed: None,
+                });
+                self.message = None;
+            }
+            Err(err) => self.message = Some(format!("Error: {}", err)),
+        }
+    }
+
+    pub fn land_dialog_select_next(&mut self) {
+        if let AppMode::LandingFeature(state) = &mut self.mode
+            && state.landed.is_none()
+        {
+            state.selected = (state.selected + 1) % LandStrategy::ALL.len();
+        }
+    }
+
+    pub fn land_dialog_select_prev(&mut self) {
+        if let AppMode::LandingFeature(state) = &mut self.mode
+            && state.landed.is_none()
+        {
+            let len = LandStrategy::ALL.len();
+            state.selected = (state.selected + len - 1) % len;
+        }
+    }
+
+    /// Land the dialog's feature with the highlighted strategy. The dialog
+    /// stays open afterwards to offer archiving or deleting the feature.
+    pub fn confirm_land_dialog(&mut self) -> Result<()> {
+        let (project_name, feature_name, strategy) = match &self.mode {
+            AppMode::LandingFeature(state) if state.landed.is_none() => (
+                state.project_name.clone(),
+                state.feature_name.clone(),
+                state.strategy(),
+            ),
+            _ => return Ok(()),
+        };
+        let Some((pi, fi)) = self
+            .automation_feature_indices(&project_name, &feature_name)
+            .ok()
+        else {
+            self.mode = AppMode::Normal;
+            return Ok(());
+        };
+
+        match self.land_feature(pi, fi, strategy) {
+            Ok(landed) => {
+                self.message = Some(format!(
+                    "Landed '{}' on '{}' at {}",
+                    feature_name,
+                    landed.target,
+                    &landed.commit[..landed.commit.len().min(12)]
+                ));
+                if let AppMode::LandingFeature(state) = &mut self.mode {
+                    state.landed = Some(landed);
+                }
+            }
+            Err(err) => self.message = Some(format!("Error: {}", err)),
+        }
+        Ok(())
+    }
+
+    /// Show the trial merge's conflicted files in the diff viewer, which
+    /// returns to the land dialog when closed.
+    pub fn open_land_conflicts(&mut self) {
+        let state = match &self.mode {
+            AppMode::LandingFeature(state) if !state.preview.conflicts.is_empty() => state.clone(),
+            _ => return,
+        };
+        let Some((pi, fi)) = self
+            .automation_feature_indices(&state.project_name, &state.feature_name)
+            .ok()
+        else {
+            return;
+        };
+
+        let workdir = self.store.projects[pi].features[fi].workdir.clone();
+        let mut viewer = DiffViewerState::new(None, workdir);
+        viewer.branch = state.preview.branch.clone();
+        viewer.base_ref = state.preview.target.clone();
+        viewer.files = state.preview.conflicts.clone();
+        viewer.layout = self.preferred_diff_viewer_layout();
+        viewer.return_to = Some(DiffViewerReturn::Land(state));
+        self.mode = AppMode::DiffViewer(viewer);
+    }
+
+    /// After landing, archive the feature or hand it to the usual delete
+    /// confirmation.
+    pub fn finish_land_dialog(&mut self, archive: bool) -> Result<()> {
+        let (project_name, feature_name) = match &self.mode {
+            AppMode::LandingFeature(state) if state.landed.is_some() => {
+                (state.project_name.clone(), state.feature_name.clone())
+            }
+            _ => return Ok(()),
+        };
+        if !archive {
+            self.mode = AppMode::DeletingFeature(project_name, feature_name);
+            return Ok(());
+        }
+
+        self.mode = AppMode::Normal;
+        let Some((pi, fi)) = self
+            .automation_feature_indices(&project_name, &feature_name)
+            .ok()
+        else {
+            return Ok(());
+        };
+        match self.archive_feature(pi, fi) {
+            Ok(()) => {
+                self.selection = Selection::Feature(pi, fi);
+                if self.selection_index().is_none() {
+                    self.selection = Selection::ArchivedFeatures(pi);
+                }
+                self.message = Some(format!("Landed and archived '{}'", feature_name));
+            }
+            Err(err) => self.message = Some(format!("Error: {}", err)),
+        }
+        Ok(())
+    }
+
+    pub fn cancel_land_dialog(&mut self) {
+        self.mode = AppMode::Normal;
+    }
+
+    /// Why `fi` cannot be landed, if anything stops it.
+    pub(crate) fn land_blocker(&self, pi: usize, fi: usize) -> Option<String> {
+        let feature = &self.store.projects[pi].features[fi];
+        if feature.is_archived() {
+            return Some(format!("'{}' is archived", feature.name));
+        }
+        if feature.is_multi_repo() {
+            return Some(format!(
+                "'{}' spans several repositories and cannot be landed",
+                feature.name
+            ));
+        }
+        if feature.pending_worktree_script {
+            return Some(format!(
+                "'{}' is still running its worktree setup script",
+                feature.name
+            ));
+        }
+        None
+    }
+
+    pub(crate) fn land_preview(&self, pi: usize, fi: usize) -> Result<LandPreview> {
+        if let Some(reason) = self.land_blocker(pi, fi) {
+            anyhow::bail!(reason);
+        }
+        let project = &self.store.projects[pi];
+        let feature = &project.features[fi];
+        let parent_branch = project.parent_of(feature).map(|p| p.branch.clone());
+        crate::land::preview(&feature.workdir, parent_branch.as_deref())
+    }
+
+    /// Land `fi` on its base branch. Only features marked ready can land.
+    pub(crate) fn land_feature(
+        &self,
+        pi: usize,
+        fi: usize,
+        strategy: LandStrategy,
+    ) -> Result<Landed> {
+        if let Some(reason) = self.land_blocker(pi, fi) {
+            anyhow::bail!(reason);
+        }
+        let project = &self.store.projects[pi];
+        let feature = &project.features[fi];
+        if !feature.ready {
+            anyhow::bail!("Mark '{}' ready before landing it", feature.name);
+        }
+        let parent_branch = project.parent_of(feature).map(|p| p.branch.clone());
+        crate::land::land(&feature.workdir, parent_branch.as_deref(), strategy)
+    }
+}
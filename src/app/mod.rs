@@ -1,4 +1,5 @@
 mod automation;
+mod checkpoint;
 mod claude_session_picker;
 mod claude_sessions;
 mod codex_live;
@@ -6,13 +7,18 @@ mod codex_session_picker;
 mod codex_sessions;
 pub mod commands;
 mod diff;
+mod events;
 mod feature_ops;
+mod git_status;
 mod harpoon;
 mod hooks;
+mod import;
+mod land;
 mod navigation;
 mod notifications;
 mod opencode;
 pub(crate) mod opencode_storage;
+mod overlap;
 mod project_ops;
 mod rename;
 mod review;
@@ -21,6 +27,7 @@ mod session_config;
 mod session_ops;
 mod session_titles;
 pub mod setup;
+mod stage;
 mod state;
 mod steering;
 mod switcher;
@@ -64,6 +71,7 @@ use crate::worktree::WorktreeManager;
 pub use self::setup::load_config;
 pub use codex_live::CodexLiveThreadState;
 pub use codex_sessions::sidebar_metadata_for_session_id as codex_sidebar_metadata_for_session_id;
+pub(crate) use notifications::review_response;
 pub use state::*;
 pub use steering::{PromptAnalysis, analyze_prompt};
 
@@ -392,6 +400,8 @@ pub struct App {
     pub mode: AppMode,
     pub message: Option<String>,
     pub should_quit: bool,
+    /// Running as `amf daemon` with no terminal attached.
+    pub headless: bool,
     pub should_switch: Option<String>,
     pub pane_content: String,
     pub pane_lines: Vec<Line<'static>>,
@@ -421,8 +431,22 @@ pub struct App {
     pub usage: UsageManager,
     pub token_tracker: SessionTokenTracker,
     pub session_status_bg: Option<Receiver<sync::SessionStatusBgResult>>,
+    /// Features of the same project changing the same files, from the last
+    /// overlap scan.
+    pub feature_overlaps: Vec<crate::overlap::FeatureOverlap>,
+    pub overlap_scan_bg: Option<Receiver<Vec<crate::overlap::FeatureOverlap>>>,
+    /// Git state of each worktree feature by feature id, refreshed in the
+    /// background.
+    pub git_summaries: HashMap<String, crate::git_status::GitSummary>,
+    pub git_summary_bg: Option<Receiver<HashMap<String, crate::git_status::GitSummary>>>,
+    checkpoint_tx: Sender<checkpoint::CheckpointResult>,
+    checkpoint_rx: Receiver<checkpoint::CheckpointResult>,
+    /// Feature ids with a checkpoint snapshot running.
+    checkpoints_inflight: std::collections::HashSet<String>,
     pub scroll_offset: usize,
     pub session_filter: SessionFilter,
+    /// Ids of projects whose archived-features section is expanded.
+    pub expanded_archives: std::collections::HashSet<String>,
     pub throbber_state: throbber_widgets_tui::ThrobberState,
     pub thinking_features: std::collections::HashSet<String>,
     pub ipc_thinking_sessions: std::collections::HashSet<String>,
@@ -437,6 +461,13 @@ pub struct App {
     pub background_hooks: HashMap<String, BackgroundHook>,
     pub ipc: Option<crate::ipc::IpcGuard>,
     pub ipc_fallback_logged: bool,
+    /// State last published to `amf automation watch` subscribers; `None`
+    /// while nobody is subscribed.
+    pub(crate) event_snapshot: Option<events::EventSnapshot>,
+    /// `wait_for_idle` requests still waiting for their reply.
+    pub(crate) automation_waits: Vec<automation::AutomationWait>,
+    /// Prompts waiting for a just-started agent to be ready.
+    pub(crate) queued_prompts: Vec<automation::QueuedPrompt>,
     pub last_file_notification_count: usize,
     pub last_file_notification_fingerprint: Option<u64>,
     pub vscode_available: bool,
@@ -532,6 +563,10 @@ impl App {
                 fi.hash(&mut hasher);
                 si.hash(&mut hasher);
             }
+            Selection::ArchivedFeatures(pi) => {
+                3usize.hash(&mut hasher);
+                pi.hash(&mut hasher);
+            }
         }
 
         if let AppMode::Viewing(view) = &self.mode {
@@ -1301,6 +1336,7 @@ impl App {
         )?;
         let store = db.load_store()?;
         let (sidebar_load_tx, sidebar_load_rx) = std::sync::mpsc::channel();
+        let (checkpoint_tx, checkpoint_rx) = std::sync::mpsc::channel();
         let latest_prompt_cache = Self::build_latest_prompt_cache(&store);
         let config = load_config();
         let zai_enabled = config.zai.is_some();
@@ -1331,6 +1367,7 @@ impl App {
             mode: AppMode::Normal,
             message: None,
             should_quit: false,
+            headless: false,
             should_switch: None,
             pane_content: String::new(),
             pane_lines: Vec::new(),
@@ -1360,8 +1397,16 @@ impl App {
             usage: UsageManager::new(zai_enabled, zai_monthly, zai_weekly, zai_five_hour),
             token_tracker: SessionTokenTracker::default(),
             session_status_bg: None,
+            feature_overlaps: Vec::new(),
+            overlap_scan_bg: None,
+            git_summaries: HashMap::new(),
+            git_summary_bg: None,
+            checkpoint_tx,
+            checkpoint_rx,
+            checkpoints_inflight: std::collections::HashSet::new(),
             scroll_offset: 0,
             session_filter: SessionFilter::default(),
+            expanded_archives: std::collections::HashSet::new(),
             throbber_state: throbber_widgets_tui::ThrobberState::default(),
             thinking_features: std::collections::HashSet::new(),
             ipc_thinking_sessions: std::collections::HashSet::new(),
@@ -1376,6 +1421,9 @@ impl App {
             background_hooks: HashMap::new(),
             ipc: None,
             ipc_fallback_logged: false,
+            event_snapshot: None,
+            automation_waits: Vec::new(),
+            queued_prompts: Vec::new(),
             last_file_notification_count: 0,
             last_file_notification_fingerprint: None,
             // Checked asynchronously — defaults to false until confirmed.
@@ -1427,6 +1475,7 @@ impl App {
     ) -> Self {
         use crate::extension::ExtensionConfig;
         let (sidebar_load_tx, sidebar_load_rx) = std::sync::mpsc::channel();
+        let (checkpoint_tx, checkpoint_rx) = std::sync::mpsc::channel();
         let latest_prompt_cache = Self::build_latest_prompt_cache(&store);
         let sidebar_plan_cache = Self::build_sidebar_plan_cache(&store);
         let (codex_sidebar_metadata_tx, codex_sidebar_metadata_rx) = std::sync::mpsc::channel();
@@ -1443,6 +1492,7 @@ impl App {
             mode: AppMode::Normal,
             message: None,
             should_quit: false,
+            headless: false,
             should_switch: None,
             pane_content: String::new(),
             pane_lines: Vec::new(),
@@ -1472,8 +1522,16 @@ impl App {
             usage: UsageManager::new(false, None, None, None),
             token_tracker: SessionTokenTracker::default(),
             session_status_bg: None,
+            feature_overlaps: Vec::new(),
+            overlap_scan_bg: None,
+            git_summaries: HashMap::new(),
+            git_summary_bg: None,
+            checkpoint_tx,
+            checkpoint_rx,
+            checkpoints_inflight: std::collections::HashSet::new(),
             scroll_offset: 0,
             session_filter: SessionFilter::default(),
+            expanded_archives: std::collections::HashSet::new(),
             throbber_state: throbber_widgets_tui::ThrobberState::default(),
             thinking_features: std::collections::HashSet::new(),
             ipc_thinking_sessions: std::collections::HashSet::new(),
@@ -1488,6 +1546,9 @@ impl App {
             background_hooks: HashMap::new(),
             ipc: None,
             ipc_fallback_logged: false,
+            event_snapshot: None,
+            automation_waits: Vec::new(),
+            queued_prompts: Vec::new(),
             last_file_notification_count: 0,
             last_file_notification_fingerprint: None,
             vscode_available: false,
@@ -1858,7 +1919,7 @@ impl App {
     pub fn reload_extension_config(&mut self) {
         let global_ext = load_global_extension_config();
         self.active_extension = match &self.selection {
-            Selection::Project(pi) => {
+            Selection::Project(pi) | Selection::ArchivedFeatures(pi) => {
                 if let Some(project) = self.store.projects.get(*pi) {
                     merge_project_extension_config(&global_ext, &project.repo)
                 } else {
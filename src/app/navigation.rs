@@ -20,6 +20,14 @@ impl App {
                 .and_then(|session| session.status_text.as_ref())
                 .map(|_| 2)
                 .unwrap_or(1),
+            VisibleItem::Feature(pi, fi) => self
+                .store
+                .projects
+                .get(*pi)
+                .and_then(|project| project.features.get(*fi))
+                .and_then(|feature| feature.notes_headline())
+                .map(|_| 2)
+                .unwrap_or(1),
             _ => 1,
         }
     }
@@ -96,8 +104,16 @@ impl App {
                                 .cmp(&project.features[a].created_at)
                         })
                 });
-                for fi in feature_indices {
+                let (archived, active): (Vec<usize>, Vec<usize>) = feature_indices
+                    .into_iter()
+                    .partition(|&fi| project.features[fi].is_archived());
+                for fi in stacked_feature_order(project, &active) {
                     let feature = &project.features[fi];
+                    if let SessionFilter::Tag(tag) = &self.session_filter
+                        && !feature.has_tag(tag)
+                    {
+                        continue;
+                    }
                     items.push(VisibleItem::Feature(pi, fi));
                     if !feature.collapsed {
                         for (si, session) in feature.sessions.iter().enumerate() {
@@ -107,14 +123,38 @@ impl App {
                         }
                     }
                 }
+                // Archived features have no running sessions, so only
+                // their feature rows are listed.
+                if !archived.is_empty() {
+                    items.push(VisibleItem::ArchivedFeatures(pi));
+                    if self.is_archive_expanded(pi) {
+                        for fi in archived {
+                            let feature = &project.features[fi];
+                            if let SessionFilter::Tag(tag) = &self.session_filter
+                                && !feature.has_tag(tag)
+                            {
+                                continue;
+                            }
+                            items.push(VisibleItem::Feature(pi, fi));
+                        }
+                    }
+                }
             }
         }
         items
     }
 
+    pub fn is_archive_expanded(&self, pi: usize) -> bool {
+        self.store
+            .projects
+            .get(pi)
+            .is_some_and(|project| self.expanded_archives.contains(&project.id))
+    }
+
     pub(crate) fn selection_index_in_items(&self, items: &[VisibleItem]) -> Option<usize> {
         items.iter().position(|item| match (&self.selection, item) {
             (Selection::Project(a), VisibleItem::Project(b)) => a == b,
+            (Selection::ArchivedFeatures(a), VisibleItem::ArchivedFeatures(b)) => a == b,
             (Selection::Feature(a1, a2), VisibleItem::Feature(b1, b2)) => a1 == b1 && a2 == b2,
             (Selection::Session(a1, a2, a3), VisibleItem::Session(b1, b2, b3)) => {
                 a1 == b1 && a2 == b2 && a3 == b3
@@ -133,7 +173,36 @@ impl App {
             SessionFilter::Terminal => session.kind == SessionKind::Terminal,
             SessionFilter::Nvim => session.kind == SessionKind::Nvim,
             SessionFilter::Vscode => session.kind == SessionKind::Vscode,
+            SessionFilter::Tag(_) => true,
+        }
+    }
+
+    /// Tags used by any feature, sorted.
+    pub fn known_tags(&self) -> Vec<String> {
+        let mut tags: Vec<String> = self
+            .store
+            .projects
+            .iter()
+            .flat_map(|p| p.features.iter())
+            .flat_map(|f| f.tags.iter().cloned())
+            .collect();
+        tags.sort();
+        tags.dedup();
+        tags
+    }
+
+    pub fn cycle_session_filter(&mut self) {
+        self.session_filter = self.session_filter.next_with_tags(&self.known_tags());
+        if self.selection_index().is_none() {
+            let pi = match self.selection {
+                Selection::Project(pi)
+                | Selection::ArchivedFeatures(pi)
+                | Selection::Feature(pi, _)
+                | Selection::Session(pi, _, _) => pi,
+            };
+            self.selection = Selection::Project(pi);
         }
+        self.message = Some(format!("Filter: {}", self.session_filter.display_name()));
     }
 
     pub(crate) fn selection_index(&self) -> Option<usize> {
@@ -150,6 +219,7 @@ impl App {
         let next = (current + 1) % items.len();
         self.selection = match items[next] {
             VisibleItem::Project(pi) => Selection::Project(pi),
+            VisibleItem::ArchivedFeatures(pi) => Selection::ArchivedFeatures(pi),
             VisibleItem::Feature(pi, fi) => Selection::Feature(pi, fi),
             VisibleItem::Session(pi, fi, si) => Selection::Session(pi, fi, si),
         };
@@ -169,6 +239,7 @@ impl App {
         };
         self.selection = match items[prev] {
             VisibleItem::Project(pi) => Selection::Project(pi),
+            VisibleItem::ArchivedFeatures(pi) => Selection::ArchivedFeatures(pi),
             VisibleItem::Feature(pi, fi) => Selection::Feature(pi, fi),
             VisibleItem::Session(pi, fi, si) => Selection::Session(pi, fi, si),
         };
@@ -247,9 +318,10 @@ impl App {
 
     pub fn selected_project(&self) -> Option<&Project> {
         match &self.selection {
-            Selection::Project(pi) | Selection::Feature(pi, _) | Selection::Session(pi, _, _) => {
-                self.store.projects.get(*pi)
-            }
+            Selection::Project(pi)
+            | Selection::ArchivedFeatures(pi)
+            | Selection::Feature(pi, _)
+            | Selection::Session(pi, _, _) => self.store.projects.get(*pi),
         }
     }
 
@@ -276,3 +348,34 @@ impl App {
         }
     }
 }
+
+/// Reorder `sorted` so every stacked feature follows its parent, keeping
+/// the existing order among siblings.
+fn stacked_feature_order(project: &Project, sorted: &[usize]) -> Vec<usize> {
+    fn push_subtree(project: &Project, sorted: &[usize], fi: usize, order: &mut Vec<usize>) {
+        if order.contains(&fi) {
+            return;
+        }
+        order.push(fi);
+        let id = project.features[fi].id.as_str();
+        for &child in sorted {
+            if project.features[child].parent_id.as_deref() == Some(id) {
+                push_subtree(project, sorted, child, order);
+            }
+        }
+    }
+
+    let mut order = Vec::with_capacity(sorted.len());
+    for &fi in sorted {
+        if project.parent_of(&project.features[fi]).is_none() {
+            push_subtree(project, sorted, fi, &mut order);
+        }
+    }
+    // Features caught in a parent cycle have no root; list them last.
+    for &fi in sorted {
+        if !order.contains(&fi) {
+            order.push(fi);
+        }
+    }
+    order
+}
@@ -4,15 +4,33 @@ use serde::Deserialize;
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
+use std::time::Instant;
 
+use super::automation::AutomationWait;
 use super::*;
 use crate::app::util::latest_prompt_path;
 use crate::automation::{
+    ADD_SESSION_ACTION, ARCHIVE_FEATURE_ACTION, AUTOMATION_PROTOCOL_VERSION, AutomationErrorCode,
     CREATE_BATCH_FEATURES_ACTION, CREATE_FEATURE_ACTION, CREATE_PROJECT_ACTION,
-    CreateBatchFeaturesRequest, CreateFeatureRequest, CreateProjectRequest,
-    automation_error_response,
+    CreateBatchFeaturesRequest, CreateFeatureRequest, CreateProjectRequest, DELETE_FEATURE_ACTION,
+    FORK_FEATURE_ACTION, HELLO_ACTION, LAND_FEATURE_ACTION, LIST_INPUTS_ACTION, LIST_STATE_ACTION,
+    READ_DIFF_ACTION, RENAME_FEATURE_ACTION, RESOLVE_INPUT_ACTION, RESTACK_FEATURE_ACTION,
+    RESTORE_FEATURE_ACTION, SEND_PROMPT_ACTION, SET_FEATURE_READY_ACTION, SET_FEATURE_TAGS_ACTION,
+    START_FEATURE_ACTION, STOP_FEATURE_ACTION, WAIT_FOR_IDLE_ACTION, WaitForIdleRequest,
+    automation_error_response, automation_failure_response,
 };
 
+/// Body a diff review hook expects back: `proceed`, `reject`, or `cancel`.
+pub(crate) fn review_response(decision: &str, reason: Option<&str>) -> serde_json::Value {
+    serde_json::json!({
+        "type": "review-response",
+        "decision": decision,
+        "reason": reason,
+        "skip": decision == "cancel",
+        "reject": decision == "reject",
+    })
+}
+
 #[derive(Deserialize)]
 struct IpcMsg {
     #[serde(rename = "type")]
@@ -93,6 +111,168 @@ impl App {
         }
     }
 
+    /// Decode an automation request, run it, and turn the outcome into the
+    /// JSON reply that goes back over IPC.
+    fn run_automation_action<Req, Resp>(
+        &mut self,
+        action: &'static str,
+        raw: &serde_json::Value,
+        run: impl FnOnce(&mut Self, &Req) -> Result<Resp>,
+    ) -> serde_json::Value
+    where
+        Req: serde::de::DeserializeOwned,
+        Resp: serde::Serialize,
+    {
+        let request = match serde_json::from_value::<Req>(raw.clone()) {
+            Ok(request) => request,
+            Err(err) => {
+                return automation_error_response(
+                    action,
+                    AutomationErrorCode::InvalidPayload,
+                    format!("Invalid automation payload: {err}"),
+                );
+            }
+        };
+
+        match run(self, &request) {
+            Ok(response) => serde_json::to_value(response).unwrap_or_else(|err| {
+                automation_error_response(
+                    action,
+                    AutomationErrorCode::OperationFailed,
+                    format!("Failed to serialize response: {err}"),
+                )
+            }),
+            Err(err) => {
+                self.log_error(
+                    "automation",
+                    format!("Automation '{}' failed: {err}", action),
+                );
+                automation_failure_response(action, &err)
+            }
+        }
+    }
+
+    /// Send an automation reply, stamped with the protocol version it follows.
+    fn send_automation_reply(
+        &mut self,
+        request_id: Option<&str>,
+        reply_socket: Option<&str>,
+        mut payload: serde_json::Value,
+    ) {
+        if let Some(obj) = payload.as_object_mut() {
+            obj.entry("protocol_version")
+                .or_insert(serde_json::json!(AUTOMATION_PROTOCOL_VERSION));
+        }
+        self.respond_to_notification(request_id, reply_socket, None, payload);
+    }
+
+    /// Park a `wait_for_idle` request until `poll_automation_waits` can
+    /// answer it. Returns the reply to send right away when the request is
+    /// invalid.
+    fn queue_wait_for_idle(
+        &mut self,
+        raw: &serde_json::Value,
+        request_id: &Option<String>,
+        reply_socket: &Option<String>,
+    ) -> Option<serde_json::Value> {
+        let request = match serde_json::from_value::<WaitForIdleRequest>(raw.clone()) {
+            Ok(request) => request,
+            Err(err) => {
+                return Some(automation_error_response(
+                    WAIT_FOR_IDLE_ACTION,
+                    AutomationErrorCode::InvalidPayload,
+                    format!("Invalid automation payload: {err}"),
+                ));
+            }
+        };
+        if let Err(err) = self.wait_for_idle_target(&request) {
+            return Some(automation_failure_response(WAIT_FOR_IDLE_ACTION, &err));
+        }
+
+        self.automation_waits.push(AutomationWait::new(
+            request,
+            request_id.clone(),
+            reply_socket.clone(),
+            Instant::now(),
+        ));
+        None
+    }
+
+    /// Reply to every parked `wait_for_idle` request whose feature has gone
+    /// idle, asked for input, stopped, or run out of time.
+    pub fn poll_automation_waits(&mut self) {
+        if self.automation_waits.is_empty() {
+            return;
+        }
+
+        let now = Instant::now();
+        let mut waits = std::mem::take(&mut self.automation_waits);
+        let mut replies = Vec::new();
+        waits.retain_mut(|wait| match self.poll_wait_for_idle(wait, now) {
+            None => true,
+            Some(result) => {
+                let payload = match result {
+                    Ok(response) => serde_json::to_value(response).unwrap_or_else(|err| {
+                        automation_error_response(
+                            WAIT_FOR_IDLE_ACTION,
+                            AutomationErrorCode::OperationFailed,
+                            format!("Failed to serialize response: {err}"),
+                        )
+                    }),
+                    Err(err) => automation_failure_response(WAIT_FOR_IDLE_ACTION, &err),
+                };
+                replies.push((wait.request_id.take(), wait.reply_socket.take(), payload));
+                false
+            }
+        });
+        self.automation_waits = waits;
+
+        for (request_id, reply_socket, payload) in replies {
+            self.send_automation_reply(request_id.as_deref(), reply_socket.as_deref(), payload);
+        }
+    }
+
+    /// A TUI is starting and wants the socket. Only a headless daemon gives
+    /// it up; it saves the store and quits once the reply is sent.
+    fn handle_daemon_handoff(&mut self, request_id: Option<&str>, reply_socket: Option<&str>) {
+        let payload = if self.headless {
+            match self.save() {
+                Ok(()) => {
+                    self.log_info("daemon", "Handing off to a new AMF dashboard".to_string());
+                    for wait in std::mem::take(&mut self.automation_waits) {
+                        let payload = automation_error_response(
+                            WAIT_FOR_IDLE_ACTION,
+                            AutomationErrorCode::InvalidState,
+                            "AMF handed off to a new dashboard; send the wait again",
+                        );
+                        self.send_automation_reply(
+                            wait.request_id.as_deref(),
+                            wait.reply_socket.as_deref(),
+                            payload,
+                        );
+                    }
+                    self.should_quit = true;
+                    serde_json::json!({ "type": "handoff-result", "ok": true })
+                }
+                Err(err) => {
+                    self.log_error("daemon", format!("Handoff failed to save store: {err}"));
+                    serde_json::json!({
+                        "type": "handoff-result",
+                        "ok": false,
+                        "error": format!("Failed to save store: {err}"),
+                    })
+                }
+            }
+        } else {
+            serde_json::json!({
+                "type": "handoff-result",
+                "ok": false,
+                "error": "Another AMF dashboard is already running",
+            })
+        };
+        self.respond_to_notification(request_id, reply_socket, None, payload);
+    }
+
     fn respond_to_notification(
         &mut self,
         request_id: Option<&str>,
@@ -133,7 +313,53 @@ impl App {
         }
     }
 
-    fn project_feature_for_cwd(
+    /// Answer a diff review hook over IPC when it is waiting on a reply
+    /// socket, otherwise through its response file and proceed signal.
+    pub(crate) fn send_review_response(
+        &mut self,
+        request_id: Option<&str>,
+        reply_socket: Option<&str>,
+        response_file: &Path,
+        proceed_signal: &Path,
+        response: &serde_json::Value,
+    ) {
+        if let (Some(req), Some(sock)) = (request_id, reply_socket)
+            && !req.is_empty()
+            && !sock.is_empty()
+        {
+            let mut payload = response.clone();
+            if let Some(obj) = payload.as_object_mut() {
+                obj.insert("request_id".to_string(), serde_json::json!(req));
+            }
+            if crate::ipc::send(
+                Path::new(sock),
+                &serde_json::to_string(&payload).unwrap_or_default(),
+            )
+            .is_ok()
+            {
+                return;
+            }
+            self.log_warn(
+                "ipc",
+                "Failed IPC response for change-reason; falling back to files".to_string(),
+            );
+        }
+
+        if let Some(parent) = response_file.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let _ = std::fs::write(
+            response_file,
+            serde_json::to_string(response).unwrap_or_default(),
+        );
+
+        if let Some(parent) = proceed_signal.parent() {
+            let _ = std::fs::create_dir_all(parent);
+        }
+        let _ = std::fs::write(proceed_signal, "");
+    }
+
+    pub(crate) fn project_feature_for_cwd(
         &self,
         cwd_path: &Path,
     ) -> (
@@ -270,10 +496,17 @@ impl App {
         // Collect first to avoid holding a borrow on self.ipc
         // while mutating other self fields below.
         let mut messages = Vec::new();
+        let mut rejected = Vec::new();
         if let Some(ref guard) = self.ipc {
             while let Ok(v) = guard.rx.try_recv() {
                 messages.push(v);
             }
+            while let Ok(reason) = guard.rejected.try_recv() {
+                rejected.push(reason);
+            }
+        }
+        for reason in rejected {
+            self.log_warn("ipc", reason);
         }
         if messages.is_empty() {
             return;
@@ -285,12 +518,43 @@ impl App {
         }
     }
 
+    /// The `type` of an IPC message. Claude's Stop hook pipes its raw input,
+    /// which names the hook in `hook_event_name` but has no `type`, straight
+    /// to `amf notify`; only that shape still falls back to "stop".
+    fn ipc_message_type(raw: &serde_json::Value) -> Option<String> {
+        match raw.get("type").and_then(|v| v.as_str()) {
+            Some(msg_type) => Some(msg_type.to_string()),
+            None if raw.get("hook_event_name").is_some() => Some("stop".to_string()),
+            None => None,
+        }
+    }
+
     pub(crate) fn handle_ipc_message_value(&mut self, raw: serde_json::Value) {
-        let msg_type = raw
-            .get("type")
-            .and_then(|v| v.as_str())
-            .unwrap_or("stop")
-            .to_string();
+        let Some(msg_type) = Self::ipc_message_type(&raw) else {
+            self.log_warn(
+                "ipc",
+                "Ignored IPC message without a `type` field".to_string(),
+            );
+            let payload = automation_error_response(
+                "unknown",
+                AutomationErrorCode::InvalidPayload,
+                "IPC message is missing the `type` field",
+            );
+            self.send_automation_reply(
+                raw.get("request_id").and_then(|v| v.as_str()),
+                raw.get("reply_socket").and_then(|v| v.as_str()),
+                payload,
+            );
+            return;
+        };
+
+        if msg_type == crate::ipc::HANDOFF_TYPE {
+            self.handle_daemon_handoff(
+                raw.get("request_id").and_then(|v| v.as_str()),
+                raw.get("reply_socket").and_then(|v| v.as_str()),
+            );
+            return;
+        }
 
         if matches!(msg_type.as_str(), "codex-live-event" | "codex_live_event") {
             let session_id = raw
@@ -330,7 +594,28 @@ impl App {
                 .unwrap_or("")
                 .to_string();
 
+            if let Err(err) = crate::automation::check_protocol_version(&raw) {
+                self.log_warn(
+                    "automation",
+                    format!("Rejected automation request '{}': {}", action, err),
+                );
+                let payload = automation_error_response(
+                    if action.is_empty() {
+                        "unknown"
+                    } else {
+                        &action
+                    },
+                    err.code,
+                    err.message,
+                );
+                self.send_automation_reply(request_id.as_deref(), reply_socket.as_deref(), payload);
+                return;
+            }
+
             let payload = match action.as_str() {
+                HELLO_ACTION => self.run_automation_action(HELLO_ACTION, &raw, |app, request| {
+                    app.hello_from_request(request)
+                }),
                 CREATE_PROJECT_ACTION => {
                     match serde_json::from_value::<CreateProjectRequest>(raw.clone()) {
                         Ok(request) => match self.create_project_from_request(&request) {
@@ -346,6 +631,7 @@ impl App {
                                 serde_json::to_value(response).unwrap_or_else(|err| {
                                     automation_error_response(
                                         CREATE_PROJECT_ACTION,
+                                        AutomationErrorCode::OperationFailed,
                                         format!("Failed to serialize response: {err}"),
                                     )
                                 })
@@ -355,11 +641,12 @@ impl App {
                                     "automation",
                                     format!("Automation '{}' failed: {err}", CREATE_PROJECT_ACTION),
                                 );
-                                automation_error_response(CREATE_PROJECT_ACTION, err.to_string())
+                                automation_failure_response(CREATE_PROJECT_ACTION, &err)
                             }
                         },
                         Err(err) => automation_error_response(
                             CREATE_PROJECT_ACTION,
+                            AutomationErrorCode::InvalidPayload,
                             format!("Invalid automation payload: {err}"),
                         ),
                     }
@@ -378,6 +665,7 @@ impl App {
                                 serde_json::to_value(response).unwrap_or_else(|err| {
                                     automation_error_response(
                                         CREATE_FEATURE_ACTION,
+                                        AutomationErrorCode::OperationFailed,
                                         format!("Failed to serialize response: {err}"),
                                     )
                                 })
@@ -387,11 +675,12 @@ impl App {
                                     "automation",
                                     format!("Automation '{}' failed: {err}", CREATE_FEATURE_ACTION),
                                 );
-                                automation_error_response(CREATE_FEATURE_ACTION, err.to_string())
+                                automation_failure_response(CREATE_FEATURE_ACTION, &err)
                             }
                         },
                         Err(err) => automation_error_response(
                             CREATE_FEATURE_ACTION,
+                            AutomationErrorCode::InvalidPayload,
                             format!("Invalid automation payload: {err}"),
                         ),
                     }
@@ -411,6 +700,7 @@ impl App {
                                 serde_json::to_value(response).unwrap_or_else(|err| {
                                     automation_error_response(
                                         CREATE_BATCH_FEATURES_ACTION,
+                                        AutomationErrorCode::OperationFailed,
                                         format!("Failed to serialize response: {err}"),
                                     )
                                 })
@@ -423,24 +713,114 @@ impl App {
                                         CREATE_BATCH_FEATURES_ACTION
                                     ),
                                 );
-                                automation_error_response(
-                                    CREATE_BATCH_FEATURES_ACTION,
-                                    err.to_string(),
-                                )
+                                automation_failure_response(CREATE_BATCH_FEATURES_ACTION, &err)
                             }
                         },
                         Err(err) => automation_error_response(
                             CREATE_BATCH_FEATURES_ACTION,
+                            AutomationErrorCode::InvalidPayload,
                             format!("Invalid automation payload: {err}"),
                         ),
                     }
                 }
+                LIST_STATE_ACTION => {
+                    self.run_automation_action(LIST_STATE_ACTION, &raw, |app, request| {
+                        app.list_state_from_request(request)
+                    })
+                }
+                SEND_PROMPT_ACTION => {
+                    self.run_automation_action(SEND_PROMPT_ACTION, &raw, |app, request| {
+                        app.send_prompt_from_request(request)
+                    })
+                }
+                START_FEATURE_ACTION => {
+                    self.run_automation_action(START_FEATURE_ACTION, &raw, |app, request| {
+                        app.start_feature_from_request(request)
+                    })
+                }
+                STOP_FEATURE_ACTION => {
+                    self.run_automation_action(STOP_FEATURE_ACTION, &raw, |app, request| {
+                        app.stop_feature_from_request(request)
+                    })
+                }
+                ARCHIVE_FEATURE_ACTION => {
+                    self.run_automation_action(ARCHIVE_FEATURE_ACTION, &raw, |app, request| {
+                        app.archive_feature_from_request(request)
+                    })
+                }
+                RESTORE_FEATURE_ACTION => {
+                    self.run_automation_action(RESTORE_FEATURE_ACTION, &raw, |app, request| {
+                        app.restore_feature_from_request(request)
+                    })
+                }
+                SET_FEATURE_READY_ACTION => {
+                    self.run_automation_action(SET_FEATURE_READY_ACTION, &raw, |app, request| {
+                        app.set_feature_ready_from_request(request)
+                    })
+                }
+                SET_FEATURE_TAGS_ACTION => {
+                    self.run_automation_action(SET_FEATURE_TAGS_ACTION, &raw, |app, request| {
+                        app.set_feature_tags_from_request(request)
+                    })
+                }
+                RENAME_FEATURE_ACTION => {
+                    self.run_automation_action(RENAME_FEATURE_ACTION, &raw, |app, request| {
+                        app.rename_feature_from_request(request)
+                    })
+                }
+                DELETE_FEATURE_ACTION => {
+                    self.run_automation_action(DELETE_FEATURE_ACTION, &raw, |app, request| {
+                        app.delete_feature_from_request(request)
+                    })
+                }
+                FORK_FEATURE_ACTION => {
+                    self.run_automation_action(FORK_FEATURE_ACTION, &raw, |app, request| {
+                        app.fork_feature_from_request(request)
+                    })
+                }
+                ADD_SESSION_ACTION => {
+                    self.run_automation_action(ADD_SESSION_ACTION, &raw, |app, request| {
+                        app.add_session_from_request(request)
+                    })
+                }
+                READ_DIFF_ACTION => {
+                    self.run_automation_action(READ_DIFF_ACTION, &raw, |app, request| {
+                        app.read_diff_from_request(request)
+                    })
+                }
+                RESTACK_FEATURE_ACTION => {
+                    self.run_automation_action(RESTACK_FEATURE_ACTION, &raw, |app, request| {
+                        app.restack_feature_from_request(request)
+                    })
+                }
+                LAND_FEATURE_ACTION => {
+                    self.run_automation_action(LAND_FEATURE_ACTION, &raw, |app, request| {
+                        app.land_feature_from_request(request)
+                    })
+                }
+                LIST_INPUTS_ACTION => {
+                    self.run_automation_action(LIST_INPUTS_ACTION, &raw, |app, request| {
+                        app.list_inputs_from_request(request)
+                    })
+                }
+                RESOLVE_INPUT_ACTION => {
+                    self.run_automation_action(RESOLVE_INPUT_ACTION, &raw, |app, request| {
+                        app.resolve_input_from_request(request)
+                    })
+                }
+                WAIT_FOR_IDLE_ACTION => {
+                    match self.queue_wait_for_idle(&raw, &request_id, &reply_socket) {
+                        Some(payload) => payload,
+                        None => return,
+                    }
+                }
                 _ => automation_error_response(
                     if action.is_empty() {
                         "unknown"
                     } else {
                         &action
                     },
+                    AutomationErrorCode::UnknownAction,
                     format!(
                         "Unknown automation action '{}'",
                         if action.is_empty() {
@@ -452,12 +832,7 @@ impl App {
                 ),
             };
 
-            self.respond_to_notification(
-                request_id.as_deref(),
-                reply_socket.as_deref(),
-                None,
-                payload,
-            );
+            self.send_automation_reply(request_id.as_deref(), reply_socket.as_deref(), payload);
             return;
         }
 
@@ -488,6 +863,17 @@ impl App {
             return;
         }
 
+        // "session-start" comes from Claude's SessionStart hook once the
+        // agent can take input.
+        if msg_type == "session-start" {
+            if let Some(sid) = msg.session_id {
+                self.touch_feature_for_session(&sid);
+                self.deliver_queued_prompts(&sid);
+                self.log_debug("ipc", format!("session-start for {sid}"));
+            }
+            return;
+        }
+
         if msg_type == "thinking-start" {
             if let Some(sid) = msg.session_id {
                 self.ipc_thinking_sessions.insert(sid.clone());
@@ -605,6 +991,7 @@ impl App {
             }
             if let Some((pi, fi)) = self.project_feature_for_cwd(&cwd_path).3 {
                 self.refresh_sidebar_plan_for_feature(pi, fi);
+                self.checkpoint_feature_background(pi, fi, "prompt submitted");
             }
             if let Some((codex_session, codex_window)) =
                 self.codex_feature_for_message(session_id.as_deref(), &cwd_path)
@@ -668,6 +1055,9 @@ impl App {
             if self.store.projects[pi].features[fi].agent == AgentKind::Codex {
                 self.refresh_sidebar_plan_for_feature(pi, fi);
             }
+            if notification_type == "stop" {
+                self.checkpoint_feature_background(pi, fi, "agent stopped");
+            }
         }
 
         // For diff-review while viewing the matching
@@ -0,0 +1,181 @@
+use super::*;
+use crate::overlap::{FeatureOverlap, OverlapJob};
+
+impl App {
+    /// Diff every active worktree feature in a background thread and look
+    /// for files two features of the same project both change. Results are
+    /// applied by [`App::poll_overlap_scan`].
+    pub fn scan_overlaps_background(&mut self) {
+        let jobs = overlap_jobs(&self.store);
+        let (tx, rx) = std::sync::mpsc::channel();
+        self.overlap_scan_bg = Some(rx);
+
+        std::thread::spawn(move || {
+            let _ = tx.send(crate::overlap::scan(&jobs));
+        });
+    }
+
+    /// Apply a finished overlap scan. Returns `true` when the overlaps
+    /// changed.
+    pub fn poll_overlap_scan(&mut self) -> bool {
+        let Some(rx) = &self.overlap_scan_bg else {
+            return false;
+        };
+        match rx.try_recv() {
+            Ok(overlaps) => {
+                self.overlap_scan_bg = None;
+                if overlaps == self.feature_overlaps {
+                    return false;
+                }
+                self.feature_overlaps = overlaps;
+                true
+            }
+            Err(std::sync::mpsc::TryRecvError::Empty) => false,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                self.overlap_scan_bg = None;
+                false
+            }
+        }
+    }
+
+    /// Overlaps involving `feature_id`, each seen from that feature.
+    pub fn overlaps_for_feature(&self, feature_id: &str) -> Vec<FeatureOverlap> {
+        self.feature_overlaps
+            .iter()
+            .filter(|overlap| overlap.involves(feature_id))
+            .map(|overlap| overlap.oriented(feature_id))
+            .collect()
+    }
+
+    /// Whether feature `fi` shares changed files with another feature, and
+    /// if so whether any of their hunks touch the same lines.
+    pub fn feature_overlap_kind(&self, pi: usize, fi: usize) -> Option<bool> {
+        let feature = self.store.projects.get(pi)?.features.get(fi)?;
+        let mut overlaps = self
+            .feature_overlaps
+            .iter()
+            .filter(|overlap| overlap.involves(&feature.id))
+            .peekable();
+        overlaps.peek()?;
+        Some(overlaps.any(FeatureOverlap::hunks_overlap))
+    }
+
+    pub fn open_overlap_dialog(&mut self) {
+        let (pi, fi) = match &self.selection {
+            Selection::Feature(pi, fi) | Selection::Session(pi, fi, _) => (*pi, *fi),
+            _ => return,
+        };
+        let Some(feature) = self.store.projects.get(pi).and_then(|p| p.features.get(fi)) else {
+            return;
+        };
+        let project = &self.store.projects[pi];
+
+        let entries: Vec<OverlapEntry> = self
+            .overlaps_for_feature(&feature.id)
+            .into_iter()
+            .filter_map(|overlap| {
+                let other = project.features.iter().find(|f| f.id == overlap.right)?;
+                Some(OverlapEntry {
+                    feature_name: other.name.clone(),
+                    files: overlap.files,
+                })
+            })
+            .collect();
+        if entries.is_empty() {
+            self.message = Some(format!(
+                "No other feature changes the files '{}' changes",
+                feature.name
+            ));
+            return;
+        }
+
+        self.mode = AppMode::ReviewingOverlaps(OverlapDialogState {
+            project_name: project.name.clone(),
+            feature_name: feature.name.clone(),
+            entries,
+            selected: 0,
+        });
+        self.message = None;
+    }
+
+    pub fn overlap_dialog_select_next(&mut self) {
+        if let AppMode::ReviewingOverlaps(state) = &mut self.mode {
+            let count = state.file_count();
+            if count > 0 {
+                state.selected = (state.selected + 1) % count;
+            }
+        }
+    }
+
+    pub fn overlap_dialog_select_prev(&mut self) {
+        if let AppMode::ReviewingOverlaps(state) = &mut self.mode {
+            let count = state.file_count();
+            if count > 0 {
+                state.selected = (state.selected + count - 1) % count;
+            }
+        }
+    }
+
+    /// Show the highlighted file side by side: the other feature's version
+    /// on the left, this feature's on the right. Closing the viewer returns
+    /// to the dialog.
+    pub fn open_overlap_diff(&mut self) {
+        let state = match &self.mode {
+            AppMode::ReviewingOverlaps(state) => state.clone(),
+            _ => return,
+        };
+        let Some((entry, file)) = state.selected_file() else {
+            return;
+        };
+        let Some((pi, fi)) = self
+            .automation_feature_indices(&state.project_name, &state.feature_name)
+            .ok()
+        else {
+            return;
+        };
+
+        let diff_file = match crate::overlap::review_file(file) {
+            Ok(diff_file) => diff_file,
+            Err(err) => {
+                self.message = Some(format!("Error: {}", err));
+                return;
+            }
+        };
+        let workdir = self.store.projects[pi].features[fi].workdir.clone();
+        let mut viewer = DiffViewerState::new(None, workdir);
+        viewer.branch = state.feature_name.clone();
+        viewer.base_ref = entry.feature_name.clone();
+        viewer.files = vec![diff_file];
+        viewer.layout = DiffViewerLayout::SideBySide;
+        viewer.return_to = Some(DiffViewerReturn::Overlaps(state));
+        self.mode = AppMode::DiffViewer(viewer);
+    }
+
+    pub fn cancel_overlap_dialog(&mut self) {
+        self.mode = AppMode::Normal;
+    }
+}
+
+fn overlap_jobs(store: &ProjectStore) -> Vec<OverlapJob> {
+    let mut jobs = Vec::new();
+    for project in store.projects.iter().filter(|p| p.is_git) {
+        for (fi, feature) in project.features.iter().enumerate() {
+            if feature.is_archived() || feature.is_multi_repo() || !feature.workdir.exists() {
+                continue;
+            }
+            let ancestors = project
+                .ancestors(fi)
+                .into_iter()
+                .map(|parent| parent.id.clone())
+                .collect();
+            jobs.push(OverlapJob {
+                project_id: project.id.clone(),
+                feature_id: feature.id.clone(),
+                workdir: feature.workdir.clone(),
+                parent_branch: project.parent_of(feature).map(|p| p.branch.clone()),
+                ancestors,
+            });
+        }
+    }
+    jobs
+}
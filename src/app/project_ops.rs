@@ -5,6 +5,7 @@ use ratatui_explorer::FileExplorer;
 
 use super::*;
 use crate::automation::CreateProjectRequest;
+use crate::project::FeatureRepo;
 use crate::tmux::TmuxManager;
 use crate::worktree::WorktreeManager;
 
@@ -17,6 +18,13 @@ impl App {
                     project.collapsed = !project.collapsed;
                 }
             }
+            Selection::ArchivedFeatures(pi) => {
+                if let Some(project) = self.store.projects.get(*pi)
+                    && !self.expanded_archives.remove(&project.id)
+                {
+                    self.expanded_archives.insert(project.id.clone());
+                }
+            }
             Selection::Feature(pi, fi) => {
                 let pi = *pi;
                 let fi = *fi;
@@ -58,7 +66,7 @@ impl App {
 
     pub fn start_create_batch_features(&mut self) {
         let workspace_path = match &self.selection {
-            Selection::Project(pi) => {
+            Selection::Project(pi) | Selection::ArchivedFeatures(pi) => {
                 if let Some(p) = self.store.projects.get(*pi) {
                     Some(p.repo.to_string_lossy().into_owned())
                 } else {
@@ -279,16 +287,31 @@ impl App {
         };
 
         if let Some(project) = self.store.find_project(&project_name) {
-            let features: Vec<(String, PathBuf, bool)> = project
+            for feature in &project.features {
+                Self::clear_feature_checkpoints(project, feature);
+            }
+            let features: Vec<(String, PathBuf, bool, Vec<FeatureRepo>)> = project
                 .features
                 .iter()
-                .map(|f| (f.tmux_session.clone(), f.workdir.clone(), f.is_worktree))
+                .map(|f| {
+                    (
+                        f.tmux_session.clone(),
+                        f.workdir.clone(),
+                        f.is_worktree,
+                        f.repos.clone(),
+                    )
+                })
                 .collect();
             let repo = project.repo.clone();
 
-            for (session, workdir, is_worktree) in features {
+            for (session, workdir, is_worktree, members) in features {
                 let _ = TmuxManager::kill_session(&session);
-                if is_worktree {
+                if !members.is_empty() {
+                    for member in &members {
+                        let _ = WorktreeManager::remove(&member.repo, &member.workdir);
+                    }
+                    let _ = std::fs::remove_dir_all(&workdir);
+                } else if is_worktree {
                     let _ = WorktreeManager::remove(&repo, &workdir);
                 }
             }
@@ -306,6 +329,7 @@ impl App {
                 let last = &items[items.len() - 1];
                 self.selection = match last {
                     VisibleItem::Project(pi) => Selection::Project(*pi),
+                    VisibleItem::ArchivedFeatures(pi) => Selection::ArchivedFeatures(*pi),
                     VisibleItem::Feature(pi, fi) => Selection::Feature(*pi, *fi),
                     VisibleItem::Session(pi, fi, si) => Selection::Session(*pi, *fi, *si),
                 };
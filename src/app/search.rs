@@ -11,16 +11,30 @@ impl App {
         self.message = None;
     }
 
+    /// Matches projects, features, and sessions by name, and features by
+    /// any line of their notes. `tag:<prefix>` terms restrict the results
+    /// to features with a matching tag; any other words must then appear
+    /// in the feature name or notes.
     pub fn perform_search(&mut self) {
         let query = match &self.mode {
             AppMode::Searching(state) => state.query.to_lowercase(),
             _ => return,
         };
 
+        let (tag_terms, words): (Vec<&str>, Vec<&str>) = query
+            .split_whitespace()
+            .partition(|term| term.starts_with("tag:"));
+        let tag_terms: Vec<String> = tag_terms.iter().map(|term| term[4..].to_string()).collect();
+        let query = if tag_terms.is_empty() {
+            query.clone()
+        } else {
+            words.join(" ")
+        };
+
         let mut matches = Vec::new();
 
         for (pi, project) in self.store.projects.iter().enumerate() {
-            if project.name.to_lowercase().contains(&query) {
+            if tag_terms.is_empty() && project.name.to_lowercase().contains(&query) {
                 matches.push(SearchMatch {
                     item: VisibleItem::Project(pi),
                     label: project.name.clone(),
@@ -29,14 +43,41 @@ impl App {
             }
 
             for (fi, feature) in project.features.iter().enumerate() {
-                if feature.name.to_lowercase().contains(&query) {
+                let tagged = tag_terms.iter().all(|term| {
+                    feature
+                        .tags
+                        .iter()
+                        .any(|tag| tag.starts_with(term.as_str()))
+                });
+                let name_hit = feature.name.to_lowercase().contains(&query);
+                let notes_hit = feature
+                    .notes
+                    .as_deref()
+                    .filter(|_| !query.is_empty())
+                    .and_then(|notes| {
+                        notes
+                            .lines()
+                            .map(str::trim)
+                            .find(|line| line.to_lowercase().contains(&query))
+                    });
+                if tagged && (name_hit || notes_hit.is_some()) {
+                    let mut context = match notes_hit {
+                        Some(line) if !name_hit => format!("{} / ✎ {}", project.name, line),
+                        _ => format!("{} / {}", project.name, shorten_path(&feature.workdir)),
+                    };
+                    if !feature.tags.is_empty() {
+                        context.push_str(&format!(" #{}", feature.tags.join(" #")));
+                    }
                     matches.push(SearchMatch {
                         item: VisibleItem::Feature(pi, fi),
                         label: feature.name.clone(),
-                        context: format!("{} / {}", project.name, shorten_path(&feature.workdir)),
+                        context,
                     });
                 }
 
+                if !tag_terms.is_empty() {
+                    continue;
+                }
                 for (si, session) in feature.sessions.iter().enumerate() {
                     if session.label.to_lowercase().contains(&query) {
                         matches.push(SearchMatch {
@@ -66,6 +107,7 @@ impl App {
         if let Some(m) = match_item {
             self.selection = match m.item {
                 VisibleItem::Project(pi) => Selection::Project(pi),
+                VisibleItem::ArchivedFeatures(pi) => Selection::ArchivedFeatures(pi),
                 VisibleItem::Feature(pi, fi) => Selection::Feature(pi, fi),
                 VisibleItem::Session(pi, fi, si) => Selection::Session(pi, fi, si),
             };
@@ -1,8 +1,9 @@
-use anyhow::Result;
+use anyhow::{Result, bail};
 
 use super::setup::{ensure_notification_hooks, ensure_review_claude_md};
 use super::util::slugify;
 use super::*;
+use crate::automation::{AutomationError, AutomationErrorCode};
 use crate::tmux::TmuxManager;
 
 fn session_kind_for_agent(agent: &AgentKind) -> SessionKind {
@@ -23,7 +24,7 @@ fn label_for_agent(agent: &AgentKind) -> String {
     }
 }
 
-fn agent_for_session_kind(kind: &SessionKind) -> Option<AgentKind> {
+pub(super) fn agent_for_session_kind(kind: &SessionKind) -> Option<AgentKind> {
     match kind {
         SessionKind::Claude => Some(AgentKind::Claude),
         SessionKind::Opencode => Some(AgentKind::Opencode),
@@ -57,6 +58,11 @@ impl App {
             return Ok(());
         }
 
+        if self.store.projects[pi].features[fi].is_archived() {
+            self.message = Some("Feature is archived; press X to restore it".into());
+            return Ok(());
+        }
+
         if self.block_if_feature_pending_worktree_script(pi, fi) {
             return Ok(());
         }
@@ -299,55 +305,83 @@ impl App {
         Ok(config.autolaunch.unwrap_or(false))
     }
 
-    pub fn add_builtin_session(&mut self, pi: usize, fi: usize, kind: SessionKind) -> Result<()> {
+    /// Add a built-in session from the session picker, selecting it and
+    /// reporting the result on the status line.
+    pub fn add_builtin_session_for_picker(
+        &mut self,
+        pi: usize,
+        fi: usize,
+        kind: SessionKind,
+    ) -> Result<()> {
+        match self.add_builtin_session(pi, fi, kind)? {
+            Some(si) => {
+                let label = self.store.projects[pi].features[fi].sessions[si]
+                    .label
+                    .clone();
+                self.selection = Selection::Session(pi, fi, si);
+                self.message = Some(format!("Added '{}'", label));
+            }
+            None => {
+                let workdir = &self.store.projects[pi].features[fi].workdir;
+                self.message = Some(format!("Opened VSCode in {}", workdir.display()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Add a built-in session to a running feature. Returns the index of
+    /// the new session, or `None` for VSCode, which opens an editor window
+    /// instead of adding one.
+    pub fn add_builtin_session(
+        &mut self,
+        pi: usize,
+        fi: usize,
+        kind: SessionKind,
+    ) -> Result<Option<usize>> {
         match kind {
-            SessionKind::Terminal => self.add_terminal_session_for_picker(pi, fi),
-            SessionKind::Nvim => self.add_nvim_session_for_picker(pi, fi),
+            SessionKind::Terminal => self.add_terminal_session(pi, fi).map(Some),
+            SessionKind::Nvim => self.add_nvim_session(pi, fi).map(Some),
             SessionKind::Claude | SessionKind::Opencode | SessionKind::Codex => {
-                self.add_agent_session_for_picker(pi, fi, kind)
-            }
-            SessionKind::Vscode => self.add_vscode_session_for_picker(pi, fi),
-            _ => {
-                self.message = Some("Unsupported session type".into());
-                Ok(())
+                self.add_agent_session(pi, fi, kind).map(Some)
             }
+            SessionKind::Vscode => self.open_vscode_session(pi, fi).map(|()| None),
+            _ => bail!(AutomationError::validation("Unsupported session type")),
         }
     }
 
-    fn add_terminal_session_for_picker(&mut self, pi: usize, fi: usize) -> Result<()> {
-        let feature = match self
+    /// The feature a session is being added to, which must be running.
+    fn running_feature_mut(&mut self, pi: usize, fi: usize) -> Result<&mut Feature> {
+        let feature = self
             .store
             .projects
             .get_mut(pi)
             .and_then(|p| p.features.get_mut(fi))
-        {
-            Some(f) => f,
-            None => return Ok(()),
-        };
-
+            .ok_or_else(|| anyhow::anyhow!("Feature not found"))?;
         if !TmuxManager::session_exists(&feature.tmux_session) {
-            self.message = Some("Error: Feature must be running to add a session".into());
-            return Ok(());
+            bail!(AutomationError::invalid_state(
+                "Feature must be running to add a session",
+            ));
         }
+        Ok(feature)
+    }
+
+    fn add_terminal_session(&mut self, pi: usize, fi: usize) -> Result<usize> {
+        let feature = self.running_feature_mut(pi, fi)?;
 
         let workdir = feature.workdir.clone();
         let tmux_session = feature.tmux_session.clone();
         let session = feature.add_session(SessionKind::Terminal);
         let window = session.tmux_window.clone();
-        let label = session.label.clone();
 
         TmuxManager::create_window(&tmux_session, &window, &workdir)?;
 
         feature.collapsed = false;
         let si = feature.sessions.len() - 1;
-        self.selection = Selection::Session(pi, fi, si);
         self.save()?;
-        self.message = Some(format!("Added '{}'", label));
-
-        Ok(())
+        Ok(si)
     }
 
-    fn add_nvim_session_for_picker(&mut self, pi: usize, fi: usize) -> Result<()> {
+    fn add_nvim_session(&mut self, pi: usize, fi: usize) -> Result<usize> {
         if std::process::Command::new("nvim")
             .arg("--version")
             .stdout(std::process::Stdio::null())
@@ -355,44 +389,29 @@ impl App {
             .status()
             .is_err()
         {
-            self.message = Some("Error: nvim is not installed".into());
-            return Ok(());
+            bail!(AutomationError::new(
+                AutomationErrorCode::OperationFailed,
+                "nvim is not installed",
+            ));
         }
 
-        let feature = match self
-            .store
-            .projects
-            .get_mut(pi)
-            .and_then(|p| p.features.get_mut(fi))
-        {
-            Some(f) => f,
-            None => return Ok(()),
-        };
-
-        if !TmuxManager::session_exists(&feature.tmux_session) {
-            self.message = Some("Error: Feature must be running to add a session".into());
-            return Ok(());
-        }
+        let feature = self.running_feature_mut(pi, fi)?;
 
         let workdir = feature.workdir.clone();
         let tmux_session = feature.tmux_session.clone();
         let session = feature.add_session(SessionKind::Nvim);
         let window = session.tmux_window.clone();
-        let label = session.label.clone();
 
         TmuxManager::create_window(&tmux_session, &window, &workdir)?;
         TmuxManager::send_keys(&tmux_session, &window, "nvim")?;
 
         feature.collapsed = false;
         let si = feature.sessions.len() - 1;
-        self.selection = Selection::Session(pi, fi, si);
         self.save()?;
-        self.message = Some(format!("Added '{}'", label));
-
-        Ok(())
+        Ok(si)
     }
 
-    fn add_vscode_session_for_picker(&mut self, pi: usize, fi: usize) -> Result<()> {
+    fn open_vscode_session(&mut self, pi: usize, fi: usize) -> Result<()> {
         if std::process::Command::new("code")
             .arg("--version")
             .stdout(std::process::Stdio::null())
@@ -400,54 +419,38 @@ impl App {
             .status()
             .is_err()
         {
-            self.message = Some("Error: code (VSCode CLI) is not installed".into());
-            return Ok(());
+            bail!(AutomationError::new(
+                AutomationErrorCode::OperationFailed,
+                "code (VSCode CLI) is not installed",
+            ));
         }
 
-        let feature = match self.store.projects.get(pi).and_then(|p| p.features.get(fi)) {
-            Some(f) => f,
-            None => return Ok(()),
-        };
+        let feature = self
+            .store
+            .projects
+            .get(pi)
+            .and_then(|p| p.features.get(fi))
+            .ok_or_else(|| anyhow::anyhow!("Feature not found"))?;
 
-        let workdir = feature.workdir.clone();
         std::process::Command::new("code")
-            .arg(&workdir)
+            .arg(&feature.workdir)
             .stdout(std::process::Stdio::null())
             .stderr(std::process::Stdio::null())
             .spawn()
             .map_err(|e| anyhow::anyhow!("Failed to launch VSCode: {}", e))?;
 
-        self.message = Some(format!("Opened VSCode in {}", workdir.display()));
-
         Ok(())
     }
 
-    fn add_agent_session_for_picker(
-        &mut self,
-        pi: usize,
-        fi: usize,
-        kind: SessionKind,
-    ) -> Result<()> {
+    fn add_agent_session(&mut self, pi: usize, fi: usize, kind: SessionKind) -> Result<usize> {
         let repo = self.store.projects[pi].repo.clone();
         let Some(agent) = agent_for_session_kind(&kind) else {
-            self.message = Some("Unsupported agent session type".into());
-            return Ok(());
+            bail!(AutomationError::validation(
+                "Unsupported agent session type"
+            ));
         };
 
-        let feature = match self
-            .store
-            .projects
-            .get_mut(pi)
-            .and_then(|p| p.features.get_mut(fi))
-        {
-            Some(f) => f,
-            None => return Ok(()),
-        };
-
-        if !TmuxManager::session_exists(&feature.tmux_session) {
-            self.message = Some("Error: Feature must be running to add a session".into());
-            return Ok(());
-        }
+        let feature = self.running_feature_mut(pi, fi)?;
 
         let workdir = feature.workdir.clone();
         let tmux_session = feature.tmux_session.clone();
@@ -457,7 +460,6 @@ impl App {
         ensure_review_claude_md(&workdir, feature.review);
         let session = feature.add_session(kind.clone());
         let window = session.tmux_window.clone();
-        let label = session.label.clone();
 
         TmuxManager::create_window(&tmux_session, &window, &workdir)?;
         let extra_refs: Vec<&str> = extra_args.iter().map(|s| s.as_str()).collect();
@@ -478,11 +480,8 @@ impl App {
 
         feature.collapsed = false;
         let si = feature.sessions.len() - 1;
-        self.selection = Selection::Session(pi, fi, si);
         self.save()?;
-        self.message = Some(format!("Added '{}'", label));
-
-        Ok(())
+        Ok(si)
     }
 
     pub fn add_claude_session(&mut self) -> Result<()> {
@@ -499,7 +498,7 @@ impl App {
         else {
             return Ok(());
         };
-        self.add_agent_session_for_picker(pi, fi, kind)
+        self.add_builtin_session_for_picker(pi, fi, kind)
     }
 
     pub fn remove_session(&mut self) -> Result<()> {
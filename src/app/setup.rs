@@ -10,6 +10,7 @@ const CLEAR_NOTIFY_SH: &str = include_str!("../../scripts/clear-notify.sh");
 const SAVE_PROMPT_SH: &str = include_str!("../../scripts/save-prompt.sh");
 const THINKING_START_SH: &str = include_str!("../../scripts/thinking-start.sh");
 const THINKING_STOP_SH: &str = include_str!("../../scripts/thinking-stop.sh");
+const SESSION_START_SH: &str = include_str!("../../scripts/session-start.sh");
 const TOOL_START_SH: &str = include_str!("../../scripts/tool-start.sh");
 const TOOL_STOP_SH: &str = include_str!("../../scripts/tool-stop.sh");
 const CODEX_NOTIFY_SH: &str = include_str!("../../scripts/codex-notify.sh");
@@ -131,6 +132,7 @@ fn claude_managed_commands() -> Vec<String> {
         "save-prompt.sh",
         "thinking-start.sh",
         "thinking-stop.sh",
+        "session-start.sh",
         "tool-start.sh",
         "tool-stop.sh",
     ]
@@ -159,6 +161,7 @@ fn remove_amf_claude_hooks(settings: &mut serde_json::Value, managed_commands: &
 
     let mut changed = false;
     for event in [
+        "SessionStart",
         "Stop",
         "Notification",
         "PreToolUse",
@@ -354,11 +357,13 @@ pub fn ensure_notify_scripts() {
     let save_prompt_path = config_dir.join("save-prompt.sh");
     let thinking_start_path = config_dir.join("thinking-start.sh");
     let thinking_stop_path = config_dir.join("thinking-stop.sh");
+    let session_start_path = config_dir.join("session-start.sh");
     let tool_start_path = config_dir.join("tool-start.sh");
     let tool_stop_path = config_dir.join("tool-stop.sh");
     let _ = std::fs::write(&save_prompt_path, SAVE_PROMPT_SH);
     let _ = std::fs::write(&thinking_start_path, THINKING_START_SH);
     let _ = std::fs::write(&thinking_stop_path, THINKING_STOP_SH);
+    let _ = std::fs::write(&session_start_path, SESSION_START_SH);
     let _ = std::fs::write(&tool_start_path, TOOL_START_SH);
     let _ = std::fs::write(&tool_stop_path, TOOL_STOP_SH);
     let codex_diff_review_path = config_dir.join("codex-diff-review.sh");
@@ -371,6 +376,8 @@ pub fn ensure_notify_scripts() {
             std::fs::set_permissions(&thinking_start_path, std::fs::Permissions::from_mode(0o755));
         let _ =
             std::fs::set_permissions(&thinking_stop_path, std::fs::Permissions::from_mode(0o755));
+        let _ =
+            std::fs::set_permissions(&session_start_path, std::fs::Permissions::from_mode(0o755));
         let _ = std::fs::set_permissions(&tool_start_path, std::fs::Permissions::from_mode(0o755));
         let _ = std::fs::set_permissions(&tool_stop_path, std::fs::Permissions::from_mode(0o755));
         let _ = std::fs::set_permissions(
@@ -862,6 +869,10 @@ pub fn ensure_notification_hooks_with_config(
         .join("thinking-stop.sh")
         .to_string_lossy()
         .into_owned();
+    let session_start_cmd = config_dir
+        .join("session-start.sh")
+        .to_string_lossy()
+        .into_owned();
     let tool_start_cmd = config_dir
         .join("tool-start.sh")
         .to_string_lossy()
@@ -881,6 +892,18 @@ pub fn ensure_notification_hooks_with_config(
     let mut settings = read_json_object(&settings_path);
     remove_amf_claude_hooks(&mut settings, &managed_commands);
 
+    // SessionStart: report the agent ready for queued prompts.
+    push_claude_hook_entry(
+        &mut settings,
+        "SessionStart",
+        serde_json::json!({
+            "matcher": "",
+            "hooks": [
+                { "type": "command", "command": session_start_cmd }
+            ]
+        }),
+    );
+
     // Stop: clear active thinking + write stop notification.
     push_claude_hook_entry(
         &mut settings,
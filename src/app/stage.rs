@@ -0,0 +1,287 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::Result;
+
+use super::*;
+use crate::stage::StageSide;
+
+impl App {
+    /// Switch the diff viewer between the branch diff and staging mode,
+    /// which shows the index so hunks and lines can be staged one by one.
+    pub fn diff_viewer_toggle_staging(&mut self) {
+        let AppMode::DiffViewer(state) = &mut self.mode else {
+            return;
+        };
+        if state.staging.take().is_some() {
+            self.refresh_diff_viewer();
+            return;
+        }
+        if state.return_to.is_some() || !state.members.is_empty() {
+            self.message =
+                Some("Staging is only available for a single repository's own diff".into());
+            return;
+        }
+        state.staging = Some(StagingState::new(StageSide::Unstaged));
+        state.focus = DiffViewerFocus::Patch;
+        self.reload_staging();
+    }
+
+    /// Show staged changes instead of unstaged ones, or the reverse.
+    pub fn diff_viewer_switch_stage_side(&mut self) {
+        if let AppMode::DiffViewer(state) = &mut self.mode
+            && let Some(staging) = &mut state.staging
+        {
+            *staging = StagingState::new(staging.side.toggled());
+            self.reload_staging();
+        }
+    }
+
+    pub fn diff_viewer_move_stage_cursor(&mut self, delta: isize) {
+        if let AppMode::DiffViewer(state) = &mut self.mode
+            && let Some(file) = state.files.get(state.selected_file)
+            && let Some(staging) = &mut state.staging
+        {
+            let last = crate::stage::change_lines(file).len().saturating_sub(1);
+            staging.cursor = staging.cursor.saturating_add_signed(delta).min(last);
+        }
+    }
+
+    pub fn diff_viewer_stage_cursor_to_end(&mut self, end: bool) {
+        self.diff_viewer_move_stage_cursor(if end { isize::MAX } else { isize::MIN });
+    }
+
+    /// Move the staging cursor to the first change of the next or
+    /// previous hunk.
+    pub fn diff_viewer_jump_hunk(&mut self, forward: bool) {
+        if let AppMode::DiffViewer(state) = &mut self.mode
+            && let Some(file) = state.files.get(state.selected_file)
+            && let Some(staging) = &mut state.staging
+        {
+            let positions = crate::stage::change_lines(file);
+            let Some(&(hunk, _)) = positions.get(staging.cursor) else {
+                return;
+            };
+            let target = if forward {
+                positions.iter().position(|(h, _)| *h > hunk)
+            } else {
+                positions
+                    .iter()
+                    .position(|(h, _)| hunk > 0 && *h == hunk - 1)
+            };
+            if let Some(target) = target {
+                staging.cursor = target;
+            }
+        }
+    }
+
+    /// Pick or unpick the changed line under the staging cursor.
+    pub fn diff_viewer_toggle_mark(&mut self) {
+        if let AppMode::DiffViewer(state) = &mut self.mode
+            && let Some(file) = state.files.get(state.selected_file)
+            && let Some(staging) = &mut state.staging
+            && let Some(position) = crate::stage::change_lines(file).get(staging.cursor)
+            && !staging.marked.remove(position)
+        {
+            staging.marked.insert(*position);
+            let last = crate::stage::change_lines(file).len().saturating_sub(1);
+            staging.cursor = (staging.cursor + 1).min(last);
+        }
+    }
+
+    /// Stage (or on the staged side, unstage) the picked lines, the hunk
+    /// under the cursor when nothing is picked, or the whole file when the
+    /// file list has focus.
+    pub fn diff_viewer_apply_stage(&mut self) {
+        let AppMode::DiffViewer(state) = &self.mode else {
+            return;
+        };
+        let (Some(staging), Some(file)) = (&state.staging, state.files.get(state.selected_file))
+        else {
+            return;
+        };
+        let side = staging.side;
+        let verb = match side {
+            StageSide::Unstaged => "Staged",
+            StageSide::Staged => "Unstaged",
+        };
+
+        let result = if state.focus == DiffViewerFocus::FileList {
+            crate::stage::apply_file(&state.workdir, &file.path, side)
+                .map(|()| format!("{} {}", verb, file.path))
+        } else if staging.marked.is_empty() {
+            crate::stage::change_lines(file)
+                .get(staging.cursor)
+                .map(|(hunk, _)| *hunk)
+                .ok_or_else(|| anyhow::anyhow!("No change under the cursor"))
+                .and_then(|hunk| crate::stage::partial_patch(file, hunk, None, side))
+                .and_then(|patch| crate::stage::apply(&state.workdir, &patch, side))
+                .map(|()| format!("{} hunk in {}", verb, file.path))
+        } else {
+            let mut by_hunk: BTreeMap<usize, BTreeSet<usize>> = BTreeMap::new();
+            for (hunk, line) in &staging.marked {
+                by_hunk.entry(*hunk).or_default().insert(*line);
+            }
+            // Later hunks first so earlier hunks keep their line numbers.
+            by_hunk
+                .iter()
+                .rev()
+                .try_for_each(|(hunk, lines)| {
+                    let patch = crate::stage::partial_patch(file, *hunk, Some(lines), side)?;
+                    crate::stage::apply(&state.workdir, &patch, side)
+                })
+                .map(|()| format!("{} {} line(s) in {}", verb, staging.marked.len(), file.path))
+        };
+
+        self.message = Some(match result {
+            Ok(message) => message,
+            Err(err) => format!("Error: {}", err),
+        });
+        self.reload_staging();
+    }
+
+    /// Open the commit message editor over the staging view.
+    pub fn open_commit_dialog(&mut self) {
+        let workdir = match &self.mode {
+            AppMode::DiffViewer(state) if state.staging.is_some() => state.workdir.clone(),
+            _ => return,
+        };
+        match crate::stage::load(&workdir, StageSide::Staged) {
+            Ok(staged) if staged.is_empty() => {
+                self.message = Some("Nothing is staged".into());
+            }
+            Ok(_) => {
+                if let AppMode::DiffViewer(viewer) =
+                    std::mem::replace(&mut self.mode, AppMode::Normal)
+                {
+                    self.mode = AppMode::CommitMessage(CommitMessageState::new(viewer));
+                }
+            }
+            Err(err) => self.message = Some(format!("Error: {}", err)),
+        }
+    }
+
+    /// Ask Claude for a message describing the staged diff. The result
+    /// replaces the editor's text when [`App::poll_commit_draft`] sees it.
+    pub fn commit_dialog_draft(&mut self) {
+        let AppMode::CommitMessage(state) = &mut self.mode else {
+            return;
+        };
+        if state.draft_rx.is_some() {
+            return;
+        }
+        let workdir = state.viewer.workdir.clone();
+        let (tx, rx) = std::sync::mpsc::channel();
+        state.draft_rx = Some(rx);
+        std::thread::spawn(move || {
+            let _ = tx.send(crate::stage::draft_message(&workdir));
+        });
+        self.message = Some("Drafting commit message...".into());
+    }
+
+    /// Returns `true` when a drafted message arrived.
+    pub fn poll_commit_draft(&mut self) -> bool {
+        let AppMode::CommitMessage(state) = &mut self.mode else {
+            return false;
+        };
+        let Some(rx) = &state.draft_rx else {
+            return false;
+        };
+        let draft = match rx.try_recv() {
+            Ok(draft) => draft,
+            Err(std::sync::mpsc::TryRecvError::Empty) => return false,
+            Err(std::sync::mpsc::TryRecvError::Disconnected) => {
+                Err(anyhow::anyhow!("Commit message draft was interrupted"))
+            }
+        };
+        state.draft_rx = None;
+        match draft {
+            Ok(message) => {
+                state.editor.clear();
+                state.editor.insert_str(&message);
+                state.request_cursor_scroll();
+                self.message = None;
+            }
+            Err(err) => self.message = Some(format!("Error: {}", err)),
+        }
+        true
+    }
+
+    pub fn confirm_commit_dialog(&mut self) -> Result<()> {
+        let (workdir, message) = match &self.mode {
+            AppMode::CommitMessage(state) => (
+                state.viewer.workdir.clone(),
+                state.editor.text().trim().to_string(),
+            ),
+            _ => return Ok(()),
+        };
+        match crate::stage::commit(&workdir, &message) {
+            Ok(hash) => {
+                self.cancel_commit_dialog();
+                self.reload_staging();
+                let subject = message.lines().next().unwrap_or_default();
+                self.message = Some(format!("Committed {} {}", hash, subject));
+            }
+            Err(err) => self.message = Some(format!("Error: {}", err)),
+        }
+        Ok(())
+    }
+
+    pub fn cancel_commit_dialog(&mut self) {
+        if let AppMode::CommitMessage(state) = std::mem::replace(&mut self.mode, AppMode::Normal) {
+            self.mode = AppMode::DiffViewer(state.viewer);
+        }
+    }
+
+    /// Reload the staging side shown by the diff viewer, keeping the
+    /// selected file when it still has changes.
+    pub(super) fn reload_staging(&mut self) {
+        let AppMode::DiffViewer(state) = &mut self.mode else {
+            return;
+        };
+        let Some(staging) = &mut state.staging else {
+            return;
+        };
+        let selected_path = state
+            .files
+            .get(state.selected_file)
+            .map(|file| file.path.clone());
+
+        state.branch = match crate::worktree::WorktreeManager::current_branch(&state.workdir) {
+            Ok(Some(branch)) => branch,
+            _ => "HEAD".to_string(),
+        };
+        state.base_ref = match staging.side {
+            StageSide::Unstaged => "index".to_string(),
+            StageSide::Staged => "HEAD".to_string(),
+        };
+        state.base_commit.clear();
+        state.patch_scroll = 0;
+        match crate::stage::load(&state.workdir, staging.side) {
+            Ok(files) => {
+                state.error = None;
+                state.files = files;
+                let same_file = selected_path
+                    .and_then(|path| state.files.iter().position(|file| file.path == path));
+                state.selected_file = same_file.unwrap_or_else(|| {
+                    state.selected_file.min(state.files.len().saturating_sub(1))
+                });
+                let changes = state
+                    .files
+                    .get(state.selected_file)
+                    .map(|file| crate::stage::change_lines(file).len())
+                    .unwrap_or(0);
+                if same_file.is_none() {
+                    staging.cursor = 0;
+                }
+                staging.cursor = staging.cursor.min(changes.saturating_sub(1));
+            }
+            Err(err) => {
+                state.files.clear();
+                state.selected_file = 0;
+                staging.cursor = 0;
+                state.error = Some(err.to_string());
+            }
+        }
+        staging.marked.clear();
+    }
+}
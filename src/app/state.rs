@@ -30,11 +30,16 @@ pub struct ForkFeatureState {
     pub review: bool,
     pub enable_chrome: bool,
     pub include_context: bool,
+    /// Seed the fork with this checkpoint of the source instead of its
+    /// current uncommitted changes.
+    pub checkpoint: Option<crate::checkpoint::Checkpoint>,
 }
 
 #[derive(Debug, Clone)]
 pub enum Selection {
     Project(usize),
+    /// Header row of a project's archived features.
+    ArchivedFeatures(usize),
     Feature(usize, usize),
     Session(usize, usize, usize),
 }
@@ -184,6 +189,12 @@ pub struct RenameFeatureState {
     pub input: String,
 }
 
+pub struct EditFeatureTagsState {
+    pub project_idx: usize,
+    pub feature_idx: usize,
+    pub input: String,
+}
+
 pub struct SessionConfigState {
     pub project_idx: usize,
     pub feature_idx: usize,
@@ -231,6 +242,30 @@ pub struct CodexSessionPickerState {
     pub workdir: PathBuf,
 }
 
+/// A git worktree or tmux session that exists on disk but is not
+/// tracked by any feature yet.
+#[derive(Debug, Clone)]
+pub struct ImportCandidate {
+    pub name: String,
+    pub branch: String,
+    pub workdir: PathBuf,
+    pub is_worktree: bool,
+    /// Untracked tmux session started in `workdir`, adopted as-is.
+    pub tmux_session: Option<String>,
+    /// Windows of `tmux_session` with the session kind detected from
+    /// their name and running command.
+    pub windows: Vec<(String, SessionKind)>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ImportPickerState {
+    pub project_idx: usize,
+    pub project_name: String,
+    pub candidates: Vec<ImportCandidate>,
+    pub selected: usize,
+    pub checked: Vec<bool>,
+}
+
 #[derive(Clone)]
 pub struct BookmarkPickerState {
     pub selected: usize,
@@ -252,8 +287,16 @@ pub enum DiffViewerLayout {
 
 #[derive(Clone)]
 pub struct DiffViewerState {
-    pub from_view: ViewState,
+    pub from_view: Option<ViewState>,
+    /// Dialog to go back to when the viewer was opened from one.
+    pub return_to: Option<DiffViewerReturn>,
+    /// Set while the viewer shows the index instead of the branch diff.
+    pub staging: Option<StagingState>,
     pub workdir: PathBuf,
+    /// Member worktrees of a multi-repo feature, each diffed on its own.
+    pub members: Vec<PathBuf>,
+    /// Branch of the feature this one is stacked on, used as the diff base.
+    pub parent_branch: Option<String>,
     pub branch: String,
     pub base_ref: String,
     pub base_commit: String,
@@ -266,10 +309,14 @@ pub struct DiffViewerState {
 }
 
 impl DiffViewerState {
-    pub fn new(from_view: ViewState, workdir: PathBuf) -> Self {
+    pub fn new(from_view: Option<ViewState>, workdir: PathBuf) -> Self {
         Self {
             from_view,
+            return_to: None,
+            staging: None,
             workdir,
+            members: Vec::new(),
+            parent_branch: None,
             branch: String::new(),
             base_ref: String::new(),
             base_commit: String::new(),
@@ -283,6 +330,128 @@ impl DiffViewerState {
     }
 }
 
+/// Cursor and picked lines of the diff viewer's staging mode.
+#[derive(Debug, Clone)]
+pub struct StagingState {
+    pub side: crate::stage::StageSide,
+    /// Index into `stage::change_lines` of the selected file.
+    pub cursor: usize,
+    /// `(hunk, line)` positions picked for a partial stage.
+    pub marked: std::collections::BTreeSet<(usize, usize)>,
+}
+
+impl StagingState {
+    pub fn new(side: crate::stage::StageSide) -> Self {
+        Self {
+            side,
+            cursor: 0,
+            marked: std::collections::BTreeSet::new(),
+        }
+    }
+}
+
+pub struct CommitMessageState {
+    /// Staging view to go back to after committing or cancelling.
+    pub viewer: DiffViewerState,
+    pub editor: TextEditor,
+    pub scroll_offset: usize,
+    pub sync_scroll_to_cursor: bool,
+    /// Message Claude is drafting from the staged diff.
+    pub draft_rx: Option<std::sync::mpsc::Receiver<anyhow::Result<String>>>,
+}
+
+impl CommitMessageState {
+    pub fn new(viewer: DiffViewerState) -> Self {
+        Self {
+            viewer,
+            editor: TextEditor::new(String::new()),
+            scroll_offset: 0,
+            sync_scroll_to_cursor: true,
+            draft_rx: None,
+        }
+    }
+
+    pub fn request_cursor_scroll(&mut self) {
+        self.sync_scroll_to_cursor = true;
+    }
+}
+
+#[derive(Clone)]
+pub enum DiffViewerReturn {
+    /// Conflicts of a land preview.
+    Land(LandFeatureState),
+    /// Files another feature also changes.
+    Overlaps(OverlapDialogState),
+    /// Changes since a checkpoint.
+    Checkpoints(CheckpointDialogState),
+}
+
+#[derive(Debug, Clone)]
+pub struct LandFeatureState {
+    pub project_name: String,
+    pub feature_name: String,
+    pub preview: crate::land::LandPreview,
+    /// Index into `LandStrategy::ALL`.
+    pub selected: usize,
+    /// Set once the feature has landed; the dialog then offers to archive
+    /// or delete it.
+    pub landed: Option<crate::land::Landed>,
+}
+
+impl LandFeatureState {
+    pub fn strategy(&self) -> crate::land::LandStrategy {
+        crate::land::LandStrategy::ALL[self.selected]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OverlapDialogState {
+    pub project_name: String,
+    pub feature_name: String,
+    pub entries: Vec<OverlapEntry>,
+    /// Index into the files of all entries, in order.
+    pub selected: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct OverlapEntry {
+    /// The other feature changing the same files.
+    pub feature_name: String,
+    /// Left is this dialog's feature, right the other one.
+    pub files: Vec<crate::overlap::OverlapFile>,
+}
+
+impl OverlapDialogState {
+    pub fn file_count(&self) -> usize {
+        self.entries.iter().map(|entry| entry.files.len()).sum()
+    }
+
+    /// The highlighted file and the entry it belongs to.
+    pub fn selected_file(&self) -> Option<(&OverlapEntry, &crate::overlap::OverlapFile)> {
+        self.entries
+            .iter()
+            .flat_map(|entry| entry.files.iter().map(move |file| (entry, file)))
+            .nth(self.selected)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CheckpointDialogState {
+    pub project_name: String,
+    pub feature_name: String,
+    pub feature_id: String,
+    pub workdir: PathBuf,
+    /// Newest first.
+    pub checkpoints: Vec<crate::checkpoint::Checkpoint>,
+    pub selected: usize,
+}
+
+impl CheckpointDialogState {
+    pub fn selected_checkpoint(&self) -> Option<&crate::checkpoint::Checkpoint> {
+        self.checkpoints.get(self.selected)
+    }
+}
+
 #[derive(Clone)]
 pub struct SteeringPromptState {
     pub view: ViewState,
@@ -336,6 +505,48 @@ impl SteeringPromptState {
     }
 }
 
+#[derive(Clone)]
+pub struct FeatureNotesState {
+    pub project_idx: usize,
+    pub feature_idx: usize,
+    pub feature_name: String,
+    pub editor: TextEditor,
+    pub scroll_offset: usize,
+    pub sync_scroll_to_cursor: bool,
+}
+
+impl FeatureNotesState {
+    pub fn new(
+        project_idx: usize,
+        feature_idx: usize,
+        feature_name: String,
+        notes: String,
+    ) -> Self {
+        Self {
+            project_idx,
+            feature_idx,
+            feature_name,
+            editor: TextEditor::with_vim(notes),
+            scroll_offset: 0,
+            sync_scroll_to_cursor: true,
+        }
+    }
+
+    pub fn request_cursor_scroll(&mut self) {
+        self.sync_scroll_to_cursor = true;
+    }
+
+    pub fn scroll_up(&mut self, lines: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_sub(lines);
+        self.sync_scroll_to_cursor = false;
+    }
+
+    pub fn scroll_down(&mut self, lines: usize) {
+        self.scroll_offset = self.scroll_offset.saturating_add(lines);
+        self.sync_scroll_to_cursor = false;
+    }
+}
+
 #[derive(Clone)]
 pub struct LatestPromptState {
     pub view: ViewState,
@@ -361,6 +572,8 @@ pub enum AppMode {
     SessionSwitcher(super::SessionSwitcherState),
     RenamingSession(RenameSessionState),
     RenamingFeature(RenameFeatureState),
+    EditingFeatureTags(EditFeatureTagsState),
+    EditingFeatureNotes(FeatureNotesState),
     SessionConfig(SessionConfigState),
     ProjectAgentConfig(ProjectAgentConfigState),
     BrowsingPath(Box<BrowsePathState>),
@@ -382,7 +595,9 @@ pub enum AppMode {
         workdir: PathBuf,
     },
     BookmarkPicker(BookmarkPickerState),
+    ImportPicker(ImportPickerState),
     DiffViewer(DiffViewerState),
+    CommitMessage(CommitMessageState),
     SteeringPrompt(SteeringPromptState),
     SessionPicker(SessionPickerState),
     DiffReviewPrompt(DiffReviewState),
@@ -390,6 +605,9 @@ pub enum AppMode {
     HookPrompt(HookPromptState),
     LatestPrompt(LatestPromptState),
     ForkingFeature(ForkFeatureState),
+    LandingFeature(LandFeatureState),
+    ReviewingOverlaps(OverlapDialogState),
+    ReviewingCheckpoints(CheckpointDialogState),
     ThemePicker(ThemePickerState),
     SyntaxLanguagePicker(SyntaxLanguagePickerState),
     DebugLog(DebugLogState),
@@ -608,6 +826,8 @@ pub enum HookNext {
         create_terminal: bool,
         enable_chrome: bool,
         steering_enabled: bool,
+        /// Feature the new worktree is stacked on.
+        parent_id: Option<String>,
     },
     StartFeature {
         pi: usize,
@@ -659,6 +879,12 @@ pub struct DeletingFeatureState {
     pub is_worktree: bool,
     pub repo: PathBuf,
     pub workdir: PathBuf,
+    /// `(repo, worktree)` pairs of a multi-repo feature still to remove
+    /// after `workdir`.
+    pub pending_worktrees: Vec<(PathBuf, PathBuf)>,
+    /// Parent directory of a multi-repo feature, removed once its member
+    /// worktrees are gone.
+    pub workspace_dir: Option<PathBuf>,
     pub stage: DeleteStage,
     pub child: Option<Child>,
     pub output: String,
@@ -672,41 +898,9 @@ impl DeletingFeatureState {
     }
 }
 
-pub struct BackgroundDeletion {
-    pub project_name: String,
-    pub feature_name: String,
-    pub tmux_session: String,
-    pub is_worktree: bool,
-    pub repo: PathBuf,
-    pub workdir: PathBuf,
-    pub stage: DeleteStage,
-    pub child: Option<Child>,
-    pub output: String,
-    pub output_rx: Option<std::sync::mpsc::Receiver<String>>,
-    pub error: Option<String>,
-}
-
-impl BackgroundDeletion {
-    pub fn key(&self) -> String {
-        format!("{}/{}", self.project_name, self.feature_name)
-    }
-
-    pub fn from_deleting_state(state: DeletingFeatureState) -> Self {
-        Self {
-            project_name: state.project_name,
-            feature_name: state.feature_name,
-            tmux_session: state.tmux_session,
-            is_worktree: state.is_worktree,
-            repo: state.repo,
-            workdir: state.workdir,
-            stage: state.stage,
-            child: state.child,
-            output: state.output,
-            output_rx: state.output_rx,
-            error: state.error,
-        }
-    }
-}
+/// A feature deletion moved off the dashboard; it advances through the
+/// same stages as the foreground one.
+pub type BackgroundDeletion = DeletingFeatureState;
 
 pub struct BackgroundHook {
     pub script: String,
@@ -975,6 +1169,8 @@ pub enum SessionFilter {
     Terminal,
     Nvim,
     Vscode,
+    /// Only features carrying this tag; their sessions are all shown.
+    Tag(String),
 }
 
 impl SessionFilter {
@@ -988,20 +1184,41 @@ impl SessionFilter {
         SessionFilter::Vscode,
     ];
 
-    pub fn display_name(&self) -> &str {
+    pub fn display_name(&self) -> String {
         match self {
-            SessionFilter::All => "all",
-            SessionFilter::Claude => "claude",
-            SessionFilter::Opencode => "opencode",
-            SessionFilter::Codex => "codex",
-            SessionFilter::Terminal => "terminal",
-            SessionFilter::Nvim => "nvim",
-            SessionFilter::Vscode => "vscode",
+            SessionFilter::All => "all".to_string(),
+            SessionFilter::Claude => "claude".to_string(),
+            SessionFilter::Opencode => "opencode".to_string(),
+            SessionFilter::Codex => "codex".to_string(),
+            SessionFilter::Terminal => "terminal".to_string(),
+            SessionFilter::Nvim => "nvim".to_string(),
+            SessionFilter::Vscode => "vscode".to_string(),
+            SessionFilter::Tag(tag) => format!("tag:{}", tag),
         }
     }
 
     pub fn next(&self) -> Self {
+        self.next_with_tags(&[])
+    }
+
+    /// Cycles through the session kinds, then one `Tag` filter per entry
+    /// in `tags`, then back to `All`.
+    pub fn next_with_tags(&self, tags: &[String]) -> Self {
         let variants = Self::ALL.as_slice();
+        let tag_idx = match self {
+            SessionFilter::Tag(tag) => Some(
+                tags.iter()
+                    .position(|t| t == tag)
+                    .map_or(tags.len(), |i| i + 1),
+            ),
+            _ if self == variants.last().unwrap() => Some(0),
+            _ => None,
+        };
+        if let Some(ti) = tag_idx {
+            return tags
+                .get(ti)
+                .map_or(SessionFilter::All, |tag| SessionFilter::Tag(tag.clone()));
+        }
         let idx = variants.iter().position(|v| v == self).unwrap_or(0);
         variants[(idx + 1) % variants.len()].clone()
     }
@@ -1023,6 +1240,8 @@ pub struct SearchMatch {
 #[derive(Debug, Clone)]
 pub enum VisibleItem {
     Project(usize),
+    /// Header row of a project's archived features.
+    ArchivedFeatures(usize),
     Feature(usize, usize),
     Session(usize, usize, usize),
 }
@@ -1054,6 +1273,21 @@ mod tests {
         assert_eq!(next, SessionFilter::ALL[0]);
     }
 
+    #[test]
+    fn session_filter_cycles_through_tags_after_session_kinds() {
+        let tags = vec!["bug".to_string(), "ui".to_string()];
+        let after_kinds = SessionFilter::Vscode.next_with_tags(&tags);
+        assert_eq!(after_kinds, SessionFilter::Tag("bug".to_string()));
+        let next = after_kinds.next_with_tags(&tags);
+        assert_eq!(next, SessionFilter::Tag("ui".to_string()));
+        assert_eq!(next.next_with_tags(&tags), SessionFilter::All);
+        // A filter on a tag that is no longer in use resets.
+        assert_eq!(
+            SessionFilter::Tag("gone".to_string()).next_with_tags(&tags),
+            SessionFilter::All
+        );
+    }
+
     #[test]
     fn session_filter_all_has_seven_variants() {
         assert_eq!(SessionFilter::ALL.len(), 7);
@@ -786,7 +786,7 @@ impl App {
         None
     }
 
-    fn get_agent_window(feature: &Feature) -> Option<String> {
+    pub(crate) fn get_agent_window(feature: &Feature) -> Option<String> {
         let target_kind = match feature.agent {
             AgentKind::Claude => SessionKind::Claude,
             AgentKind::Opencode => SessionKind::Opencode,
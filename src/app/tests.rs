@@ -1,3 +1,4 @@
+use super::events::EventSnapshot;
 use super::setup::{
     cleanup_agent_injected_files, ensure_notification_hooks, strip_between_markers,
 };
@@ -7,7 +8,14 @@ use super::util::{
     latest_prompt_path, read_all_prompts, read_latest_prompt, shorten_path, slugify,
 };
 use super::*;
-use crate::automation::{CreateBatchFeaturesRequest, CreateFeatureRequest, CreateProjectRequest};
+use crate::automation::{
+    AUTOMATION_ACTIONS, AUTOMATION_PROTOCOL_VERSION, AddSessionRequest, AutomationError,
+    AutomationErrorCode, AutomationEvent, BatchFeatureSpec, CreateBatchFeaturesRequest,
+    CreateFeatureRequest, CreateProjectRequest, FeatureLifecycleRequest, ForkFeatureRequest,
+    InputDecision, LandFeatureRequest, ListInputsRequest, ListStateRequest, ReadDiffRequest,
+    RenameFeatureRequest, ResolveInputRequest, RestackFeatureRequest, SendPromptRequest,
+    SetFeatureReadyRequest, SetFeatureTagsRequest, WaitForIdleRequest, WaitOutcome,
+};
 use crate::extension::{ExtensionConfig, HookConfig, HookPrompt, LifecycleHooks};
 use std::collections::HashMap;
 use std::sync::{
@@ -521,6 +529,12 @@ fn store_with_feature(status: ProjectStatus) -> ProjectStore {
         summary: None,
         summary_updated_at: None,
         nickname: None,
+        parent_id: None,
+        tags: Vec::new(),
+        notes: None,
+        archived_at: None,
+        archived_prompt: None,
+        repos: Vec::new(),
     };
     let project = Project {
         id: "proj-1".to_string(),
@@ -565,6 +579,12 @@ fn store_with_repo(repo: PathBuf, status: ProjectStatus) -> ProjectStore {
         summary: None,
         summary_updated_at: None,
         nickname: None,
+        parent_id: None,
+        tags: Vec::new(),
+        notes: None,
+        archived_at: None,
+        archived_prompt: None,
+        repos: Vec::new(),
     };
     let project = Project {
         id: "proj-1".to_string(),
@@ -833,6 +853,12 @@ fn visible_items_prioritizes_non_worktree_features() {
                 summary: None,
                 summary_updated_at: None,
                 nickname: None,
+                parent_id: None,
+                tags: Vec::new(),
+                notes: None,
+                archived_at: None,
+                archived_prompt: None,
+                repos: Vec::new(),
             },
             Feature {
                 id: "feat-repo".to_string(),
@@ -856,6 +882,12 @@ fn visible_items_prioritizes_non_worktree_features() {
                 summary: None,
                 summary_updated_at: None,
                 nickname: None,
+                parent_id: None,
+                tags: Vec::new(),
+                notes: None,
+                archived_at: None,
+                archived_prompt: None,
+                repos: Vec::new(),
             },
         ],
         created_at: now,
@@ -1540,6 +1572,12 @@ fn restore_claude_session_resizes_window_before_launch_when_viewport_known() {
         summary: None,
         summary_updated_at: None,
         nickname: None,
+        parent_id: None,
+        tags: Vec::new(),
+        notes: None,
+        archived_at: None,
+        archived_prompt: None,
+        repos: Vec::new(),
     };
     let store = ProjectStore {
         version: 4,
@@ -2039,6 +2077,12 @@ fn open_session_picker_selects_project_preferred_agent_by_default() {
         summary: None,
         summary_updated_at: None,
         nickname: None,
+        parent_id: None,
+        tags: Vec::new(),
+        notes: None,
+        archived_at: None,
+        archived_prompt: None,
+        repos: Vec::new(),
     };
     let project = Project {
         id: "proj-1".to_string(),
@@ -2117,6 +2161,12 @@ fn reload_extension_config_uses_project_repo_for_worktree_feature() {
         summary: None,
         summary_updated_at: None,
         nickname: None,
+        parent_id: None,
+        tags: Vec::new(),
+        notes: None,
+        archived_at: None,
+        archived_prompt: None,
+        repos: Vec::new(),
     };
     let project = Project {
         id: "proj-1".to_string(),
@@ -2247,6 +2297,8 @@ fn complete_deleting_feature_clears_sidebar_caches() {
         is_worktree: false,
         repo: repo.path().to_path_buf(),
         workdir: repo.path().to_path_buf(),
+        pending_worktrees: Vec::new(),
+        workspace_dir: None,
         stage: DeleteStage::Completed,
         child: None,
         output: String::new(),
@@ -2263,6 +2315,7 @@ fn complete_deleting_feature_clears_sidebar_caches() {
 
 // ── ensure_notification_hooks ─────────────────────────────
 
+use crate::git_fixture::{git_ok, init_repo_with_src};
 use tempfile::TempDir;
 
 fn read_settings(dir: &TempDir) -> serde_json::Value {
@@ -2314,6 +2367,18 @@ fn call_ensure_hooks(workdir: &TempDir, mode: VibeMode) {
     call_ensure_hooks_for(workdir, mode, AgentKind::Claude, true);
 }
 
+#[test]
+fn session_start_hook_reports_the_agent_ready() {
+    let workdir = TempDir::new().unwrap();
+    call_ensure_hooks(&workdir, VibeMode::Vibe);
+    let s = read_settings(&workdir);
+    let cmds = hook_commands_for(&s, "SessionStart");
+    assert!(
+        cmds.iter().any(|c| c.contains("session-start.sh")),
+        "SessionStart hook missing session-start.sh; got: {cmds:?}"
+    );
+}
+
 #[test]
 fn stop_hook_has_thinking_stop_and_notify() {
     let workdir = TempDir::new().unwrap();
@@ -2809,6 +2874,12 @@ fn store_with_worktree_agent(
         summary: None,
         summary_updated_at: None,
         nickname: None,
+        parent_id: None,
+        tags: Vec::new(),
+        notes: None,
+        archived_at: None,
+        archived_prompt: None,
+        repos: Vec::new(),
     };
     let project = Project {
         id: "proj-1".to_string(),
@@ -3015,6 +3086,12 @@ fn store_with_custom_session(workdir: &std::path::Path, session_id: &str) -> Pro
         summary: None,
         summary_updated_at: None,
         nickname: None,
+        parent_id: None,
+        tags: Vec::new(),
+        notes: None,
+        archived_at: None,
+        archived_prompt: None,
+        repos: Vec::new(),
     };
     let project = Project {
         id: "proj-1".to_string(),
@@ -3073,6 +3150,12 @@ fn store_with_codex_session(workdir: &std::path::Path, is_worktree: bool) -> Pro
         summary: None,
         summary_updated_at: None,
         nickname: None,
+        parent_id: None,
+        tags: Vec::new(),
+        notes: None,
+        archived_at: None,
+        archived_prompt: None,
+        repos: Vec::new(),
     };
     let project = Project {
         id: "proj-1".to_string(),
@@ -3178,6 +3261,12 @@ fn sync_session_status_shows_agent_token_usage() {
         summary: None,
         summary_updated_at: None,
         nickname: None,
+        parent_id: None,
+        tags: Vec::new(),
+        notes: None,
+        archived_at: None,
+        archived_prompt: None,
+        repos: Vec::new(),
     };
     let project = Project {
         id: "proj-1".to_string(),
@@ -3280,6 +3369,12 @@ fn sync_session_status_marks_discovered_codex_usage_as_inferred() {
         summary: None,
         summary_updated_at: None,
         nickname: None,
+        parent_id: None,
+        tags: Vec::new(),
+        notes: None,
+        archived_at: None,
+        archived_prompt: None,
+        repos: Vec::new(),
     };
     let project = Project {
         id: "proj-1".to_string(),
@@ -3367,6 +3462,12 @@ fn sync_session_status_skips_sidebar_reload_until_prompt_inputs_change() {
         summary: None,
         summary_updated_at: None,
         nickname: None,
+        parent_id: None,
+        tags: Vec::new(),
+        notes: None,
+        archived_at: None,
+        archived_prompt: None,
+        repos: Vec::new(),
     };
     let project = Project {
         id: "proj-1".to_string(),
@@ -4048,7 +4149,7 @@ fn contextual_syntax_install_returns_to_diff_viewer_and_refreshes() {
         Box::new(MockWorktreeOps::new()),
     );
     let mut diff_viewer = DiffViewerState::new(
-        ViewState::new(
+        Some(ViewState::new(
             "proj".into(),
             "feat".into(),
             "sess".into(),
@@ -4057,7 +4158,7 @@ fn contextual_syntax_install_returns_to_diff_viewer_and_refreshes() {
             SessionKind::Claude,
             VibeMode::Vibe,
             false,
-        ),
+        )),
         workdir.path().to_path_buf(),
     );
     diff_viewer.files = vec![crate::diff::DiffFile {
@@ -4309,6 +4410,12 @@ fn sync_session_status_skips_non_custom_sessions() {
         summary: None,
         summary_updated_at: None,
         nickname: None,
+        parent_id: None,
+        tags: Vec::new(),
+        notes: None,
+        archived_at: None,
+        archived_prompt: None,
+        repos: Vec::new(),
     };
     let project = Project {
         id: "proj-1".to_string(),
@@ -4511,6 +4618,12 @@ fn store_with_single_claude_session() -> ProjectStore {
         summary: None,
         summary_updated_at: None,
         nickname: None,
+        parent_id: None,
+        tags: Vec::new(),
+        notes: None,
+        archived_at: None,
+        archived_prompt: None,
+        repos: Vec::new(),
     };
     let project = Project {
         id: "proj-1".to_string(),
@@ -4699,6 +4812,9 @@ fn create_feature_automation_dry_run_returns_plan_without_mutating_store() {
         use_worktree: Some(true),
         enable_chrome: false,
         hook_choice: None,
+        prompt: None,
+        parent_feature: None,
+        extra_projects: Vec::new(),
         dry_run: true,
     };
 
@@ -4750,6 +4866,9 @@ fn create_feature_automation_dry_run_surfaces_hook_prompt_options() {
         use_worktree: Some(true),
         enable_chrome: false,
         hook_choice: None,
+        prompt: None,
+        parent_feature: None,
+        extra_projects: Vec::new(),
         dry_run: true,
     };
 
@@ -4783,6 +4902,9 @@ fn create_feature_automation_rejects_codex_vibeless_mode() {
         use_worktree: Some(true),
         enable_chrome: false,
         hook_choice: None,
+        prompt: None,
+        parent_feature: None,
+        extra_projects: Vec::new(),
         dry_run: true,
     };
 
@@ -4850,6 +4972,9 @@ fn create_feature_automation_creates_and_starts_feature() {
         use_worktree: Some(true),
         enable_chrome: false,
         hook_choice: None,
+        prompt: Some("Fix the login redirect\n".to_string()),
+        parent_feature: None,
+        extra_projects: Vec::new(),
         dry_run: false,
     };
 
@@ -4863,6 +4988,9 @@ fn create_feature_automation_creates_and_starts_feature() {
     assert!(app.store.projects[0].features[0].is_worktree);
     assert!(app.store.projects[0].features[0].review);
     assert_eq!(app.store.projects[0].features[0].sessions.len(), 1);
+    assert!(response.prompt_queued);
+    assert_eq!(app.queued_prompts.len(), 1);
+    assert_eq!(app.queued_prompts[0].tmux_session, "amf-feature-1");
 }
 
 #[test]
@@ -4899,6 +5027,7 @@ fn batch_feature_automation_dry_run_returns_plan_without_mutating_store() {
         mode: VibeMode::Vibe,
         review: false,
         enable_chrome: false,
+        features: Vec::new(),
         dry_run: true,
     };
 
@@ -4949,6 +5078,7 @@ fn batch_feature_automation_rejects_review_as_a_mode() {
         mode: VibeMode::Vibeless,
         review: true,
         enable_chrome: false,
+        features: Vec::new(),
         dry_run: true,
     };
 
@@ -5037,6 +5167,7 @@ fn batch_feature_automation_creates_project_and_starts_features() {
         mode: VibeMode::Vibe,
         review: false,
         enable_chrome: false,
+        features: Vec::new(),
         dry_run: false,
     };
 
@@ -5056,3 +5187,3236 @@ fn batch_feature_automation_creates_project_and_starts_features() {
             .all(|feature| feature.sessions.len() == 1)
     );
 }
+
+fn empty_store() -> ProjectStore {
+    ProjectStore {
+        version: 4,
+        projects: vec![],
+        session_bookmarks: vec![],
+        available_harnesses: vec![],
+        extra: HashMap::new(),
+    }
+}
+
+#[test]
+fn batch_feature_automation_resolves_entries_against_presets_and_defaults() {
+    let workspace = TempDir::new().unwrap();
+    let repo = workspace.path().join("repo");
+    std::fs::create_dir_all(repo.join(".amf")).unwrap();
+    std::fs::write(
+        repo.join(".amf").join("config.json"),
+        r#"{
+            "feature_presets": [
+                {
+                    "name": "bugfix",
+                    "branch_prefix": "fix-",
+                    "mode": "supervibe",
+                    "agent": "claude",
+                    "review": true,
+                    "plan_mode": true,
+                    "enable_chrome": false
+                }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let mut worktree = MockWorktreeOps::new();
+    let repo_clone = repo.clone();
+    worktree
+        .expect_repo_root()
+        .returning(move |_| Ok(repo_clone.clone()));
+    let mut app = App::new_for_test(
+        empty_store(),
+        Box::new(MockTmuxOps::new()),
+        Box::new(worktree),
+    );
+
+    let mut request: CreateBatchFeaturesRequest = serde_json::from_value(serde_json::json!({
+        "workspace_path": repo,
+        "project_name": "triage",
+        "agent": "codex",
+        "mode": "vibe",
+        "features": [
+            { "branch": "issue-a", "base_ref": "release", "prompt": "Fix issue A" },
+            { "preset": "bugfix", "prompt": "Fix issue B" },
+            { "preset": "bugfix", "agent": "codex", "mode": "vibe" }
+        ],
+        "dry_run": true
+    }))
+    .unwrap();
+
+    let response = app.create_batch_features_from_request(&request).unwrap();
+
+    assert!(response.dry_run);
+    let features = &response.features;
+    assert_eq!(features.len(), 3);
+    assert_eq!(features[0].branch, "issue-a");
+    assert_eq!(features[0].agent, AgentKind::Codex);
+    assert_eq!(features[0].mode, VibeMode::Vibe);
+    assert_eq!(features[0].base_ref.as_deref(), Some("release"));
+    assert_eq!(features[1].branch, "fix-2");
+    assert_eq!(features[1].agent, AgentKind::Claude);
+    assert_eq!(features[1].mode, VibeMode::SuperVibe);
+    assert_eq!(features[1].preset.as_deref(), Some("bugfix"));
+    assert_eq!(features[2].branch, "fix-3");
+    assert_eq!(features[2].agent, AgentKind::Codex);
+    assert!(features.iter().all(|feature| !feature.prompt_sent));
+    assert!(app.store.projects.is_empty());
+
+    request.features[2].preset = Some("missing".to_string());
+    let err = app
+        .create_batch_features_from_request(&request)
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("Unknown feature preset 'missing'"),
+        "got: {err}"
+    );
+
+    request.features[2] = BatchFeatureSpec {
+        branch: Some("issue-a".to_string()),
+        ..Default::default()
+    };
+    let err = app
+        .create_batch_features_from_request(&request)
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("appears more than once"),
+        "got: {err}"
+    );
+}
+
+#[test]
+fn batch_feature_automation_branches_from_base_ref_and_sends_entry_prompts() {
+    let workspace = TempDir::new().unwrap();
+    let repo = workspace.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    let worktree_a = repo.join(".worktrees").join("issue-a");
+    let worktree_b = repo.join(".worktrees").join("issue-b");
+
+    let mut worktree = MockWorktreeOps::new();
+    let repo_clone = repo.clone();
+    worktree
+        .expect_repo_root()
+        .times(1)
+        .returning(move |_| Ok(repo_clone.clone()));
+    let worktree_a_clone = worktree_a.clone();
+    worktree
+        .expect_create_from()
+        .times(1)
+        .withf(|_, name, branch, base| {
+            name == "issue-a" && branch == "issue-a" && base == "release"
+        })
+        .returning(move |_, _, _, _| Ok(worktree_a_clone.clone()));
+    let worktree_b_clone = worktree_b.clone();
+    worktree
+        .expect_create()
+        .times(1)
+        .withf(|_, name, branch| name == "issue-b" && branch == "issue-b")
+        .returning(move |_, _, _| Ok(worktree_b_clone.clone()));
+
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_session_exists().times(2).returning(|_| false);
+    tmux.expect_create_session_with_window()
+        .times(2)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_set_session_env()
+        .times(2)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_launch_codex()
+        .times(2)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_select_window()
+        .times(2)
+        .returning(|_, _| Ok(()));
+    tmux.expect_paste_text()
+        .times(1)
+        .withf(|session, window, text| {
+            session == "amf-issue-a" && window == "codex" && text == "Fix issue A"
+        })
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_send_key_name()
+        .times(1)
+        .withf(|session, window, key| {
+            session == "amf-issue-a" && window == "codex" && key == "Enter"
+        })
+        .returning(|_, _, _| Ok(()));
+
+    let mut app = App::new_for_test(empty_store(), Box::new(tmux), Box::new(worktree));
+    let store_file = NamedTempFile::new().unwrap();
+    app.store_path = store_file.path().to_path_buf();
+
+    let request = CreateBatchFeaturesRequest {
+        workspace_path: repo.clone(),
+        project_name: "triage".to_string(),
+        agent: AgentKind::Codex,
+        mode: VibeMode::Vibe,
+        features: vec![
+            BatchFeatureSpec {
+                branch: Some("issue-a".to_string()),
+                base_ref: Some("release".to_string()),
+                prompt: Some("Fix issue A\n".to_string()),
+                ..Default::default()
+            },
+            BatchFeatureSpec {
+                branch: Some("issue-b".to_string()),
+                mode: Some(VibeMode::SuperVibe),
+                ..Default::default()
+            },
+        ],
+        ..Default::default()
+    };
+
+    let started_at = std::time::Instant::now();
+    let response = app.create_batch_features_from_request(&request).unwrap();
+
+    assert_eq!(response.features.len(), 2);
+    assert!(!response.features[0].prompt_sent);
+    assert!(response.features[0].prompt_queued);
+    assert!(!response.features[1].prompt_queued);
+    let prompt_path = worktree_a.join(".claude").join("latest-prompt.txt");
+    app.poll_queued_prompts(started_at);
+    assert!(
+        !prompt_path.exists(),
+        "prompt sent before the agent started"
+    );
+
+    app.poll_queued_prompts(started_at + std::time::Duration::from_secs(10));
+    assert!(app.queued_prompts.is_empty());
+    assert_eq!(std::fs::read_to_string(prompt_path).unwrap(), "Fix issue A");
+    let features = &app.store.projects[0].features;
+    assert_eq!(features[0].workdir, worktree_a);
+    assert_eq!(features[1].mode, VibeMode::SuperVibe);
+}
+
+fn input_request_for(project_name: &str, feature_name: &str) -> PendingInput {
+    PendingInput {
+        session_id: format!("amf-{feature_name}"),
+        cwd: String::new(),
+        message: "Agent finished and is waiting for input".to_string(),
+        notification_type: "input-request".to_string(),
+        file_path: PathBuf::new(),
+        target_file_path: None,
+        relative_path: None,
+        change_id: None,
+        tool: None,
+        old_snippet: None,
+        new_snippet: None,
+        original_file: None,
+        proposed_file: None,
+        is_new_file: None,
+        reason: None,
+        response_file: None,
+        project_name: Some(project_name.to_string()),
+        feature_name: Some(feature_name.to_string()),
+        proceed_signal: None,
+        request_id: None,
+        reply_socket: None,
+    }
+}
+
+#[test]
+fn list_state_automation_reports_features_sessions_and_pending_inputs() {
+    let mut store = store_with_feature(ProjectStatus::Idle);
+    store.projects[0].features[0].nickname = Some("Login fix".to_string());
+    store.projects[0].features[0].ready = true;
+    store.projects[0].features[0].sessions = vec![make_session("Claude", Some("12k tok"))];
+    let mut app = App::new_for_test(
+        store,
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.thinking_features.insert("amf-my-feat".to_string());
+    app.pending_inputs
+        .push(input_request_for("my-project", "my-feat"));
+    app.pending_inputs
+        .push(input_request_for("other-project", "my-feat"));
+
+    let response = app
+        .list_state_from_request(&ListStateRequest::default())
+        .unwrap();
+
+    assert!(response.ok);
+    assert_eq!(response.projects.len(), 1);
+    let feature = &response.projects[0].features[0];
+    assert_eq!(feature.name, "my-feat");
+    assert_eq!(feature.status, ProjectStatus::Idle);
+    assert_eq!(feature.nickname.as_deref(), Some("Login fix"));
+    assert!(feature.ready);
+    assert!(feature.thinking);
+    assert_eq!(feature.pending_inputs.len(), 1);
+    assert_eq!(feature.sessions.len(), 1);
+    assert_eq!(feature.sessions[0].status_text.as_deref(), Some("12k tok"));
+
+    let json = serde_json::to_value(&response).unwrap();
+    assert_eq!(json["type"], "automation-result");
+    assert_eq!(json["action"], "list_state");
+    assert_eq!(json["projects"][0]["features"][0]["status"], "idle");
+}
+
+#[test]
+fn list_state_automation_rejects_unknown_project_filter() {
+    let app = App::new_for_test(
+        store_with_feature(ProjectStatus::Idle),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+
+    let err = app
+        .list_state_from_request(&ListStateRequest {
+            project_name: Some("missing".to_string()),
+            ..Default::default()
+        })
+        .unwrap_err();
+
+    assert!(err.to_string().contains("Project 'missing' not found"));
+}
+
+fn store_with_claude_agent_session(repo: PathBuf, status: ProjectStatus) -> ProjectStore {
+    let mut store = store_with_repo(repo, status);
+    let mut session = make_session("Claude 1", None);
+    session.tmux_window = "claude".to_string();
+    store.projects[0].features[0].sessions = vec![session];
+    store
+}
+
+#[test]
+fn send_prompt_automation_pastes_into_agent_window() {
+    let repo = TempDir::new().unwrap();
+
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_session_exists()
+        .withf(|session| session == "amf-my-feat")
+        .times(1)
+        .returning(|_| true);
+    tmux.expect_paste_text()
+        .withf(|session, window, text| {
+            session == "amf-my-feat" && window == "claude" && text == "Add a regression test."
+        })
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_send_key_name()
+        .withf(|session, window, key| {
+            session == "amf-my-feat" && window == "claude" && key == "Enter"
+        })
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+
+    let mut app = App::new_for_test(
+        store_with_claude_agent_session(repo.path().to_path_buf(), ProjectStatus::Idle),
+        Box::new(tmux),
+        Box::new(MockWorktreeOps::new()),
+    );
+
+    let request = SendPromptRequest {
+        project_name: "my-project".to_string(),
+        feature_name: "my-feat".to_string(),
+        prompt: "  Add a regression test.\n".to_string(),
+        ..Default::default()
+    };
+
+    let response = app.send_prompt_from_request(&request).unwrap();
+
+    assert!(response.ok);
+    assert!(!response.started);
+    assert_eq!(response.tmux_window, "claude");
+    let prompt_path = repo.path().join(".claude").join("latest-prompt.txt");
+    assert_eq!(
+        std::fs::read_to_string(prompt_path).unwrap(),
+        "Add a regression test."
+    );
+}
+
+#[test]
+fn send_prompt_automation_holds_the_prompt_until_a_started_claude_is_ready() {
+    let repo = TempDir::new().unwrap();
+
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_session_exists().returning(|_| false);
+    tmux.expect_create_session_with_window()
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_set_session_env()
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_launch_claude()
+        .times(1)
+        .returning(|_, _, _, _| Ok(()));
+    tmux.expect_select_window().returning(|_, _| Ok(()));
+    tmux.expect_paste_text()
+        .withf(|session, window, text| {
+            session == "amf-my-feat" && window == "claude" && text == "Add a regression test."
+        })
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_send_key_name()
+        .withf(|session, window, key| {
+            session == "amf-my-feat" && window == "claude" && key == "Enter"
+        })
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+
+    let mut app = App::new_for_test(
+        store_with_claude_agent_session(repo.path().to_path_buf(), ProjectStatus::Stopped),
+        Box::new(tmux),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let store_file = NamedTempFile::new().unwrap();
+    app.store_path = store_file.path().to_path_buf();
+
+    let request = SendPromptRequest {
+        project_name: "my-project".to_string(),
+        feature_name: "my-feat".to_string(),
+        prompt: "Add a regression test.".to_string(),
+        start: true,
+        ..Default::default()
+    };
+    let started_at = std::time::Instant::now();
+    let response = app.send_prompt_from_request(&request).unwrap();
+
+    assert!(response.started);
+    assert!(!response.prompt_sent);
+    let prompt_path = repo.path().join(".claude").join("latest-prompt.txt");
+    app.poll_queued_prompts(started_at + std::time::Duration::from_secs(10));
+    assert!(
+        !prompt_path.exists(),
+        "Claude prompts wait for SessionStart"
+    );
+
+    app.handle_ipc_message_value(serde_json::json!({
+        "type": "session-start",
+        "session_id": "amf-my-feat",
+        "cwd": repo.path(),
+    }));
+
+    assert!(app.queued_prompts.is_empty());
+    assert_eq!(
+        std::fs::read_to_string(prompt_path).unwrap(),
+        "Add a regression test."
+    );
+}
+
+#[test]
+fn send_prompt_automation_fails_when_feature_is_not_running() {
+    let repo = TempDir::new().unwrap();
+
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_session_exists().times(1).returning(|_| false);
+    tmux.expect_paste_text().times(0);
+    tmux.expect_send_key_name().times(0);
+
+    let mut app = App::new_for_test(
+        store_with_claude_agent_session(repo.path().to_path_buf(), ProjectStatus::Stopped),
+        Box::new(tmux),
+        Box::new(MockWorktreeOps::new()),
+    );
+
+    let request = SendPromptRequest {
+        project_name: "my-project".to_string(),
+        feature_name: "my-feat".to_string(),
+        prompt: "Add a regression test.".to_string(),
+        ..Default::default()
+    };
+
+    let err = app.send_prompt_from_request(&request).unwrap_err();
+
+    assert!(err.to_string().contains("is not running"), "got: {err}");
+}
+
+fn lifecycle_request(dry_run: bool) -> FeatureLifecycleRequest {
+    FeatureLifecycleRequest {
+        project_name: "my-project".to_string(),
+        feature_name: "my-feat".to_string(),
+        hook_choice: None,
+        dry_run,
+    }
+}
+
+#[test]
+fn stop_feature_automation_kills_session_and_marks_stopped() {
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_kill_session()
+        .withf(|session| session == "amf-my-feat")
+        .times(1)
+        .returning(|_| Ok(()));
+
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Idle),
+        Box::new(tmux),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let store_file = NamedTempFile::new().unwrap();
+    app.store_path = store_file.path().to_path_buf();
+
+    let response = app
+        .stop_feature_from_request(&lifecycle_request(false))
+        .unwrap();
+
+    assert!(response.ok);
+    assert_eq!(response.status, ProjectStatus::Stopped);
+    assert_eq!(
+        app.store.projects[0].features[0].status,
+        ProjectStatus::Stopped
+    );
+}
+
+#[test]
+fn start_feature_automation_rejects_running_feature() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Idle),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+
+    let err = app
+        .start_feature_from_request(&lifecycle_request(false))
+        .unwrap_err();
+
+    assert!(err.to_string().contains("already running"), "got: {err}");
+}
+
+#[test]
+fn set_ready_and_rename_automation_update_feature() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Idle),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let store_file = NamedTempFile::new().unwrap();
+    app.store_path = store_file.path().to_path_buf();
+
+    let ready = app
+        .set_feature_ready_from_request(&SetFeatureReadyRequest {
+            project_name: "my-project".to_string(),
+            feature_name: "my-feat".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+    assert!(ready.ready);
+
+    let renamed = app
+        .rename_feature_from_request(&RenameFeatureRequest {
+            project_name: "my-project".to_string(),
+            feature_name: "my-feat".to_string(),
+            nickname: Some("  Login fix ".to_string()),
+            dry_run: false,
+        })
+        .unwrap();
+    assert_eq!(renamed.nickname.as_deref(), Some("Login fix"));
+
+    let feature = &app.store.projects[0].features[0];
+    assert!(feature.ready);
+    assert_eq!(feature.nickname.as_deref(), Some("Login fix"));
+}
+
+#[test]
+fn delete_feature_automation_dry_run_keeps_feature() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Idle),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+
+    let response = app
+        .delete_feature_from_request(&lifecycle_request(true))
+        .unwrap();
+
+    assert!(response.dry_run);
+    assert!(response.message.starts_with("Dry run: would delete"));
+    assert_eq!(app.store.projects[0].features.len(), 1);
+    assert!(app.background_deletions.is_empty());
+}
+
+#[test]
+fn daemon_handoff_quits_only_when_headless() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Idle),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let store_file = NamedTempFile::new().unwrap();
+    app.store_path = store_file.path().to_path_buf();
+
+    app.handle_ipc_message_value(serde_json::json!({ "type": "daemon-handoff" }));
+    assert!(!app.should_quit, "interactive dashboards keep the socket");
+
+    app.headless = true;
+    app.handle_ipc_message_value(serde_json::json!({ "type": "daemon-handoff" }));
+    assert!(app.should_quit);
+}
+
+/// Run an automation request through the IPC handler and return the reply
+/// it sends back over a throwaway socket.
+fn automation_reply(app: &mut App, mut payload: serde_json::Value) -> serde_json::Value {
+    let dir = tempfile::TempDir::new().unwrap();
+    let socket = dir.path().join("reply.sock");
+    let guard = crate::ipc::start(&socket).unwrap();
+    payload["request_id"] = serde_json::json!("req-1");
+    payload["reply_socket"] = serde_json::json!(socket.display().to_string());
+
+    app.handle_ipc_message_value(payload);
+
+    guard
+        .rx
+        .recv_timeout(std::time::Duration::from_secs(1))
+        .expect("no automation reply within 1s")
+}
+
+#[test]
+fn hello_advertises_protocol_version_and_every_dispatched_action() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Stopped),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+
+    let reply = automation_reply(
+        &mut app,
+        serde_json::json!({ "type": "automation", "action": "hello" }),
+    );
+    assert_eq!(reply["ok"], true);
+    assert_eq!(reply["protocol_version"], AUTOMATION_PROTOCOL_VERSION);
+    assert_eq!(reply["headless"], false);
+    assert_eq!(
+        reply["actions"].as_array().unwrap().len(),
+        AUTOMATION_ACTIONS.len()
+    );
+
+    for action in AUTOMATION_ACTIONS {
+        let reply = automation_reply(
+            &mut app,
+            serde_json::json!({
+                "type": "automation",
+                "action": action,
+                "dry_run": true,
+            }),
+        );
+        assert_ne!(
+            reply["code"], "unknown_action",
+            "{action} is not dispatched"
+        );
+        assert_eq!(reply["protocol_version"], AUTOMATION_PROTOCOL_VERSION);
+    }
+}
+
+#[test]
+fn automation_rejects_unsupported_protocol_versions() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Stopped),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+
+    let reply = automation_reply(
+        &mut app,
+        serde_json::json!({
+            "type": "automation",
+            "action": "list_state",
+            "protocol_version": AUTOMATION_PROTOCOL_VERSION + 1,
+        }),
+    );
+    assert_eq!(reply["ok"], false);
+    assert_eq!(reply["code"], "unsupported_protocol_version");
+    assert!(reply["error"].as_str().unwrap().contains("upgrade amf"));
+
+    let reply = automation_reply(
+        &mut app,
+        serde_json::json!({
+            "type": "automation",
+            "action": "list_state",
+            "protocol_version": "one",
+        }),
+    );
+    assert_eq!(reply["code"], "invalid_payload");
+
+    // Clients that predate versioning send no protocol_version at all.
+    let reply = automation_reply(
+        &mut app,
+        serde_json::json!({ "type": "automation", "action": "list_state" }),
+    );
+    assert_eq!(reply["ok"], true);
+}
+
+#[test]
+fn automation_errors_carry_typed_codes() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Stopped),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+
+    let cases = [
+        (
+            serde_json::json!({
+                "action": "stop_feature",
+                "project_name": "missing",
+                "feature_name": "my-feat",
+            }),
+            "project_not_found",
+        ),
+        (
+            serde_json::json!({
+                "action": "stop_feature",
+                "project_name": "my-project",
+                "feature_name": "missing",
+            }),
+            "feature_not_found",
+        ),
+        (
+            serde_json::json!({
+                "action": "stop_feature",
+                "project_name": "my-project",
+                "feature_name": "my-feat",
+            }),
+            "invalid_state",
+        ),
+        (
+            serde_json::json!({
+                "action": "create_feature",
+                "project_name": "my-project",
+                "branch": "my-feat",
+            }),
+            "branch_exists",
+        ),
+        (
+            serde_json::json!({
+                "action": "create_project",
+                "project_name": "my-project",
+                "path": "/tmp",
+            }),
+            "project_exists",
+        ),
+        (
+            serde_json::json!({ "action": "send_prompt", "prompt": "  " }),
+            "validation_failed",
+        ),
+        (
+            serde_json::json!({ "action": "create_feature", "review": "yes" }),
+            "invalid_payload",
+        ),
+        (
+            serde_json::json!({ "action": "merge_everything" }),
+            "unknown_action",
+        ),
+    ];
+
+    for (mut payload, code) in cases {
+        payload["type"] = serde_json::json!("automation");
+        let reply = automation_reply(&mut app, payload.clone());
+        assert_eq!(reply["ok"], false, "{payload}");
+        assert_eq!(reply["code"], code, "{payload}");
+    }
+}
+
+#[test]
+fn ipc_message_without_type_is_rejected_unless_it_is_a_claude_hook_payload() {
+    let repo = tempfile::TempDir::new().unwrap();
+    let mut app = App::new_for_test(
+        store_with_repo(repo.path().to_path_buf(), ProjectStatus::Idle),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+
+    let reply = automation_reply(
+        &mut app,
+        serde_json::json!({
+            "session_id": "amf-my-feat",
+            "cwd": repo.path().display().to_string(),
+        }),
+    );
+    assert_eq!(reply["code"], "invalid_payload");
+    assert!(app.pending_inputs.is_empty());
+
+    // Claude's Stop hook input has no `type`; it still counts as "stop".
+    app.handle_ipc_message_value(serde_json::json!({
+        "session_id": "amf-my-feat",
+        "cwd": repo.path().display().to_string(),
+        "hook_event_name": "Stop",
+    }));
+    assert_eq!(app.pending_inputs.len(), 1);
+    assert_eq!(app.pending_inputs[0].notification_type, "stop");
+}
+
+#[test]
+fn every_advertised_action_has_a_schema_contract() {
+    for action in AUTOMATION_ACTIONS {
+        let contract = crate::automation::action_contract(action)
+            .unwrap_or_else(|| panic!("{action} has no schema contract"));
+        assert_eq!(contract.request.get("type").unwrap(), "object");
+        assert_eq!(contract.response.get("type").unwrap(), "object");
+    }
+}
+
+#[test]
+fn automation_doc_templates_match_request_schemas() {
+    let docs = [
+        (
+            "create_project",
+            include_str!("../../docs/automation/create-project.template.json"),
+        ),
+        (
+            "create_project",
+            include_str!("../../docs/automation/create-project.example.json"),
+        ),
+        (
+            "create_feature",
+            include_str!("../../docs/automation/create-feature.template.json"),
+        ),
+        (
+            "create_feature",
+            include_str!("../../docs/automation/create-feature.example.json"),
+        ),
+        (
+            "create_batch_features",
+            include_str!("../../docs/automation/create-batch-features.template.json"),
+        ),
+        (
+            "create_batch_features",
+            include_str!("../../docs/automation/create-batch-features.example.json"),
+        ),
+        (
+            "create_batch_features",
+            include_str!("../../docs/automation/create-batch-features-tasks.example.json"),
+        ),
+        (
+            "fork_feature",
+            include_str!("../../docs/automation/fork-feature.template.json"),
+        ),
+        (
+            "fork_feature",
+            include_str!("../../docs/automation/fork-feature.example.json"),
+        ),
+        (
+            "send_prompt",
+            include_str!("../../docs/automation/send-prompt.template.json"),
+        ),
+        (
+            "send_prompt",
+            include_str!("../../docs/automation/send-prompt.example.json"),
+        ),
+        (
+            "wait_for_idle",
+            include_str!("../../docs/automation/wait-for-idle.example.json"),
+        ),
+    ];
+
+    for (action, doc) in docs {
+        let payload: serde_json::Value = serde_json::from_str(doc).unwrap();
+        let errors = crate::automation::validate_request(action, &payload);
+        assert!(errors.is_empty(), "{action} doc drifted: {errors:?}");
+    }
+}
+
+#[test]
+fn validate_request_reports_field_level_errors() {
+    let errors = crate::automation::validate_request(
+        "create_feature",
+        &serde_json::json!({
+            "type": "automation",
+            "action": "create_feature",
+            "protocol_version": 1,
+            "project_name": "my-project",
+            "branch": "  ",
+            "use_worktrees": true,
+            "mode": "turbo",
+        }),
+    );
+    assert_eq!(
+        errors,
+        vec![
+            "use_worktrees: unknown field".to_string(),
+            "branch: must not be empty".to_string(),
+            "mode: unknown variant `turbo`, expected one of `vibeless`, `vibe`, `supervibe`, `review`"
+                .to_string(),
+        ]
+    );
+
+    let errors = crate::automation::validate_request("send_prompt", &serde_json::json!({}));
+    assert_eq!(
+        errors,
+        vec![
+            "feature_name: missing required field".to_string(),
+            "project_name: missing required field".to_string(),
+            "prompt: missing required field".to_string(),
+        ]
+    );
+
+    let errors = crate::automation::validate_request("merge", &serde_json::json!({}));
+    assert!(errors[0].starts_with("unknown action 'merge'"));
+}
+
+fn store_with_git_feature(repo: PathBuf, agent: AgentKind) -> ProjectStore {
+    let mut store = store_with_repo(repo, ProjectStatus::Stopped);
+    store.projects[0].is_git = true;
+    store.projects[0].features[0].agent = agent;
+    store.projects[0].features[0].mode = VibeMode::Vibe;
+    store
+}
+
+#[test]
+fn fork_feature_automation_branches_from_source_and_copies_dirty_files() {
+    let workspace = TempDir::new().unwrap();
+    let repo = workspace.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    git_ok(&repo, &["init", "-q"]);
+    std::fs::write(repo.join("notes.txt"), "half-finished idea\n").unwrap();
+    let fork_dir = workspace.path().join("fork");
+    std::fs::create_dir_all(&fork_dir).unwrap();
+
+    let mut worktree = MockWorktreeOps::new();
+    let fork_dir_clone = fork_dir.clone();
+    worktree
+        .expect_create_from()
+        .times(1)
+        .withf(|_, name, branch, base| {
+            name == "experiment" && branch == "experiment" && base == "my-feat"
+        })
+        .returning(move |_, _, _, _| Ok(fork_dir_clone.clone()));
+
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_session_exists().returning(|_| false);
+    tmux.expect_create_session_with_window()
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_set_session_env().returning(|_, _, _| Ok(()));
+    tmux.expect_launch_codex()
+        .times(1)
+        .withf(|session, _, _| session == "amf-experiment")
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_select_window().returning(|_, _| Ok(()));
+
+    let mut app = App::new_for_test(
+        store_with_git_feature(repo.clone(), AgentKind::Codex),
+        Box::new(tmux),
+        Box::new(worktree),
+    );
+    let store_file = NamedTempFile::new().unwrap();
+    app.store_path = store_file.path().to_path_buf();
+
+    let response = app
+        .fork_feature_from_request(&ForkFeatureRequest {
+            project_name: "my-project".to_string(),
+            feature_name: "my-feat".to_string(),
+            branch: Some("experiment".to_string()),
+            mode: Some(VibeMode::SuperVibe),
+            include_context: false,
+            ..Default::default()
+        })
+        .unwrap();
+
+    assert!(response.started);
+    assert!(response.copied_uncommitted_changes);
+    assert!(!response.included_context);
+    assert_eq!(response.source_branch, "my-feat");
+    assert_eq!(response.agent, AgentKind::Codex);
+    assert_eq!(
+        std::fs::read_to_string(fork_dir.join("notes.txt")).unwrap(),
+        "half-finished idea\n"
+    );
+    let fork = &app.store.projects[0].features[1];
+    assert_eq!(fork.branch, "experiment");
+    assert_eq!(fork.workdir, fork_dir);
+    assert_eq!(fork.mode, VibeMode::SuperVibe);
+    assert!(fork.is_worktree);
+    assert_eq!(fork.parent_id.as_deref(), Some("feat-1"));
+}
+
+#[test]
+fn fork_feature_automation_dry_run_defaults_to_the_source_settings() {
+    let workspace = TempDir::new().unwrap();
+    let repo = workspace.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+
+    let mut app = App::new_for_test(
+        store_with_git_feature(repo.clone(), AgentKind::Codex),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let mut request = ForkFeatureRequest {
+        project_name: "my-project".to_string(),
+        feature_name: "my-feat".to_string(),
+        dry_run: true,
+        ..Default::default()
+    };
+
+    let response = app.fork_feature_from_request(&request).unwrap();
+
+    assert!(response.dry_run);
+    assert!(!response.started);
+    assert_eq!(response.branch, "my-feat-fork");
+    assert_eq!(response.tmux_session, "amf-my-feat-fork");
+    assert_eq!(response.agent, AgentKind::Codex);
+    assert_eq!(
+        response.workdir,
+        repo.join(".worktrees").join("my-feat-fork")
+    );
+    assert_eq!(app.store.projects[0].features.len(), 1);
+
+    request.branch = Some("my-feat".to_string());
+    let err = app.fork_feature_from_request(&request).unwrap_err();
+    let code = err.downcast_ref::<AutomationError>().map(|err| err.code);
+    assert_eq!(code, Some(AutomationErrorCode::BranchExists), "got: {err}");
+}
+
+#[test]
+fn add_session_automation_records_custom_sessions_on_stopped_features() {
+    let workspace = TempDir::new().unwrap();
+    let repo = workspace.path().join("repo");
+    std::fs::create_dir_all(repo.join(".amf")).unwrap();
+    std::fs::write(
+        repo.join(".amf").join("config.json"),
+        r#"{
+            "custom_sessions": [
+                { "name": "Logs", "command": "tail -f app.log", "window_name": "logs" }
+            ]
+        }"#,
+    )
+    .unwrap();
+
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_session_exists().returning(|_| false);
+    let mut app = App::new_for_test(
+        store_with_repo(repo, ProjectStatus::Stopped),
+        Box::new(tmux),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let store_file = NamedTempFile::new().unwrap();
+    app.store_path = store_file.path().to_path_buf();
+
+    let response = app
+        .add_session_from_request(&AddSessionRequest {
+            project_name: "my-project".to_string(),
+            feature_name: "my-feat".to_string(),
+            custom: Some("Logs".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+
+    assert_eq!(response.kind, SessionKind::Custom);
+    assert_eq!(response.tmux_window.as_deref(), Some("logs"));
+    assert!(!response.launched);
+    let session = &app.store.projects[0].features[0].sessions[0];
+    assert_eq!(session.label, "Logs");
+    assert_eq!(session.command.as_deref(), Some("tail -f app.log"));
+    assert_eq!(response.session_id.as_deref(), Some(session.id.as_str()));
+}
+
+#[test]
+fn add_builtin_session_reports_failures_as_errors_without_touching_the_dashboard() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Stopped),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.selection = Selection::Feature(0, 0);
+    let error_code = |err: anyhow::Error| err.downcast_ref::<AutomationError>().map(|err| err.code);
+
+    let err = app
+        .add_builtin_session(0, 0, SessionKind::Terminal)
+        .unwrap_err();
+    assert_eq!(error_code(err), Some(AutomationErrorCode::InvalidState));
+    let err = app
+        .add_builtin_session(0, 0, SessionKind::Custom)
+        .unwrap_err();
+    assert_eq!(error_code(err), Some(AutomationErrorCode::ValidationFailed));
+
+    assert!(app.store.projects[0].features[0].sessions.is_empty());
+    assert!(matches!(app.selection, Selection::Feature(0, 0)));
+    assert_eq!(app.message, None);
+}
+
+#[test]
+fn add_session_automation_rejects_ambiguous_or_unavailable_sessions() {
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_session_exists().returning(|_| false);
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Stopped),
+        Box::new(tmux),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let request = |kind: Option<SessionKind>, custom: Option<&str>| AddSessionRequest {
+        project_name: "my-project".to_string(),
+        feature_name: "my-feat".to_string(),
+        kind,
+        custom: custom.map(str::to_string),
+        dry_run: true,
+    };
+    let error_code = |err: anyhow::Error| err.downcast_ref::<AutomationError>().map(|err| err.code);
+
+    let err = app
+        .add_session_from_request(&request(Some(SessionKind::Terminal), Some("Logs")))
+        .unwrap_err();
+    assert!(err.to_string().contains("exactly one"), "got: {err}");
+
+    let err = app
+        .add_session_from_request(&request(Some(SessionKind::Vscode), None))
+        .unwrap_err();
+    assert_eq!(error_code(err), Some(AutomationErrorCode::ValidationFailed));
+
+    let err = app
+        .add_session_from_request(&request(Some(SessionKind::Terminal), None))
+        .unwrap_err();
+    assert_eq!(error_code(err), Some(AutomationErrorCode::InvalidState));
+
+    let err = app
+        .add_session_from_request(&request(None, Some("missing")))
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("Unknown custom session 'missing'"),
+        "got: {err}"
+    );
+}
+
+fn wait_for_idle_request(timeout_ms: u64, settle_ms: u64) -> WaitForIdleRequest {
+    WaitForIdleRequest {
+        project_name: "my-project".to_string(),
+        feature_name: "my-feat".to_string(),
+        session: None,
+        timeout_ms,
+        settle_ms,
+    }
+}
+
+#[test]
+fn wait_for_idle_automation_waits_for_thinking_to_settle() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Active),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.store.projects[0].features[0].summary = Some("Refactored login".to_string());
+    app.thinking_features.insert("amf-my-feat".to_string());
+    let start = std::time::Instant::now();
+    let ms = std::time::Duration::from_millis;
+    let mut wait = super::automation::AutomationWait::new(
+        wait_for_idle_request(60_000, 1_000),
+        None,
+        None,
+        start,
+    );
+
+    assert!(app.poll_wait_for_idle(&mut wait, start + ms(500)).is_none());
+
+    app.thinking_features.clear();
+    assert!(
+        app.poll_wait_for_idle(&mut wait, start + ms(1_000))
+            .is_none()
+    );
+    // Thinking again before the settle window ends restarts it.
+    app.thinking_features.insert("amf-my-feat".to_string());
+    assert!(
+        app.poll_wait_for_idle(&mut wait, start + ms(1_500))
+            .is_none()
+    );
+    app.thinking_features.clear();
+    assert!(
+        app.poll_wait_for_idle(&mut wait, start + ms(2_000))
+            .is_none()
+    );
+
+    let response = app
+        .poll_wait_for_idle(&mut wait, start + ms(3_000))
+        .unwrap()
+        .unwrap();
+    assert_eq!(response.outcome, WaitOutcome::Idle);
+    assert_eq!(response.waited_ms, 3_000);
+    assert!(!response.feature.thinking);
+    assert_eq!(
+        response.feature.summary.as_deref(),
+        Some("Refactored login")
+    );
+}
+
+#[test]
+fn wait_for_idle_automation_returns_on_input_request_stop_or_timeout() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Active),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.thinking_features.insert("amf-my-feat".to_string());
+    let start = std::time::Instant::now();
+    let ms = std::time::Duration::from_millis;
+    let wait = |timeout_ms| {
+        super::automation::AutomationWait::new(
+            wait_for_idle_request(timeout_ms, 1_000),
+            None,
+            None,
+            start,
+        )
+    };
+
+    let response = app
+        .poll_wait_for_idle(&mut wait(5_000), start + ms(5_000))
+        .unwrap()
+        .unwrap();
+    assert_eq!(response.outcome, WaitOutcome::TimedOut);
+
+    app.pending_inputs
+        .push(input_request_for("my-project", "my-feat"));
+    let response = app
+        .poll_wait_for_idle(&mut wait(60_000), start)
+        .unwrap()
+        .unwrap();
+    assert_eq!(response.outcome, WaitOutcome::InputRequested);
+    assert_eq!(response.feature.pending_inputs.len(), 1);
+
+    app.pending_inputs.clear();
+    app.store.projects[0].features[0].status = ProjectStatus::Stopped;
+    let response = app
+        .poll_wait_for_idle(&mut wait(60_000), start)
+        .unwrap()
+        .unwrap();
+    assert_eq!(response.outcome, WaitOutcome::Stopped);
+}
+
+#[test]
+fn wait_for_idle_automation_can_wait_on_one_session() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Active),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let mut claude = make_session("claude", None);
+    claude.claude_session_id = Some("claude-1".to_string());
+    let mut reviewer = make_session("reviewer", None);
+    reviewer.claude_session_id = Some("claude-2".to_string());
+    let mut shell = make_session("shell", None);
+    shell.kind = SessionKind::Terminal;
+    app.store.projects[0].features[0].sessions = vec![claude, reviewer, shell];
+    app.thinking_features.insert("amf-my-feat".to_string());
+    let start = std::time::Instant::now();
+    let wait = |session: &str| {
+        let mut request = wait_for_idle_request(60_000, 0);
+        request.session = Some(session.to_string());
+        super::automation::AutomationWait::new(request, None, None, start)
+    };
+
+    // An input raised by the reviewer's hooks only ends the reviewer's wait.
+    let mut input = input_request_for("my-project", "my-feat");
+    input.session_id = "claude-2".to_string();
+    app.pending_inputs.push(input);
+    assert!(app.poll_wait_for_idle(&mut wait("claude"), start).is_none());
+    let response = app
+        .poll_wait_for_idle(&mut wait("session-reviewer"), start)
+        .unwrap()
+        .unwrap();
+    assert_eq!(response.outcome, WaitOutcome::InputRequested);
+    assert_eq!(response.session.as_deref(), Some("session-reviewer"));
+    assert_eq!(
+        response.message,
+        "Session 'session-reviewer' of feature 'my-feat' is waiting for input"
+    );
+
+    // A terminal window never thinks, even while the feature's agent does.
+    app.pending_inputs.clear();
+    let response = app
+        .poll_wait_for_idle(&mut wait("shell"), start)
+        .unwrap()
+        .unwrap();
+    assert_eq!(response.outcome, WaitOutcome::Idle);
+
+    let err = app
+        .poll_wait_for_idle(&mut wait("missing"), start)
+        .unwrap()
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<AutomationError>().map(|err| err.code),
+        Some(AutomationErrorCode::SessionNotFound)
+    );
+}
+
+#[test]
+fn wait_for_idle_automation_replies_from_the_main_loop() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Active),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.thinking_features.insert("amf-my-feat".to_string());
+
+    let reply = automation_reply(
+        &mut app,
+        serde_json::json!({
+            "type": "automation",
+            "action": "wait_for_idle",
+            "project_name": "my-project",
+            "feature_name": "missing",
+        }),
+    );
+    assert_eq!(reply["code"], "feature_not_found");
+    assert!(app.automation_waits.is_empty());
+
+    let reply = automation_reply(
+        &mut app,
+        serde_json::json!({
+            "type": "automation",
+            "action": "wait_for_idle",
+            "project_name": "my-project",
+            "feature_name": "my-feat",
+            "session": "missing",
+        }),
+    );
+    assert_eq!(reply["code"], "session_not_found");
+    assert!(app.automation_waits.is_empty());
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let socket = dir.path().join("reply.sock");
+    let guard = crate::ipc::start(&socket).unwrap();
+    app.handle_ipc_message_value(serde_json::json!({
+        "type": "automation",
+        "action": "wait_for_idle",
+        "project_name": "my-project",
+        "feature_name": "my-feat",
+        "settle_ms": 0,
+        "request_id": "req-1",
+        "reply_socket": socket.display().to_string(),
+    }));
+    assert_eq!(app.automation_waits.len(), 1);
+
+    app.poll_automation_waits();
+    assert_eq!(app.automation_waits.len(), 1);
+
+    app.thinking_features.clear();
+    app.poll_automation_waits();
+    assert!(app.automation_waits.is_empty());
+    let reply = guard
+        .rx
+        .recv_timeout(std::time::Duration::from_secs(1))
+        .expect("no automation reply within 1s");
+    assert_eq!(reply["ok"], true);
+    assert_eq!(reply["action"], "wait_for_idle");
+    assert_eq!(reply["outcome"], "idle");
+    assert_eq!(reply["request_id"], "req-1");
+    assert_eq!(reply["feature"]["name"], "my-feat");
+}
+
+fn diff_review_for(
+    feature_name: &str,
+    request_id: &str,
+    reply_socket: Option<&Path>,
+) -> PendingInput {
+    PendingInput {
+        notification_type: "change-reason".to_string(),
+        message: "Review: src/login.rs".to_string(),
+        target_file_path: Some("/tmp/test-workdir/src/login.rs".to_string()),
+        relative_path: Some("src/login.rs".to_string()),
+        change_id: Some("chg-1".to_string()),
+        tool: Some("Edit".to_string()),
+        old_snippet: Some("old()".to_string()),
+        new_snippet: Some("new()".to_string()),
+        reason: Some("Fix the redirect".to_string()),
+        request_id: Some(request_id.to_string()),
+        reply_socket: reply_socket.map(|path| path.display().to_string()),
+        ..input_request_for("my-project", feature_name)
+    }
+}
+
+fn resolve_input_request(input_id: &str, decision: InputDecision) -> ResolveInputRequest {
+    ResolveInputRequest {
+        input_id: input_id.to_string(),
+        decision: Some(decision),
+        reason: None,
+        dry_run: false,
+    }
+}
+
+#[test]
+fn list_inputs_automation_reports_review_details_and_filters_by_feature() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Active),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.pending_inputs
+        .push(diff_review_for("my-feat", "review-1", None));
+    app.pending_inputs
+        .push(input_request_for("my-project", "other-feat"));
+
+    let response = app
+        .list_inputs_from_request(&ListInputsRequest::default())
+        .unwrap();
+    assert_eq!(response.inputs.len(), 2);
+    let review = &response.inputs[0];
+    assert_eq!(review.request_id.as_deref(), Some("review-1"));
+    assert_eq!(review.change_id.as_deref(), Some("chg-1"));
+    assert_eq!(review.tool.as_deref(), Some("Edit"));
+    assert_eq!(review.relative_path.as_deref(), Some("src/login.rs"));
+    assert_eq!(review.new_snippet.as_deref(), Some("new()"));
+    assert!(review.reviewable);
+    assert!(!review.open_in_dashboard);
+    assert!(!response.inputs[1].reviewable);
+
+    app.pending_inputs.pop();
+    app.pending_inputs
+        .push(input_request_for("my-project", "my-feat"));
+    let response = app
+        .list_inputs_from_request(&ListInputsRequest {
+            project_name: Some("my-project".to_string()),
+            feature_name: Some("my-feat".to_string()),
+        })
+        .unwrap();
+    assert_eq!(response.inputs.len(), 2);
+
+    let err = app
+        .list_inputs_from_request(&ListInputsRequest {
+            project_name: Some("missing".to_string()),
+            feature_name: None,
+        })
+        .unwrap_err();
+    assert!(err.to_string().contains("Project 'missing' not found"));
+}
+
+#[test]
+fn resolve_input_automation_answers_waiting_hooks_over_ipc() {
+    let dir = TempDir::new().unwrap();
+    let socket = dir.path().join("hook.sock");
+    let hook = crate::ipc::start(&socket).unwrap();
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Active),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.pending_inputs
+        .push(diff_review_for("my-feat", "review-1", Some(&socket)));
+
+    let mut request = resolve_input_request("review-1", InputDecision::Reject);
+    request.reason = Some("Keep the old redirect".to_string());
+    request.dry_run = true;
+    let response = app.resolve_input_from_request(&request).unwrap();
+    assert!(response.dry_run);
+    assert_eq!(app.pending_inputs.len(), 1);
+
+    request.dry_run = false;
+    let response = app.resolve_input_from_request(&request).unwrap();
+    assert!(response.answered_hook);
+    assert_eq!(response.feature_name.as_deref(), Some("my-feat"));
+    assert!(app.pending_inputs.is_empty());
+
+    let reply = hook
+        .rx
+        .recv_timeout(std::time::Duration::from_secs(1))
+        .expect("hook was not answered within 1s");
+    assert_eq!(reply["type"], "review-response");
+    assert_eq!(reply["decision"], "reject");
+    assert_eq!(reply["reject"], true);
+    assert_eq!(reply["reason"], "Keep the old redirect");
+    assert_eq!(reply["request_id"], "review-1");
+}
+
+#[test]
+fn resolve_input_automation_falls_back_to_response_files() {
+    let dir = TempDir::new().unwrap();
+    let response_file = dir.path().join("response.json");
+    let proceed_signal = dir.path().join("proceed");
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Active),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.pending_inputs.push(PendingInput {
+        response_file: Some(response_file.display().to_string()),
+        proceed_signal: Some(proceed_signal.display().to_string()),
+        ..diff_review_for("my-feat", "review-1", None)
+    });
+
+    app.resolve_input_from_request(&resolve_input_request("review-1", InputDecision::Approve))
+        .unwrap();
+
+    let written: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&response_file).unwrap()).unwrap();
+    assert_eq!(written["decision"], "proceed");
+    assert!(written["reason"].is_null());
+    assert!(proceed_signal.exists());
+}
+
+#[test]
+fn resolve_input_automation_closes_the_open_review_prompt() {
+    let dir = TempDir::new().unwrap();
+    let socket = dir.path().join("hook.sock");
+    let hook = crate::ipc::start(&socket).unwrap();
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Active),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.open_diff_review_prompt(&PendingInput {
+        cwd: "/tmp/test-workdir".to_string(),
+        ..diff_review_for("my-feat", "review-1", Some(&socket))
+    });
+
+    let inputs = app
+        .list_inputs_from_request(&ListInputsRequest::default())
+        .unwrap()
+        .inputs;
+    assert_eq!(inputs.len(), 1);
+    assert!(inputs[0].open_in_dashboard);
+    assert_eq!(inputs[0].feature_name.as_deref(), Some("my-feat"));
+
+    app.resolve_input_from_request(&resolve_input_request("review-1", InputDecision::Dismiss))
+        .unwrap();
+
+    assert!(matches!(app.mode, AppMode::Normal));
+    let reply = hook
+        .rx
+        .recv_timeout(std::time::Duration::from_secs(1))
+        .expect("hook was not answered within 1s");
+    assert_eq!(reply["decision"], "cancel");
+    assert_eq!(reply["skip"], true);
+}
+
+#[test]
+fn resolve_input_automation_only_dismisses_plain_input_requests() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Active),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.pending_inputs.push(PendingInput {
+        request_id: Some("stop-1".to_string()),
+        ..input_request_for("my-project", "my-feat")
+    });
+    let error_code = |err: anyhow::Error| err.downcast_ref::<AutomationError>().map(|err| err.code);
+
+    let err = app
+        .resolve_input_from_request(&resolve_input_request("stop-1", InputDecision::Approve))
+        .unwrap_err();
+    assert_eq!(error_code(err), Some(AutomationErrorCode::InvalidState));
+
+    let err = app
+        .resolve_input_from_request(&resolve_input_request("missing", InputDecision::Dismiss))
+        .unwrap_err();
+    assert_eq!(error_code(err), Some(AutomationErrorCode::InputNotFound));
+
+    let err = app
+        .resolve_input_from_request(&ResolveInputRequest {
+            decision: None,
+            ..resolve_input_request("stop-1", InputDecision::Dismiss)
+        })
+        .unwrap_err();
+    assert_eq!(error_code(err), Some(AutomationErrorCode::ValidationFailed));
+
+    let response = app
+        .resolve_input_from_request(&resolve_input_request("stop-1", InputDecision::Dismiss))
+        .unwrap();
+    assert!(!response.answered_hook);
+    assert!(app.pending_inputs.is_empty());
+}
+
+#[test]
+fn read_diff_automation_reports_changes_against_the_base_branch() {
+    let workspace = init_repo_with_src("base\n");
+    let repo = workspace.path().to_path_buf();
+    git_ok(&repo, &["checkout", "-q", "-b", "my-feat"]);
+    std::fs::write(repo.join("src.txt"), "base\nfeature\n").unwrap();
+    std::fs::write(repo.join("notes.txt"), "todo\n").unwrap();
+
+    let app = App::new_for_test(
+        store_with_git_feature(repo, AgentKind::Claude),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let request = ReadDiffRequest {
+        project_name: "my-project".to_string(),
+        feature_name: "my-feat".to_string(),
+        include_patch: true,
+    };
+
+    let response = app.read_diff_from_request(&request).unwrap();
+    assert_eq!(response.branch, "my-feat");
+    assert_eq!(response.base_ref, "main");
+    assert_eq!(response.total_additions, 2);
+    let paths: Vec<_> = response
+        .files
+        .iter()
+        .map(|file| file.path.as_str())
+        .collect();
+    assert_eq!(paths, ["notes.txt", "src.txt"]);
+    assert_eq!(
+        response.files[0].status,
+        crate::diff::DiffFileStatus::Untracked
+    );
+    assert!(
+        response.files[1]
+            .patch
+            .as_deref()
+            .unwrap()
+            .contains("+feature")
+    );
+
+    let response = app
+        .read_diff_from_request(&ReadDiffRequest {
+            include_patch: false,
+            ..request
+        })
+        .unwrap();
+    assert!(response.files.iter().all(|file| file.patch.is_none()));
+}
+
+#[test]
+fn read_diff_automation_requires_a_git_project() {
+    let app = App::new_for_test(
+        store_with_feature(ProjectStatus::Active),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+
+    let err = app
+        .read_diff_from_request(&ReadDiffRequest {
+            project_name: "my-project".to_string(),
+            feature_name: "my-feat".to_string(),
+            include_patch: true,
+        })
+        .unwrap_err();
+    assert_eq!(
+        err.downcast_ref::<AutomationError>().map(|err| err.code),
+        Some(AutomationErrorCode::NotGit)
+    );
+}
+
+/// `my-feat` with `middle` stacked on it and `top` stacked on `middle`.
+fn store_with_feature_stack(repo: PathBuf) -> ProjectStore {
+    let mut store = store_with_git_feature(repo.clone(), AgentKind::Codex);
+    for (id, parent_id) in [("top", "middle"), ("middle", "feat-1")] {
+        let mut feature = store.projects[0].features[0].clone();
+        feature.id = id.to_string();
+        feature.name = id.to_string();
+        feature.branch = id.to_string();
+        feature.workdir = repo.join(".worktrees").join(id);
+        feature.is_worktree = true;
+        feature.tmux_session = format!("amf-{id}");
+        feature.parent_id = Some(parent_id.to_string());
+        store.projects[0].features.push(feature);
+    }
+    store
+}
+
+#[test]
+fn create_feature_automation_stacks_on_the_parent_branch() {
+    let workspace = TempDir::new().unwrap();
+    let repo = workspace.path().join("repo");
+    std::fs::create_dir_all(&repo).unwrap();
+    let worktree_path = repo.join(".worktrees").join("follow-up");
+
+    let mut worktree = MockWorktreeOps::new();
+    let worktree_clone = worktree_path.clone();
+    worktree
+        .expect_create_from()
+        .times(1)
+        .withf(|_, name, branch, base| {
+            name == "follow-up" && branch == "follow-up" && base == "my-feat"
+        })
+        .returning(move |_, _, _, _| Ok(worktree_clone.clone()));
+
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_session_exists().returning(|_| false);
+    tmux.expect_create_session_with_window()
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_set_session_env().returning(|_, _, _| Ok(()));
+    tmux.expect_launch_codex()
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_select_window().returning(|_, _| Ok(()));
+
+    let mut app = App::new_for_test(
+        store_with_git_feature(repo.clone(), AgentKind::Codex),
+        Box::new(tmux),
+        Box::new(worktree),
+    );
+    let store_file = NamedTempFile::new().unwrap();
+    app.store_path = store_file.path().to_path_buf();
+
+    let mut request = CreateFeatureRequest {
+        project_name: "my-project".to_string(),
+        branch: "follow-up".to_string(),
+        agent: AgentKind::Codex,
+        mode: VibeMode::Vibe,
+        parent_feature: Some("my-feat".to_string()),
+        ..Default::default()
+    };
+
+    let response = app.create_feature_from_request(&request).unwrap();
+
+    assert!(response.is_worktree);
+    assert_eq!(response.parent_feature.as_deref(), Some("my-feat"));
+    let stacked = &app.store.projects[0].features[1];
+    assert_eq!(stacked.workdir, worktree_path);
+    assert_eq!(stacked.parent_id.as_deref(), Some("feat-1"));
+    let state = app
+        .list_state_from_request(&ListStateRequest::default())
+        .unwrap();
+    let listed = &state.projects[0].features;
+    assert_eq!(listed[0].parent_feature, None);
+    assert_eq!(listed[1].parent_feature.as_deref(), Some("my-feat"));
+
+    request.branch = "second".to_string();
+    request.parent_feature = Some("missing".to_string());
+    let err = app.create_feature_from_request(&request).unwrap_err();
+    let code = err.downcast_ref::<AutomationError>().map(|err| err.code);
+    assert_eq!(
+        code,
+        Some(AutomationErrorCode::FeatureNotFound),
+        "got: {err}"
+    );
+
+    request.parent_feature = Some("my-feat".to_string());
+    request.use_worktree = Some(false);
+    let err = app.create_feature_from_request(&request).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Stacked features require a worktree"),
+        "got: {err}"
+    );
+}
+
+#[test]
+fn restack_feature_automation_rebases_the_stack_parents_first() {
+    let workspace = TempDir::new().unwrap();
+    let repo = workspace.path().join("repo");
+    let middle_dir = repo.join(".worktrees").join("middle");
+    let top_dir = repo.join(".worktrees").join("top");
+
+    let mut worktree = MockWorktreeOps::new();
+    let mut seq = mockall::Sequence::new();
+    worktree
+        .expect_rebase()
+        .times(1)
+        .in_sequence(&mut seq)
+        .withf(move |workdir, onto| workdir == middle_dir && onto == "my-feat")
+        .returning(|_, _| Ok(()));
+    worktree
+        .expect_rebase()
+        .times(1)
+        .in_sequence(&mut seq)
+        .withf(move |workdir, onto| workdir == top_dir && onto == "middle")
+        .returning(|_, _| Ok(()));
+
+    let mut app = App::new_for_test(
+        store_with_feature_stack(repo),
+        Box::new(MockTmuxOps::new()),
+        Box::new(worktree),
+    );
+    let mut request = RestackFeatureRequest {
+        project_name: "my-project".to_string(),
+        feature_name: "my-feat".to_string(),
+        dry_run: true,
+    };
+
+    let planned = app.restack_feature_from_request(&request).unwrap();
+    assert!(planned.dry_run);
+    let planned: Vec<_> = planned
+        .restacked
+        .iter()
+        .map(|entry| (entry.feature_name.as_str(), entry.onto.as_str()))
+        .collect();
+    assert_eq!(planned, vec![("middle", "my-feat"), ("top", "middle")]);
+
+    request.dry_run = false;
+    let response = app.restack_feature_from_request(&request).unwrap();
+    assert_eq!(response.restacked.len(), 2);
+    assert_eq!(response.message, "Restacked 2 feature(s) onto 'my-feat'");
+
+    request.feature_name = "top".to_string();
+    let response = app.restack_feature_from_request(&request).unwrap();
+    assert!(response.restacked.is_empty());
+    assert_eq!(response.message, "No features are stacked on 'top'");
+}
+
+#[test]
+fn restack_feature_automation_reports_the_feature_that_failed() {
+    let workspace = TempDir::new().unwrap();
+    let repo = workspace.path().join("repo");
+
+    let mut worktree = MockWorktreeOps::new();
+    worktree
+        .expect_rebase()
+        .times(1)
+        .returning(|_, _| Err(anyhow::anyhow!("conflict in src.txt")));
+
+    let mut app = App::new_for_test(
+        store_with_feature_stack(repo),
+        Box::new(MockTmuxOps::new()),
+        Box::new(worktree),
+    );
+
+    let err = app
+        .restack_feature_from_request(&RestackFeatureRequest {
+            project_name: "my-project".to_string(),
+            feature_name: "my-feat".to_string(),
+            dry_run: false,
+        })
+        .unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("Could not restack 'middle' onto 'my-feat'"),
+        "got: {err}"
+    );
+}
+
+#[test]
+fn visible_items_list_stacked_features_under_their_parent() {
+    let workspace = TempDir::new().unwrap();
+    let mut store = store_with_feature_stack(workspace.path().join("repo"));
+    // Ordering by worktree/creation alone would put `top` before `middle`.
+    store.projects[0].features[1].created_at += Duration::minutes(1);
+
+    let app = App::new_for_test(
+        store,
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let features: Vec<usize> = app
+        .visible_items()
+        .into_iter()
+        .filter_map(|item| match item {
+            VisibleItem::Feature(_, fi) => Some(fi),
+            _ => None,
+        })
+        .collect();
+
+    assert_eq!(features, vec![0, 2, 1]);
+}
+
+/// Three stopped features: `login-bug` tagged `bug` and `ui`,
+/// `api-bug` tagged `bug`, and untagged `docs`.
+fn store_with_tagged_features() -> ProjectStore {
+    let mut store = store_with_feature(ProjectStatus::Stopped);
+    let template = store.projects[0].features[0].clone();
+    store.projects[0].features.clear();
+    for (name, tags) in [
+        ("login-bug", vec!["bug", "ui"]),
+        ("api-bug", vec!["bug"]),
+        ("docs", vec![]),
+    ] {
+        let mut feature = template.clone();
+        feature.id = name.to_string();
+        feature.name = name.to_string();
+        feature.branch = name.to_string();
+        feature.tags = tags.into_iter().map(str::to_string).collect();
+        store.projects[0].features.push(feature);
+    }
+    store
+}
+
+#[test]
+fn set_feature_tags_automation_normalizes_tags_and_filters_list_state() {
+    let mut app = App::new_for_test(
+        store_with_tagged_features(),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let store_file = NamedTempFile::new().unwrap();
+    app.store_path = store_file.path().to_path_buf();
+
+    let mut request = SetFeatureTagsRequest {
+        project_name: "my-project".to_string(),
+        feature_name: "docs".to_string(),
+        tags: vec!["#Docs".to_string(), " docs ".to_string(), "Bug".to_string()],
+        dry_run: false,
+    };
+    let response = app.set_feature_tags_from_request(&request).unwrap();
+    assert_eq!(response.action, "set_feature_tags");
+    assert_eq!(response.tags, vec!["docs", "bug"]);
+    assert_eq!(app.store.projects[0].features[2].tags, vec!["docs", "bug"]);
+
+    let listed = app
+        .list_state_from_request(&ListStateRequest {
+            tag: Some("bug".to_string()),
+            ..Default::default()
+        })
+        .unwrap();
+    let names: Vec<&str> = listed.projects[0]
+        .features
+        .iter()
+        .map(|feature| feature.name.as_str())
+        .collect();
+    assert_eq!(names, vec!["login-bug", "api-bug", "docs"]);
+
+    request.tags = vec!["needs review".to_string()];
+    let err = app.set_feature_tags_from_request(&request).unwrap_err();
+    let code = err.downcast_ref::<AutomationError>().map(|err| err.code);
+    assert_eq!(
+        code,
+        Some(AutomationErrorCode::ValidationFailed),
+        "got: {err}"
+    );
+
+    request.tags.clear();
+    app.set_feature_tags_from_request(&request).unwrap();
+    assert!(app.store.projects[0].features[2].tags.is_empty());
+}
+
+#[test]
+fn search_with_tag_terms_only_matches_tagged_features() {
+    let mut app = App::new_for_test(
+        store_with_tagged_features(),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let search = |app: &mut App, query: &str| -> Vec<String> {
+        app.start_search();
+        if let AppMode::Searching(state) = &mut app.mode {
+            state.query = query.to_string();
+        }
+        app.perform_search();
+        match &app.mode {
+            AppMode::Searching(state) => state.matches.iter().map(|m| m.label.clone()).collect(),
+            _ => unreachable!(),
+        }
+    };
+
+    assert_eq!(search(&mut app, "tag:bug"), vec!["login-bug", "api-bug"]);
+    assert_eq!(search(&mut app, "tag:b tag:ui"), vec!["login-bug"]);
+    assert_eq!(search(&mut app, "api tag:bug"), vec!["api-bug"]);
+    assert!(search(&mut app, "tag:docs").is_empty());
+}
+
+#[test]
+fn tag_filter_hides_untagged_features_and_cycles_back_to_all() {
+    let mut app = App::new_for_test(
+        store_with_tagged_features(),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.selection = Selection::Feature(0, 2);
+    app.session_filter = SessionFilter::Vscode;
+
+    app.cycle_session_filter();
+
+    assert_eq!(app.session_filter, SessionFilter::Tag("bug".to_string()));
+    let features: Vec<usize> = app
+        .visible_items()
+        .into_iter()
+        .filter_map(|item| match item {
+            VisibleItem::Feature(_, fi) => Some(fi),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(features.len(), 2);
+    assert!(!features.contains(&2));
+    // The hidden `docs` feature was selected, so selection moves up.
+    assert!(matches!(app.selection, Selection::Project(0)));
+
+    app.cycle_session_filter();
+    assert_eq!(app.session_filter, SessionFilter::Tag("ui".to_string()));
+    app.cycle_session_filter();
+    assert_eq!(app.session_filter, SessionFilter::All);
+}
+
+#[test]
+fn feature_notes_editor_saves_trimmed_notes_and_clears_empty_ones() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Stopped),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let store_file = NamedTempFile::new().unwrap();
+    app.store_path = store_file.path().to_path_buf();
+    app.selection = Selection::Feature(0, 0);
+
+    app.start_edit_feature_notes();
+    match &mut app.mode {
+        AppMode::EditingFeatureNotes(state) => {
+            assert!(state.editor.text().is_empty());
+            state
+                .editor
+                .insert_str("Why: users get logged out\nTODO: tests\n\n");
+        }
+        _ => panic!("expected EditingFeatureNotes mode"),
+    }
+    app.save_feature_notes().unwrap();
+
+    let feature = &app.store.projects[0].features[0];
+    assert!(matches!(app.mode, AppMode::Normal));
+    assert_eq!(
+        feature.notes.as_deref(),
+        Some("Why: users get logged out\nTODO: tests")
+    );
+    assert_eq!(feature.notes_headline(), Some("Why: users get logged out"));
+    assert_eq!(
+        app.visible_item_height(&VisibleItem::Feature(0, 0)),
+        2,
+        "feature rows with notes show a second line"
+    );
+
+    app.start_edit_feature_notes();
+    if let AppMode::EditingFeatureNotes(state) = &mut app.mode {
+        state.editor.clear();
+        state.editor.insert_str("  \n");
+    }
+    app.save_feature_notes().unwrap();
+
+    assert_eq!(app.store.projects[0].features[0].notes, None);
+    assert_eq!(app.visible_item_height(&VisibleItem::Feature(0, 0)), 1);
+}
+
+#[test]
+fn search_matches_feature_notes_and_shows_matching_line() {
+    let mut store = store_with_tagged_features();
+    store.projects[0].features[2].notes =
+        Some("Why: onboarding guide\nTODO: screenshots for login flow".to_string());
+    let mut app = App::new_for_test(
+        store,
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+
+    app.start_search();
+    if let AppMode::Searching(state) = &mut app.mode {
+        state.query = "screenshots".to_string();
+    }
+    app.perform_search();
+
+    let AppMode::Searching(state) = &app.mode else {
+        panic!("expected Searching mode");
+    };
+    assert_eq!(state.matches.len(), 1);
+    assert_eq!(state.matches[0].label, "docs");
+    assert_eq!(
+        state.matches[0].context,
+        "my-project / ✎ TODO: screenshots for login flow"
+    );
+}
+
+#[test]
+fn archive_feature_removes_worktree_and_keeps_prompt() {
+    let workdir = repo_with_feature_commit();
+    let claude_dir = workdir.path().join(".claude");
+    std::fs::create_dir_all(&claude_dir).unwrap();
+    std::fs::write(
+        claude_dir.join("latest-prompt.txt"),
+        "fix the login redirect",
+    )
+    .unwrap();
+
+    let mut store = store_with_feature(ProjectStatus::Idle);
+    let feature = &mut store.projects[0].features[0];
+    feature.is_worktree = true;
+    feature.workdir = workdir.path().to_path_buf();
+
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_session_exists().return_const(true);
+    tmux.expect_kill_session()
+        .withf(|session| session == "amf-my-feat")
+        .times(1)
+        .returning(|_| Ok(()));
+    let mut worktree = MockWorktreeOps::new();
+    let expected_workdir = workdir.path().to_path_buf();
+    worktree
+        .expect_remove()
+        .withf(move |repo, path| repo == Path::new("/tmp/test-repo") && path == expected_workdir)
+        .times(1)
+        .returning(|_, _| Ok(()));
+
+    let mut app = App::new_for_test(store, Box::new(tmux), Box::new(worktree));
+    let tmp = NamedTempFile::new().unwrap();
+    app.store_path = tmp.path().to_path_buf();
+    app.selection = Selection::Feature(0, 0);
+
+    app.toggle_archive_selected_feature().unwrap();
+
+    let feature = &app.store.projects[0].features[0];
+    assert!(feature.is_archived());
+    assert_eq!(feature.status, ProjectStatus::Stopped);
+    assert_eq!(
+        feature.archived_prompt.as_deref(),
+        Some("fix the login redirect")
+    );
+    assert_eq!(app.message.as_deref(), Some("Archived 'my-feat'"));
+    assert!(matches!(app.selection, Selection::ArchivedFeatures(0)));
+    let visible = app.visible_items();
+    assert_eq!(visible.len(), 2, "archived features start collapsed");
+    assert!(matches!(visible[1], VisibleItem::ArchivedFeatures(0)));
+
+    app.toggle_collapse();
+    let visible = app.visible_items();
+    assert_eq!(visible.len(), 3);
+    assert!(matches!(visible[2], VisibleItem::Feature(0, 0)));
+}
+
+#[test]
+fn restore_feature_recreates_worktree_and_resumes_claude() {
+    let workspace = repo_with_feature_commit();
+    let repo = workspace.path().to_path_buf();
+    let original = workspace.path().join("elsewhere/my-feat");
+    let mut store = store_with_feature(ProjectStatus::Stopped);
+    store.projects[0].repo = repo.clone();
+    let feature = &mut store.projects[0].features[0];
+    feature.is_worktree = true;
+    feature.workdir = original.clone();
+    feature.archived_at = Some(Utc::now());
+    feature.archived_prompt = Some("fix the login redirect".to_string());
+    let mut session = make_session("claude", None);
+    session.claude_session_id = Some("resume-123".to_string());
+    feature.sessions = vec![session];
+
+    let mut worktree = MockWorktreeOps::new();
+    let expected_path = original.clone();
+    worktree
+        .expect_create_at()
+        .withf(move |called_repo, path, branch| {
+            called_repo == repo && path == expected_path && branch == "my-feat"
+        })
+        .times(1)
+        .returning(|_, path, _| Ok(path.to_path_buf()));
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_session_exists().return_const(false);
+    tmux.expect_create_session_with_window()
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_set_session_env()
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_launch_claude()
+        .withf(|session, window, resume_id, _| {
+            session == "amf-my-feat"
+                && window == "claude"
+                && resume_id.as_deref() == Some("resume-123")
+        })
+        .times(1)
+        .returning(|_, _, _, _| Ok(()));
+    tmux.expect_select_window().returning(|_, _| Ok(()));
+
+    let mut app = App::new_for_test(store, Box::new(tmux), Box::new(worktree));
+    let tmp = NamedTempFile::new().unwrap();
+    app.store_path = tmp.path().to_path_buf();
+    app.selection = Selection::Feature(0, 0);
+
+    app.toggle_archive_selected_feature().unwrap();
+
+    let feature = &app.store.projects[0].features[0];
+    assert!(!feature.is_archived());
+    assert_eq!(feature.workdir, original);
+    assert_eq!(feature.archived_prompt, None);
+    assert_eq!(
+        std::fs::read_to_string(original.join(".claude/latest-prompt.txt")).unwrap(),
+        "fix the login redirect"
+    );
+    assert_eq!(app.message.as_deref(), Some("Restored 'my-feat'"));
+}
+
+#[test]
+fn restore_feature_refuses_when_the_branch_is_gone() {
+    let workspace = init_repo_with_src("base\n");
+    let mut store = store_with_feature(ProjectStatus::Stopped);
+    store.projects[0].repo = workspace.path().to_path_buf();
+    let feature = &mut store.projects[0].features[0];
+    feature.is_worktree = true;
+    feature.workdir = workspace.path().join(".worktrees/my-feat");
+    feature.archived_at = Some(Utc::now());
+
+    let mut app = App::new_for_test(
+        store,
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let tmp = NamedTempFile::new().unwrap();
+    app.store_path = tmp.path().to_path_buf();
+
+    let err = app.restore_feature(0, 0).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Cannot restore 'my-feat': branch 'my-feat' no longer exists"
+    );
+    assert!(app.store.projects[0].features[0].is_archived());
+}
+
+#[test]
+fn archive_feature_keeps_a_codex_prompt() {
+    let workdir = repo_with_feature_commit();
+    let codex_dir = workdir.path().join(".codex");
+    std::fs::create_dir_all(&codex_dir).unwrap();
+    std::fs::write(
+        codex_dir.join("latest-prompt.txt"),
+        "tidy the settings page",
+    )
+    .unwrap();
+
+    let mut store = store_with_feature(ProjectStatus::Stopped);
+    let feature = &mut store.projects[0].features[0];
+    feature.is_worktree = true;
+    feature.workdir = workdir.path().to_path_buf();
+
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_session_exists().return_const(false);
+    let mut worktree = MockWorktreeOps::new();
+    worktree.expect_remove().times(1).returning(|_, _| Ok(()));
+
+    let mut app = App::new_for_test(store, Box::new(tmux), Box::new(worktree));
+    let tmp = NamedTempFile::new().unwrap();
+    app.store_path = tmp.path().to_path_buf();
+
+    app.archive_feature(0, 0).unwrap();
+
+    assert_eq!(
+        app.store.projects[0].features[0].archived_prompt.as_deref(),
+        Some("tidy the settings page")
+    );
+}
+
+#[test]
+fn archived_feature_automation_rejects_start_and_bad_restore() {
+    let mut store = store_with_feature(ProjectStatus::Stopped);
+    store.projects[0].features[0].archived_at = Some(Utc::now());
+    let mut app = App::new_for_test(
+        store,
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let error_code = |err: anyhow::Error| err.downcast_ref::<AutomationError>().map(|err| err.code);
+
+    let err = app
+        .start_feature_from_request(&lifecycle_request(false))
+        .unwrap_err();
+    assert_eq!(error_code(err), Some(AutomationErrorCode::InvalidState));
+
+    let response = app
+        .restore_feature_from_request(&lifecycle_request(true))
+        .unwrap();
+    assert!(response.archived_at.is_some());
+    assert!(response.message.contains("would restore feature 'my-feat'"));
+
+    app.store.projects[0].features[0].archived_at = None;
+    let err = app
+        .restore_feature_from_request(&lifecycle_request(false))
+        .unwrap_err();
+    assert_eq!(error_code(err), Some(AutomationErrorCode::InvalidState));
+}
+
+#[test]
+fn import_picker_adopts_untracked_worktrees_and_sessions() {
+    let mut store = store_with_feature(ProjectStatus::Idle);
+    store.projects[0].features[0].is_worktree = true;
+    store.projects[0].features[0].workdir = PathBuf::from("/tmp/wt/my-feat");
+
+    let mut worktree = MockWorktreeOps::new();
+    worktree.expect_list().returning(|_| {
+        Ok(vec![
+            crate::worktree::WorktreeInfo {
+                path: PathBuf::from("/tmp/test-repo"),
+                branch: Some("main".to_string()),
+            },
+            crate::worktree::WorktreeInfo {
+                path: PathBuf::from("/tmp/wt/my-feat"),
+                branch: Some("my-feat".to_string()),
+            },
+            crate::worktree::WorktreeInfo {
+                path: PathBuf::from("/tmp/wt/login-fix"),
+                branch: Some("login-fix".to_string()),
+            },
+            crate::worktree::WorktreeInfo {
+                path: PathBuf::from("/tmp/wt/spike"),
+                branch: None,
+            },
+        ])
+    });
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_list_session_paths().returning(|| {
+        Ok(vec![
+            ("amf-my-feat".to_string(), PathBuf::from("/tmp/wt/my-feat")),
+            ("stray".to_string(), PathBuf::from("/tmp/wt/my-feat/src")),
+            ("work".to_string(), PathBuf::from("/tmp/wt/login-fix/src")),
+            ("scratch".to_string(), PathBuf::from("/tmp/test-repo")),
+            ("music".to_string(), PathBuf::from("/home/me/music")),
+        ])
+    });
+    tmux.expect_list_window_commands()
+        .withf(|session| session == "work")
+        .returning(|_| {
+            Ok(vec![
+                ("agent".to_string(), "claude".to_string()),
+                ("shell".to_string(), "zsh".to_string()),
+            ])
+        });
+    tmux.expect_list_window_commands()
+        .withf(|session| session == "scratch")
+        .returning(|_| Ok(vec![("zsh".to_string(), "zsh".to_string())]));
+    tmux.expect_set_session_env()
+        .withf(|session, key, value| key == "AMF_SESSION" && session == value)
+        .times(2)
+        .returning(|_, _, _| Ok(()));
+
+    let mut app = App::new_for_test(store, Box::new(tmux), Box::new(worktree));
+    let tmp = NamedTempFile::new().unwrap();
+    app.store_path = tmp.path().to_path_buf();
+    app.selection = Selection::Project(0);
+
+    app.open_import_picker();
+    let AppMode::ImportPicker(state) = &mut app.mode else {
+        panic!("expected ImportPicker mode");
+    };
+    let names: Vec<&str> = state.candidates.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["login-fix", "spike", "scratch"]);
+    assert_eq!(state.candidates[0].tmux_session.as_deref(), Some("work"));
+    assert_eq!(state.candidates[1].tmux_session, None);
+    assert!(!state.candidates[2].is_worktree);
+    state.checked[0] = true;
+    state.checked[2] = true;
+
+    app.import_checked_candidates().unwrap();
+
+    let features = &app.store.projects[0].features;
+    assert_eq!(features.len(), 3);
+    let login = &features[1];
+    assert_eq!(login.tmux_session, "work");
+    assert_eq!(login.agent, AgentKind::Claude);
+    assert_eq!(login.status, ProjectStatus::Idle);
+    let windows: Vec<(&str, &SessionKind)> = login
+        .sessions
+        .iter()
+        .map(|s| (s.tmux_window.as_str(), &s.kind))
+        .collect();
+    assert_eq!(
+        windows,
+        vec![
+            ("agent", &SessionKind::Claude),
+            ("shell", &SessionKind::Terminal)
+        ]
+    );
+    let scratch = &features[2];
+    assert_eq!(scratch.workdir, PathBuf::from("/tmp/test-repo"));
+    assert!(!scratch.is_worktree);
+    assert!(matches!(app.selection, Selection::Feature(0, 2)));
+    assert_eq!(app.message.as_deref(), Some("Imported 2 feature(s)"));
+}
+
+fn multi_repo_request(extra_projects: &[&str], dry_run: bool) -> CreateFeatureRequest {
+    CreateFeatureRequest {
+        project_name: "automation-project".to_string(),
+        branch: "checkout".to_string(),
+        agent: AgentKind::Codex,
+        mode: VibeMode::Vibe,
+        extra_projects: extra_projects.iter().map(|name| name.to_string()).collect(),
+        dry_run,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn multi_repo_feature_creates_member_worktrees_under_one_session() {
+    let workspace = TempDir::new().unwrap();
+    let backend = workspace.path().join("backend");
+    let frontend = workspace.path().join("frontend");
+    std::fs::create_dir_all(&backend).unwrap();
+    std::fs::create_dir_all(&frontend).unwrap();
+    let parent_dir = backend.join(".worktrees").join("checkout");
+
+    let mut store = store_with_empty_project(backend.clone(), true);
+    store.projects.push(Project::new(
+        "frontend".to_string(),
+        frontend.clone(),
+        true,
+        AgentKind::Codex,
+    ));
+    let frontend_id = store.projects[1].id.clone();
+
+    let mut worktree = MockWorktreeOps::new();
+    let expected = [
+        (backend.clone(), parent_dir.join("automation-project")),
+        (frontend.clone(), parent_dir.join("frontend")),
+    ];
+    worktree
+        .expect_create_at()
+        .times(2)
+        .withf(move |repo, path, branch| {
+            branch == "checkout" && expected.iter().any(|(r, p)| r == repo && p == path)
+        })
+        .returning(|_, path, _| Ok(path.to_path_buf()));
+
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_session_exists().returning(|_| false);
+    let session_dir = parent_dir.clone();
+    tmux.expect_create_session_with_window()
+        .times(1)
+        .withf(move |_, _, workdir| workdir == session_dir.as_path())
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_set_session_env().returning(|_, _, _| Ok(()));
+    tmux.expect_launch_codex()
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_select_window().returning(|_, _| Ok(()));
+
+    let mut app = App::new_for_test(store, Box::new(tmux), Box::new(worktree));
+    let store_file = NamedTempFile::new().unwrap();
+    app.store_path = store_file.path().to_path_buf();
+
+    let preview = app
+        .create_feature_from_request(&multi_repo_request(&["frontend"], true))
+        .unwrap();
+    assert_eq!(preview.workdir, parent_dir);
+    assert_eq!(
+        preview.repos,
+        vec![
+            parent_dir.join("automation-project"),
+            parent_dir.join("frontend")
+        ]
+    );
+    assert!(app.store.projects[0].features.is_empty());
+
+    let response = app
+        .create_feature_from_request(&multi_repo_request(&["frontend"], false))
+        .unwrap();
+    assert!(response.started);
+    assert!(response.is_worktree);
+    assert_eq!(response.repos.len(), 2);
+
+    let feature = &app.store.projects[0].features[0];
+    assert!(feature.is_multi_repo());
+    assert_eq!(feature.workdir, parent_dir);
+    assert_eq!(feature.repos[1].project_id, frontend_id);
+    assert_eq!(feature.repos[1].repo, frontend);
+    assert_eq!(feature.repos[1].label(), "frontend");
+    assert!(app.store.projects[1].features.is_empty());
+    assert!(app.archive_blocker(0, 0).is_some());
+}
+
+#[test]
+fn multi_repo_feature_rejects_unknown_duplicate_or_stacked_members() {
+    let workspace = TempDir::new().unwrap();
+    let mut store = store_with_empty_project(workspace.path().to_path_buf(), true);
+    let parent = Feature::new(
+        "base".to_string(),
+        "base".to_string(),
+        workspace.path().join(".worktrees/base"),
+        true,
+        VibeMode::Vibe,
+        false,
+        false,
+        AgentKind::Codex,
+        false,
+    );
+    store.projects[0].features.push(parent);
+    let mut app = App::new_for_test(
+        store,
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let error_code = |err: anyhow::Error| err.downcast_ref::<AutomationError>().map(|err| err.code);
+
+    let err = app
+        .create_feature_from_request(&multi_repo_request(&["missing"], true))
+        .unwrap_err();
+    assert_eq!(error_code(err), Some(AutomationErrorCode::ProjectNotFound));
+
+    let err = app
+        .create_feature_from_request(&multi_repo_request(&["automation-project"], true))
+        .unwrap_err();
+    assert_eq!(error_code(err), Some(AutomationErrorCode::ValidationFailed));
+
+    let err = app
+        .create_feature_from_request(&CreateFeatureRequest {
+            parent_feature: Some("base".to_string()),
+            ..multi_repo_request(&["missing"], true)
+        })
+        .unwrap_err();
+    assert_eq!(error_code(err), Some(AutomationErrorCode::ValidationFailed));
+
+    let err = app
+        .create_feature_from_request(&CreateFeatureRequest {
+            use_worktree: Some(false),
+            ..multi_repo_request(&["missing"], true)
+        })
+        .unwrap_err();
+    assert_eq!(error_code(err), Some(AutomationErrorCode::ValidationFailed));
+}
+
+#[test]
+fn multi_repo_feature_ready_and_delete_cover_every_member() {
+    let fixtures = [init_repo_with_src("base\n"), init_repo_with_src("base\n")];
+    let repos: Vec<PathBuf> = fixtures
+        .iter()
+        .map(|fixture| fixture.path().to_path_buf())
+        .collect();
+    let parent_dir = repos[0].join(".worktrees").join("checkout");
+
+    let mut store = store_with_empty_project(repos[0].clone(), true);
+    let mut feature = Feature::new(
+        "checkout".to_string(),
+        "checkout".to_string(),
+        parent_dir.clone(),
+        true,
+        VibeMode::Vibe,
+        false,
+        false,
+        AgentKind::Codex,
+        false,
+    );
+    feature.tmux_session = format!("amf-multi-repo-test-{}", std::process::id());
+    for (repo, name) in repos.iter().zip(["backend", "frontend"]) {
+        let workdir = parent_dir.join(name);
+        WorktreeManager::create_at(repo, &workdir, "checkout").unwrap();
+        feature.repos.push(crate::project::FeatureRepo {
+            project_id: name.to_string(),
+            repo: repo.clone(),
+            workdir,
+        });
+    }
+    store.projects[0].features.push(feature);
+    std::fs::write(parent_dir.join("frontend/src.txt"), "base\nui\n").unwrap();
+
+    let mut app = App::new_for_test(
+        store,
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let store_file = NamedTempFile::new().unwrap();
+    app.store_path = store_file.path().to_path_buf();
+
+    let response = app
+        .set_feature_ready_from_request(&SetFeatureReadyRequest {
+            project_name: "automation-project".to_string(),
+            feature_name: "checkout".to_string(),
+            ready: true,
+            dry_run: false,
+        })
+        .unwrap();
+    assert_eq!(
+        response.message,
+        "Marked 'checkout' as ready (uncommitted changes in frontend)"
+    );
+
+    let diff = app
+        .read_diff_from_request(&ReadDiffRequest {
+            project_name: "automation-project".to_string(),
+            feature_name: "checkout".to_string(),
+            ..Default::default()
+        })
+        .unwrap();
+    assert_eq!(diff.files.len(), 1);
+    assert_eq!(diff.files[0].path, "frontend/src.txt");
+
+    let state = app
+        .begin_feature_deletion("automation-project", "checkout")
+        .unwrap()
+        .unwrap();
+    assert_eq!(state.workdir, parent_dir.join("backend"));
+    assert_eq!(state.pending_worktrees.len(), 1);
+    app.mode = AppMode::DeletingFeatureInProgress(state);
+    let deadline = Instant::now() + std::time::Duration::from_secs(10);
+    while !matches!(
+        &app.mode,
+        AppMode::DeletingFeatureInProgress(state)
+            if state.stage == DeleteStage::Completed && state.child.is_none()
+    ) {
+        assert!(Instant::now() < deadline, "deletion did not finish");
+        app.poll_deleting_feature().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    app.complete_deleting_feature().unwrap();
+
+    assert!(app.store.projects[0].features.is_empty());
+    assert!(!parent_dir.exists());
+    for repo in &repos {
+        let worktrees = WorktreeManager::list(repo).unwrap();
+        assert_eq!(worktrees.len(), 1, "{}", repo.display());
+    }
+}
+
+/// A git repo whose `my-feat` branch is checked out in the repo itself,
+/// one commit ahead of `main` and editing `src.txt`.
+fn repo_with_feature_commit() -> TempDir {
+    let workspace = init_repo_with_src("base\n");
+    let repo = workspace.path();
+    git_ok(repo, &["checkout", "-q", "-b", "my-feat"]);
+    std::fs::write(repo.join("src.txt"), "feature\n").unwrap();
+    git_ok(repo, &["commit", "-q", "-am", "feature change"]);
+    workspace
+}
+
+#[test]
+fn land_feature_automation_previews_then_lands_a_ready_feature() {
+    let workspace = repo_with_feature_commit();
+    let repo = workspace.path().to_path_buf();
+    let mut app = App::new_for_test(
+        store_with_git_feature(repo.clone(), AgentKind::Claude),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let error_code = |err: anyhow::Error| err.downcast_ref::<AutomationError>().map(|err| err.code);
+    let mut request = LandFeatureRequest {
+        project_name: "my-project".to_string(),
+        feature_name: "my-feat".to_string(),
+        strategy: crate::land::LandStrategy::Squash,
+        dry_run: true,
+    };
+
+    let preview = app.land_feature_from_request(&request).unwrap();
+    assert_eq!(preview.target, "main");
+    assert_eq!(preview.ahead, 1);
+    assert!(preview.conflicts.is_empty());
+    assert!(preview.commit.is_none());
+
+    request.dry_run = false;
+    let err = app.land_feature_from_request(&request).unwrap_err();
+    assert_eq!(error_code(err), Some(AutomationErrorCode::InvalidState));
+
+    app.store.projects[0].features[0].ready = true;
+    let response = app.land_feature_from_request(&request).unwrap();
+    assert_eq!(response.message, "Landed 'my-feat' on 'main'");
+    let main = git_ok(&repo, &["rev-parse", "main"]);
+    assert_eq!(response.commit.as_deref(), Some(main.as_str()));
+}
+
+#[test]
+fn land_dialog_shows_conflicts_in_the_diff_viewer_and_refuses_to_land() {
+    let workspace = repo_with_feature_commit();
+    let repo = workspace.path().to_path_buf();
+    git_ok(&repo, &["checkout", "-q", "main"]);
+    std::fs::write(repo.join("src.txt"), "main\n").unwrap();
+    git_ok(&repo, &["commit", "-q", "-am", "main change"]);
+    git_ok(&repo, &["checkout", "-q", "my-feat"]);
+
+    let mut store = store_with_git_feature(repo, AgentKind::Claude);
+    store.projects[0].features[0].ready = true;
+    let mut app = App::new_for_test(
+        store,
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.selection = Selection::Feature(0, 0);
+
+    app.open_land_dialog();
+    match &app.mode {
+        AppMode::LandingFeature(state) => {
+            assert_eq!(state.preview.conflict_paths(), vec!["src.txt".to_string()]);
+        }
+        _ => panic!("expected the land dialog, message: {:?}", app.message),
+    }
+
+    app.open_land_conflicts();
+    match &app.mode {
+        AppMode::DiffViewer(state) => {
+            assert!(state.from_view.is_none());
+            assert_eq!(state.files.len(), 1);
+            assert_eq!(state.base_ref, "main");
+        }
+        _ => panic!("expected the diff viewer"),
+    }
+    app.refresh_diff_viewer();
+    assert!(matches!(&app.mode, AppMode::DiffViewer(state) if state.files.len() == 1));
+    app.close_diff_viewer();
+    assert!(matches!(app.mode, AppMode::LandingFeature(_)));
+
+    app.confirm_land_dialog().unwrap();
+    assert!(matches!(&app.mode, AppMode::LandingFeature(state) if state.landed.is_none()));
+    assert!(
+        app.message
+            .as_deref()
+            .is_some_and(|message| message.contains("conflicts with 'main' in src.txt")),
+        "got: {:?}",
+        app.message
+    );
+}
+
+#[test]
+fn overlap_scan_badges_features_editing_the_same_lines_and_opens_a_side_by_side_diff() {
+    let fixture = init_repo_with_src("1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n");
+    let repo = fixture.path().to_path_buf();
+    std::fs::write(repo.join("notes.txt"), "a\nb\nc\nd\ne\nf\ng\nh\n").unwrap();
+    git_ok(&repo, &["add", "notes.txt"]);
+    git_ok(&repo, &["commit", "-q", "-m", "add notes"]);
+    let workspace = TempDir::new().unwrap();
+
+    let mut store = store_with_git_feature(repo.clone(), AgentKind::Claude);
+    let template = store.projects[0].features[0].clone();
+    store.projects[0].features.clear();
+    for (name, src, notes) in [
+        (
+            "left",
+            "1\n2\nleft\n4\n5\n6\n7\n8\n9\n10\n",
+            "left\nb\nc\nd\ne\nf\ng\nh\n",
+        ),
+        (
+            "right",
+            "1\n2\nright\n4\n5\n6\n7\n8\n9\n10\n",
+            "a\nb\nc\nd\ne\nf\ng\nright\n",
+        ),
+    ] {
+        let workdir = workspace.path().join(name);
+        git_ok(
+            &repo,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                name,
+                workdir.to_str().unwrap(),
+            ],
+        );
+        std::fs::write(workdir.join("src.txt"), src).unwrap();
+        std::fs::write(workdir.join("notes.txt"), notes).unwrap();
+        let mut feature = template.clone();
+        feature.id = format!("{name}-id");
+        feature.name = name.to_string();
+        feature.branch = name.to_string();
+        feature.workdir = workdir;
+        store.projects[0].features.push(feature);
+    }
+    let mut app = App::new_for_test(
+        store,
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+
+    app.scan_overlaps_background();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+    while !app.poll_overlap_scan() {
+        assert!(
+            app.overlap_scan_bg.is_some(),
+            "overlap scan ended without results"
+        );
+        assert!(
+            std::time::Instant::now() < deadline,
+            "overlap scan timed out"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    assert_eq!(app.feature_overlaps.len(), 1);
+    assert_eq!(app.feature_overlap_kind(0, 0), Some(true));
+    assert_eq!(app.feature_overlap_kind(0, 1), Some(true));
+
+    app.selection = Selection::Feature(0, 1);
+    app.open_overlap_dialog();
+    match &app.mode {
+        AppMode::ReviewingOverlaps(state) => {
+            assert_eq!(state.feature_name, "right");
+            assert_eq!(state.entries.len(), 1);
+            assert_eq!(state.entries[0].feature_name, "left");
+            let files: Vec<(&str, bool)> = state.entries[0]
+                .files
+                .iter()
+                .map(|file| (file.path.as_str(), file.hunks_overlap()))
+                .collect();
+            assert_eq!(files, vec![("notes.txt", false), ("src.txt", true)]);
+        }
+        _ => panic!("expected the overlap dialog, message: {:?}", app.message),
+    }
+
+    app.overlap_dialog_select_next();
+    app.open_overlap_diff();
+    match &app.mode {
+        AppMode::DiffViewer(state) => {
+            assert_eq!(state.layout, DiffViewerLayout::SideBySide);
+            assert_eq!(
+                (state.branch.as_str(), state.base_ref.as_str()),
+                ("right", "left")
+            );
+            let file = &state.files[0];
+            assert_eq!(file.path, "src.txt");
+            assert!(file.old_content.as_deref().unwrap().contains("left"));
+            assert!(file.new_content.as_deref().unwrap().contains("right"));
+        }
+        _ => panic!("expected the diff viewer, message: {:?}", app.message),
+    }
+    app.refresh_diff_viewer();
+    assert!(matches!(&app.mode, AppMode::DiffViewer(state) if state.files.len() == 1));
+    app.close_diff_viewer();
+    assert!(matches!(&app.mode, AppMode::ReviewingOverlaps(state) if state.selected == 1));
+    app.cancel_overlap_dialog();
+    assert!(matches!(app.mode, AppMode::Normal));
+}
+
+#[test]
+fn git_summaries_refresh_in_the_background_per_feature() {
+    let workspace = repo_with_feature_commit();
+    let repo = workspace.path().to_path_buf();
+    std::fs::write(repo.join("scratch.txt"), "wip\n").unwrap();
+    let mut app = App::new_for_test(
+        store_with_git_feature(repo, AgentKind::Claude),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let feature_id = app.store.projects[0].features[0].id.clone();
+
+    app.refresh_git_summaries_background();
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(30);
+    while !app.poll_git_summaries() {
+        assert!(
+            app.git_summary_bg.is_some(),
+            "git summary refresh ended without results"
+        );
+        assert!(
+            std::time::Instant::now() < deadline,
+            "git summary refresh timed out"
+        );
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    let summary = app.git_summary(&feature_id).unwrap();
+    assert_eq!(summary.dirty, 1);
+    assert_eq!(summary.base_ref.as_deref(), Some("main"));
+    assert_eq!((summary.ahead, summary.behind), (1, 0));
+    assert!(app.git_summary_bg.is_none());
+}
+
+#[test]
+fn diff_viewer_stages_a_hunk_and_commits_it() {
+    let workspace = repo_with_feature_commit();
+    let repo = workspace.path().to_path_buf();
+    std::fs::write(repo.join("src.txt"), "feature\nwip\n").unwrap();
+    let mut app = App::new_for_test(
+        store_with_git_feature(repo.clone(), AgentKind::Claude),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.mode = AppMode::DiffViewer(DiffViewerState::new(None, repo.clone()));
+
+    app.diff_viewer_toggle_staging();
+    match &app.mode {
+        AppMode::DiffViewer(state) => {
+            assert_eq!(
+                state.staging.as_ref().map(|staging| staging.side),
+                Some(crate::stage::StageSide::Unstaged)
+            );
+            assert_eq!(state.files.len(), 1);
+            assert_eq!(state.files[0].path, "src.txt");
+        }
+        _ => panic!("expected the diff viewer"),
+    }
+
+    app.open_commit_dialog();
+    assert_eq!(app.message.as_deref(), Some("Nothing is staged"));
+
+    app.diff_viewer_apply_stage();
+    assert_eq!(app.message.as_deref(), Some("Staged hunk in src.txt"));
+    assert!(matches!(&app.mode, AppMode::DiffViewer(state) if state.files.is_empty()));
+
+    app.open_commit_dialog();
+    app.confirm_commit_dialog().unwrap();
+    assert!(matches!(app.mode, AppMode::CommitMessage(_)));
+    assert_eq!(
+        app.message.as_deref(),
+        Some("Error: Commit message is empty")
+    );
+
+    if let AppMode::CommitMessage(state) = &mut app.mode {
+        state.editor.insert_str("Add wip line");
+    }
+    app.confirm_commit_dialog().unwrap();
+    assert!(matches!(&app.mode, AppMode::DiffViewer(state) if state.staging.is_some()));
+    assert!(app.message.as_deref().unwrap().starts_with("Committed "));
+    assert_eq!(git_ok(&repo, &["log", "-1", "--format=%s"]), "Add wip line");
+
+    app.diff_viewer_toggle_staging();
+    match &app.mode {
+        AppMode::DiffViewer(state) => {
+            assert!(state.staging.is_none());
+            assert_eq!(state.base_ref, "main");
+            assert_eq!(state.files.len(), 1);
+        }
+        _ => panic!("expected the diff viewer"),
+    }
+}
+
+#[test]
+fn checkpoints_snapshot_diff_restore_and_fork() {
+    let workspace = repo_with_feature_commit();
+    let repo = workspace.path().to_path_buf();
+    std::fs::write(repo.join("src.txt"), "feature\nagent work\n").unwrap();
+    let mut app = App::new_for_test(
+        store_with_git_feature(repo.clone(), AgentKind::Claude),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.selection = Selection::Feature(0, 0);
+    app.open_checkpoint_dialog();
+    assert!(
+        matches!(&app.mode, AppMode::ReviewingCheckpoints(state) if state.checkpoints.is_empty())
+    );
+
+    app.checkpoint_feature_background(0, 0, "agent stopped");
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+    while !app.poll_checkpoints() {
+        assert!(std::time::Instant::now() < deadline, "checkpoint timed out");
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+    match &app.mode {
+        AppMode::ReviewingCheckpoints(state) => {
+            assert_eq!(state.checkpoints.len(), 1);
+            assert_eq!(state.checkpoints[0].number, 1);
+            assert_eq!(state.checkpoints[0].reason, "agent stopped");
+        }
+        _ => panic!("expected the checkpoint dialog"),
+    }
+    let status = std::process::Command::new("git")
+        .args(["status", "--porcelain"])
+        .current_dir(&repo)
+        .output()
+        .unwrap();
+    assert_eq!(String::from_utf8_lossy(&status.stdout), " M src.txt\n");
+
+    app.open_checkpoint_diff();
+    assert_eq!(
+        app.message.as_deref(),
+        Some("No changes since checkpoint #1")
+    );
+
+    std::fs::write(repo.join("src.txt"), "broken\n").unwrap();
+    std::fs::write(repo.join("scratch.txt"), "temp\n").unwrap();
+    app.open_checkpoint_diff();
+    match &app.mode {
+        AppMode::DiffViewer(state) => {
+            assert_eq!(state.base_ref, "checkpoint #1");
+            let paths: Vec<_> = state.files.iter().map(|file| file.path.as_str()).collect();
+            assert_eq!(paths, vec!["scratch.txt", "src.txt"]);
+        }
+        _ => panic!("expected the diff viewer"),
+    }
+    app.close_diff_viewer();
+    assert!(matches!(app.mode, AppMode::ReviewingCheckpoints(_)));
+
+    app.restore_selected_checkpoint();
+    assert_eq!(
+        app.message.as_deref(),
+        Some("Restored checkpoint #1 (previous state saved as #2)")
+    );
+    assert_eq!(
+        std::fs::read_to_string(repo.join("src.txt")).unwrap(),
+        "feature\nagent work\n"
+    );
+    assert!(!repo.join("scratch.txt").exists());
+    match &app.mode {
+        AppMode::ReviewingCheckpoints(state) => {
+            let numbers: Vec<_> = state.checkpoints.iter().map(|cp| cp.number).collect();
+            assert_eq!(numbers, vec![2, 1]);
+            assert_eq!(state.selected_checkpoint().unwrap().number, 1);
+        }
+        _ => panic!("expected the checkpoint dialog"),
+    }
+
+    app.fork_from_selected_checkpoint();
+    match &app.mode {
+        AppMode::ForkingFeature(state) => {
+            assert_eq!(state.new_branch, "my-feat-checkpoint-1");
+            assert_eq!(state.checkpoint.as_ref().map(|cp| cp.number), Some(1));
+        }
+        _ => panic!("expected the fork dialog"),
+    }
+}
+
+#[test]
+fn background_and_project_deletion_clear_checkpoints() {
+    let workspace = repo_with_feature_commit();
+    let repo = workspace.path().to_path_buf();
+    let mut store = store_with_git_feature(repo.clone(), AgentKind::Claude);
+    let mut second = store.projects[0].features[0].clone();
+    second.id = "feat-2".to_string();
+    second.name = "second".to_string();
+    second.tmux_session = "amf-second".to_string();
+    store.projects[0].features.push(second);
+    for feature_id in ["feat-1", "feat-2"] {
+        std::fs::write(repo.join("src.txt"), format!("{feature_id} work\n")).unwrap();
+        assert!(
+            crate::checkpoint::snapshot(&repo, feature_id, "agent stopped")
+                .unwrap()
+                .is_some()
+        );
+    }
+    let mut app = App::new_for_test(
+        store,
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let tmp = NamedTempFile::new().unwrap();
+    app.store_path = tmp.path().to_path_buf();
+
+    app.background_deletions.insert(
+        "my-project/my-feat".to_string(),
+        DeletingFeatureState {
+            project_name: "my-project".to_string(),
+            feature_name: "my-feat".to_string(),
+            tmux_session: "amf-my-feat".to_string(),
+            is_worktree: false,
+            repo: repo.clone(),
+            workdir: repo.clone(),
+            pending_worktrees: Vec::new(),
+            workspace_dir: None,
+            stage: DeleteStage::Completed,
+            child: None,
+            output: String::new(),
+            output_rx: None,
+            error: None,
+        },
+    );
+    app.poll_background_deletions().unwrap();
+
+    assert_eq!(app.store.projects[0].features.len(), 1);
+    assert!(crate::checkpoint::list(&repo, "feat-1").unwrap().is_empty());
+    assert_eq!(crate::checkpoint::list(&repo, "feat-2").unwrap().len(), 1);
+
+    app.mode = AppMode::DeletingProject("my-project".to_string());
+    app.delete_project().unwrap();
+
+    assert!(app.store.projects.is_empty());
+    assert!(crate::checkpoint::list(&repo, "feat-2").unwrap().is_empty());
+}
+
+#[test]
+fn daemon_takes_a_checkpoint_on_every_stop() {
+    let workspace = repo_with_feature_commit();
+    let repo = workspace.path().to_path_buf();
+    let mut app = App::new_for_test(
+        store_with_git_feature(repo.clone(), AgentKind::Claude),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let feature_id = app.store.projects[0].features[0].id.clone();
+
+    for (count, content) in [(1, "first stop\n"), (2, "second stop\n")] {
+        std::fs::write(repo.join("src.txt"), content).unwrap();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        loop {
+            app.handle_ipc_message_value(serde_json::json!({
+                "type": "stop",
+                "session_id": "amf-my-feat",
+                "cwd": repo.display().to_string(),
+            }));
+            crate::daemon::poll_background_results(&mut app);
+            if crate::checkpoint::list(&repo, &feature_id).unwrap().len() == count {
+                break;
+            }
+            assert!(
+                std::time::Instant::now() < deadline,
+                "checkpoint {count} was never taken"
+            );
+            std::thread::sleep(std::time::Duration::from_millis(20));
+        }
+    }
+
+    let checkpoints = crate::checkpoint::list(&repo, &feature_id).unwrap();
+    assert_eq!(
+        checkpoints
+            .iter()
+            .map(|cp| cp.reason.as_str())
+            .collect::<Vec<_>>(),
+        vec!["agent stopped", "agent stopped"]
+    );
+}
+
+#[test]
+fn archive_feature_refuses_a_worktree_with_untracked_files() {
+    let workspace = repo_with_feature_commit();
+    let repo = workspace.path().to_path_buf();
+    std::fs::create_dir_all(repo.join(".claude")).unwrap();
+    std::fs::write(repo.join(".claude/latest-prompt.txt"), "add notes").unwrap();
+    let mut store = store_with_git_feature(repo.clone(), AgentKind::Claude);
+    store.projects[0].features[0].is_worktree = true;
+    let mut app = App::new_for_test(
+        store,
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    assert_eq!(app.archive_blocker(0, 0), None);
+
+    std::fs::write(repo.join("notes.md"), "never added").unwrap();
+    let err = app.archive_feature(0, 0).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "'my-feat' has 1 untracked file(s) such as notes.md; commit or remove them before archiving"
+    );
+    assert!(!app.store.projects[0].features[0].is_archived());
+    assert!(repo.join("notes.md").exists());
+}
+
+#[test]
+fn archive_feature_refuses_when_git_cannot_check_the_worktree() {
+    let workdir = TempDir::new().unwrap();
+    std::fs::write(workdir.path().join("notes.md"), "unsaved work").unwrap();
+    let mut store = store_with_feature(ProjectStatus::Idle);
+    let feature = &mut store.projects[0].features[0];
+    feature.is_worktree = true;
+    feature.workdir = workdir.path().to_path_buf();
+    let mut app = App::new_for_test(
+        store,
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+
+    let err = app.archive_feature(0, 0).unwrap_err();
+    assert!(
+        err.to_string()
+            .starts_with("could not check 'my-feat' for changes: "),
+        "{err}"
+    );
+    assert!(!app.store.projects[0].features[0].is_archived());
+    assert!(workdir.path().join("notes.md").exists());
+}
+
+#[test]
+fn archive_and_restore_keep_a_custom_worktree_path() {
+    let workspace = repo_with_feature_commit();
+    let workdir = workspace.path().join("custom/my-feat");
+    let path = workdir.to_str().unwrap();
+    git_ok(
+        workspace.path(),
+        &["worktree", "add", "-q", "-b", "custom-feat", path],
+    );
+    let mut store = store_with_feature(ProjectStatus::Idle);
+    store.projects[0].repo = workspace.path().to_path_buf();
+    let feature = &mut store.projects[0].features[0];
+    feature.is_worktree = true;
+    feature.workdir = workdir.clone();
+    let mut session = make_session("claude", None);
+    session.claude_session_id = Some("resume-123".to_string());
+    feature.sessions = vec![session];
+
+    let mut worktree = MockWorktreeOps::new();
+    worktree
+        .expect_remove()
+        .times(1)
+        .returning(|_, path| Ok(std::fs::remove_dir_all(path)?));
+    let expected_path = workdir.clone();
+    worktree
+        .expect_create_at()
+        .withf(move |_, path, branch| path == expected_path && branch == "my-feat")
+        .times(1)
+        .returning(|_, path, _| {
+            std::fs::create_dir_all(path)?;
+            Ok(path.to_path_buf())
+        });
+    let mut tmux = MockTmuxOps::new();
+    tmux.expect_session_exists().return_const(false);
+    tmux.expect_create_session_with_window()
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_set_session_env()
+        .times(1)
+        .returning(|_, _, _| Ok(()));
+    tmux.expect_launch_claude()
+        .withf(|_, _, resume_id, _| resume_id.as_deref() == Some("resume-123"))
+        .times(1)
+        .returning(|_, _, _, _| Ok(()));
+    tmux.expect_select_window().returning(|_, _| Ok(()));
+
+    let mut app = App::new_for_test(store, Box::new(tmux), Box::new(worktree));
+    let tmp = NamedTempFile::new().unwrap();
+    app.store_path = tmp.path().to_path_buf();
+
+    app.archive_feature(0, 0).unwrap();
+    assert!(!workdir.exists());
+    app.restore_feature(0, 0).unwrap();
+
+    let feature = &app.store.projects[0].features[0];
+    assert!(!feature.is_archived());
+    assert_eq!(feature.workdir, workdir);
+    assert!(workdir.exists());
+}
+
+#[test]
+fn wait_for_idle_automation_returns_when_a_diff_review_opens() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Active),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    app.thinking_features.insert("amf-my-feat".to_string());
+
+    let dir = tempfile::TempDir::new().unwrap();
+    let socket = dir.path().join("reply.sock");
+    let guard = crate::ipc::start(&socket).unwrap();
+    app.handle_ipc_message_value(serde_json::json!({
+        "type": "automation",
+        "action": "wait_for_idle",
+        "project_name": "my-project",
+        "feature_name": "my-feat",
+        "request_id": "req-1",
+        "reply_socket": socket.display().to_string(),
+    }));
+    app.poll_automation_waits();
+    assert_eq!(app.automation_waits.len(), 1);
+
+    // The review goes straight to the prompt, never into `pending_inputs`.
+    app.open_diff_review_prompt(&PendingInput {
+        cwd: "/tmp/test-workdir".to_string(),
+        ..diff_review_for("my-feat", "review-1", None)
+    });
+    assert!(app.pending_inputs.is_empty());
+    let state = app
+        .list_state_from_request(&ListStateRequest::default())
+        .unwrap();
+    let pending = &state.projects[0].features[0].pending_inputs;
+    assert_eq!(pending.len(), 1);
+    assert_eq!(pending[0].request_id.as_deref(), Some("review-1"));
+
+    app.poll_automation_waits();
+    assert!(app.automation_waits.is_empty());
+    let reply = guard
+        .rx
+        .recv_timeout(std::time::Duration::from_secs(1))
+        .expect("no automation reply within 1s");
+    assert_eq!(reply["outcome"], "input_requested");
+    assert_eq!(
+        reply["feature"]["pending_inputs"][0]["notification_type"],
+        "diff-review"
+    );
+}
+
+#[test]
+fn event_snapshot_diff_reports_status_and_thinking_transitions() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Stopped),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let before = EventSnapshot::capture(&app);
+
+    app.store.projects[0].features[0].status = ProjectStatus::Idle;
+    app.thinking_features.insert("amf-my-feat".to_string());
+    let after = EventSnapshot::capture(&app);
+
+    assert_eq!(
+        before.diff(&after),
+        vec![
+            AutomationEvent::FeatureStatusChanged {
+                project_name: "my-project".to_string(),
+                feature_name: "my-feat".to_string(),
+                from: ProjectStatus::Stopped,
+                to: ProjectStatus::Idle,
+            },
+            AutomationEvent::ThinkingStarted {
+                project_name: "my-project".to_string(),
+                feature_name: "my-feat".to_string(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn event_snapshot_diff_reports_created_and_deleted_features() {
+    let mut app = App::new_for_test(
+        store_with_feature(ProjectStatus::Idle),
+        Box::new(MockTmuxOps::new()),
+        Box::new(MockWorktreeOps::new()),
+    );
+    let before = EventSnapshot::capture(&app);
+    app.store.remove_feature("my-project", "my-feat");
+    let after = EventSnapshot::capture(&app);
+
+    assert_eq!(
+        before.diff(&after),
+        vec![AutomationEvent::FeatureDeleted {
+            project_name: "my-project".to_string(),
+            feature_name: "my-feat".to_string(),
+        }]
+    );
+    assert!(matches!(
+        after.diff(&before).as_slice(),
+        [AutomationEvent::FeatureCreated { feature_name, .. }] if feature_name == "my-feat"
+    ));
+}
+
+#[test]
+fn automation_event_message_carries_type_and_event_tag() {
+    let message = AutomationEvent::ThinkingStopped {
+        project_name: "my-project".to_string(),
+        feature_name: "my-feat".to_string(),
+    }
+    .to_message(Utc::now());
+
+    assert_eq!(message["type"], "automation-event");
+    assert_eq!(message["event"], "thinking_stopped");
+    assert_eq!(message["feature_name"], "my-feat");
+    assert!(message.get("at").is_some());
+}
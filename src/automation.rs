@@ -1,21 +1,278 @@
 use std::path::PathBuf;
 
+use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use crate::project::{AgentKind, VibeMode};
+use crate::diff::{DiffFile, DiffFileStatus, DiffSnapshot};
+use crate::land::{LandPreview, LandStrategy};
+use crate::project::{AgentKind, Feature, ProjectStatus, SessionKind, VibeMode};
+use crate::token_tracking::SessionTokenUsage;
+
+/// Version of the automation request/response contract. Bump it whenever an
+/// existing field changes meaning, so clients built against another `amf`
+/// get an `unsupported_protocol_version` error instead of a misread payload.
+pub const AUTOMATION_PROTOCOL_VERSION: u32 = 1;
+/// Oldest client protocol version this build still accepts.
+pub const MIN_AUTOMATION_PROTOCOL_VERSION: u32 = 1;
 
 pub const AUTOMATION_REQUEST_TYPE: &str = "automation";
 pub const AUTOMATION_RESULT_TYPE: &str = "automation-result";
+pub const AUTOMATION_EVENT_TYPE: &str = "automation-event";
+pub const HELLO_ACTION: &str = "hello";
 pub const CREATE_PROJECT_ACTION: &str = "create_project";
 pub const CREATE_FEATURE_ACTION: &str = "create_feature";
 pub const CREATE_BATCH_FEATURES_ACTION: &str = "create_batch_features";
+pub const LIST_STATE_ACTION: &str = "list_state";
+pub const SEND_PROMPT_ACTION: &str = "send_prompt";
+pub const START_FEATURE_ACTION: &str = "start_feature";
+pub const STOP_FEATURE_ACTION: &str = "stop_feature";
+pub const SET_FEATURE_READY_ACTION: &str = "set_feature_ready";
+pub const RENAME_FEATURE_ACTION: &str = "rename_feature";
+pub const DELETE_FEATURE_ACTION: &str = "delete_feature";
+pub const FORK_FEATURE_ACTION: &str = "fork_feature";
+pub const ADD_SESSION_ACTION: &str = "add_session";
+pub const WAIT_FOR_IDLE_ACTION: &str = "wait_for_idle";
+pub const LIST_INPUTS_ACTION: &str = "list_inputs";
+pub const RESOLVE_INPUT_ACTION: &str = "resolve_input";
+pub const READ_DIFF_ACTION: &str = "read_diff";
+pub const RESTACK_FEATURE_ACTION: &str = "restack_feature";
+pub const SET_FEATURE_TAGS_ACTION: &str = "set_feature_tags";
+pub const ARCHIVE_FEATURE_ACTION: &str = "archive_feature";
+pub const RESTORE_FEATURE_ACTION: &str = "restore_feature";
+pub const LAND_FEATURE_ACTION: &str = "land_feature";
+
+/// Every action the dashboard dispatches, as advertised by `hello`.
+pub const AUTOMATION_ACTIONS: &[&str] = &[
+    HELLO_ACTION,
+    CREATE_PROJECT_ACTION,
+    CREATE_FEATURE_ACTION,
+    CREATE_BATCH_FEATURES_ACTION,
+    LIST_STATE_ACTION,
+    SEND_PROMPT_ACTION,
+    START_FEATURE_ACTION,
+    STOP_FEATURE_ACTION,
+    SET_FEATURE_READY_ACTION,
+    RENAME_FEATURE_ACTION,
+    DELETE_FEATURE_ACTION,
+    FORK_FEATURE_ACTION,
+    ADD_SESSION_ACTION,
+    WAIT_FOR_IDLE_ACTION,
+    LIST_INPUTS_ACTION,
+    RESOLVE_INPUT_ACTION,
+    READ_DIFF_ACTION,
+    RESTACK_FEATURE_ACTION,
+    SET_FEATURE_TAGS_ACTION,
+    ARCHIVE_FEATURE_ACTION,
+    RESTORE_FEATURE_ACTION,
+    LAND_FEATURE_ACTION,
+];
+
+/// Machine-readable reason attached to every failed automation reply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum AutomationErrorCode {
+    ProjectNotFound,
+    ProjectExists,
+    FeatureNotFound,
+    SessionNotFound,
+    InputNotFound,
+    BranchExists,
+    NotGit,
+    ValidationFailed,
+    InvalidState,
+    InvalidPayload,
+    UnknownAction,
+    UnsupportedProtocolVersion,
+    OperationFailed,
+}
+
+impl AutomationErrorCode {
+    pub const ALL: [AutomationErrorCode; 13] = [
+        AutomationErrorCode::ProjectNotFound,
+        AutomationErrorCode::ProjectExists,
+        AutomationErrorCode::FeatureNotFound,
+        AutomationErrorCode::SessionNotFound,
+        AutomationErrorCode::InputNotFound,
+        AutomationErrorCode::BranchExists,
+        AutomationErrorCode::NotGit,
+        AutomationErrorCode::ValidationFailed,
+        AutomationErrorCode::InvalidState,
+        AutomationErrorCode::InvalidPayload,
+        AutomationErrorCode::UnknownAction,
+        AutomationErrorCode::UnsupportedProtocolVersion,
+        AutomationErrorCode::OperationFailed,
+    ];
+}
+
+/// An automation failure with a stable code. Raised through `anyhow` so the
+/// dispatcher can recover the code with `downcast_ref`; any other error is
+/// reported as `operation_failed`.
+#[derive(Debug, Clone)]
+pub struct AutomationError {
+    pub code: AutomationErrorCode,
+    pub message: String,
+}
+
+impl AutomationError {
+    pub fn new(code: AutomationErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+
+    pub fn validation(message: impl Into<String>) -> Self {
+        Self::new(AutomationErrorCode::ValidationFailed, message)
+    }
+
+    pub fn invalid_state(message: impl Into<String>) -> Self {
+        Self::new(AutomationErrorCode::InvalidState, message)
+    }
+
+    pub fn project_not_found(project_name: &str) -> Self {
+        Self::new(
+            AutomationErrorCode::ProjectNotFound,
+            format!("Project '{}' not found", project_name),
+        )
+    }
+
+    pub fn project_exists(project_name: &str) -> Self {
+        Self::new(
+            AutomationErrorCode::ProjectExists,
+            format!("Project '{}' already exists", project_name),
+        )
+    }
+
+    pub fn feature_not_found(project_name: &str, feature_name: &str) -> Self {
+        Self::new(
+            AutomationErrorCode::FeatureNotFound,
+            format!("Feature '{}' not found in '{}'", feature_name, project_name),
+        )
+    }
+}
+
+impl std::fmt::Display for AutomationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for AutomationError {}
+
+/// Check the `protocol_version` stamped on an automation request. Requests
+/// without one come from clients that predate versioning and are read as
+/// version 1.
+pub fn check_protocol_version(raw: &serde_json::Value) -> Result<u32, AutomationError> {
+    let version = match raw.get("protocol_version") {
+        None | Some(serde_json::Value::Null) => 1,
+        Some(value) => value
+            .as_u64()
+            .and_then(|v| u32::try_from(v).ok())
+            .ok_or_else(|| {
+                AutomationError::new(
+                    AutomationErrorCode::InvalidPayload,
+                    format!("Invalid protocol_version {value}; expected a positive integer"),
+                )
+            })?,
+    };
+
+    if version > AUTOMATION_PROTOCOL_VERSION {
+        return Err(AutomationError::new(
+            AutomationErrorCode::UnsupportedProtocolVersion,
+            format!(
+                "Client uses automation protocol v{version}, but this amf only supports up to v{AUTOMATION_PROTOCOL_VERSION}; upgrade amf"
+            ),
+        ));
+    }
+    if version < MIN_AUTOMATION_PROTOCOL_VERSION {
+        return Err(AutomationError::new(
+            AutomationErrorCode::UnsupportedProtocolVersion,
+            format!(
+                "Client uses automation protocol v{version}, but this amf requires at least v{MIN_AUTOMATION_PROTOCOL_VERSION}; upgrade the client"
+            ),
+        ));
+    }
+    Ok(version)
+}
+
+/// Request structs default every field so older payloads keep working, which
+/// leaves schemars nothing to mark as required. Fields tagged with
+/// `length(min = 1)` whose default is empty can only be satisfied by the
+/// caller, so list them as required and drop the misleading default.
+fn require_non_empty_fields(schema: &mut schemars::Schema) {
+    let mut required = Vec::new();
+    if let Some(props) = schema.get_mut("properties").and_then(|p| p.as_object_mut()) {
+        for (name, prop) in props.iter_mut() {
+            if let Some(prop) = prop.as_object_mut()
+                && prop.contains_key("minLength")
+                && prop.get("default").is_none_or(|d| d == "")
+            {
+                prop.remove("default");
+                required.push(serde_json::json!(name));
+            }
+        }
+    }
+    if !required.is_empty() {
+        schema.insert("required".to_string(), serde_json::json!(required));
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct HelloRequest {}
+
+impl HelloRequest {
+    pub fn ipc_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": HELLO_ACTION,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct HelloResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub protocol_version: u32,
+    pub min_protocol_version: u32,
+    pub amf_version: &'static str,
+    pub headless: bool,
+    pub actions: Vec<&'static str>,
+    pub error_codes: Vec<AutomationErrorCode>,
+}
+
+impl HelloResponse {
+    pub fn new(headless: bool) -> Self {
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action: HELLO_ACTION,
+            ok: true,
+            protocol_version: AUTOMATION_PROTOCOL_VERSION,
+            min_protocol_version: MIN_AUTOMATION_PROTOCOL_VERSION,
+            amf_version: env!("CARGO_PKG_VERSION"),
+            headless,
+            actions: AUTOMATION_ACTIONS.to_vec(),
+            error_codes: AutomationErrorCode::ALL.to_vec(),
+        }
+    }
+}
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
 pub struct CreateProjectRequest {
+    /// Repo or directory to register. Must exist.
+    #[schemars(length(min = 1))]
     pub path: PathBuf,
+    #[schemars(length(min = 1))]
     pub project_name: String,
     pub preferred_agent: Option<AgentKind>,
+    /// Validate and preview without changing AMF state.
     pub dry_run: bool,
 }
 
@@ -43,19 +300,35 @@ impl CreateProjectRequest {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
 #[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
 pub struct CreateFeatureRequest {
+    /// An existing AMF project.
+    #[schemars(length(min = 1))]
     pub project_name: String,
+    /// Feature and branch name to create.
+    #[schemars(length(min = 1))]
     pub branch: String,
     pub agent: AgentKind,
     pub mode: VibeMode,
+    /// Enable the diff-review / final-review flows.
     pub review: bool,
     pub plan_mode: bool,
     pub create_terminal: bool,
+    /// Create a git worktree. Defaults to true unless this is the project's first feature.
     pub use_worktree: Option<bool>,
     pub enable_chrome: bool,
+    /// Stack the new feature on this feature's branch. Requires a worktree.
+    pub parent_feature: Option<String>,
+    /// Other git projects to create a worktree in on the same branch. The
+    /// feature then runs in a parent directory holding one worktree per repo.
+    pub extra_projects: Vec<String>,
+    /// Answer for a prompted `on_worktree_created` hook.
     pub hook_choice: Option<String>,
+    /// First prompt for the agent, sent once it is ready for input.
+    pub prompt: Option<String>,
+    /// Validate and preview without changing AMF state.
     pub dry_run: bool,
 }
 
@@ -73,26 +346,57 @@ impl CreateFeatureRequest {
             "create_terminal": self.create_terminal,
             "use_worktree": self.use_worktree,
             "enable_chrome": self.enable_chrome,
+            "parent_feature": self.parent_feature,
+            "extra_projects": self.extra_projects,
             "hook_choice": self.hook_choice,
+            "prompt": self.prompt,
             "dry_run": self.dry_run,
         })
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
 pub struct CreateBatchFeaturesRequest {
+    /// Any path inside the target git repository.
+    #[schemars(length(min = 1))]
     pub workspace_path: PathBuf,
+    /// Name of the AMF project to create.
+    #[schemars(length(min = 1))]
     pub project_name: String,
+    /// Ignored when `features` is set.
+    #[schemars(range(min = 1))]
     pub feature_count: usize,
+    /// Features are named `<prefix>1`, `<prefix>2`, ...
+    #[schemars(length(min = 1))]
     pub feature_prefix: String,
     pub agent: AgentKind,
     pub mode: VibeMode,
     pub review: bool,
     pub enable_chrome: bool,
+    /// One entry per feature. Unset entry fields fall back to the entry's
+    /// preset, then to the request-level values above.
+    pub features: Vec<BatchFeatureSpec>,
     pub dry_run: bool,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct BatchFeatureSpec {
+    /// Feature and branch name. Defaults to `<prefix><n>`.
+    pub branch: Option<String>,
+    pub agent: Option<AgentKind>,
+    pub mode: Option<VibeMode>,
+    pub review: Option<bool>,
+    /// Name of a feature preset from the workspace's extension config.
+    pub preset: Option<String>,
+    /// Branch or commit the new branch starts from. Defaults to the repo's HEAD.
+    pub base_ref: Option<String>,
+    /// Task prompt sent to the agent once it starts.
+    pub prompt: Option<String>,
+}
+
 impl Default for CreateBatchFeaturesRequest {
     fn default() -> Self {
         Self {
@@ -104,6 +408,7 @@ impl Default for CreateBatchFeaturesRequest {
             mode: VibeMode::default(),
             review: false,
             enable_chrome: false,
+            features: Vec::new(),
             dry_run: false,
         }
     }
@@ -122,154 +427,1541 @@ impl CreateBatchFeaturesRequest {
             "mode": self.mode,
             "review": self.review,
             "enable_chrome": self.enable_chrome,
+            "features": self.features,
             "dry_run": self.dry_run,
         })
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct CreateProjectResponse {
-    #[serde(rename = "type")]
-    pub msg_type: &'static str,
-    pub action: &'static str,
-    pub ok: bool,
-    pub dry_run: bool,
-    pub input_path: PathBuf,
-    pub project_name: String,
-    pub project_path: PathBuf,
-    pub is_git: bool,
-    pub message: String,
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct ListStateRequest {
+    pub project_name: Option<String>,
+    /// Only include features carrying this tag.
+    pub tag: Option<String>,
 }
 
-impl CreateProjectResponse {
-    pub fn success(
-        request: &CreateProjectRequest,
-        project_path: PathBuf,
-        is_git: bool,
-        message: String,
-    ) -> Self {
-        Self {
-            msg_type: AUTOMATION_RESULT_TYPE,
-            action: CREATE_PROJECT_ACTION,
-            ok: true,
-            dry_run: request.dry_run,
-            input_path: request.path.clone(),
-            project_name: request.project_name.clone(),
-            project_path,
-            is_git,
-            message,
-        }
+impl ListStateRequest {
+    pub fn ipc_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": LIST_STATE_ACTION,
+            "project_name": self.project_name,
+            "tag": self.tag,
+        })
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct AutomationHookPrompt {
-    pub title: String,
-    pub options: Vec<String>,
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
+pub struct SendPromptRequest {
+    #[schemars(length(min = 1))]
+    pub project_name: String,
+    #[schemars(length(min = 1))]
+    pub feature_name: String,
+    /// Session id, label, or tmux window. Defaults to the feature's agent session.
+    pub session: Option<String>,
+    #[schemars(length(min = 1))]
+    pub prompt: String,
+    /// Start the feature first when its tmux session is not running.
+    pub start: bool,
+    pub dry_run: bool,
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct CreateFeatureResponse {
-    #[serde(rename = "type")]
-    pub msg_type: &'static str,
-    pub action: &'static str,
-    pub ok: bool,
+impl SendPromptRequest {
+    pub fn ipc_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": SEND_PROMPT_ACTION,
+            "project_name": self.project_name,
+            "feature_name": self.feature_name,
+            "session": self.session,
+            "prompt": self.prompt,
+            "start": self.start,
+            "dry_run": self.dry_run,
+        })
+    }
+}
+
+/// Targets one feature for `start_feature`, `stop_feature`, `delete_feature`,
+/// `archive_feature`, or `restore_feature`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
+pub struct FeatureLifecycleRequest {
+    #[schemars(length(min = 1))]
+    pub project_name: String,
+    #[schemars(length(min = 1))]
+    pub feature_name: String,
+    pub hook_choice: Option<String>,
     pub dry_run: bool,
+}
+
+impl FeatureLifecycleRequest {
+    pub fn ipc_payload(&self, action: &str) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": action,
+            "project_name": self.project_name,
+            "feature_name": self.feature_name,
+            "hook_choice": self.hook_choice,
+            "dry_run": self.dry_run,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
+pub struct SetFeatureReadyRequest {
+    #[schemars(length(min = 1))]
     pub project_name: String,
-    pub branch: String,
-    pub workdir: PathBuf,
-    pub is_worktree: bool,
-    pub tmux_session: String,
-    pub started: bool,
-    pub worktree_hook_ran: bool,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub worktree_hook_succeeded: Option<bool>,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub worktree_hook_prompt: Option<AutomationHookPrompt>,
-    pub message: String,
+    #[schemars(length(min = 1))]
+    pub feature_name: String,
+    pub ready: bool,
+    pub dry_run: bool,
 }
 
-impl CreateFeatureResponse {
-    #[allow(clippy::too_many_arguments)]
-    pub fn success(
-        request: &CreateFeatureRequest,
-        workdir: PathBuf,
-        is_worktree: bool,
-        tmux_session: String,
-        started: bool,
-        worktree_hook_ran: bool,
-        worktree_hook_succeeded: Option<bool>,
-        worktree_hook_prompt: Option<AutomationHookPrompt>,
-        message: String,
-    ) -> Self {
+impl Default for SetFeatureReadyRequest {
+    fn default() -> Self {
         Self {
-            msg_type: AUTOMATION_RESULT_TYPE,
-            action: CREATE_FEATURE_ACTION,
-            ok: true,
-            dry_run: request.dry_run,
-            project_name: request.project_name.clone(),
-            branch: request.branch.clone(),
-            workdir,
-            is_worktree,
-            tmux_session,
-            started,
-            worktree_hook_ran,
-            worktree_hook_succeeded,
-            worktree_hook_prompt,
-            message,
+            project_name: String::new(),
+            feature_name: String::new(),
+            ready: true,
+            dry_run: false,
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct BatchFeatureAutomationResult {
-    pub name: String,
-    pub branch: String,
-    pub workdir: PathBuf,
-    pub tmux_session: String,
-    pub started: bool,
+impl SetFeatureReadyRequest {
+    pub fn ipc_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": SET_FEATURE_READY_ACTION,
+            "project_name": self.project_name,
+            "feature_name": self.feature_name,
+            "ready": self.ready,
+            "dry_run": self.dry_run,
+        })
+    }
 }
 
-#[derive(Debug, Clone, Serialize)]
-pub struct CreateBatchFeaturesResponse {
-    #[serde(rename = "type")]
-    pub msg_type: &'static str,
-    pub action: &'static str,
-    pub ok: bool,
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
+pub struct RenameFeatureRequest {
+    #[schemars(length(min = 1))]
+    pub project_name: String,
+    #[schemars(length(min = 1))]
+    pub feature_name: String,
+    /// New display nickname. `null` or an empty string clears it.
+    pub nickname: Option<String>,
     pub dry_run: bool,
-    pub workspace_path: PathBuf,
+}
+
+impl RenameFeatureRequest {
+    pub fn ipc_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": RENAME_FEATURE_ACTION,
+            "project_name": self.project_name,
+            "feature_name": self.feature_name,
+            "nickname": self.nickname,
+            "dry_run": self.dry_run,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
+pub struct SetFeatureTagsRequest {
+    #[schemars(length(min = 1))]
     pub project_name: String,
-    pub project_repo: PathBuf,
-    pub features: Vec<BatchFeatureAutomationResult>,
-    pub message: String,
+    #[schemars(length(min = 1))]
+    pub feature_name: String,
+    /// Replacement tag list. An empty list clears the tags.
+    pub tags: Vec<String>,
+    pub dry_run: bool,
 }
 
-impl CreateBatchFeaturesResponse {
-    pub fn success(
-        request: &CreateBatchFeaturesRequest,
-        project_repo: PathBuf,
-        features: Vec<BatchFeatureAutomationResult>,
-        message: String,
-    ) -> Self {
+impl SetFeatureTagsRequest {
+    pub fn ipc_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": SET_FEATURE_TAGS_ACTION,
+            "project_name": self.project_name,
+            "feature_name": self.feature_name,
+            "tags": self.tags,
+            "dry_run": self.dry_run,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
+pub struct ForkFeatureRequest {
+    #[schemars(length(min = 1))]
+    pub project_name: String,
+    /// Feature to fork. The new branch starts from its branch.
+    #[schemars(length(min = 1))]
+    pub feature_name: String,
+    /// Feature and branch name for the fork. Defaults to `<branch>-fork`.
+    pub branch: Option<String>,
+    /// Defaults to the source feature's agent.
+    pub agent: Option<AgentKind>,
+    /// Defaults to the source feature's mode.
+    pub mode: Option<VibeMode>,
+    pub review: Option<bool>,
+    pub enable_chrome: Option<bool>,
+    /// Export the source's latest transcript to `.claude/context.md`.
+    pub include_context: bool,
+    /// Copy the source worktree's uncommitted and untracked files.
+    pub copy_uncommitted_changes: bool,
+    /// Answer for a prompted `on_worktree_created` hook.
+    pub hook_choice: Option<String>,
+    pub dry_run: bool,
+}
+
+impl Default for ForkFeatureRequest {
+    fn default() -> Self {
         Self {
-            msg_type: AUTOMATION_RESULT_TYPE,
-            action: CREATE_BATCH_FEATURES_ACTION,
-            ok: true,
-            dry_run: request.dry_run,
-            workspace_path: request.workspace_path.clone(),
-            project_name: request.project_name.clone(),
-            project_repo,
-            features,
-            message,
+            project_name: String::new(),
+            feature_name: String::new(),
+            branch: None,
+            agent: None,
+            mode: None,
+            review: None,
+            enable_chrome: None,
+            include_context: true,
+            copy_uncommitted_changes: true,
+            hook_choice: None,
+            dry_run: false,
         }
     }
 }
 
-pub fn automation_error_response(action: &str, error: impl Into<String>) -> serde_json::Value {
-    serde_json::json!({
-        "type": AUTOMATION_RESULT_TYPE,
-        "action": action,
-        "ok": false,
-        "error": error.into(),
-    })
+impl ForkFeatureRequest {
+    pub fn ipc_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": FORK_FEATURE_ACTION,
+            "project_name": self.project_name,
+            "feature_name": self.feature_name,
+            "branch": self.branch,
+            "agent": self.agent,
+            "mode": self.mode,
+            "review": self.review,
+            "enable_chrome": self.enable_chrome,
+            "include_context": self.include_context,
+            "copy_uncommitted_changes": self.copy_uncommitted_changes,
+            "hook_choice": self.hook_choice,
+            "dry_run": self.dry_run,
+        })
+    }
+}
+
+/// Adds a session window to an existing feature. Set exactly one of `kind`
+/// or `custom`.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
+pub struct AddSessionRequest {
+    #[schemars(length(min = 1))]
+    pub project_name: String,
+    #[schemars(length(min = 1))]
+    pub feature_name: String,
+    /// Built-in session: `terminal`, `nvim`, `claude`, `opencode`, or `codex`.
+    pub kind: Option<SessionKind>,
+    /// Name of a session from the extension config's `custom_sessions`.
+    pub custom: Option<String>,
+    pub dry_run: bool,
+}
+
+impl AddSessionRequest {
+    pub fn ipc_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": ADD_SESSION_ACTION,
+            "project_name": self.project_name,
+            "feature_name": self.feature_name,
+            "kind": self.kind,
+            "custom": self.custom,
+            "dry_run": self.dry_run,
+        })
+    }
+}
+
+/// Blocks until a feature's agent stops thinking or asks for input.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
+pub struct WaitForIdleRequest {
+    #[schemars(length(min = 1))]
+    pub project_name: String,
+    #[schemars(length(min = 1))]
+    pub feature_name: String,
+    /// Session id, label, or tmux window to wait on. Defaults to the whole feature.
+    pub session: Option<String>,
+    /// Give up after this long and reply with `outcome: "timed_out"`.
+    pub timeout_ms: u64,
+    /// How long the agent must stay idle before the wait ends. Covers the
+    /// gap between submitting a prompt and the agent starting to think.
+    pub settle_ms: u64,
+}
+
+impl Default for WaitForIdleRequest {
+    fn default() -> Self {
+        Self {
+            project_name: String::new(),
+            feature_name: String::new(),
+            session: None,
+            timeout_ms: 600_000,
+            settle_ms: 3_000,
+        }
+    }
+}
+
+impl WaitForIdleRequest {
+    pub fn ipc_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": WAIT_FOR_IDLE_ACTION,
+            "project_name": self.project_name,
+            "feature_name": self.feature_name,
+            "session": self.session,
+            "timeout_ms": self.timeout_ms,
+            "settle_ms": self.settle_ms,
+        })
+    }
+}
+
+/// Lists outstanding input requests and diff reviews, optionally for one
+/// project or feature.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields)]
+pub struct ListInputsRequest {
+    pub project_name: Option<String>,
+    pub feature_name: Option<String>,
+}
+
+impl ListInputsRequest {
+    pub fn ipc_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": LIST_INPUTS_ACTION,
+            "project_name": self.project_name,
+            "feature_name": self.feature_name,
+        })
+    }
+}
+
+/// How `resolve_input` answers a pending input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InputDecision {
+    /// Let the reviewed change through.
+    Approve,
+    /// Block the change; `reason` is passed back to the agent as feedback.
+    Reject,
+    /// Clear the input. A waiting diff review is cancelled.
+    Dismiss,
+}
+
+/// Answers one pending input.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
+pub struct ResolveInputRequest {
+    /// The input's `request_id` as `list_inputs` reports it. Named apart
+    /// from the envelope's own `request_id`.
+    #[schemars(length(min = 1))]
+    pub input_id: String,
+    /// `approve`, `reject`, or `dismiss`. Only diff reviews can be approved
+    /// or rejected.
+    pub decision: Option<InputDecision>,
+    /// Feedback sent with an approval or rejection.
+    pub reason: Option<String>,
+    pub dry_run: bool,
+}
+
+impl ResolveInputRequest {
+    pub fn ipc_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": RESOLVE_INPUT_ACTION,
+            "input_id": self.input_id,
+            "decision": self.decision,
+            "reason": self.reason,
+            "dry_run": self.dry_run,
+        })
+    }
+}
+
+/// Reads a feature's changes against its base branch, as the dashboard's
+/// diff viewer shows them.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
+pub struct ReadDiffRequest {
+    #[schemars(length(min = 1))]
+    pub project_name: String,
+    #[schemars(length(min = 1))]
+    pub feature_name: String,
+    /// Include each file's unified diff. Set to `false` for a file list only.
+    pub include_patch: bool,
+}
+
+impl Default for ReadDiffRequest {
+    fn default() -> Self {
+        Self {
+            project_name: String::new(),
+            feature_name: String::new(),
+            include_patch: true,
+        }
+    }
+}
+
+impl ReadDiffRequest {
+    pub fn ipc_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": READ_DIFF_ACTION,
+            "project_name": self.project_name,
+            "feature_name": self.feature_name,
+            "include_patch": self.include_patch,
+        })
+    }
+}
+
+/// Rebases every feature stacked on a feature, directly or further down
+/// the stack, onto its parent's current branch.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
+pub struct RestackFeatureRequest {
+    #[schemars(length(min = 1))]
+    pub project_name: String,
+    #[schemars(length(min = 1))]
+    pub feature_name: String,
+    pub dry_run: bool,
+}
+
+impl RestackFeatureRequest {
+    pub fn ipc_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": RESTACK_FEATURE_ACTION,
+            "project_name": self.project_name,
+            "feature_name": self.feature_name,
+            "dry_run": self.dry_run,
+        })
+    }
+}
+
+/// Lands a ready feature on its base branch. A dry run only does the
+/// trial merge and reports what landing would do.
+#[derive(Debug, Clone, Serialize, Deserialize, Default, JsonSchema)]
+#[serde(default)]
+#[schemars(deny_unknown_fields, transform = require_non_empty_fields)]
+pub struct LandFeatureRequest {
+    #[schemars(length(min = 1))]
+    pub project_name: String,
+    #[schemars(length(min = 1))]
+    pub feature_name: String,
+    pub strategy: LandStrategy,
+    pub dry_run: bool,
+}
+
+impl LandFeatureRequest {
+    pub fn ipc_payload(&self) -> serde_json::Value {
+        serde_json::json!({
+            "type": AUTOMATION_REQUEST_TYPE,
+            "action": LAND_FEATURE_ACTION,
+            "project_name": self.project_name,
+            "feature_name": self.feature_name,
+            "strategy": self.strategy,
+            "dry_run": self.dry_run,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CreateProjectResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub dry_run: bool,
+    pub input_path: PathBuf,
+    pub project_name: String,
+    pub project_path: PathBuf,
+    pub is_git: bool,
+    pub message: String,
+}
+
+impl CreateProjectResponse {
+    pub fn success(
+        request: &CreateProjectRequest,
+        project_path: PathBuf,
+        is_git: bool,
+        message: String,
+    ) -> Self {
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action: CREATE_PROJECT_ACTION,
+            ok: true,
+            dry_run: request.dry_run,
+            input_path: request.path.clone(),
+            project_name: request.project_name.clone(),
+            project_path,
+            is_git,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct AutomationHookPrompt {
+    pub title: String,
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CreateFeatureResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub dry_run: bool,
+    pub project_name: String,
+    pub branch: String,
+    pub workdir: PathBuf,
+    pub is_worktree: bool,
+    pub tmux_session: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_feature: Option<String>,
+    /// Member worktrees of a multi-repo feature.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub repos: Vec<PathBuf>,
+    pub started: bool,
+    pub worktree_hook_ran: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worktree_hook_succeeded: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worktree_hook_prompt: Option<AutomationHookPrompt>,
+    /// The request's `prompt` is held until the agent is ready for it.
+    pub prompt_queued: bool,
+    pub message: String,
+}
+
+impl CreateFeatureResponse {
+    #[allow(clippy::too_many_arguments)]
+    pub fn success(
+        request: &CreateFeatureRequest,
+        workdir: PathBuf,
+        is_worktree: bool,
+        tmux_session: String,
+        started: bool,
+        worktree_hook_ran: bool,
+        worktree_hook_succeeded: Option<bool>,
+        worktree_hook_prompt: Option<AutomationHookPrompt>,
+        message: String,
+    ) -> Self {
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action: CREATE_FEATURE_ACTION,
+            ok: true,
+            dry_run: request.dry_run,
+            project_name: request.project_name.clone(),
+            branch: request.branch.clone(),
+            workdir,
+            is_worktree,
+            tmux_session,
+            parent_feature: request.parent_feature.clone(),
+            repos: Vec::new(),
+            started,
+            worktree_hook_ran,
+            worktree_hook_succeeded,
+            worktree_hook_prompt,
+            prompt_queued: false,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct BatchFeatureAutomationResult {
+    pub name: String,
+    pub branch: String,
+    pub workdir: PathBuf,
+    pub tmux_session: String,
+    pub agent: AgentKind,
+    pub mode: VibeMode,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preset: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_ref: Option<String>,
+    pub started: bool,
+    /// The prompt was typed into the agent.
+    pub prompt_sent: bool,
+    /// The prompt waits for the newly started agent to be ready.
+    pub prompt_queued: bool,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct CreateBatchFeaturesResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub dry_run: bool,
+    pub workspace_path: PathBuf,
+    pub project_name: String,
+    pub project_repo: PathBuf,
+    pub features: Vec<BatchFeatureAutomationResult>,
+    pub message: String,
+}
+
+impl CreateBatchFeaturesResponse {
+    pub fn success(
+        request: &CreateBatchFeaturesRequest,
+        project_repo: PathBuf,
+        features: Vec<BatchFeatureAutomationResult>,
+        message: String,
+    ) -> Self {
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action: CREATE_BATCH_FEATURES_ACTION,
+            ok: true,
+            dry_run: request.dry_run,
+            workspace_path: request.workspace_path.clone(),
+            project_name: request.project_name.clone(),
+            project_repo,
+            features,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PendingInputState {
+    pub notification_type: String,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relative_path: Option<String>,
+}
+
+/// A pending input as `list_inputs` reports it, with the change under
+/// review when it is a diff review.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct PendingInputDetail {
+    /// Pass to `resolve_input`. Inputs without one were raised through
+    /// notification files and can only be cleared from the dashboard.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub request_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feature_name: Option<String>,
+    pub notification_type: String,
+    pub message: String,
+    pub session_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub change_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub relative_path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_snippet: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub new_snippet: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_new_file: Option<bool>,
+    /// The agent's explanation for the change, when it gave one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Whether the input is a diff review that can be approved or rejected.
+    pub reviewable: bool,
+    /// Whether the dashboard has the review open in its prompt dialog.
+    pub open_in_dashboard: bool,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SessionState {
+    pub id: String,
+    pub kind: SessionKind,
+    pub label: String,
+    pub tmux_window: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claude_session_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub token_usage: Option<SessionTokenUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FeatureState {
+    pub name: String,
+    pub branch: String,
+    pub workdir: PathBuf,
+    pub is_worktree: bool,
+    pub tmux_session: String,
+    pub agent: AgentKind,
+    pub mode: VibeMode,
+    pub review: bool,
+    pub status: ProjectStatus,
+    pub ready: bool,
+    pub thinking: bool,
+    pub deleting: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    /// Feature this one is stacked on.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub parent_feature: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Member worktrees of a multi-repo feature; `workdir` is their parent.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub repos: Vec<PathBuf>,
+    /// Set while the feature is archived and has no worktree.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub summary_updated_at: Option<DateTime<Utc>>,
+    pub pending_inputs: Vec<PendingInputState>,
+    pub sessions: Vec<SessionState>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ProjectState {
+    pub name: String,
+    pub repo: PathBuf,
+    pub is_git: bool,
+    pub preferred_agent: AgentKind,
+    pub features: Vec<FeatureState>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ListStateResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub projects: Vec<ProjectState>,
+}
+
+impl ListStateResponse {
+    pub fn success(projects: Vec<ProjectState>) -> Self {
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action: LIST_STATE_ACTION,
+            ok: true,
+            projects,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct SendPromptResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub dry_run: bool,
+    pub project_name: String,
+    pub feature_name: String,
+    pub tmux_session: String,
+    pub tmux_window: String,
+    pub started: bool,
+    /// Whether the prompt was typed into the agent; a feature started by
+    /// this request gets it once the agent is ready instead.
+    pub prompt_sent: bool,
+    pub message: String,
+}
+
+impl SendPromptResponse {
+    pub fn success(
+        request: &SendPromptRequest,
+        tmux_session: String,
+        tmux_window: String,
+        started: bool,
+        prompt_sent: bool,
+        message: String,
+    ) -> Self {
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action: SEND_PROMPT_ACTION,
+            ok: true,
+            dry_run: request.dry_run,
+            project_name: request.project_name.clone(),
+            feature_name: request.feature_name.clone(),
+            tmux_session,
+            tmux_window,
+            started,
+            prompt_sent,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct FeatureLifecycleResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub dry_run: bool,
+    pub project_name: String,
+    pub feature_name: String,
+    pub status: ProjectStatus,
+    pub ready: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<DateTime<Utc>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hook_prompt: Option<AutomationHookPrompt>,
+    pub message: String,
+}
+
+impl FeatureLifecycleResponse {
+    pub fn success(
+        action: &'static str,
+        project_name: &str,
+        feature: &Feature,
+        dry_run: bool,
+        hook_prompt: Option<AutomationHookPrompt>,
+        message: String,
+    ) -> Self {
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action,
+            ok: true,
+            dry_run,
+            project_name: project_name.to_string(),
+            feature_name: feature.name.clone(),
+            status: feature.status.clone(),
+            ready: feature.ready,
+            nickname: feature.nickname.clone(),
+            tags: feature.tags.clone(),
+            archived_at: feature.archived_at,
+            hook_prompt,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ForkFeatureResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub dry_run: bool,
+    pub project_name: String,
+    pub source_feature: String,
+    pub source_branch: String,
+    pub feature_name: String,
+    pub branch: String,
+    pub workdir: PathBuf,
+    pub tmux_session: String,
+    pub agent: AgentKind,
+    pub mode: VibeMode,
+    pub started: bool,
+    pub copied_uncommitted_changes: bool,
+    pub included_context: bool,
+    pub worktree_hook_ran: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worktree_hook_succeeded: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub worktree_hook_prompt: Option<AutomationHookPrompt>,
+    pub message: String,
+}
+
+impl ForkFeatureResponse {
+    #[allow(clippy::too_many_arguments)]
+    pub fn success(
+        request: &ForkFeatureRequest,
+        source_branch: String,
+        branch: String,
+        workdir: PathBuf,
+        agent: AgentKind,
+        mode: VibeMode,
+        started: bool,
+        copied_uncommitted_changes: bool,
+        included_context: bool,
+        worktree_hook_succeeded: Option<bool>,
+        worktree_hook_prompt: Option<AutomationHookPrompt>,
+        message: String,
+    ) -> Self {
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action: FORK_FEATURE_ACTION,
+            ok: true,
+            dry_run: request.dry_run,
+            project_name: request.project_name.clone(),
+            source_feature: request.feature_name.clone(),
+            source_branch,
+            feature_name: branch.clone(),
+            tmux_session: format!("amf-{}", branch),
+            branch,
+            workdir,
+            agent,
+            mode,
+            started,
+            copied_uncommitted_changes,
+            included_context,
+            worktree_hook_ran: worktree_hook_succeeded.is_some(),
+            worktree_hook_succeeded,
+            worktree_hook_prompt,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct AddSessionResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub dry_run: bool,
+    pub project_name: String,
+    pub feature_name: String,
+    pub kind: SessionKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    pub label: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tmux_window: Option<String>,
+    /// Whether the session's window was opened now. Sessions added to a
+    /// stopped feature open when it starts.
+    pub launched: bool,
+    pub message: String,
+}
+
+impl AddSessionResponse {
+    pub fn success(
+        request: &AddSessionRequest,
+        kind: SessionKind,
+        session_id: Option<String>,
+        label: String,
+        tmux_window: Option<String>,
+        launched: bool,
+        message: String,
+    ) -> Self {
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action: ADD_SESSION_ACTION,
+            ok: true,
+            dry_run: request.dry_run,
+            project_name: request.project_name.clone(),
+            feature_name: request.feature_name.clone(),
+            kind,
+            session_id,
+            label,
+            tmux_window,
+            launched,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ListInputsResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub inputs: Vec<PendingInputDetail>,
+}
+
+impl ListInputsResponse {
+    pub fn success(inputs: Vec<PendingInputDetail>) -> Self {
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action: LIST_INPUTS_ACTION,
+            ok: true,
+            inputs,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ResolveInputResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub dry_run: bool,
+    pub input_id: String,
+    pub decision: InputDecision,
+    pub notification_type: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feature_name: Option<String>,
+    /// Whether a waiting hook was sent the decision. `false` for inputs
+    /// that only needed clearing.
+    pub answered_hook: bool,
+    pub message: String,
+}
+
+impl ResolveInputResponse {
+    pub fn success(
+        request: &ResolveInputRequest,
+        decision: InputDecision,
+        input: &PendingInputDetail,
+        answered_hook: bool,
+        message: String,
+    ) -> Self {
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action: RESOLVE_INPUT_ACTION,
+            ok: true,
+            dry_run: request.dry_run,
+            input_id: request.input_id.clone(),
+            decision,
+            notification_type: input.notification_type.clone(),
+            project_name: input.project_name.clone(),
+            feature_name: input.feature_name.clone(),
+            answered_hook,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct DiffFileState {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub old_path: Option<String>,
+    pub status: DiffFileStatus,
+    pub additions: usize,
+    pub deletions: usize,
+    pub is_binary: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub patch: Option<String>,
+}
+
+impl DiffFileState {
+    fn from_file(file: DiffFile, include_patch: bool) -> Self {
+        Self {
+            path: file.path,
+            old_path: file.old_path,
+            status: file.status,
+            additions: file.additions,
+            deletions: file.deletions,
+            is_binary: file.is_binary,
+            patch: include_patch.then_some(file.patch),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ReadDiffResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub project_name: String,
+    pub feature_name: String,
+    pub branch: String,
+    pub base_ref: String,
+    pub base_commit: String,
+    pub total_additions: usize,
+    pub total_deletions: usize,
+    pub files: Vec<DiffFileState>,
+}
+
+impl ReadDiffResponse {
+    pub fn success(request: &ReadDiffRequest, snapshot: DiffSnapshot) -> Self {
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action: READ_DIFF_ACTION,
+            ok: true,
+            project_name: request.project_name.clone(),
+            feature_name: request.feature_name.clone(),
+            branch: snapshot.branch,
+            base_ref: snapshot.base_ref,
+            base_commit: snapshot.base_commit,
+            total_additions: snapshot.total_additions,
+            total_deletions: snapshot.total_deletions,
+            files: snapshot
+                .files
+                .into_iter()
+                .map(|file| DiffFileState::from_file(file, request.include_patch))
+                .collect(),
+        }
+    }
+}
+
+/// One stacked feature rebased by `restack_feature`.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RestackedFeature {
+    pub feature_name: String,
+    pub branch: String,
+    /// Branch it was rebased onto.
+    pub onto: String,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct RestackFeatureResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub dry_run: bool,
+    pub project_name: String,
+    pub feature_name: String,
+    /// Features rebased, parents before children.
+    pub restacked: Vec<RestackedFeature>,
+    pub message: String,
+}
+
+impl RestackFeatureResponse {
+    pub fn success(
+        request: &RestackFeatureRequest,
+        restacked: Vec<RestackedFeature>,
+        message: String,
+    ) -> Self {
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action: RESTACK_FEATURE_ACTION,
+            ok: true,
+            dry_run: request.dry_run,
+            project_name: request.project_name.clone(),
+            feature_name: request.feature_name.clone(),
+            restacked,
+            message,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct LandFeatureResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub dry_run: bool,
+    pub project_name: String,
+    pub feature_name: String,
+    pub strategy: LandStrategy,
+    pub branch: String,
+    /// Local branch the feature lands on.
+    pub target: String,
+    /// Base ref the target was resolved from, e.g. `origin/main`.
+    pub base_ref: String,
+    /// Commits on the feature branch that the target does not have.
+    pub ahead: usize,
+    /// Files the trial merge could not resolve.
+    pub conflicts: Vec<String>,
+    /// New tip of the target branch; absent on a dry run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    pub message: String,
+}
+
+impl LandFeatureResponse {
+    pub fn success(
+        request: &LandFeatureRequest,
+        preview: &LandPreview,
+        commit: Option<String>,
+        message: String,
+    ) -> Self {
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action: LAND_FEATURE_ACTION,
+            ok: true,
+            dry_run: request.dry_run,
+            project_name: request.project_name.clone(),
+            feature_name: request.feature_name.clone(),
+            strategy: request.strategy,
+            branch: preview.branch.clone(),
+            target: preview.target.clone(),
+            base_ref: preview.base_ref.clone(),
+            ahead: preview.ahead,
+            conflicts: preview.conflict_paths(),
+            commit,
+            message,
+        }
+    }
+}
+
+/// Why a `wait_for_idle` request returned.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum WaitOutcome {
+    /// The agent stopped thinking and stayed idle for `settle_ms`.
+    Idle,
+    /// The feature has a pending input request.
+    InputRequested,
+    /// The feature's tmux session is not running.
+    Stopped,
+    TimedOut,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct WaitForIdleResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: &'static str,
+    pub ok: bool,
+    pub project_name: String,
+    pub feature_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<String>,
+    pub outcome: WaitOutcome,
+    pub waited_ms: u64,
+    /// The feature as `list_state` reports it, including its latest summary
+    /// and pending inputs.
+    pub feature: FeatureState,
+    pub message: String,
+}
+
+impl WaitForIdleResponse {
+    pub fn success(
+        request: &WaitForIdleRequest,
+        outcome: WaitOutcome,
+        waited_ms: u64,
+        feature: FeatureState,
+    ) -> Self {
+        let feature_name = &request.feature_name;
+        let (subject, target) = match &request.session {
+            Some(session) => (
+                format!("Session '{session}' of feature '{feature_name}'"),
+                format!("session '{session}' of feature '{feature_name}'"),
+            ),
+            None => (
+                format!("Feature '{feature_name}'"),
+                format!("feature '{feature_name}'"),
+            ),
+        };
+        let message = match outcome {
+            WaitOutcome::Idle => format!("{subject} is idle"),
+            WaitOutcome::InputRequested => format!("{subject} is waiting for input"),
+            WaitOutcome::Stopped => format!("{subject} is not running"),
+            WaitOutcome::TimedOut => format!("Timed out after {waited_ms}ms waiting for {target}"),
+        };
+        Self {
+            msg_type: AUTOMATION_RESULT_TYPE,
+            action: WAIT_FOR_IDLE_ACTION,
+            ok: true,
+            project_name: request.project_name.clone(),
+            feature_name: request.feature_name.clone(),
+            session: request.session.clone(),
+            outcome,
+            waited_ms,
+            feature,
+            message,
+        }
+    }
+}
+
+/// State changes streamed to `amf automation watch` subscribers.
+#[derive(Debug, Clone, Serialize, PartialEq, JsonSchema)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AutomationEvent {
+    FeatureCreated {
+        project_name: String,
+        feature_name: String,
+        branch: String,
+        workdir: PathBuf,
+    },
+    FeatureDeleted {
+        project_name: String,
+        feature_name: String,
+    },
+    FeatureStatusChanged {
+        project_name: String,
+        feature_name: String,
+        from: ProjectStatus,
+        to: ProjectStatus,
+    },
+    ThinkingStarted {
+        project_name: String,
+        feature_name: String,
+    },
+    ThinkingStopped {
+        project_name: String,
+        feature_name: String,
+    },
+    PendingInputRaised {
+        project_name: Option<String>,
+        feature_name: Option<String>,
+        notification_type: String,
+        message: String,
+        request_id: Option<String>,
+    },
+    PendingInputCleared {
+        project_name: Option<String>,
+        feature_name: Option<String>,
+        notification_type: String,
+        request_id: Option<String>,
+    },
+    DiffReviewDecision {
+        project_name: Option<String>,
+        feature_name: Option<String>,
+        change_id: String,
+        relative_path: String,
+        decision: String,
+        reason: Option<String>,
+    },
+    HookCompleted {
+        project_name: String,
+        feature_name: String,
+        hook: String,
+        success: bool,
+    },
+}
+
+impl AutomationEvent {
+    /// Wrap the event in the envelope written to subscribers.
+    pub fn to_message(&self, at: DateTime<Utc>) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_else(|_| serde_json::json!({}));
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert("type".to_string(), serde_json::json!(AUTOMATION_EVENT_TYPE));
+            obj.insert("at".to_string(), serde_json::json!(at));
+        }
+        value
+    }
+}
+
+/// Reply shape shared by every failed action.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct AutomationErrorResponse {
+    #[serde(rename = "type")]
+    pub msg_type: &'static str,
+    pub action: String,
+    pub ok: bool,
+    pub code: AutomationErrorCode,
+    pub error: String,
+}
+
+pub fn automation_error_response(
+    action: &str,
+    code: AutomationErrorCode,
+    error: impl Into<String>,
+) -> serde_json::Value {
+    serde_json::to_value(AutomationErrorResponse {
+        msg_type: AUTOMATION_RESULT_TYPE,
+        action: action.to_string(),
+        ok: false,
+        code,
+        error: error.into(),
+    })
+    .unwrap_or_else(|_| serde_json::json!({ "type": AUTOMATION_RESULT_TYPE, "ok": false }))
+}
+
+/// Error reply for a failed action, keeping the code of an
+/// [`AutomationError`] and falling back to `operation_failed`.
+pub fn automation_failure_response(action: &str, err: &anyhow::Error) -> serde_json::Value {
+    let code = err
+        .downcast_ref::<AutomationError>()
+        .map_or(AutomationErrorCode::OperationFailed, |err| err.code);
+    automation_error_response(action, code, err.to_string())
+}
+
+/// Send an automation request to the running AMF instance and wait for its
+/// reply, stamping the client's protocol version on the way out.
+pub fn send_request(
+    payload: &serde_json::Value,
+    timeout: std::time::Duration,
+) -> anyhow::Result<serde_json::Value> {
+    let mut payload = payload.clone();
+    if let Some(obj) = payload.as_object_mut() {
+        obj.insert(
+            "protocol_version".to_string(),
+            serde_json::json!(AUTOMATION_PROTOCOL_VERSION),
+        );
+    }
+    let outbound = serde_json::to_string(&payload)?;
+    crate::ipc::send_wait(&crate::ipc::socket_path(), &outbound, timeout)
+}
+
+/// Fields that wrap a request on the wire rather than belong to it.
+const ENVELOPE_FIELDS: &[&str] = &[
+    "type",
+    "action",
+    "protocol_version",
+    "request_id",
+    "reply_socket",
+];
+
+/// Request and success-reply schemas for one action, plus a decoder that
+/// runs the payload through the same request type the dashboard uses.
+pub struct ActionContract {
+    pub request: schemars::Schema,
+    pub response: schemars::Schema,
+    decode: fn(&serde_json::Value) -> Result<(), String>,
+}
+
+impl ActionContract {
+    fn of<Req, Resp>() -> Self
+    where
+        Req: JsonSchema + serde::de::DeserializeOwned,
+        Resp: JsonSchema,
+    {
+        Self {
+            request: schemars::schema_for!(Req),
+            response: schemars::schema_for!(Resp),
+            decode: |value| {
+                serde_path_to_error::deserialize::<_, Req>(value)
+                    .map(|_| ())
+                    .map_err(|err| format!("{}: {}", err.path(), err.inner()))
+            },
+        }
+    }
+}
+
+pub fn action_contract(action: &str) -> Option<ActionContract> {
+    let contract = match action {
+        HELLO_ACTION => ActionContract::of::<HelloRequest, HelloResponse>(),
+        CREATE_PROJECT_ACTION => {
+            ActionContract::of::<CreateProjectRequest, CreateProjectResponse>()
+        }
+        CREATE_FEATURE_ACTION => {
+            ActionContract::of::<CreateFeatureRequest, CreateFeatureResponse>()
+        }
+        CREATE_BATCH_FEATURES_ACTION => {
+            ActionContract::of::<CreateBatchFeaturesRequest, CreateBatchFeaturesResponse>()
+        }
+        LIST_STATE_ACTION => ActionContract::of::<ListStateRequest, ListStateResponse>(),
+        SEND_PROMPT_ACTION => ActionContract::of::<SendPromptRequest, SendPromptResponse>(),
+        START_FEATURE_ACTION
+        | STOP_FEATURE_ACTION
+        | DELETE_FEATURE_ACTION
+        | ARCHIVE_FEATURE_ACTION
+        | RESTORE_FEATURE_ACTION => {
+            ActionContract::of::<FeatureLifecycleRequest, FeatureLifecycleResponse>()
+        }
+        SET_FEATURE_READY_ACTION => {
+            ActionContract::of::<SetFeatureReadyRequest, FeatureLifecycleResponse>()
+        }
+        RENAME_FEATURE_ACTION => {
+            ActionContract::of::<RenameFeatureRequest, FeatureLifecycleResponse>()
+        }
+        SET_FEATURE_TAGS_ACTION => {
+            ActionContract::of::<SetFeatureTagsRequest, FeatureLifecycleResponse>()
+        }
+        FORK_FEATURE_ACTION => ActionContract::of::<ForkFeatureRequest, ForkFeatureResponse>(),
+        ADD_SESSION_ACTION => ActionContract::of::<AddSessionRequest, AddSessionResponse>(),
+        WAIT_FOR_IDLE_ACTION => ActionContract::of::<WaitForIdleRequest, WaitForIdleResponse>(),
+        LIST_INPUTS_ACTION => ActionContract::of::<ListInputsRequest, ListInputsResponse>(),
+        READ_DIFF_ACTION => ActionContract::of::<ReadDiffRequest, ReadDiffResponse>(),
+        RESTACK_FEATURE_ACTION => {
+            ActionContract::of::<RestackFeatureRequest, RestackFeatureResponse>()
+        }
+        LAND_FEATURE_ACTION => ActionContract::of::<LandFeatureRequest, LandFeatureResponse>(),
+        RESOLVE_INPUT_ACTION => ActionContract::of::<ResolveInputRequest, ResolveInputResponse>(),
+        _ => return None,
+    };
+    Some(contract)
+}
+
+/// Every action's request and reply schema, plus the shared error reply and
+/// the `watch` event stream, as one JSON document.
+pub fn automation_schema_document() -> serde_json::Value {
+    let actions: serde_json::Map<String, serde_json::Value> = AUTOMATION_ACTIONS
+        .iter()
+        .filter_map(|action| {
+            let contract = action_contract(action)?;
+            Some((
+                action.to_string(),
+                serde_json::json!({
+                    "request": contract.request,
+                    "response": contract.response,
+                }),
+            ))
+        })
+        .collect();
+
+    serde_json::json!({
+        "protocol_version": AUTOMATION_PROTOCOL_VERSION,
+        "actions": actions,
+        "error": schemars::schema_for!(AutomationErrorResponse),
+        "event": schemars::schema_for!(AutomationEvent),
+    })
+}
+
+/// Check a request payload for `action` without a running AMF instance.
+/// Returns one message per problem, each prefixed with the offending field;
+/// an empty list means the dashboard will accept the payload's shape.
+pub fn validate_request(action: &str, payload: &serde_json::Value) -> Vec<String> {
+    let Some(contract) = action_contract(action) else {
+        return vec![format!(
+            "unknown action '{}'; expected one of: {}",
+            action,
+            AUTOMATION_ACTIONS.join(", ")
+        )];
+    };
+    let Some(fields) = payload.as_object() else {
+        return vec!["payload must be a JSON object".to_string()];
+    };
+
+    let mut body = fields.clone();
+    body.retain(|key, _| !ENVELOPE_FIELDS.contains(&key.as_str()));
+
+    let schema = contract.request.as_value();
+    let properties = schema.get("properties").and_then(|p| p.as_object());
+    let mut errors = Vec::new();
+
+    for key in body.keys() {
+        if !properties.is_some_and(|props| props.contains_key(key)) {
+            errors.push(format!("{key}: unknown field"));
+        }
+    }
+
+    let required = schema
+        .get("required")
+        .and_then(|r| r.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|key| key.as_str());
+    for key in required {
+        if !body.contains_key(key) {
+            errors.push(format!("{key}: missing required field"));
+        }
+    }
+
+    for (key, value) in &body {
+        let Some(prop) = properties.and_then(|props| props.get(key)) else {
+            continue;
+        };
+        let min_length = prop.get("minLength").and_then(|min| min.as_u64());
+        if let (Some(min_length), Some(text)) = (min_length, value.as_str())
+            && (text.trim().chars().count() as u64) < min_length
+        {
+            errors.push(format!("{key}: must not be empty"));
+        }
+        let minimum = prop.get("minimum").and_then(|min| min.as_f64());
+        if let (Some(minimum), Some(number)) = (minimum, value.as_f64())
+            && number < minimum
+        {
+            errors.push(format!("{key}: must be at least {minimum}"));
+        }
+    }
+
+    if let Err(err) = (contract.decode)(&serde_json::Value::Object(body)) {
+        errors.push(err);
+    }
+    errors
 }
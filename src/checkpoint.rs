@@ -0,0 +1,353 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, TimeZone, Utc};
+use tempfile::TempDir;
+
+use crate::diff::{DiffFile, git_output, git_output_with_env};
+
+/// Checkpoints live under `refs/amf/checkpoints/<feature id>/<n>`, out of
+/// the way of branches and tags.
+const REF_ROOT: &str = "refs/amf/checkpoints";
+
+/// Checkpoints kept per feature; older ones are dropped as new ones land.
+pub const CHECKPOINT_LIMIT: usize = 50;
+
+const SUBJECT_PREFIX: &str = "amf checkpoint: ";
+
+/// A snapshot of a feature's worktree, stored as a commit whose parent is
+/// the HEAD it was taken on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub number: usize,
+    pub commit: String,
+    pub created_at: DateTime<Utc>,
+    /// What triggered the snapshot, e.g. `agent stopped`.
+    pub reason: String,
+}
+
+impl Checkpoint {
+    pub fn short_commit(&self) -> &str {
+        &self.commit[..self.commit.len().min(8)]
+    }
+}
+
+/// Snapshot `workdir`, untracked files included, into a new checkpoint of
+/// `feature_id`. The index and HEAD are left alone. Returns `None` when
+/// nothing changed since the latest checkpoint.
+pub fn snapshot(workdir: &Path, feature_id: &str, reason: &str) -> Result<Option<Checkpoint>> {
+    let tree = worktree_tree(workdir)?;
+    let existing = list(workdir, feature_id)?;
+    if let Some(latest) = existing.last()
+        && git_output(
+            workdir,
+            &["rev-parse", &format!("{}^{{tree}}", latest.commit)],
+        )? == tree
+    {
+        return Ok(None);
+    }
+
+    let message = format!("{SUBJECT_PREFIX}{reason}");
+    let head = git_output(workdir, &["rev-parse", "--verify", "-q", "HEAD"]).ok();
+    let mut args = vec!["commit-tree", tree.as_str(), "-m", message.as_str()];
+    if let Some(head) = &head {
+        args.extend(["-p", head.as_str()]);
+    }
+    let commit = git_output_with_env(workdir, &args, &identity_env())?;
+
+    let number = existing.last().map_or(1, |latest| latest.number + 1);
+    // An empty old value makes the update fail if the ref already exists,
+    // so two snapshots racing for the same number cannot clobber each other.
+    git_output(
+        workdir,
+        &["update-ref", &ref_name(feature_id, number), &commit, ""],
+    )?;
+    let stale = (existing.len() + 1).saturating_sub(CHECKPOINT_LIMIT);
+    for old in &existing[..stale] {
+        let _ = git_output(
+            workdir,
+            &["update-ref", "-d", &ref_name(feature_id, old.number)],
+        );
+    }
+
+    Ok(Some(Checkpoint {
+        number,
+        commit,
+        created_at: Utc::now(),
+        reason: reason.to_string(),
+    }))
+}
+
+/// Checkpoints of `feature_id`, oldest first.
+pub fn list(workdir: &Path, feature_id: &str) -> Result<Vec<Checkpoint>> {
+    let prefix = format!("{}/", ref_prefix(feature_id));
+    let output = git_output(
+        workdir,
+        &[
+            "for-each-ref",
+            "--format=%(refname)%09%(objectname)%09%(committerdate:unix)%09%(contents:subject)",
+            &prefix,
+        ],
+    )?;
+
+    let mut checkpoints: Vec<Checkpoint> = output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, '\t');
+            let number = fields.next()?.strip_prefix(&prefix)?.parse().ok()?;
+            let commit = fields.next()?.to_string();
+            let secs = fields.next()?.parse().ok()?;
+            let subject = fields.next().unwrap_or_default();
+            Some(Checkpoint {
+                number,
+                commit,
+                created_at: Utc.timestamp_opt(secs, 0).single()?,
+                reason: subject
+                    .strip_prefix(SUBJECT_PREFIX)
+                    .unwrap_or(subject)
+                    .to_string(),
+            })
+        })
+        .collect();
+    checkpoints.sort_by_key(|checkpoint| checkpoint.number);
+    Ok(checkpoints)
+}
+
+/// What changed in `workdir` since `checkpoint`, untracked files included.
+pub fn diff(workdir: &Path, checkpoint: &Checkpoint) -> Result<Vec<DiffFile>> {
+    let tree = worktree_tree(workdir)?;
+    crate::diff::load_commit_diff(workdir, &checkpoint.commit, &tree)
+}
+
+/// Put `workdir` back to `checkpoint`: files are rewritten to their
+/// checkpointed contents and files created since are removed. The current
+/// state is checkpointed first so the restore can itself be undone; that
+/// checkpoint is returned when one was taken.
+pub fn restore(
+    workdir: &Path,
+    feature_id: &str,
+    checkpoint: &Checkpoint,
+) -> Result<Option<Checkpoint>> {
+    let reason = format!("before restoring #{}", checkpoint.number);
+    let safety = snapshot(workdir, feature_id, &reason)?;
+
+    let current = worktree_tree(workdir)?;
+    let added = git_output(
+        workdir,
+        &[
+            "diff",
+            "--name-only",
+            "--no-renames",
+            "--diff-filter=A",
+            "-z",
+            &checkpoint.commit,
+            &current,
+        ],
+    )?;
+    for path in added.split('\0').filter(|path| !path.is_empty()) {
+        let path = workdir.join(path);
+        std::fs::remove_file(&path)
+            .with_context(|| format!("Failed to remove {}", path.display()))?;
+        remove_empty_parents(workdir, &path);
+    }
+
+    write_tree(workdir, &checkpoint.commit)?;
+    Ok(safety)
+}
+
+/// The commit `checkpoint` was taken on, which a fork of it branches
+/// from. Checkpoints taken before the branch had any commits have none.
+pub fn base_commit(workdir: &Path, checkpoint: &Checkpoint) -> Result<String> {
+    let parent = format!("{}^", checkpoint.commit);
+    match git_output(workdir, &["rev-parse", "--verify", "-q", &parent]) {
+        Ok(commit) => Ok(commit),
+        Err(_) => bail!(
+            "Checkpoint #{} was taken before the branch had a commit, so there is nothing to fork from",
+            checkpoint.number
+        ),
+    }
+}
+
+/// Write every file of `commit` into `workdir` without touching the index
+/// or HEAD. Used to seed a fork with a checkpoint's contents.
+pub fn write_tree(workdir: &Path, commit: &str) -> Result<()> {
+    let index = TempIndex::new(workdir, false)?;
+    git_output_with_env(workdir, &["read-tree", commit], &index.env())?;
+    git_output_with_env(workdir, &["checkout-index", "-a", "-f"], &index.env())?;
+    Ok(())
+}
+
+/// Drop every checkpoint of `feature_id`.
+pub fn clear(workdir: &Path, feature_id: &str) -> Result<()> {
+    for checkpoint in list(workdir, feature_id)? {
+        git_output(
+            workdir,
+            &["update-ref", "-d", &ref_name(feature_id, checkpoint.number)],
+        )?;
+    }
+    Ok(())
+}
+
+pub fn ref_prefix(feature_id: &str) -> String {
+    format!("{REF_ROOT}/{feature_id}")
+}
+
+fn ref_name(feature_id: &str, number: usize) -> String {
+    format!("{}/{number}", ref_prefix(feature_id))
+}
+
+/// Tree object of the worktree as `git add -A` would stage it, built in a
+/// scratch index seeded from the real one so unchanged files are not
+/// rehashed.
+fn worktree_tree(workdir: &Path) -> Result<String> {
+    let index = TempIndex::new(workdir, true)?;
+    git_output_with_env(workdir, &["add", "-A"], &index.env())?;
+    git_output_with_env(workdir, &["write-tree"], &index.env())
+}
+
+fn remove_empty_parents(workdir: &Path, path: &Path) {
+    let mut dir = path.parent();
+    while let Some(current) = dir
+        && current != workdir
+        && current.starts_with(workdir)
+        && std::fs::remove_dir(current).is_ok()
+    {
+        dir = current.parent();
+    }
+}
+
+/// Checkpoints are AMF's commits, not the user's, and must work in repos
+/// with no identity configured.
+fn identity_env() -> Vec<(&'static str, String)> {
+    [
+        "GIT_AUTHOR_NAME",
+        "GIT_COMMITTER_NAME",
+        "GIT_AUTHOR_EMAIL",
+        "GIT_COMMITTER_EMAIL",
+    ]
+    .into_iter()
+    .map(|key| {
+        let value = if key.ends_with("NAME") {
+            "AMF"
+        } else {
+            "amf@localhost"
+        };
+        (key, value.to_string())
+    })
+    .collect()
+}
+
+struct TempIndex {
+    _dir: TempDir,
+    path: PathBuf,
+}
+
+impl TempIndex {
+    fn new(workdir: &Path, seed_from_index: bool) -> Result<Self> {
+        let dir = TempDir::new().context("Failed to create temp dir")?;
+        let path = dir.path().join("index");
+        if seed_from_index {
+            let real = PathBuf::from(git_output(workdir, &["rev-parse", "--git-path", "index"])?);
+            let real = if real.is_absolute() {
+                real
+            } else {
+                workdir.join(real)
+            };
+            if real.exists() {
+                std::fs::copy(&real, &path).context("Failed to copy the git index")?;
+                // Keep the index's mtime: git re-reads entries written in the
+                // same second as the index, and a fresh mtime would make a
+                // same-size edit look unchanged.
+                let modified = std::fs::metadata(&real)?.modified()?;
+                std::fs::File::options()
+                    .write(true)
+                    .open(&path)?
+                    .set_modified(modified)?;
+            }
+        }
+        Ok(Self { _dir: dir, path })
+    }
+
+    fn env(&self) -> Vec<(&'static str, String)> {
+        vec![("GIT_INDEX_FILE", self.path.to_string_lossy().into_owned())]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git_fixture::{git_ok, init_repo_with_main};
+
+    #[test]
+    fn snapshot_records_the_worktree_without_touching_index_or_head() {
+        let repo = init_repo_with_main();
+        let head = git_ok(repo.path(), &["rev-parse", "HEAD"]);
+        std::fs::write(repo.path().join("src.txt"), "good\n").unwrap();
+        std::fs::write(repo.path().join("new.txt"), "new\n").unwrap();
+
+        let first = snapshot(repo.path(), "feat", "agent stopped")
+            .unwrap()
+            .unwrap();
+        assert!(snapshot(repo.path(), "feat", "again").unwrap().is_none());
+
+        assert_eq!(first.number, 1);
+        assert_eq!(git_ok(repo.path(), &["rev-parse", "HEAD"]), head);
+        assert_eq!(
+            git_ok(repo.path(), &["status", "--porcelain"]),
+            "M src.txt\n?? new.txt"
+        );
+        let spec = format!("{}:new.txt", first.commit);
+        assert_eq!(git_ok(repo.path(), &["show", &spec]), "new");
+        let listed = list(repo.path(), "feat").unwrap();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].reason, "agent stopped");
+        assert_eq!(listed[0].commit, first.commit);
+    }
+
+    #[test]
+    fn restore_rolls_back_and_checkpoints_the_state_it_replaces() {
+        let repo = init_repo_with_main();
+        std::fs::write(repo.path().join("src.txt"), "good\n").unwrap();
+        let good = snapshot(repo.path(), "feat", "good").unwrap().unwrap();
+
+        std::fs::write(repo.path().join("src.txt"), "wrecked\n").unwrap();
+        std::fs::create_dir_all(repo.path().join("junk")).unwrap();
+        std::fs::write(repo.path().join("junk/file.txt"), "junk\n").unwrap();
+        let changed = diff(repo.path(), &good).unwrap();
+        let paths: Vec<&str> = changed.iter().map(|file| file.path.as_str()).collect();
+        assert_eq!(paths, vec!["junk/file.txt", "src.txt"]);
+
+        let safety = restore(repo.path(), "feat", &good).unwrap().unwrap();
+
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join("src.txt")).unwrap(),
+            "good\n"
+        );
+        assert!(!repo.path().join("junk").exists());
+        assert_eq!(safety.number, 2);
+        assert_eq!(safety.reason, "before restoring #1");
+        assert!(diff(repo.path(), &good).unwrap().is_empty());
+
+        clear(repo.path(), "feat").unwrap();
+        assert!(list(repo.path(), "feat").unwrap().is_empty());
+    }
+
+    #[test]
+    fn base_commit_is_the_checkpointed_head_and_missing_on_an_unborn_branch() {
+        let repo = init_repo_with_main();
+        let head = git_ok(repo.path(), &["rev-parse", "HEAD"]);
+        std::fs::write(repo.path().join("src.txt"), "good\n").unwrap();
+        let checkpoint = snapshot(repo.path(), "feat", "good").unwrap().unwrap();
+        assert_eq!(base_commit(repo.path(), &checkpoint).unwrap(), head);
+
+        let unborn = TempDir::new().unwrap();
+        git_ok(unborn.path(), &["init", "--initial-branch=main"]);
+        std::fs::write(unborn.path().join("src.txt"), "first\n").unwrap();
+        let checkpoint = snapshot(unborn.path(), "feat", "first").unwrap().unwrap();
+        let err = base_commit(unborn.path(), &checkpoint).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Checkpoint #1 was taken before the branch had a commit, so there is nothing to fork from"
+        );
+    }
+}
@@ -0,0 +1,187 @@
+//! Headless `amf daemon`: runs the dashboard's background work (IPC,
+//! status sync, notifications, hook polling) without a terminal so
+//! automation keeps working when nobody has the TUI open.
+
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use crate::app::{App, load_config};
+use crate::tmux::TmuxManager;
+use crate::{debug, ipc, project};
+
+const TICK: Duration = Duration::from_millis(50);
+const STATUS_SYNC_INTERVAL: Duration = Duration::from_secs(5);
+const NOTIFICATION_SCAN_INTERVAL: Duration = Duration::from_millis(500);
+const THINKING_SYNC_INTERVAL: Duration = Duration::from_millis(500);
+const EVENT_PUBLISH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long a starting TUI waits for a daemon to release the socket.
+const HANDOFF_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub fn run() -> Result<()> {
+    let socket = ipc::socket_path();
+    if ipc::is_live(&socket) {
+        bail!(
+            "AMF is already running (socket {} is accepting connections)",
+            socket.display()
+        );
+    }
+
+    let config = load_config();
+    TmuxManager::configure_control_mode(config.tmux_control_mode);
+    TmuxManager::check_available()?;
+
+    debug::install_panic_hook();
+    crate::cleanup_global_hooks();
+    App::cleanup_stale_thinking_files();
+
+    let mut app = App::new(project::db_path())?;
+    app.headless = true;
+    app.log_startup();
+
+    let guard = ipc::start(&socket).context("Failed to start IPC socket")?;
+    app.ipc = Some(guard);
+    app.log_info(
+        "daemon",
+        format!("Daemon listening at {}", socket.display()),
+    );
+    eprintln!("amf daemon listening at {}", socket.display());
+
+    app.sync_statuses();
+    app.sync_session_status_background();
+    app.scan_notifications();
+    let refreshed = crate::app::setup::refresh_claude_hooks_for_store(&app.store, &app.config);
+    app.log_info(
+        "setup",
+        format!("Refreshed Claude hooks for {refreshed} feature(s)"),
+    );
+    let refreshed = crate::app::setup::refresh_opencode_plugins_for_store(&app.store);
+    app.log_info(
+        "setup",
+        format!("Refreshed opencode plugins for {refreshed} feature(s)"),
+    );
+
+    let result = run_loop(&mut app);
+    if let Err(err) = app.save() {
+        app.log_error("daemon", format!("Failed to save store on exit: {err}"));
+    }
+    app.log_info("daemon", "Daemon exiting".to_string());
+    result
+}
+
+fn run_loop(app: &mut App) -> Result<()> {
+    let mut last_sync = Instant::now();
+    let mut last_notif_scan = Instant::now();
+    let mut last_thinking_sync = Instant::now();
+    let mut last_event_publish = Instant::now();
+
+    while !app.should_quit {
+        if !app.background_deletions.is_empty()
+            && let Err(e) = app.poll_background_deletions()
+        {
+            app.report_logged_error("daemon", e.to_string());
+        }
+
+        if !app.background_hooks.is_empty()
+            && let Err(e) = app.poll_background_hooks()
+        {
+            app.report_logged_error("daemon", e.to_string());
+        }
+
+        if app.session_status_bg.is_some() {
+            app.poll_session_status_bg();
+        }
+
+        if last_sync.elapsed() >= STATUS_SYNC_INTERVAL {
+            app.sync_statuses();
+            app.sync_session_status();
+            last_sync = Instant::now();
+        }
+
+        app.drain_ipc_messages();
+
+        if last_notif_scan.elapsed() >= NOTIFICATION_SCAN_INTERVAL {
+            app.scan_notifications();
+            last_notif_scan = Instant::now();
+        }
+
+        if last_thinking_sync.elapsed() >= THINKING_SYNC_INTERVAL {
+            app.sync_thinking_status();
+            last_thinking_sync = Instant::now();
+        }
+
+        if last_event_publish.elapsed() >= EVENT_PUBLISH_INTERVAL {
+            app.publish_automation_events();
+            last_event_publish = Instant::now();
+        }
+
+        poll_background_results(app);
+
+        std::thread::sleep(TICK);
+    }
+
+    Ok(())
+}
+
+/// Apply results that background work handed back since the last tick.
+pub(crate) fn poll_background_results(app: &mut App) {
+    app.poll_automation_waits();
+    app.poll_queued_prompts(Instant::now());
+    app.poll_checkpoints();
+
+    if let Err(e) = app.poll_summary_result() {
+        app.report_logged_error("daemon", e.to_string());
+    }
+}
+
+/// Ask a running daemon to hand the IPC socket over. Returns `Ok(true)`
+/// when a daemon was running and has released the socket, `Ok(false)` when
+/// nothing was listening.
+pub fn take_over(socket: &Path) -> Result<bool> {
+    if !ipc::is_live(socket) {
+        return Ok(false);
+    }
+
+    let request = serde_json::json!({ "type": ipc::HANDOFF_TYPE });
+    let reply = ipc::send_wait(socket, &request.to_string(), HANDOFF_TIMEOUT)?;
+    if reply["ok"] != true {
+        bail!(
+            "{}",
+            reply["error"].as_str().unwrap_or("Handoff was refused")
+        );
+    }
+
+    let deadline = Instant::now() + HANDOFF_TIMEOUT;
+    while ipc::is_live(socket) {
+        if Instant::now() >= deadline {
+            bail!("Timed out waiting for amf daemon to release the socket");
+        }
+        std::thread::sleep(TICK);
+    }
+    Ok(true)
+}
+
+/// Relaunch `amf daemon` detached from the current terminal, used when a
+/// TUI that took over from a daemon exits.
+pub fn spawn_detached() -> Result<()> {
+    use std::os::unix::process::CommandExt;
+    use std::process::{Command, Stdio};
+
+    let exe = std::env::current_exe().context("Failed to locate amf executable")?;
+    let mut cmd = Command::new(exe);
+    cmd.arg("daemon")
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    // SAFETY: setsid is async-signal-safe and only detaches the child
+    // from the controlling terminal.
+    unsafe {
+        cmd.pre_exec(|| {
+            libc::setsid();
+            Ok(())
+        });
+    }
+    cmd.spawn().context("Failed to start amf daemon")?;
+    Ok(())
+}
@@ -29,6 +29,14 @@ pub(super) fn run(conn: &Connection) -> Result<()> {
             "Replace per-file session-status with DB table",
             MIGRATION_004,
         ),
+        (
+            "Track the feature a stacked feature is built on",
+            MIGRATION_005,
+        ),
+        ("Free-form feature tags", MIGRATION_006),
+        ("Per-feature notes", MIGRATION_007),
+        ("Archived features", MIGRATION_008),
+        ("Worktrees of multi-repo features", MIGRATION_009),
     ];
 
     for (i, (desc, sql)) in migrations.iter().enumerate() {
@@ -92,6 +100,40 @@ CREATE TABLE IF NOT EXISTS session_status (
 );
 ";
 
+const MIGRATION_005: &str = "
+ALTER TABLE features ADD COLUMN parent_id TEXT;
+";
+
+const MIGRATION_006: &str = "
+CREATE TABLE IF NOT EXISTS feature_tags (
+    feature_id TEXT NOT NULL REFERENCES features(id) ON DELETE CASCADE,
+    tag        TEXT NOT NULL,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (feature_id, tag)
+);
+CREATE INDEX IF NOT EXISTS idx_feature_tags_tag ON feature_tags(tag);
+";
+
+const MIGRATION_007: &str = "
+ALTER TABLE features ADD COLUMN notes TEXT;
+";
+
+const MIGRATION_008: &str = "
+ALTER TABLE features ADD COLUMN archived_at TEXT;
+ALTER TABLE features ADD COLUMN archived_prompt TEXT;
+";
+
+const MIGRATION_009: &str = "
+CREATE TABLE IF NOT EXISTS feature_repos (
+    feature_id TEXT NOT NULL REFERENCES features(id) ON DELETE CASCADE,
+    project_id TEXT NOT NULL,
+    repo       TEXT NOT NULL,
+    workdir    TEXT NOT NULL,
+    sort_order INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (feature_id, workdir)
+);
+";
+
 const MIGRATION_001: &str = "
 CREATE TABLE IF NOT EXISTS store_meta (
     key   TEXT PRIMARY KEY,
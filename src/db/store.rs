@@ -4,8 +4,8 @@ use rusqlite::{Connection, OptionalExtension, params};
 use std::path::PathBuf;
 
 use crate::project::{
-    AgentKind, Feature, FeatureSession, Project, ProjectStatus, ProjectStore, SessionBookmark,
-    SessionKind, TokenUsageSourceMatch, VibeMode, CURRENT_PROJECT_STORE_VERSION,
+    AgentKind, Feature, FeatureRepo, FeatureSession, Project, ProjectStatus, ProjectStore,
+    SessionBookmark, SessionKind, TokenUsageSourceMatch, VibeMode, CURRENT_PROJECT_STORE_VERSION,
 };
 use crate::token_tracking::TokenUsageSource;
 
@@ -201,7 +201,8 @@ fn load_features(conn: &Connection, project_id: &str) -> Result<Vec<Feature>> {
         "SELECT id, name, branch, workdir, is_worktree, tmux_session,
                 mode, review, plan_mode, agent, enable_chrome, status,
                 summary, summary_updated_at, nickname, collapsed,
-                created_at, last_accessed, ready
+                created_at, last_accessed, ready, parent_id, notes,
+                archived_at, archived_prompt
          FROM features WHERE project_id = ?1
          ORDER BY sort_order ASC, rowid ASC",
     )?;
@@ -226,6 +227,10 @@ fn load_features(conn: &Connection, project_id: &str) -> Result<Vec<Feature>> {
         String,
         String,
         bool,
+        Option<String>,
+        Option<String>,
+        Option<String>,
+        Option<String>,
     )> = stmt
         .query_map(params![project_id], |row| {
             Ok((
@@ -248,6 +253,10 @@ fn load_features(conn: &Connection, project_id: &str) -> Result<Vec<Feature>> {
                 row.get(16)?,
                 row.get(17)?,
                 row.get(18)?,
+                row.get(19)?,
+                row.get(20)?,
+                row.get(21)?,
+                row.get(22)?,
             ))
         })?
         .collect::<Result<Vec<_>, _>>()?;
@@ -273,9 +282,15 @@ fn load_features(conn: &Connection, project_id: &str) -> Result<Vec<Feature>> {
         feat_created_at,
         last_accessed,
         ready,
+        parent_id,
+        notes,
+        archived_at_str,
+        archived_prompt,
     ) in rows
     {
         let sessions = load_sessions(conn, &feat_id)?;
+        let tags = load_tags(conn, &feat_id)?;
+        let repos = load_repos(conn, &feat_id)?;
         features.push(Feature {
             id: feat_id,
             name: feat_name,
@@ -298,11 +313,45 @@ fn load_features(conn: &Connection, project_id: &str) -> Result<Vec<Feature>> {
             summary,
             summary_updated_at: summary_updated_at_str.as_deref().map(dt_from_str),
             nickname,
+            parent_id,
+            tags,
+            notes,
+            archived_at: archived_at_str.as_deref().map(dt_from_str),
+            archived_prompt,
+            repos,
         });
     }
     Ok(features)
 }
 
+fn load_tags(conn: &Connection, feature_id: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT tag FROM feature_tags WHERE feature_id = ?1
+         ORDER BY sort_order ASC, rowid ASC",
+    )?;
+    let tags = stmt
+        .query_map(params![feature_id], |row| row.get(0))?
+        .collect::<Result<Vec<String>, _>>()?;
+    Ok(tags)
+}
+
+fn load_repos(conn: &Connection, feature_id: &str) -> Result<Vec<FeatureRepo>> {
+    let mut stmt = conn.prepare(
+        "SELECT project_id, repo, workdir FROM feature_repos WHERE feature_id = ?1
+         ORDER BY sort_order ASC, rowid ASC",
+    )?;
+    let repos = stmt
+        .query_map(params![feature_id], |row| {
+            Ok(FeatureRepo {
+                project_id: row.get(0)?,
+                repo: PathBuf::from(row.get::<_, String>(1)?),
+                workdir: PathBuf::from(row.get::<_, String>(2)?),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(repos)
+}
+
 fn load_sessions(conn: &Connection, feature_id: &str) -> Result<Vec<FeatureSession>> {
     let mut stmt = conn.prepare(
         "SELECT id, kind, label, tmux_window, claude_session_id,
@@ -413,9 +462,11 @@ fn do_save(conn: &Connection, store: &ProjectStore) -> Result<()> {
                     id, project_id, name, branch, workdir, is_worktree,
                     tmux_session, mode, review, plan_mode, agent, enable_chrome,
                     status, summary, summary_updated_at, nickname, collapsed,
-                    created_at, last_accessed, ready, sort_order
+                    created_at, last_accessed, ready, sort_order, parent_id, notes,
+                    archived_at, archived_prompt
                 ) VALUES (
-                    ?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18,?19,?20,?21
+                    ?1,?2,?3,?4,?5,?6,?7,?8,?9,?10,?11,?12,?13,?14,?15,?16,?17,?18,?19,?20,?21,?22,?23,
+                    ?24,?25
                 )",
                 params![
                     feature.id,
@@ -439,9 +490,35 @@ fn do_save(conn: &Connection, store: &ProjectStore) -> Result<()> {
                     dt_to_str(&feature.last_accessed),
                     feature.ready as i32,
                     fi as i64,
+                    feature.parent_id,
+                    feature.notes,
+                    feature.archived_at.as_ref().map(dt_to_str),
+                    feature.archived_prompt,
                 ],
             )?;
 
+            for (ti, tag) in feature.tags.iter().enumerate() {
+                conn.execute(
+                    "INSERT INTO feature_tags (feature_id, tag, sort_order)
+                     VALUES (?1, ?2, ?3)",
+                    params![feature.id, tag, ti as i64],
+                )?;
+            }
+
+            for (ri, member) in feature.repos.iter().enumerate() {
+                conn.execute(
+                    "INSERT INTO feature_repos (feature_id, project_id, repo, workdir, sort_order)
+                     VALUES (?1, ?2, ?3, ?4, ?5)",
+                    params![
+                        feature.id,
+                        member.project_id,
+                        member.repo.to_string_lossy(),
+                        member.workdir.to_string_lossy(),
+                        ri as i64,
+                    ],
+                )?;
+            }
+
             for (si, session) in feature.sessions.iter().enumerate() {
                 conn.execute(
                     "INSERT INTO feature_sessions (
@@ -553,6 +630,16 @@ mod tests {
             summary: Some("did some stuff".to_string()),
             summary_updated_at: Some(Utc::now()),
             nickname: Some("myf".to_string()),
+            parent_id: Some("feat-0".to_string()),
+            tags: vec!["bug".to_string(), "ui".to_string()],
+            notes: Some("Why: users get logged out\nTODO: tests".to_string()),
+            archived_at: Some(Utc::now()),
+            archived_prompt: Some("fix the login redirect".to_string()),
+            repos: vec![crate::project::FeatureRepo {
+                project_id: "proj-2".to_string(),
+                repo: PathBuf::from("/tmp/frontend"),
+                workdir: PathBuf::from("/tmp/repo/.worktrees/my-feature/frontend"),
+            }],
         };
 
         let project = Project {
@@ -585,6 +672,20 @@ mod tests {
         assert!(lf.ready);
         assert_eq!(lf.summary, Some("did some stuff".to_string()));
         assert_eq!(lf.nickname, Some("myf".to_string()));
+        assert_eq!(lf.parent_id.as_deref(), Some("feat-0"));
+        assert_eq!(lf.tags, vec!["bug", "ui"]);
+        assert_eq!(
+            lf.notes.as_deref(),
+            Some("Why: users get logged out\nTODO: tests")
+        );
+        assert!(lf.is_archived());
+        assert_eq!(
+            lf.archived_prompt.as_deref(),
+            Some("fix the login redirect")
+        );
+        assert_eq!(lf.repos.len(), 1);
+        assert_eq!(lf.repos[0].project_id, "proj-2");
+        assert_eq!(lf.repos[0].label(), "frontend");
 
         assert_eq!(lf.sessions.len(), 1);
         let ls = &lf.sessions[0];
@@ -687,6 +788,12 @@ mod tests {
                     summary: None,
                     summary_updated_at: None,
                     nickname: None,
+                    parent_id: None,
+                    tags: Vec::new(),
+                    notes: None,
+                    archived_at: None,
+                    archived_prompt: None,
+                    repos: Vec::new(),
                 },
                 Feature {
                     id: "feat-skip".to_string(),
@@ -710,6 +817,12 @@ mod tests {
                     summary: None,
                     summary_updated_at: None,
                     nickname: None,
+                    parent_id: None,
+                    tags: Vec::new(),
+                    notes: None,
+                    archived_at: None,
+                    archived_prompt: None,
+                    repos: Vec::new(),
                 },
             ],
             created_at: Utc::now(),
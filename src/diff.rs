@@ -1,9 +1,12 @@
-use std::path::Path;
-use std::process::Command;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
 use std::sync::OnceLock;
 
 use anyhow::{Context, Result, anyhow, bail};
 use regex::Regex;
+use schemars::JsonSchema;
+use serde::Serialize;
 
 use crate::worktree::WorktreeManager;
 
@@ -38,7 +41,8 @@ pub struct DiffFile {
     pub hunks: Vec<DiffHunk>,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
 pub enum DiffFileStatus {
     Added,
     Modified,
@@ -73,8 +77,11 @@ pub enum DiffLineKind {
     NoNewlineMarker,
 }
 
-pub fn load_snapshot(workdir: &Path) -> Result<DiffSnapshot> {
-    let base = resolve_base_ref(workdir)?;
+/// Diff `workdir` against its base. `parent_branch` is the branch a
+/// stacked feature was built on; when it still exists it is the base, so
+/// each layer of a stack shows only its own changes.
+pub fn load_snapshot(workdir: &Path, parent_branch: Option<&str>) -> Result<DiffSnapshot> {
+    let base = resolve_base_ref(workdir, parent_branch)?;
     let tracked_patch = git_capture(
         workdir,
         &[
@@ -91,35 +98,7 @@ pub fn load_snapshot(workdir: &Path) -> Result<DiffSnapshot> {
 
     let mut files = parse_unified_diff(&tracked_patch)?;
     hydrate_file_contents(workdir, &base.base_commit, &mut files)?;
-
-    for rel_path in list_untracked_files(workdir)? {
-        let patch = git_capture(
-            workdir,
-            &[
-                "diff",
-                "--no-index",
-                "--no-ext-diff",
-                "--no-color",
-                "--unified=3",
-                "--",
-                "/dev/null",
-                &rel_path,
-            ],
-            true,
-        )?;
-        for mut file in parse_unified_diff(&patch)? {
-            file.status = DiffFileStatus::Untracked;
-            if file.old_path.is_none() {
-                file.old_path = None;
-            }
-            if file.path.is_empty() {
-                file.path = rel_path.clone();
-            }
-            file.old_content = None;
-            file.new_content = read_worktree_file(workdir, &file.path)?;
-            files.push(file);
-        }
-    }
+    files.extend(load_untracked_files(workdir)?);
 
     files.sort_by(|a, b| a.path.cmp(&b.path));
 
@@ -136,6 +115,110 @@ pub fn load_snapshot(workdir: &Path) -> Result<DiffSnapshot> {
     })
 }
 
+/// Changes not yet staged in `workdir`, untracked files included, or with
+/// `cached` the changes staged for the next commit. Paths are relative to
+/// the repository root so the hunks can be fed back to `git apply --cached`.
+pub fn load_index_diff(workdir: &Path, cached: bool) -> Result<Vec<DiffFile>> {
+    let mut args = vec!["diff", "--no-ext-diff", "--no-color", "--unified=3"];
+    if cached {
+        args.push("--cached");
+    }
+    let mut files = parse_unified_diff(&git_capture(workdir, &args, false)?)?;
+    if !cached {
+        files.extend(load_untracked_files(workdir)?);
+    }
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Diff `commit` against `tree`, a snapshot of the worktree in `workdir`
+/// (see `checkpoint`). New-side contents are read from the worktree.
+pub fn load_commit_diff(workdir: &Path, commit: &str, tree: &str) -> Result<Vec<DiffFile>> {
+    let patch = git_capture(
+        workdir,
+        &[
+            "diff",
+            "--find-renames",
+            "--no-ext-diff",
+            "--no-color",
+            "--unified=3",
+            commit,
+            tree,
+        ],
+        false,
+    )?;
+    let mut files = parse_unified_diff(&patch)?;
+    hydrate_file_contents(workdir, commit, &mut files)?;
+    files.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(files)
+}
+
+/// Diff each member worktree of a multi-repo feature and merge the
+/// results, prefixing every path with the member's directory under
+/// `workdir`. With no members this is [`load_snapshot`] on `workdir`.
+pub fn load_workspace_snapshot(
+    workdir: &Path,
+    members: &[PathBuf],
+    parent_branch: Option<&str>,
+) -> Result<DiffSnapshot> {
+    if members.is_empty() {
+        return load_snapshot(workdir, parent_branch);
+    }
+
+    let mut snapshots = Vec::new();
+    for member in members {
+        let label = member
+            .strip_prefix(workdir)
+            .unwrap_or(member)
+            .to_string_lossy()
+            .into_owned();
+        let snapshot = load_snapshot(member, parent_branch)
+            .with_context(|| format!("failed to diff {label}"))?;
+        snapshots.push((label, snapshot));
+    }
+
+    let branch = snapshots
+        .iter()
+        .map(|(_, snapshot)| snapshot.branch.clone())
+        .find(|branch| !branch.is_empty())
+        .unwrap_or_default();
+    let same_base = snapshots
+        .windows(2)
+        .all(|pair| pair[0].1.base_ref == pair[1].1.base_ref);
+    let (base_ref, base_commit) = if snapshots.len() == 1 {
+        let snapshot = &snapshots[0].1;
+        (snapshot.base_ref.clone(), snapshot.base_commit.clone())
+    } else if same_base {
+        (snapshots[0].1.base_ref.clone(), String::new())
+    } else {
+        let refs: Vec<String> = snapshots
+            .iter()
+            .map(|(label, snapshot)| format!("{label}: {}", snapshot.base_ref))
+            .collect();
+        (refs.join(", "), String::new())
+    };
+
+    let mut files = Vec::new();
+    for (label, snapshot) in snapshots {
+        for mut file in snapshot.files {
+            file.path = format!("{label}/{}", file.path);
+            file.old_path = file.old_path.map(|path| format!("{label}/{path}"));
+            files.push(file);
+        }
+    }
+    let total_additions = files.iter().map(|file| file.additions).sum();
+    let total_deletions = files.iter().map(|file| file.deletions).sum();
+
+    Ok(DiffSnapshot {
+        branch,
+        base_ref,
+        base_commit,
+        files,
+        total_additions,
+        total_deletions,
+    })
+}
+
 pub fn load_review_file(original: &Path, proposed: &Path, display_path: &str) -> Result<DiffFile> {
     let output = Command::new("git")
         .args([
@@ -223,12 +306,12 @@ fn hydrate_file_contents(workdir: &Path, base_commit: &str, files: &mut [DiffFil
     Ok(())
 }
 
-pub fn resolve_base_ref(workdir: &Path) -> Result<ResolvedBase> {
+pub fn resolve_base_ref(workdir: &Path, parent_branch: Option<&str>) -> Result<ResolvedBase> {
     let branch = WorktreeManager::current_branch(workdir)?
         .filter(|branch| !branch.is_empty())
         .ok_or_else(|| anyhow!("{} is not on a named branch", workdir.display()))?;
 
-    let mut candidates = Vec::new();
+    let mut candidates: Vec<String> = parent_branch.map(str::to_string).into_iter().collect();
 
     if let Some(origin_head) = git_optional_trimmed(
         workdir,
@@ -493,6 +576,39 @@ fn normalize_patch_path(path: &str) -> Option<String> {
     }
 }
 
+fn load_untracked_files(workdir: &Path) -> Result<Vec<DiffFile>> {
+    let mut files = Vec::new();
+    for rel_path in list_untracked_files(workdir)? {
+        let patch = git_capture(
+            workdir,
+            &[
+                "diff",
+                "--no-index",
+                "--no-ext-diff",
+                "--no-color",
+                "--unified=3",
+                "--",
+                "/dev/null",
+                &rel_path,
+            ],
+            true,
+        )?;
+        for mut file in parse_unified_diff(&patch)? {
+            file.status = DiffFileStatus::Untracked;
+            if file.old_path.is_none() {
+                file.old_path = None;
+            }
+            if file.path.is_empty() {
+                file.path = rel_path.clone();
+            }
+            file.old_content = None;
+            file.new_content = read_worktree_file(workdir, &file.path)?;
+            files.push(file);
+        }
+    }
+    Ok(files)
+}
+
 fn list_untracked_files(workdir: &Path) -> Result<Vec<String>> {
     let stdout = git_capture(
         workdir,
@@ -517,18 +633,11 @@ fn fork_point_or_merge_base(workdir: &Path, candidate: &str) -> Result<Option<St
 }
 
 fn git_ref_exists(workdir: &Path, reference: &str) -> bool {
-    Command::new("git")
-        .args(["rev-parse", "--verify", reference])
-        .current_dir(workdir)
-        .output()
-        .map(|output| output.status.success())
-        .unwrap_or(false)
+    git_succeeds(workdir, &["rev-parse", "--verify", reference]).unwrap_or(false)
 }
 
 fn git_optional_trimmed(workdir: &Path, args: &[&str]) -> Result<Option<String>> {
-    let output = Command::new("git")
-        .args(args)
-        .current_dir(workdir)
+    let output = git_command(workdir, args)
         .output()
         .with_context(|| format!("failed to run git {}", args.join(" ")))?;
 
@@ -544,28 +653,87 @@ fn git_optional_trimmed(workdir: &Path, args: &[&str]) -> Result<Option<String>>
     }
 }
 
-fn git_capture(workdir: &Path, args: &[&str], allow_diff_exit_code: bool) -> Result<String> {
-    let output = Command::new("git")
-        .args(args)
-        .current_dir(workdir)
-        .output()
-        .with_context(|| format!("failed to run git {}", args.join(" ")))?;
+fn git_command(dir: &Path, args: &[&str]) -> Command {
+    let mut command = Command::new("git");
+    command.args(args).current_dir(dir);
+    command
+}
+
+/// Run `command` and return its stdout, failing with git's stderr when it
+/// exits non-zero. `allow_diff_exit_code` also accepts exit code 1, which
+/// `git diff --no-index` uses for "files differ".
+fn git_run(
+    mut command: Command,
+    args: &[&str],
+    input: Option<&str>,
+    allow_diff_exit_code: bool,
+) -> Result<String> {
+    let output = match input {
+        Some(input) => output_with_input(command, input),
+        None => command.output(),
+    }
+    .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
 
     let success =
         output.status.success() || (allow_diff_exit_code && output.status.code() == Some(1));
     if !success {
-        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
-        bail!("git {} failed: {}", args.join(" "), stderr);
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git {} failed: {}", args.join(" "), stderr.trim());
     }
-
     Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
 
-fn git_show_file(workdir: &Path, commit: &str, path: &str) -> Result<Option<String>> {
+fn output_with_input(mut command: Command, input: &str) -> std::io::Result<Output> {
+    let mut child = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes())?;
+    }
+    child.wait_with_output()
+}
+
+fn git_capture(workdir: &Path, args: &[&str], allow_diff_exit_code: bool) -> Result<String> {
+    git_run(git_command(workdir, args), args, None, allow_diff_exit_code)
+}
+
+/// Run git in `dir` and return its trimmed stdout, failing with git's
+/// stderr when it exits non-zero.
+pub(crate) fn git_output(dir: &Path, args: &[&str]) -> Result<String> {
+    git_output_with_env(dir, args, &[])
+}
+
+/// `git_output` with extra environment variables for the git process.
+pub(crate) fn git_output_with_env(
+    dir: &Path,
+    args: &[&str],
+    env: &[(&str, String)],
+) -> Result<String> {
+    let mut command = git_command(dir, args);
+    command.envs(env.iter().map(|(key, value)| (key, value)));
+    Ok(git_run(command, args, None, false)?.trim().to_string())
+}
+
+/// `git_output` with `input` written to git's stdin.
+pub(crate) fn git_output_with_input(dir: &Path, args: &[&str], input: &str) -> Result<String> {
+    Ok(git_run(git_command(dir, args), args, Some(input), false)?
+        .trim()
+        .to_string())
+}
+
+/// Run git in `dir` and report only whether it exited successfully.
+pub(crate) fn git_succeeds(dir: &Path, args: &[&str]) -> Result<bool> {
+    let output = git_command(dir, args)
+        .output()
+        .with_context(|| format!("Failed to run git {}", args.join(" ")))?;
+    Ok(output.status.success())
+}
+
+pub(crate) fn git_show_file(workdir: &Path, commit: &str, path: &str) -> Result<Option<String>> {
     let spec = format!("{commit}:{path}");
-    let output = Command::new("git")
-        .args(["show", &spec])
-        .current_dir(workdir)
+    let output = git_command(workdir, &["show", &spec])
         .output()
         .with_context(|| format!("failed to run git show {spec}"))?;
 
@@ -601,12 +769,10 @@ fn dedupe_preserving_order(values: &mut Vec<String>) {
 
 #[cfg(test)]
 mod tests {
-    use std::path::Path;
-    use std::process::Command;
-
     use tempfile::TempDir;
 
     use super::*;
+    use crate::git_fixture::{git_ok, init_repo_with_main};
 
     #[test]
     fn parse_unified_diff_tracks_status_paths_and_hunks() {
@@ -642,12 +808,12 @@ index 1111111..2222222 100644
     fn load_snapshot_hydrates_old_and_new_file_contents() {
         let repo = init_repo_with_main();
         std::fs::write(repo.path().join("src.txt"), "base\nline two\n").unwrap();
-        git(repo.path(), &["add", "src.txt"]);
-        git(repo.path(), &["commit", "-m", "add src"]);
-        git(repo.path(), &["checkout", "-b", "feature"]);
+        git_ok(repo.path(), &["add", "src.txt"]);
+        git_ok(repo.path(), &["commit", "-m", "add src"]);
+        git_ok(repo.path(), &["checkout", "-b", "feature"]);
         std::fs::write(repo.path().join("src.txt"), "base\nline changed\n").unwrap();
 
-        let snapshot = load_snapshot(repo.path()).unwrap();
+        let snapshot = load_snapshot(repo.path(), None).unwrap();
         let file = snapshot
             .files
             .iter()
@@ -661,47 +827,77 @@ index 1111111..2222222 100644
     #[test]
     fn resolve_base_ref_falls_back_to_local_main() {
         let repo = init_repo_with_main();
-        git(repo.path(), &["checkout", "-b", "feature"]);
+        git_ok(repo.path(), &["checkout", "-b", "feature"]);
         std::fs::write(repo.path().join("src.txt"), "base\nfeature\n").unwrap();
-        git(repo.path(), &["commit", "-am", "feature change"]);
+        git_ok(repo.path(), &["commit", "-am", "feature change"]);
 
-        let base = resolve_base_ref(repo.path()).unwrap();
+        let base = resolve_base_ref(repo.path(), None).unwrap();
 
         assert_eq!(base.branch, "feature");
         assert_eq!(base.base_ref, "main");
-        assert_eq!(base.base_commit, rev_parse(repo.path(), "main"));
+        assert_eq!(
+            base.base_commit,
+            git_ok(repo.path(), &["rev-parse", "main"])
+        );
     }
 
     #[test]
     fn resolve_base_ref_prefers_origin_head_when_available() {
         let remote = TempDir::new().unwrap();
-        git(remote.path(), &["init", "--bare", "--initial-branch=main"]);
+        git_ok(remote.path(), &["init", "--bare", "--initial-branch=main"]);
 
         let repo = init_repo_with_main();
-        git(
+        git_ok(
             repo.path(),
             &["remote", "add", "origin", remote.path().to_str().unwrap()],
         );
-        git(repo.path(), &["push", "-u", "origin", "main"]);
-        git(repo.path(), &["remote", "set-head", "origin", "-a"]);
-        git(repo.path(), &["checkout", "-b", "feature"]);
+        git_ok(repo.path(), &["push", "-u", "origin", "main"]);
+        git_ok(repo.path(), &["remote", "set-head", "origin", "-a"]);
+        git_ok(repo.path(), &["checkout", "-b", "feature"]);
         std::fs::write(repo.path().join("src.txt"), "base\nfeature\n").unwrap();
-        git(repo.path(), &["commit", "-am", "feature change"]);
+        git_ok(repo.path(), &["commit", "-am", "feature change"]);
 
-        let base = resolve_base_ref(repo.path()).unwrap();
+        let base = resolve_base_ref(repo.path(), None).unwrap();
 
         assert_eq!(base.base_ref, "origin/main");
-        assert_eq!(base.base_commit, rev_parse(repo.path(), "origin/main"));
+        assert_eq!(
+            base.base_commit,
+            git_ok(repo.path(), &["rev-parse", "origin/main"])
+        );
+    }
+
+    #[test]
+    fn resolve_base_ref_prefers_the_parent_branch_of_a_stack() {
+        let repo = init_repo_with_main();
+        git_ok(repo.path(), &["checkout", "-b", "parent"]);
+        std::fs::write(repo.path().join("src.txt"), "base\nparent\n").unwrap();
+        git_ok(repo.path(), &["commit", "-am", "parent change"]);
+        git_ok(repo.path(), &["checkout", "-b", "child"]);
+        std::fs::write(repo.path().join("src.txt"), "base\nparent\nchild\n").unwrap();
+        git_ok(repo.path(), &["commit", "-am", "child change"]);
+
+        let base = resolve_base_ref(repo.path(), Some("parent")).unwrap();
+        assert_eq!(base.base_ref, "parent");
+        assert_eq!(
+            base.base_commit,
+            git_ok(repo.path(), &["rev-parse", "parent"])
+        );
+
+        let snapshot = load_snapshot(repo.path(), Some("parent")).unwrap();
+        assert_eq!(snapshot.total_additions, 1);
+
+        let base = resolve_base_ref(repo.path(), Some("deleted-parent")).unwrap();
+        assert_eq!(base.base_ref, "main");
     }
 
     #[test]
     fn load_snapshot_includes_tracked_and_untracked_changes() {
         let repo = init_repo_with_main();
-        git(repo.path(), &["checkout", "-b", "feature"]);
+        git_ok(repo.path(), &["checkout", "-b", "feature"]);
         std::fs::write(repo.path().join("src.txt"), "base\nfeature\n").unwrap();
         std::fs::write(repo.path().join("notes.md"), "todo\n").unwrap();
 
-        let snapshot = load_snapshot(repo.path()).unwrap();
+        let snapshot = load_snapshot(repo.path(), None).unwrap();
 
         assert_eq!(snapshot.branch, "feature");
         assert_eq!(snapshot.base_ref, "main");
@@ -714,38 +910,28 @@ index 1111111..2222222 100644
         assert_eq!(snapshot.total_deletions, 0);
     }
 
-    fn init_repo_with_main() -> TempDir {
-        let repo = TempDir::new().unwrap();
-        git(repo.path(), &["init", "--initial-branch=main"]);
-        git(repo.path(), &["config", "user.name", "AMF Test"]);
-        git(repo.path(), &["config", "user.email", "amf@example.com"]);
-        std::fs::write(repo.path().join("src.txt"), "base\n").unwrap();
-        git(repo.path(), &["add", "src.txt"]);
-        git(repo.path(), &["commit", "-m", "initial"]);
-        repo
-    }
+    #[test]
+    fn load_workspace_snapshot_prefixes_paths_with_member_dirs() {
+        let backend = init_repo_with_main();
+        let frontend = init_repo_with_main();
+        let workspace = TempDir::new().unwrap();
+        let members = [
+            workspace.path().join("backend"),
+            workspace.path().join("frontend"),
+        ];
+        for (repo, member) in [(&backend, &members[0]), (&frontend, &members[1])] {
+            let path = member.to_str().unwrap();
+            git_ok(repo.path(), &["worktree", "add", "-b", "feature", path]);
+        }
+        std::fs::write(members[0].join("src.txt"), "base\napi\n").unwrap();
+        std::fs::write(members[1].join("app.ts"), "ui\n").unwrap();
 
-    fn git(repo: &Path, args: &[&str]) {
-        let output = Command::new("git")
-            .args(args)
-            .current_dir(repo)
-            .output()
-            .unwrap();
-        assert!(
-            output.status.success(),
-            "git {} failed: {}",
-            args.join(" "),
-            String::from_utf8_lossy(&output.stderr)
-        );
-    }
+        let snapshot = load_workspace_snapshot(workspace.path(), &members, None).unwrap();
 
-    fn rev_parse(repo: &Path, rev: &str) -> String {
-        let output = Command::new("git")
-            .args(["rev-parse", rev])
-            .current_dir(repo)
-            .output()
-            .unwrap();
-        assert!(output.status.success());
-        String::from_utf8_lossy(&output.stdout).trim().to_string()
+        assert_eq!(snapshot.branch, "feature");
+        assert_eq!(snapshot.base_ref, "main");
+        let paths: Vec<&str> = snapshot.files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(paths, vec!["backend/src.txt", "frontend/app.ts"]);
+        assert_eq!(snapshot.total_additions, 2);
     }
 }
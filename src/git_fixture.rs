@@ -0,0 +1,41 @@
+//! Throwaway git repositories for tests.
+
+use std::path::Path;
+use std::process::Command;
+
+use tempfile::TempDir;
+
+/// A repo on `main` with a test identity and `src.txt` committed as `base`.
+pub fn init_repo_with_main() -> TempDir {
+    init_repo_with_src("base\n")
+}
+
+/// A repo on `main` with a test identity and `src.txt` committed with
+/// `content`.
+pub fn init_repo_with_src(content: &str) -> TempDir {
+    let repo = TempDir::new().unwrap();
+    git_ok(repo.path(), &["init", "--initial-branch=main"]);
+    git_ok(repo.path(), &["config", "user.name", "AMF Test"]);
+    git_ok(repo.path(), &["config", "user.email", "amf@example.com"]);
+    std::fs::write(repo.path().join("src.txt"), content).unwrap();
+    git_ok(repo.path(), &["add", "src.txt"]);
+    git_ok(repo.path(), &["commit", "-m", "initial"]);
+    repo
+}
+
+/// Run git in `dir` and return its trimmed stdout, failing the test with
+/// git's stderr when it errors.
+pub fn git_ok(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "git {} failed: {}",
+        args.join(" "),
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).trim().to_string()
+}
@@ -0,0 +1,149 @@
+use std::path::Path;
+
+use anyhow::Result;
+use chrono::{DateTime, TimeZone, Utc};
+
+use crate::diff::git_output;
+
+/// Git state of a feature worktree, shown next to its agent status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitSummary {
+    /// Files with uncommitted changes, untracked files included.
+    pub dirty: usize,
+    /// Base from `diff::resolve_base_ref`, when one could be resolved.
+    pub base_ref: Option<String>,
+    pub ahead: usize,
+    pub behind: usize,
+    /// Commits not on the upstream branch, or `None` without an upstream.
+    pub unpushed: Option<usize>,
+    pub last_commit: Option<DateTime<Utc>>,
+}
+
+impl GitSummary {
+    /// One line per fact, for the view sidebar.
+    pub fn sidebar_text(&self, now: DateTime<Utc>) -> String {
+        let mut lines = Vec::new();
+        lines.push(match self.dirty {
+            0 => "Clean".to_string(),
+            1 => "1 uncommitted file".to_string(),
+            n => format!("{} uncommitted files", n),
+        });
+        if let Some(base_ref) = &self.base_ref {
+            lines.push(format!(
+                "{} ahead, {} behind {}",
+                self.ahead, self.behind, base_ref
+            ));
+        }
+        lines.push(match self.unpushed {
+            Some(0) => "Pushed".to_string(),
+            Some(n) => format!("{} unpushed", n),
+            None => "No upstream".to_string(),
+        });
+        if let Some(at) = self.last_commit {
+            lines.push(format!("Last commit {} ago", compact_age(at, now)));
+        }
+        lines.join("\n")
+    }
+}
+
+/// Age in its largest whole unit, e.g. `45s`, `3h` or `2w`.
+pub fn compact_age(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = now.signed_duration_since(at).num_seconds().max(0);
+    match secs {
+        s if s < 60 => format!("{}s", s),
+        s if s < 3600 => format!("{}m", s / 60),
+        s if s < 86400 => format!("{}h", s / 3600),
+        s if s < 7 * 86400 => format!("{}d", s / 86400),
+        s => format!("{}w", s / (7 * 86400)),
+    }
+}
+
+/// Read the git state of `workdir`. Ahead/behind are counted against the
+/// base `diff::load_snapshot` would diff against.
+pub fn load(workdir: &Path, parent_branch: Option<&str>) -> Result<GitSummary> {
+    let status = git_output(workdir, &["status", "--porcelain", "-uall"])?;
+    let dirty = status.lines().filter(|line| !line.is_empty()).count();
+
+    let (base_ref, ahead, behind) = match crate::diff::resolve_base_ref(workdir, parent_branch) {
+        Ok(base) => {
+            let range = format!("{}...HEAD", base.base_ref);
+            let counts = git_output(workdir, &["rev-list", "--left-right", "--count", &range])?;
+            let (behind, ahead) = parse_counts(&counts);
+            (Some(base.base_ref), ahead, behind)
+        }
+        Err(_) => (None, 0, 0),
+    };
+
+    let unpushed = git_output(workdir, &["rev-list", "--count", "@{upstream}..HEAD"])
+        .ok()
+        .and_then(|count| count.parse().ok());
+
+    let last_commit = git_output(workdir, &["log", "-1", "--format=%ct"])
+        .ok()
+        .and_then(|secs| secs.parse::<i64>().ok())
+        .and_then(|secs| Utc.timestamp_opt(secs, 0).single());
+
+    Ok(GitSummary {
+        dirty,
+        base_ref,
+        ahead,
+        behind,
+        unpushed,
+        last_commit,
+    })
+}
+
+fn parse_counts(counts: &str) -> (usize, usize) {
+    let mut parts = counts.split_whitespace().map(|n| n.parse().unwrap_or(0));
+    (parts.next().unwrap_or(0), parts.next().unwrap_or(0))
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::Duration;
+
+    use super::*;
+    use crate::git_fixture::{git_ok, init_repo_with_main};
+    use crate::worktree::WorktreeManager;
+
+    #[test]
+    fn load_counts_dirty_files_and_commits_against_the_base() {
+        let repo = init_repo_with_main();
+        let feature = WorktreeManager::create(repo.path(), "feature", "feature").unwrap();
+
+        std::fs::write(feature.join("src.txt"), "feature\n").unwrap();
+        git_ok(&feature, &["commit", "-am", "feature change"]);
+        std::fs::write(repo.path().join("main.txt"), "main\n").unwrap();
+        git_ok(repo.path(), &["add", "main.txt"]);
+        git_ok(repo.path(), &["commit", "-m", "main change"]);
+        std::fs::write(feature.join("src.txt"), "edited\n").unwrap();
+        std::fs::write(feature.join("new.txt"), "new\n").unwrap();
+
+        let summary = load(&feature, None).unwrap();
+
+        assert_eq!(summary.dirty, 2);
+        assert_eq!(summary.base_ref.as_deref(), Some("main"));
+        assert_eq!((summary.ahead, summary.behind), (1, 1));
+        assert_eq!(summary.unpushed, None);
+        assert!(summary.last_commit.is_some());
+    }
+
+    #[test]
+    fn sidebar_text_describes_each_fact() {
+        let now = Utc::now();
+        let summary = GitSummary {
+            dirty: 3,
+            base_ref: Some("origin/main".into()),
+            ahead: 2,
+            behind: 0,
+            unpushed: Some(1),
+            last_commit: Some(now - Duration::hours(5)),
+        };
+
+        assert_eq!(
+            summary.sidebar_text(now),
+            "3 uncommitted files\n2 ahead, 0 behind origin/main\n1 unpushed\nLast commit 5h ago"
+        );
+        assert_eq!(compact_age(now - Duration::days(15), now), "2w");
+    }
+}
@@ -0,0 +1,17 @@
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use crate::app::App;
+
+pub fn handle_checkpoint_dialog_key(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q') => app.cancel_checkpoint_dialog(),
+        KeyCode::Down | KeyCode::Char('j') => app.checkpoint_dialog_select_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.checkpoint_dialog_select_prev(),
+        KeyCode::Enter | KeyCode::Char('v') => app.open_checkpoint_diff(),
+        KeyCode::Char('r') => app.restore_selected_checkpoint(),
+        KeyCode::Char('f') => app.fork_from_selected_checkpoint(),
+        _ => {}
+    }
+    Ok(())
+}
@@ -373,6 +373,66 @@ pub fn handle_steering_prompt_key(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+pub fn handle_feature_notes_key(app: &mut App, key: KeyEvent) -> Result<()> {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('q') {
+        app.cancel_edit_feature_notes();
+        return Ok(());
+    }
+
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('v') {
+        if let AppMode::EditingFeatureNotes(state) = &mut app.mode {
+            state.editor.toggle_vim();
+            app.message = Some(if state.editor.vim_mode().is_some() {
+                "Vim mode enabled".into()
+            } else {
+                "Vim mode disabled".into()
+            });
+        }
+        return Ok(());
+    }
+
+    if let AppMode::EditingFeatureNotes(state) = &mut app.mode {
+        match key.code {
+            KeyCode::Char('j') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                state.scroll_down(1);
+                return Ok(());
+            }
+            KeyCode::Char('k') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                state.scroll_up(1);
+                return Ok(());
+            }
+            KeyCode::PageDown => {
+                state.scroll_down(STEERING_PROMPT_PAGE_SCROLL);
+                return Ok(());
+            }
+            KeyCode::PageUp => {
+                state.scroll_up(STEERING_PROMPT_PAGE_SCROLL);
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+
+    match key.code {
+        KeyCode::Tab => {
+            app.save_feature_notes()?;
+        }
+        KeyCode::Esc if matches!(&app.mode, AppMode::EditingFeatureNotes(state) if state.editor.vim_mode().is_none()) =>
+        {
+            app.cancel_edit_feature_notes();
+        }
+        _ => {
+            if let AppMode::EditingFeatureNotes(state) = &mut app.mode {
+                let outcome = state.editor.handle_key(key);
+                if outcome.text_changed || outcome.cursor_moved {
+                    state.request_cursor_scroll();
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 pub fn handle_delete_project_key(app: &mut App, key: KeyCode) -> Result<()> {
     match key {
         KeyCode::Char('y') => {
@@ -500,6 +560,29 @@ pub fn handle_rename_feature_key(app: &mut App, key: KeyCode) -> Result<()> {
     Ok(())
 }
 
+pub fn handle_feature_tags_key(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Esc => {
+            app.cancel_edit_feature_tags();
+        }
+        KeyCode::Enter => {
+            app.apply_feature_tags()?;
+        }
+        KeyCode::Backspace => {
+            if let AppMode::EditingFeatureTags(state) = &mut app.mode {
+                state.input.pop();
+            }
+        }
+        KeyCode::Char(c) => {
+            if let AppMode::EditingFeatureTags(state) = &mut app.mode {
+                state.input.push(c);
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 pub fn handle_session_config_key(app: &mut App, key: KeyCode) -> Result<()> {
     match key {
         KeyCode::Char('j') | KeyCode::Down => match &mut app.mode {
@@ -1,12 +1,16 @@
 use anyhow::Result;
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
-use crate::app::{App, DiffViewerFocus};
+use crate::app::{App, AppMode, DiffViewerFocus};
 
 const PATCH_SCROLL_STEP: usize = 1;
 const PATCH_PAGE_STEP: usize = 20;
 
 pub fn handle_diff_viewer_key(app: &mut App, key: KeyCode) -> Result<()> {
+    if app.diff_viewer_is_staging() && handle_staging_key(app, key) {
+        return Ok(());
+    }
+
     match key {
         KeyCode::Esc | KeyCode::Char('q') => {
             app.close_diff_viewer();
@@ -23,6 +27,9 @@ pub fn handle_diff_viewer_key(app: &mut App, key: KeyCode) -> Result<()> {
         KeyCode::Char('i') => {
             app.open_syntax_language_picker_for_selected_diff_file();
         }
+        KeyCode::Char('S') => {
+            app.diff_viewer_toggle_staging();
+        }
         KeyCode::Char('j') | KeyCode::Down => match app.diff_viewer_focus() {
             Some(DiffViewerFocus::FileList) => app.diff_viewer_select_next_file(),
             Some(DiffViewerFocus::Patch) => app.diff_viewer_scroll_patch_down(PATCH_SCROLL_STEP),
@@ -79,6 +86,58 @@ pub fn handle_diff_viewer_key(app: &mut App, key: KeyCode) -> Result<()> {
     Ok(())
 }
 
+/// Keys that act on the staging cursor instead of scrolling. Returns
+/// `false` for keys the regular viewer bindings handle.
+fn handle_staging_key(app: &mut App, key: KeyCode) -> bool {
+    let on_patch = matches!(app.diff_viewer_focus(), Some(DiffViewerFocus::Patch));
+    match key {
+        KeyCode::Char('t') => app.diff_viewer_switch_stage_side(),
+        KeyCode::Char('c') => app.open_commit_dialog(),
+        KeyCode::Char(' ') if on_patch => app.diff_viewer_toggle_mark(),
+        KeyCode::Enter => app.diff_viewer_apply_stage(),
+        KeyCode::Char('j') | KeyCode::Down if on_patch => app.diff_viewer_move_stage_cursor(1),
+        KeyCode::Char('k') | KeyCode::Up if on_patch => app.diff_viewer_move_stage_cursor(-1),
+        KeyCode::PageDown => app.diff_viewer_jump_hunk(true),
+        KeyCode::PageUp => app.diff_viewer_jump_hunk(false),
+        KeyCode::Char('g') if on_patch => app.diff_viewer_stage_cursor_to_end(false),
+        KeyCode::Char('G') if on_patch => app.diff_viewer_stage_cursor_to_end(true),
+        _ => return false,
+    }
+    true
+}
+
+pub fn handle_commit_message_key(app: &mut App, key: KeyEvent) -> Result<()> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    match key.code {
+        KeyCode::Char('q') if ctrl => app.cancel_commit_dialog(),
+        KeyCode::Char('d') if ctrl => app.commit_dialog_draft(),
+        KeyCode::Char('v') if ctrl => {
+            if let AppMode::CommitMessage(state) = &mut app.mode {
+                state.editor.toggle_vim();
+                app.message = Some(if state.editor.vim_mode().is_some() {
+                    "Vim mode enabled".into()
+                } else {
+                    "Vim mode disabled".into()
+                });
+            }
+        }
+        KeyCode::Tab => app.confirm_commit_dialog()?,
+        KeyCode::Esc if matches!(&app.mode, AppMode::CommitMessage(state) if state.editor.vim_mode().is_none()) =>
+        {
+            app.cancel_commit_dialog();
+        }
+        _ => {
+            if let AppMode::CommitMessage(state) = &mut app.mode {
+                let outcome = state.editor.handle_key(key);
+                if outcome.text_changed || outcome.cursor_moved {
+                    state.request_cursor_scroll();
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,7 +163,7 @@ mod tests {
             Box::new(MockWorktreeOps::new()),
         );
         let mut state = DiffViewerState::new(
-            ViewState::new(
+            Some(ViewState::new(
                 "proj".into(),
                 "feat".into(),
                 "sess".into(),
@@ -113,7 +172,7 @@ mod tests {
                 crate::project::SessionKind::Claude,
                 VibeMode::Vibe,
                 false,
-            ),
+            )),
             PathBuf::from("/tmp/project"),
         );
         state.layout = DiffViewerLayout::Unified;
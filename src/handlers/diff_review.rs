@@ -2,7 +2,7 @@ use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::fs;
 
-use crate::app::{App, AppMode};
+use crate::app::{App, AppMode, review_response};
 use crate::claude::ClaudeLauncher;
 
 const PATCH_SCROLL_STEP: usize = 1;
@@ -162,82 +162,55 @@ pub fn handle_diff_review_key(app: &mut App, key: KeyEvent) -> Result<()> {
 }
 
 fn submit_diff_review(app: &mut App, reject: bool, skip: bool) -> Result<()> {
-    let (response_file, proceed_signal, reason, request_id, reply_socket, return_to_view) =
-        match &app.mode {
-            AppMode::DiffReviewPrompt(state) => (
-                state.response_file.clone(),
-                state.proceed_signal.clone(),
-                state.reason.clone(),
-                state.request_id.clone(),
-                state.reply_socket.clone(),
-                state.return_to_view.clone(),
-            ),
-            _ => return Ok(()),
-        };
+    let (
+        response_file,
+        proceed_signal,
+        reason,
+        request_id,
+        reply_socket,
+        return_to_view,
+        workdir,
+        change_id,
+        relative_path,
+    ) = match &app.mode {
+        AppMode::DiffReviewPrompt(state) => (
+            state.response_file.clone(),
+            state.proceed_signal.clone(),
+            state.reason.clone(),
+            state.request_id.clone(),
+            state.reply_socket.clone(),
+            state.return_to_view.clone(),
+            state.workdir.clone(),
+            state.change_id.clone(),
+            state.relative_path.clone(),
+        ),
+        _ => return Ok(()),
+    };
 
+    let reason = Some(reason.as_str()).filter(|reason| !reason.is_empty());
     let response = if skip {
-        serde_json::json!({
-            "type": "review-response",
-            "decision": "cancel",
-            "reason": null,
-            "skip": true,
-            "reject": false,
-        })
+        review_response("cancel", None)
     } else if reject {
-        serde_json::json!({
-            "type": "review-response",
-            "decision": "reject",
-            "reason": if reason.is_empty() { serde_json::Value::Null } else { serde_json::json!(reason) },
-            "skip": false,
-            "reject": true,
-        })
+        review_response("reject", reason)
     } else {
-        serde_json::json!({
-            "type": "review-response",
-            "decision": "proceed",
-            "reason": if reason.is_empty() { serde_json::Value::Null } else { serde_json::json!(reason) },
-            "skip": false,
-            "reject": false,
-        })
+        review_response("proceed", reason)
     };
 
-    let mut responded_over_ipc = false;
-    if let (Some(req), Some(sock)) = (request_id, reply_socket) {
-        if !req.is_empty() && !sock.is_empty() {
-            let mut payload = response.clone();
-            if let Some(obj) = payload.as_object_mut() {
-                obj.insert("request_id".to_string(), serde_json::json!(req));
-            }
-            if crate::ipc::send(
-                std::path::Path::new(&sock),
-                &serde_json::to_string(&payload).unwrap_or_default(),
-            )
-            .is_ok()
-            {
-                responded_over_ipc = true;
-            } else {
-                app.log_warn(
-                    "ipc",
-                    "Failed IPC response for change-reason; falling back to files".to_string(),
-                );
-            }
-        }
-    }
-
-    if !responded_over_ipc {
-        if let Some(parent) = response_file.parent() {
-            let _ = std::fs::create_dir_all(parent);
-        }
-        let _ = std::fs::write(
-            &response_file,
-            serde_json::to_string(&response).unwrap_or_default(),
-        );
+    app.send_review_response(
+        request_id.as_deref(),
+        reply_socket.as_deref(),
+        &response_file,
+        &proceed_signal,
+        &response,
+    );
 
-        if let Some(parent) = proceed_signal.parent() {
-            let _ = std::fs::create_dir_all(parent);
-        }
-        let _ = std::fs::write(&proceed_signal, "");
-    }
+    app.emit_diff_review_decision(
+        &workdir,
+        &change_id,
+        &relative_path,
+        response["decision"].as_str().unwrap_or_default(),
+        response["reason"].as_str().map(str::to_string),
+    );
 
     app.mode = match return_to_view {
         Some(view) => AppMode::Viewing(view),
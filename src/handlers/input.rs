@@ -48,6 +48,11 @@ pub fn handle_paste(app: &mut App, text: &str) -> Result<()> {
                 state.input.push_str(text);
             }
         }
+        AppMode::EditingFeatureTags(_) => {
+            if let AppMode::EditingFeatureTags(state) = &mut app.mode {
+                state.input.push_str(text);
+            }
+        }
         AppMode::Searching(_) => {
             if let AppMode::Searching(state) = &mut app.mode {
                 state.query.push_str(text);
@@ -62,6 +67,22 @@ pub fn handle_paste(app: &mut App, text: &str) -> Result<()> {
                 }
             }
         }
+        AppMode::EditingFeatureNotes(_) => {
+            if let AppMode::EditingFeatureNotes(state) = &mut app.mode {
+                let outcome = state.editor.insert_str(text);
+                if outcome.text_changed {
+                    state.request_cursor_scroll();
+                }
+            }
+        }
+        AppMode::CommitMessage(_) => {
+            if let AppMode::CommitMessage(state) = &mut app.mode {
+                let outcome = state.editor.insert_str(text);
+                if outcome.text_changed {
+                    state.request_cursor_scroll();
+                }
+            }
+        }
         _ => {}
     }
     Ok(())
@@ -0,0 +1,31 @@
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use crate::app::{App, AppMode};
+
+pub fn handle_land_feature_key(app: &mut App, key: KeyCode) -> Result<()> {
+    let landed = match &app.mode {
+        AppMode::LandingFeature(state) => state.landed.is_some(),
+        _ => return Ok(()),
+    };
+
+    if landed {
+        match key {
+            KeyCode::Char('a') => app.finish_land_dialog(true)?,
+            KeyCode::Char('d') => app.finish_land_dialog(false)?,
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => app.cancel_land_dialog(),
+            _ => {}
+        }
+        return Ok(());
+    }
+
+    match key {
+        KeyCode::Esc | KeyCode::Char('q') => app.cancel_land_dialog(),
+        KeyCode::Down | KeyCode::Char('j') => app.land_dialog_select_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.land_dialog_select_prev(),
+        KeyCode::Char('v') => app.open_land_conflicts(),
+        KeyCode::Enter => app.confirm_land_dialog()?,
+        _ => {}
+    }
+    Ok(())
+}
@@ -1,5 +1,6 @@
 mod batch_creation;
 mod browse;
+mod checkpoint;
 mod dialog;
 mod diff;
 mod diff_review;
@@ -8,8 +9,10 @@ mod fork;
 mod harness;
 mod hooks;
 mod input;
+mod land;
 mod mouse;
 mod normal;
+mod overlap;
 mod picker;
 mod search;
 mod view;
@@ -21,26 +24,30 @@ use crate::app::App;
 
 pub use batch_creation::handle_create_batch_features_key;
 pub use browse::handle_browse_path_key;
+pub use checkpoint::handle_checkpoint_dialog_key;
 pub use dialog::{
     handle_create_project_key, handle_debug_log_key, handle_delete_feature_key,
-    handle_delete_project_key, handle_help_key, handle_latest_prompt_key,
-    handle_markdown_viewer_key, handle_rename_feature_key, handle_rename_session_key,
-    handle_session_config_key, handle_steering_prompt_key, handle_theme_picker_key,
+    handle_delete_project_key, handle_feature_notes_key, handle_feature_tags_key, handle_help_key,
+    handle_latest_prompt_key, handle_markdown_viewer_key, handle_rename_feature_key,
+    handle_rename_session_key, handle_session_config_key, handle_steering_prompt_key,
+    handle_theme_picker_key,
 };
-pub use diff::handle_diff_viewer_key;
+pub use diff::{handle_commit_message_key, handle_diff_viewer_key};
 pub use diff_review::handle_diff_review_key;
 pub use feature_creation::handle_create_feature_key;
 pub use fork::handle_fork_feature_key;
 pub use harness::handle_harness_setup_key;
 pub use hooks::{handle_deleting_feature_key, handle_hook_prompt_key, handle_running_hook_key};
 pub use input::handle_paste;
+pub use land::handle_land_feature_key;
 pub use mouse::handle_mouse;
 pub use normal::handle_normal_key;
+pub use overlap::handle_overlap_dialog_key;
 pub use picker::{
     handle_bookmark_picker_key, handle_claude_session_confirm_key,
     handle_claude_session_picker_key, handle_codex_session_confirm_key,
-    handle_codex_session_picker_key, handle_command_picker_key, handle_markdown_file_picker_key,
-    handle_notification_picker_key, handle_opencode_session_confirm_key,
+    handle_codex_session_picker_key, handle_command_picker_key, handle_import_picker_key,
+    handle_markdown_file_picker_key, handle_notification_picker_key, handle_opencode_session_confirm_key,
     handle_opencode_session_picker_key, handle_session_picker_key, handle_session_switcher_key,
     handle_syntax_language_picker_key,
 };
@@ -65,6 +72,8 @@ pub fn handle_key(app: &mut App, key: KeyEvent, visible_rows: u16) -> Result<()>
         AppMode::SessionSwitcher(_) => handle_session_switcher_key(app, key.code),
         AppMode::RenamingSession(_) => handle_rename_session_key(app, key.code),
         AppMode::RenamingFeature(_) => handle_rename_feature_key(app, key.code),
+        AppMode::EditingFeatureTags(_) => handle_feature_tags_key(app, key.code),
+        AppMode::EditingFeatureNotes(_) => handle_feature_notes_key(app, key),
         AppMode::SessionConfig(_) => handle_session_config_key(app, key.code),
         AppMode::ProjectAgentConfig(_) => handle_session_config_key(app, key.code),
         AppMode::CommandPicker(_) => handle_command_picker_key(app, key.code),
@@ -80,13 +89,18 @@ pub fn handle_key(app: &mut App, key: KeyEvent, visible_rows: u16) -> Result<()>
         AppMode::ConfirmingCodexSession { .. } => handle_codex_session_confirm_key(app, key.code),
         AppMode::SessionPicker(_) => handle_session_picker_key(app, key.code),
         AppMode::BookmarkPicker(_) => handle_bookmark_picker_key(app, key.code),
+        AppMode::ImportPicker(_) => handle_import_picker_key(app, key.code),
         AppMode::DiffViewer(_) => handle_diff_viewer_key(app, key.code),
+        AppMode::CommitMessage(_) => handle_commit_message_key(app, key),
         AppMode::DiffReviewPrompt(_) => handle_diff_review_key(app, key),
         AppMode::RunningHook(_) => handle_running_hook_key(app, key.code),
         AppMode::DeletingFeatureInProgress(_) => handle_deleting_feature_key(app, key.code),
         AppMode::HookPrompt(_) => handle_hook_prompt_key(app, key.code),
         AppMode::LatestPrompt(_) => handle_latest_prompt_key(app, key.code),
         AppMode::ForkingFeature(_) => handle_fork_feature_key(app, key.code),
+        AppMode::LandingFeature(_) => handle_land_feature_key(app, key.code),
+        AppMode::ReviewingOverlaps(_) => handle_overlap_dialog_key(app, key.code),
+        AppMode::ReviewingCheckpoints(_) => handle_checkpoint_dialog_key(app, key.code),
         AppMode::ThemePicker(_) => handle_theme_picker_key(app, key.code),
         AppMode::SyntaxLanguagePicker(_) => handle_syntax_language_picker_key(app, key.code),
         AppMode::DebugLog(_) => handle_debug_log_key(app, key.code),
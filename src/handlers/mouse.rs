@@ -245,10 +245,17 @@ fn handle_click(
             | AppMode::ConfirmingCodexSession { .. }
             | AppMode::SessionPicker(_)
             | AppMode::BookmarkPicker(_)
+            | AppMode::ImportPicker(_)
+            | AppMode::LandingFeature(_)
+            | AppMode::ReviewingOverlaps(_)
+            | AppMode::ReviewingCheckpoints(_)
             | AppMode::DiffViewer(_)
+            | AppMode::CommitMessage(_)
             | AppMode::SessionSwitcher(_)
             | AppMode::RenamingSession(_)
             | AppMode::RenamingFeature(_)
+            | AppMode::EditingFeatureTags(_)
+            | AppMode::EditingFeatureNotes(_)
             | AppMode::NotificationPicker(_, _)
             | AppMode::DiffReviewPrompt(_)
             | AppMode::RunningHook(_)
@@ -284,6 +291,7 @@ fn handle_click(
             } else {
                 app.selection = match clicked_item {
                     VisibleItem::Project(pi) => Selection::Project(pi),
+                    VisibleItem::ArchivedFeatures(pi) => Selection::ArchivedFeatures(pi),
                     VisibleItem::Feature(pi, fi) => Selection::Feature(pi, fi),
                     VisibleItem::Session(pi, fi, si) => Selection::Session(pi, fi, si),
                 };
@@ -302,6 +310,10 @@ fn handle_double_click(app: &mut App, item: &VisibleItem, col: u16) -> Result<()
                 project.collapsed = !project.collapsed;
             }
         }
+        VisibleItem::ArchivedFeatures(pi) => {
+            app.selection = Selection::ArchivedFeatures(*pi);
+            app.toggle_collapse();
+        }
         VisibleItem::Feature(_pi, _fi) => {
             if col < 10 {
                 if let Some(project) = app.store.projects.get_mut(*_pi)
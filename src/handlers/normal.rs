@@ -63,13 +63,16 @@ pub fn handle_normal_key(app: &mut App, key: KeyEvent) -> Result<()> {
         KeyCode::Char('O') => {
             app.open_settings_project()?;
         }
+        KeyCode::Char('I') => {
+            app.open_import_picker();
+        }
         KeyCode::Char('n') => {
             if app.selected_project().is_some() {
                 app.start_create_feature();
             }
         }
         KeyCode::Enter => match &app.selection {
-            Selection::Project(_) => {
+            Selection::Project(_) | Selection::ArchivedFeatures(_) => {
                 app.toggle_collapse();
             }
             Selection::Feature(_, _) => {
@@ -113,6 +116,7 @@ pub fn handle_normal_key(app: &mut App, key: KeyEvent) -> Result<()> {
             Selection::Session(_, _, _) => {
                 app.remove_session()?;
             }
+            Selection::ArchivedFeatures(_) => {}
         },
         KeyCode::Char('s') => {
             app.open_session_picker()?;
@@ -160,7 +164,7 @@ pub fn handle_normal_key(app: &mut App, key: KeyEvent) -> Result<()> {
             }
         }
         KeyCode::Char('u') => match &app.selection {
-            Selection::Project(_) => {
+            Selection::Project(_) | Selection::ArchivedFeatures(_) => {
                 app.start_project_agent_config()?;
             }
             Selection::Feature(_, _) | Selection::Session(_, _, _) => {
@@ -192,6 +196,11 @@ pub fn handle_normal_key(app: &mut App, key: KeyEvent) -> Result<()> {
                     app.toggle_collapse();
                 }
             }
+            Selection::ArchivedFeatures(pi) => {
+                if app.is_archive_expanded(*pi) {
+                    app.toggle_collapse();
+                }
+            }
             Selection::Session(_, _, _) => {
                 app.toggle_collapse();
             }
@@ -215,6 +224,11 @@ pub fn handle_normal_key(app: &mut App, key: KeyEvent) -> Result<()> {
                     app.toggle_collapse();
                 }
             }
+            Selection::ArchivedFeatures(pi) => {
+                if !app.is_archive_expanded(*pi) {
+                    app.toggle_collapse();
+                }
+            }
             Selection::Session(_, _, _) => {}
         },
         KeyCode::Char('?') => {
@@ -263,6 +277,53 @@ pub fn handle_normal_key(app: &mut App, key: KeyEvent) -> Result<()> {
                 app.start_fork_feature();
             }
         }
+        KeyCode::Char('t') => {
+            if matches!(app.selection, Selection::Feature(_, _)) {
+                app.start_edit_feature_tags();
+            }
+        }
+        KeyCode::Char('e') => {
+            if matches!(app.selection, Selection::Feature(_, _)) {
+                app.start_edit_feature_notes();
+            }
+        }
+        KeyCode::Char('X') => {
+            if matches!(app.selection, Selection::Feature(_, _)) {
+                app.toggle_archive_selected_feature()?;
+            }
+        }
+        KeyCode::Char('L') => {
+            if matches!(
+                app.selection,
+                Selection::Feature(_, _) | Selection::Session(_, _, _)
+            ) {
+                app.open_land_dialog();
+            }
+        }
+        KeyCode::Char('C') => {
+            if matches!(
+                app.selection,
+                Selection::Feature(_, _) | Selection::Session(_, _, _)
+            ) {
+                app.open_overlap_dialog();
+            }
+        }
+        KeyCode::Char('K') => {
+            if matches!(
+                app.selection,
+                Selection::Feature(_, _) | Selection::Session(_, _, _)
+            ) {
+                app.open_checkpoint_dialog();
+            }
+        }
+        KeyCode::Char('U') => {
+            if matches!(
+                app.selection,
+                Selection::Feature(_, _) | Selection::Session(_, _, _)
+            ) {
+                app.restack_selected_feature()?;
+            }
+        }
         KeyCode::Char('T') => {
             app.start_theme_picker();
         }
@@ -270,8 +331,7 @@ pub fn handle_normal_key(app: &mut App, key: KeyEvent) -> Result<()> {
             app.start_syntax_language_picker();
         }
         KeyCode::Char('f') => {
-            app.session_filter = app.session_filter.next();
-            app.message = Some(format!("Filter: {}", app.session_filter.display_name()));
+            app.cycle_session_filter();
         }
         KeyCode::Char('Z') => {
             app.trigger_summary_for_selected()?;
@@ -304,6 +364,14 @@ fn default_key_for_action(action: &str) -> Option<char> {
         "session_config" => Some('u'),
         "fork_feature" => Some('F'),
         "mark_ready" => Some('y'),
+        "restack" => Some('U'),
+        "tags" => Some('t'),
+        "notes" => Some('e'),
+        "archive" => Some('X'),
+        "import" => Some('I'),
+        "land" => Some('L'),
+        "conflicts" => Some('C'),
+        "checkpoints" => Some('K'),
         _ => None,
     }
 }
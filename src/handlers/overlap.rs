@@ -0,0 +1,15 @@
+use anyhow::Result;
+use crossterm::event::KeyCode;
+
+use crate::app::App;
+
+pub fn handle_overlap_dialog_key(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q') => app.cancel_overlap_dialog(),
+        KeyCode::Down | KeyCode::Char('j') => app.overlap_dialog_select_next(),
+        KeyCode::Up | KeyCode::Char('k') => app.overlap_dialog_select_prev(),
+        KeyCode::Enter | KeyCode::Char('v') => app.open_overlap_diff(),
+        _ => {}
+    }
+    Ok(())
+}
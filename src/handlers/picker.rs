@@ -413,6 +413,12 @@ mod tests {
             summary: None,
             summary_updated_at: None,
             nickname: None,
+            parent_id: None,
+            tags: Vec::new(),
+            notes: None,
+            archived_at: None,
+            archived_prompt: None,
+            repos: Vec::new(),
         };
         let store = ProjectStore {
             version: 5,
@@ -856,13 +862,10 @@ pub fn handle_session_picker_key(app: &mut App, key: KeyCode) -> Result<()> {
                         app.mode = AppMode::SessionPicker(state);
                         return Ok(());
                     }
-                    match app.add_builtin_session(state.pi, state.fi, builtin.kind.clone()) {
-                        Ok(()) => {
-                            app.message = Some(format!("Added '{}'", builtin.label));
-                        }
-                        Err(e) => {
-                            app.message = Some(format!("Error: {}", e));
-                        }
+                    if let Err(e) =
+                        app.add_builtin_session_for_picker(state.pi, state.fi, builtin.kind.clone())
+                    {
+                        app.message = Some(format!("Error: {}", e));
                     }
                 } else {
                     let custom_idx = state.selected - builtin_len;
@@ -923,6 +926,44 @@ pub fn handle_session_picker_key(app: &mut App, key: KeyCode) -> Result<()> {
     Ok(())
 }
 
+pub fn handle_import_picker_key(app: &mut App, key: KeyCode) -> Result<()> {
+    match key {
+        KeyCode::Esc | KeyCode::Char('q') => {
+            app.cancel_import_picker();
+        }
+        KeyCode::Down | KeyCode::Char('j') => {
+            if let AppMode::ImportPicker(ref mut state) = app.mode {
+                let len = state.candidates.len();
+                if len > 0 {
+                    state.selected = (state.selected + 1) % len;
+                }
+            }
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            if let AppMode::ImportPicker(ref mut state) = app.mode {
+                let len = state.candidates.len();
+                if len > 0 {
+                    state.selected = if state.selected == 0 {
+                        len - 1
+                    } else {
+                        state.selected - 1
+                    };
+                }
+            }
+        }
+        KeyCode::Char(' ') => {
+            app.toggle_import_candidate();
+        }
+        KeyCode::Enter => {
+            if let Err(e) = app.import_checked_candidates() {
+                app.message = Some(format!("Error: {}", e));
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
 pub fn handle_bookmark_picker_key(app: &mut App, key: KeyCode) -> Result<()> {
     match key {
         KeyCode::Esc | KeyCode::Char('q') => {
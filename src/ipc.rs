@@ -1,6 +1,8 @@
 use anyhow::{Context, Result};
+use std::os::unix::net::UnixStream;
 use std::path::{Path, PathBuf};
 use std::sync::mpsc::{Receiver, channel};
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
 use crate::debug::{LogLevel, log_to_file};
@@ -8,24 +10,199 @@ use crate::debug::{LogLevel, log_to_file};
 /// Path to the AMF IPC socket.
 /// Matches the state directory used by the debug log.
 pub fn socket_path() -> PathBuf {
-    dirs::state_dir()
-        .unwrap_or_else(|| PathBuf::from("/tmp"))
-        .join("amf")
-        .join("amf.sock")
+    socket_path_in(dirs::state_dir())
 }
 
+/// Socket path under `state_dir`, or under a per-user `/tmp` directory
+/// when there is none (always on macOS), so users do not share it.
+fn socket_path_in(state_dir: Option<PathBuf>) -> PathBuf {
+    match state_dir {
+        Some(dir) => dir.join("amf").join("amf.sock"),
+        None => PathBuf::from(format!("/tmp/amf-{}", current_uid())).join("amf.sock"),
+    }
+}
+
+/// Message type a client sends to keep its connection open and receive
+/// broadcast events instead of sending one-shot messages.
+pub const SUBSCRIBE_TYPE: &str = "subscribe";
+
+/// Message type a TUI sends to ask a running `amf daemon` to save its
+/// state, release the socket, and exit.
+pub const HANDOFF_TYPE: &str = "daemon-handoff";
+
+/// How long a broadcast may block on a slow subscriber before it is dropped.
+const SUBSCRIBER_WRITE_TIMEOUT: Duration = Duration::from_millis(100);
+
 /// Directory used for temporary reply sockets for `notify-wait`.
+/// Keyed by UID because the temp dir is usually shared between users.
 pub fn reply_dir() -> PathBuf {
-    std::env::temp_dir().join("amf-ipc-reply")
+    std::env::temp_dir().join(format!("amf-ipc-reply-{}", current_uid()))
+}
+
+fn current_uid() -> u32 {
+    unsafe { libc::geteuid() }
+}
+
+/// UID of the process on the other end of a Unix socket.
+#[cfg(target_os = "linux")]
+fn peer_uid(stream: &UnixStream) -> std::io::Result<u32> {
+    use std::os::unix::io::AsRawFd;
+
+    let mut cred: libc::ucred = unsafe { std::mem::zeroed() };
+    let mut len = std::mem::size_of::<libc::ucred>() as libc::socklen_t;
+    let rc = unsafe {
+        libc::getsockopt(
+            stream.as_raw_fd(),
+            libc::SOL_SOCKET,
+            libc::SO_PEERCRED,
+            &mut cred as *mut libc::ucred as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    if rc == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(cred.uid)
+}
+
+/// UID of the process on the other end of a Unix socket.
+#[cfg(not(target_os = "linux"))]
+fn peer_uid(stream: &UnixStream) -> std::io::Result<u32> {
+    use std::os::unix::io::AsRawFd;
+
+    let mut uid: libc::uid_t = 0;
+    let mut gid: libc::gid_t = 0;
+    if unsafe { libc::getpeereid(stream.as_raw_fd(), &mut uid, &mut gid) } == -1 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(uid)
+}
+
+/// Accept only connections from processes running as the current user.
+/// Returns the reason when the peer is rejected.
+fn check_peer(stream: &UnixStream) -> std::result::Result<(), String> {
+    match peer_uid(stream) {
+        Ok(uid) if uid == current_uid() => Ok(()),
+        Ok(uid) => Err(format!("Rejected IPC connection from uid {uid}")),
+        Err(e) => Err(format!(
+            "Rejected IPC connection with unreadable peer credentials: {e}"
+        )),
+    }
+}
+
+/// Create `dir` if needed and make sure only the current user can use it.
+/// Symlinks and directories owned by someone else are refused, which
+/// matters for the shared `/tmp` fallbacks.
+fn ensure_private_dir(dir: &Path) -> Result<()> {
+    use std::os::unix::fs::{DirBuilderExt, MetadataExt, PermissionsExt};
+
+    match std::fs::symlink_metadata(dir) {
+        Ok(meta) => {
+            if meta.file_type().is_symlink() {
+                anyhow::bail!("Refusing to use symlinked IPC directory {}", dir.display());
+            }
+            if !meta.is_dir() {
+                anyhow::bail!("IPC path {} is not a directory", dir.display());
+            }
+            if meta.uid() != current_uid() {
+                anyhow::bail!("IPC directory {} is owned by another user", dir.display());
+            }
+            if meta.mode() & 0o077 != 0 {
+                std::fs::set_permissions(dir, std::fs::Permissions::from_mode(0o700))
+                    .with_context(|| format!("Failed to restrict {}", dir.display()))?;
+            }
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => std::fs::DirBuilder::new()
+            .recursive(true)
+            .mode(0o700)
+            .create(dir)
+            .with_context(|| format!("Failed to create {}", dir.display())),
+        Err(e) => {
+            Err(e).with_context(|| format!("Failed to inspect IPC directory {}", dir.display()))
+        }
+    }
+}
+
+/// Bind a listener at `path` that only the current user can reach. A
+/// stale socket is replaced; a symlink is refused rather than followed.
+fn bind_private(path: &Path) -> Result<std::os::unix::net::UnixListener> {
+    use std::os::unix::fs::PermissionsExt;
+
+    if let Some(parent) = path.parent() {
+        ensure_private_dir(parent)?;
+    }
+    if std::fs::symlink_metadata(path).is_ok_and(|meta| meta.file_type().is_symlink()) {
+        anyhow::bail!("Refusing to use symlinked IPC socket {}", path.display());
+    }
+    let _ = std::fs::remove_file(path);
+
+    let listener = std::os::unix::net::UnixListener::bind(path)
+        .with_context(|| format!("Failed to bind IPC socket at {}", path.display()))?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))
+        .with_context(|| format!("Failed to restrict {}", path.display()))?;
+    Ok(listener)
+}
+
+/// Connect to an AMF socket, refusing to follow a symlink planted in
+/// its place.
+fn connect(path: &Path) -> Result<UnixStream> {
+    if std::fs::symlink_metadata(path).is_ok_and(|meta| meta.file_type().is_symlink()) {
+        anyhow::bail!("Refusing to use symlinked IPC socket {}", path.display());
+    }
+    UnixStream::connect(path).with_context(|| {
+        format!(
+            "AMF socket not found at {} — is amf running?",
+            path.display()
+        )
+    })
 }
 
 /// Owns the IPC socket lifetime. Removes the socket file on drop
 /// so the filesystem is always cleaned up on normal exit or panic.
 pub struct IpcGuard {
     pub rx: Receiver<serde_json::Value>,
+    /// Why each refused connection was dropped, for the debug log.
+    pub rejected: Receiver<String>,
+    subscribers: Arc<Mutex<Vec<UnixStream>>>,
     path: PathBuf,
 }
 
+impl IpcGuard {
+    pub fn has_subscribers(&self) -> bool {
+        self.subscribers
+            .lock()
+            .map(|subs| !subs.is_empty())
+            .unwrap_or(false)
+    }
+
+    /// Write `event` as one JSON line to every subscriber. Subscribers
+    /// whose connection has closed or stalled are dropped.
+    pub fn broadcast(&self, event: &serde_json::Value) {
+        use std::io::Write;
+
+        let Ok(line) = serde_json::to_string(event) else {
+            return;
+        };
+        let Ok(mut subs) = self.subscribers.lock() else {
+            return;
+        };
+        let before = subs.len();
+        subs.retain_mut(|stream| {
+            writeln!(stream, "{line}")
+                .and_then(|_| stream.flush())
+                .is_ok()
+        });
+        if subs.len() < before {
+            log_to_file(
+                LogLevel::Debug,
+                "ipc",
+                &format!("Dropped {} event subscriber(s)", before - subs.len()),
+            );
+        }
+    }
+}
+
 impl Drop for IpcGuard {
     fn drop(&mut self) {
         let _ = std::fs::remove_file(&self.path);
@@ -46,31 +223,40 @@ impl Drop for IpcGuard {
 /// The server thread exits when the listener errors or when the
 /// receiver side is dropped (channel disconnected).
 pub fn start(path: &Path) -> Result<IpcGuard> {
-    // Remove stale socket from a previous run.
-    let _ = std::fs::remove_file(path);
-
-    if let Some(parent) = path.parent() {
-        std::fs::create_dir_all(parent).context("Failed to create IPC socket directory")?;
-    }
-
-    let listener =
-        std::os::unix::net::UnixListener::bind(path).context("Failed to bind IPC socket")?;
+    let listener = bind_private(path)?;
 
     let (tx, rx) = channel::<serde_json::Value>();
+    let (rejected_tx, rejected) = channel::<String>();
+    let subscribers = Arc::new(Mutex::new(Vec::new()));
 
     let path_buf = path.to_path_buf();
+    let thread_subscribers = Arc::clone(&subscribers);
     std::thread::spawn(move || {
         for stream in listener.incoming() {
             match stream {
                 Ok(s) => {
+                    if let Err(reason) = check_peer(&s) {
+                        let _ = rejected_tx.send(reason);
+                        continue;
+                    }
                     log_to_file(LogLevel::Debug, "ipc", "Accepted connection");
                     let tx = tx.clone();
+                    let subscribers = Arc::clone(&thread_subscribers);
                     std::thread::spawn(move || {
                         use std::io::BufRead;
+                        let writer = s.try_clone().ok();
                         for line in std::io::BufReader::new(s).lines() {
                             match line {
                                 Ok(l) if !l.trim().is_empty() => {
                                     match serde_json::from_str::<serde_json::Value>(&l) {
+                                        Ok(v)
+                                            if v.get("type").and_then(|t| t.as_str())
+                                                == Some(SUBSCRIBE_TYPE) =>
+                                        {
+                                            if let Some(w) = writer.as_ref() {
+                                                add_subscriber(&subscribers, w);
+                                            }
+                                        }
                                         Ok(v) => {
                                             // Channel closed means
                                             // App is shutting down.
@@ -121,10 +307,30 @@ pub fn start(path: &Path) -> Result<IpcGuard> {
 
     Ok(IpcGuard {
         rx,
+        rejected,
+        subscribers,
         path: path.to_path_buf(),
     })
 }
 
+fn add_subscriber(subscribers: &Mutex<Vec<UnixStream>>, stream: &UnixStream) {
+    use std::io::Write;
+
+    let Ok(mut stream) = stream.try_clone() else {
+        return;
+    };
+    let _ = stream.set_write_timeout(Some(SUBSCRIBER_WRITE_TIMEOUT));
+    // Hold the lock across the acknowledgement so a client that has read
+    // it is guaranteed to be registered.
+    let Ok(mut subs) = subscribers.lock() else {
+        return;
+    };
+    if writeln!(stream, r#"{{"type":"subscribed"}}"#).is_ok() {
+        subs.push(stream);
+        log_to_file(LogLevel::Debug, "ipc", "Added event subscriber");
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -135,6 +341,18 @@ mod tests {
         std::thread::sleep(Duration::from_millis(ms));
     }
 
+    #[test]
+    fn socket_path_falls_back_to_a_per_user_tmp_dir() {
+        assert_eq!(
+            socket_path_in(Some(PathBuf::from("/home/me/.local/state"))),
+            PathBuf::from("/home/me/.local/state/amf/amf.sock")
+        );
+        assert_eq!(
+            socket_path_in(None),
+            PathBuf::from(format!("/tmp/amf-{}/amf.sock", current_uid()))
+        );
+    }
+
     #[test]
     fn send_and_receive() {
         let dir = TempDir::new().unwrap();
@@ -163,6 +381,18 @@ mod tests {
         assert!(!path.exists(), "socket should be removed after drop");
     }
 
+    #[test]
+    fn is_live_only_while_listening() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("amf.sock");
+        assert!(!is_live(&path));
+        {
+            let _guard = start(&path).unwrap();
+            assert!(is_live(&path));
+        }
+        assert!(!is_live(&path));
+    }
+
     #[test]
     fn send_fails_when_no_server() {
         let path = std::path::PathBuf::from("/tmp/amf-ipc-test-no-server.sock");
@@ -243,6 +473,56 @@ mod tests {
         assert_eq!(msg["session_id"], "xyz");
     }
 
+    #[test]
+    fn subscribers_receive_broadcast_events() {
+        use std::io::BufRead;
+
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("amf.sock");
+        let guard = start(&path).unwrap();
+        wait(10);
+
+        let mut reader = subscribe(&path).unwrap();
+        let mut line = String::new();
+        reader.read_line(&mut line).unwrap();
+        assert_eq!(line.trim(), r#"{"type":"subscribed"}"#);
+        assert!(guard.has_subscribers());
+        assert!(
+            guard.rx.try_recv().is_err(),
+            "subscribe should not be forwarded as a message"
+        );
+
+        guard.broadcast(&serde_json::json!({"type": "automation-event", "event": "x"}));
+
+        line.clear();
+        reader.read_line(&mut line).unwrap();
+        let event: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(event["event"], "x");
+    }
+
+    #[test]
+    fn closed_subscribers_are_dropped_on_broadcast() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("amf.sock");
+        let guard = start(&path).unwrap();
+        wait(10);
+
+        {
+            use std::io::BufRead;
+            let mut reader = subscribe(&path).unwrap();
+            let mut ack = String::new();
+            reader.read_line(&mut ack).unwrap();
+        }
+        assert!(guard.has_subscribers());
+
+        // The first write may land in the kernel buffer; the next one
+        // fails once the peer is gone.
+        for _ in 0..3 {
+            guard.broadcast(&serde_json::json!({"type": "automation-event"}));
+        }
+        assert!(!guard.has_subscribers());
+    }
+
     #[test]
     fn empty_payload_does_not_send() {
         // amf notify exits early for empty stdin; mirror that here.
@@ -258,6 +538,55 @@ mod tests {
         wait(50);
         assert!(guard.rx.try_recv().is_err());
     }
+
+    #[test]
+    fn socket_and_directory_are_private() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = TempDir::new().unwrap();
+        let socket_dir = dir.path().join("amf");
+        std::fs::create_dir(&socket_dir).unwrap();
+        std::fs::set_permissions(&socket_dir, std::fs::Permissions::from_mode(0o755)).unwrap();
+        let path = socket_dir.join("amf.sock");
+        let _guard = start(&path).unwrap();
+
+        let dir_mode = std::fs::metadata(&socket_dir).unwrap().permissions().mode();
+        let socket_mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(dir_mode & 0o777, 0o700);
+        assert_eq!(socket_mode & 0o777, 0o600);
+    }
+
+    #[test]
+    fn symlinked_socket_paths_are_refused() {
+        let dir = TempDir::new().unwrap();
+        let target = dir.path().join("elsewhere.sock");
+        let _guard = start(&target).unwrap();
+        let link = dir.path().join("amf.sock");
+        std::os::unix::fs::symlink(&target, &link).unwrap();
+
+        assert!(start(&link).is_err());
+        assert!(send(&link, r#"{"type":"stop"}"#).is_err());
+        assert!(!is_live(&link));
+        assert!(target.exists(), "the symlink target must not be removed");
+    }
+
+    #[test]
+    fn symlinked_socket_directories_are_refused() {
+        let dir = TempDir::new().unwrap();
+        let real = dir.path().join("real");
+        std::fs::create_dir(&real).unwrap();
+        let link = dir.path().join("amf");
+        std::os::unix::fs::symlink(&real, &link).unwrap();
+
+        assert!(start(&link.join("amf.sock")).is_err());
+    }
+
+    #[test]
+    fn same_user_peers_pass_the_credential_check() {
+        let (a, b) = UnixStream::pair().unwrap();
+        assert_eq!(peer_uid(&a).unwrap(), current_uid());
+        assert!(check_peer(&b).is_ok());
+    }
 }
 
 /// Connect to a running AMF instance and send a single
@@ -271,12 +600,7 @@ pub fn send(path: &Path, payload: &str) -> Result<()> {
     if payload.is_empty() {
         anyhow::bail!("Payload is empty");
     }
-    let mut stream = std::os::unix::net::UnixStream::connect(path).with_context(|| {
-        format!(
-            "AMF socket not found at {} — is amf running?",
-            path.display()
-        )
-    })?;
+    let mut stream = connect(path)?;
     writeln!(stream, "{payload}").context("Failed to write to AMF socket")?;
     stream.flush().context("Failed to flush AMF socket")?;
     Ok(())
@@ -302,13 +626,7 @@ pub fn send_wait(path: &Path, payload: &str, timeout: Duration) -> Result<serde_
         .unwrap_or_else(|| uuid::Uuid::new_v4().to_string());
 
     let reply_path = reply_dir().join(format!("{request_id}.sock"));
-    if let Some(parent) = reply_path.parent() {
-        std::fs::create_dir_all(parent).context("Failed to create reply socket directory")?;
-    }
-    let _ = std::fs::remove_file(&reply_path);
-
-    let listener = std::os::unix::net::UnixListener::bind(&reply_path)
-        .with_context(|| format!("Failed to bind reply socket at {}", reply_path.display()))?;
+    let listener = bind_private(&reply_path)?;
     listener
         .set_nonblocking(true)
         .context("Failed to set reply listener nonblocking")?;
@@ -330,6 +648,10 @@ pub fn send_wait(path: &Path, payload: &str, timeout: Duration) -> Result<serde_
     let result = loop {
         match listener.accept() {
             Ok((stream, _)) => {
+                if let Err(reason) = check_peer(&stream) {
+                    log_to_file(LogLevel::Warn, "ipc", &reason);
+                    continue;
+                }
                 let mut reader = std::io::BufReader::new(stream);
                 let mut line = String::new();
                 reader
@@ -354,3 +676,22 @@ pub fn send_wait(path: &Path, payload: &str, timeout: Duration) -> Result<serde_
     let _ = std::fs::remove_file(&reply_path);
     result
 }
+
+/// Whether something is accepting connections on `path`. A socket file
+/// left behind by a crashed instance is not live.
+pub fn is_live(path: &Path) -> bool {
+    connect(path).is_ok()
+}
+
+/// Open a long-lived subscription to a running AMF instance. The returned
+/// reader yields one JSON event per line, starting with a
+/// `{"type":"subscribed"}` acknowledgement, until AMF exits.
+pub fn subscribe(path: &Path) -> Result<std::io::BufReader<UnixStream>> {
+    use std::io::Write;
+
+    let mut stream = connect(path)?;
+    writeln!(stream, r#"{{"type":"{SUBSCRIBE_TYPE}"}}"#)
+        .context("Failed to write to AMF socket")?;
+    stream.flush().context("Failed to flush AMF socket")?;
+    Ok(std::io::BufReader::new(stream))
+}
@@ -0,0 +1,315 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result, bail};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use tempfile::TempDir;
+
+use crate::diff::{DiffFile, git_output, git_show_file, git_succeeds};
+use crate::worktree::WorktreeManager;
+
+/// How a feature branch is brought into its base branch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum LandStrategy {
+    #[default]
+    Merge,
+    Squash,
+    Rebase,
+}
+
+impl LandStrategy {
+    pub const ALL: [LandStrategy; 3] = [
+        LandStrategy::Merge,
+        LandStrategy::Squash,
+        LandStrategy::Rebase,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            LandStrategy::Merge => "Merge commit",
+            LandStrategy::Squash => "Squash into one commit",
+            LandStrategy::Rebase => "Rebase, then fast-forward",
+        }
+    }
+}
+
+/// What landing a feature would do, worked out with a trial merge.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LandPreview {
+    /// The feature branch.
+    pub branch: String,
+    /// Local branch the feature lands on.
+    pub target: String,
+    /// Ref `diff::resolve_base_ref` picked, e.g. `origin/main`.
+    pub base_ref: String,
+    /// Commits on the feature branch that the target does not have.
+    pub ahead: usize,
+    /// Files the trial merge could not resolve, diffed against the
+    /// target's version so the conflict markers show up as additions.
+    pub conflicts: Vec<DiffFile>,
+}
+
+impl LandPreview {
+    pub fn conflict_paths(&self) -> Vec<String> {
+        self.conflicts
+            .iter()
+            .map(|file| file.path.clone())
+            .collect()
+    }
+}
+
+/// Result of a successful land.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Landed {
+    pub target: String,
+    pub commit: String,
+}
+
+/// Resolve where `workdir`'s branch would land and trial-merge it there
+/// in a scratch worktree, leaving every real worktree untouched.
+pub fn preview(workdir: &Path, parent_branch: Option<&str>) -> Result<LandPreview> {
+    let base = crate::diff::resolve_base_ref(workdir, parent_branch)?;
+    let target = local_target(workdir, &base.base_ref)?;
+    let range = format!("{}..{}", target, base.branch);
+    let ahead = git_output(workdir, &["rev-list", "--count", &range])?
+        .parse()
+        .unwrap_or(0);
+
+    let scratch = Scratch::add(workdir, &target)?;
+    let merged = scratch.git_succeeds(&["merge", "--no-commit", "--no-ff", &base.branch])?;
+    let conflicts = if merged {
+        Vec::new()
+    } else {
+        let conflicts = conflicted_files(scratch.path())?;
+        let _ = scratch.git_succeeds(&["merge", "--abort"]);
+        conflicts
+    };
+
+    Ok(LandPreview {
+        branch: base.branch,
+        target,
+        base_ref: base.base_ref,
+        ahead,
+        conflicts,
+    })
+}
+
+/// Land `workdir`'s branch on the target found by `preview`. Merge and
+/// squash commit in a scratch worktree; rebase rewrites the feature
+/// branch in `workdir` itself. Either way the target only ever moves
+/// forward, through whichever worktree has it checked out.
+pub fn land(workdir: &Path, parent_branch: Option<&str>, strategy: LandStrategy) -> Result<Landed> {
+    if WorktreeManager::has_tracked_changes(workdir)? {
+        bail!("{} has uncommitted changes", workdir.display());
+    }
+    let preview = preview(workdir, parent_branch)?;
+    if preview.ahead == 0 {
+        bail!(
+            "'{}' has no commits that '{}' does not already have",
+            preview.branch,
+            preview.target
+        );
+    }
+    if !preview.conflicts.is_empty() {
+        bail!(
+            "'{}' conflicts with '{}' in {}",
+            preview.branch,
+            preview.target,
+            preview.conflict_paths().join(", ")
+        );
+    }
+
+    let commit = match strategy {
+        LandStrategy::Merge => {
+            let scratch = Scratch::add(workdir, &preview.target)?;
+            let message = format!("Merge branch '{}' into {}", preview.branch, preview.target);
+            git_output(
+                scratch.path(),
+                &["merge", "--no-ff", "-m", &message, &preview.branch],
+            )?;
+            git_output(scratch.path(), &["rev-parse", "HEAD"])?
+        }
+        LandStrategy::Squash => {
+            let scratch = Scratch::add(workdir, &preview.target)?;
+            git_output(scratch.path(), &["merge", "--squash", &preview.branch])?;
+            git_output(scratch.path(), &["commit", "--no-edit"])?;
+            git_output(scratch.path(), &["rev-parse", "HEAD"])?
+        }
+        LandStrategy::Rebase => {
+            WorktreeManager::rebase(workdir, &preview.target)?;
+            git_output(workdir, &["rev-parse", "HEAD"])?
+        }
+    };
+
+    fast_forward(workdir, &preview.target, &commit)?;
+    Ok(Landed {
+        target: preview.target,
+        commit,
+    })
+}
+
+/// Local branch behind `base_ref`: remote-tracking refs land on the
+/// local branch of the same name.
+fn local_target(workdir: &Path, base_ref: &str) -> Result<String> {
+    let candidates = [
+        Some(base_ref),
+        base_ref.split_once('/').map(|(_, branch)| branch),
+    ];
+    for candidate in candidates.into_iter().flatten() {
+        let full = format!("refs/heads/{}", candidate);
+        if git_succeeds(workdir, &["show-ref", "--verify", "--quiet", &full])? {
+            return Ok(candidate.to_string());
+        }
+    }
+    bail!("No local branch to land on for '{}'", base_ref)
+}
+
+/// Move `target` to `commit`, which must be a descendant of it. When the
+/// target is checked out somewhere its worktree is fast-forwarded too.
+fn fast_forward(workdir: &Path, target: &str, commit: &str) -> Result<()> {
+    let checked_out = WorktreeManager::list(workdir)?
+        .into_iter()
+        .find(|wt| wt.branch.as_deref() == Some(target));
+    if let Some(wt) = checked_out {
+        git_output(&wt.path, &["merge", "--ff-only", commit])?;
+        return Ok(());
+    }
+
+    let full = format!("refs/heads/{}", target);
+    let old = git_output(workdir, &["rev-parse", &full])?;
+    let range = format!("{}..{}", commit, old);
+    if git_output(workdir, &["rev-list", "--count", &range])? != "0" {
+        bail!("'{}' moved while landing; try again", target);
+    }
+    git_output(workdir, &["update-ref", &full, commit, &old])?;
+    Ok(())
+}
+
+fn conflicted_files(scratch: &Path) -> Result<Vec<DiffFile>> {
+    let names = git_output(scratch, &["diff", "--name-only", "--diff-filter=U"])?;
+    let ours = TempDir::new().context("Failed to create temp dir")?;
+    let mut files = Vec::new();
+    for (index, path) in names.lines().filter(|line| !line.is_empty()).enumerate() {
+        let original = ours.path().join(index.to_string());
+        let content = git_show_file(scratch, "HEAD", path)?.unwrap_or_default();
+        std::fs::write(&original, content)?;
+        files.push(crate::diff::load_review_file(
+            &original,
+            &scratch.join(path),
+            path,
+        )?);
+    }
+    Ok(files)
+}
+
+/// Detached worktree at `target` in a temp dir, removed on drop.
+struct Scratch {
+    repo: PathBuf,
+    path: PathBuf,
+    _dir: TempDir,
+}
+
+impl Scratch {
+    fn add(repo: &Path, target: &str) -> Result<Self> {
+        let dir = TempDir::new().context("Failed to create temp dir")?;
+        let path = dir.path().join("land");
+        let path_arg = path.to_string_lossy();
+        git_output(repo, &["worktree", "add", "--detach", &path_arg, target])?;
+        Ok(Self {
+            repo: repo.to_path_buf(),
+            path,
+            _dir: dir,
+        })
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn git_succeeds(&self, args: &[&str]) -> Result<bool> {
+        git_succeeds(&self.path, args)
+    }
+}
+
+impl Drop for Scratch {
+    fn drop(&mut self) {
+        let path = self.path.to_string_lossy();
+        let _ = git_succeeds(&self.repo, &["worktree", "remove", "--force", &path]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::git_fixture::{git_ok, init_repo_with_main};
+
+    #[test]
+    fn preview_reports_ahead_commits_and_conflicts() {
+        let repo = init_repo_with_main();
+        let feature = feature_worktree(&repo, "feature");
+        std::fs::write(feature.join("src.txt"), "feature\n").unwrap();
+        git_ok(&feature, &["commit", "-am", "feature change"]);
+
+        let clean = preview(&feature, None).unwrap();
+        assert_eq!(clean.branch, "feature");
+        assert_eq!(clean.target, "main");
+        assert_eq!(clean.ahead, 1);
+        assert!(clean.conflicts.is_empty());
+
+        std::fs::write(repo.path().join("src.txt"), "main\n").unwrap();
+        git_ok(repo.path(), &["commit", "-am", "main change"]);
+
+        let conflicted = preview(&feature, None).unwrap();
+        assert_eq!(conflicted.conflict_paths(), vec!["src.txt".to_string()]);
+        let file = &conflicted.conflicts[0];
+        assert_eq!(file.old_content.as_deref(), Some("main\n"));
+        assert!(file.new_content.as_deref().unwrap().contains("<<<<<<<"));
+
+        // The trial merge leaves no worktree or merge state behind.
+        assert_eq!(WorktreeManager::list(repo.path()).unwrap().len(), 2);
+        assert!(!WorktreeManager::has_tracked_changes(repo.path()).unwrap());
+        assert!(land(&feature, None, LandStrategy::Merge).is_err());
+    }
+
+    #[test]
+    fn land_merges_squashes_or_fast_forwards_onto_main() {
+        for strategy in LandStrategy::ALL {
+            let repo = init_repo_with_main();
+            let feature = feature_worktree(&repo, "feature");
+            std::fs::write(feature.join("a.txt"), "a\n").unwrap();
+            git_ok(&feature, &["add", "a.txt"]);
+            git_ok(&feature, &["commit", "-m", "add a"]);
+            std::fs::write(feature.join("b.txt"), "b\n").unwrap();
+            git_ok(&feature, &["add", "b.txt"]);
+            git_ok(&feature, &["commit", "-m", "add b"]);
+            std::fs::write(repo.path().join("main.txt"), "main\n").unwrap();
+            git_ok(repo.path(), &["add", "main.txt"]);
+            git_ok(repo.path(), &["commit", "-m", "main change"]);
+
+            let landed = land(&feature, None, strategy).unwrap();
+
+            assert_eq!(landed.target, "main");
+            assert_eq!(
+                git_output(repo.path(), &["rev-parse", "HEAD"]).unwrap(),
+                landed.commit
+            );
+            assert!(repo.path().join("a.txt").exists());
+            assert!(repo.path().join("b.txt").exists());
+            let parents =
+                git_output(repo.path(), &["rev-list", "--parents", "-n1", "HEAD"]).unwrap();
+            let count = git_output(repo.path(), &["rev-list", "--count", "HEAD"]).unwrap();
+            match strategy {
+                LandStrategy::Merge => assert_eq!(parents.split(' ').count(), 3),
+                LandStrategy::Squash => assert_eq!(count, "3"),
+                LandStrategy::Rebase => assert_eq!(count, "4"),
+            }
+        }
+    }
+
+    fn feature_worktree(repo: &TempDir, branch: &str) -> PathBuf {
+        WorktreeManager::create(repo.path(), branch, branch).unwrap()
+    }
+}
@@ -2,21 +2,31 @@
 
 mod app;
 mod automation;
+mod checkpoint;
 mod claude;
 mod codex;
+mod daemon;
 mod db;
 mod debug;
 mod diff;
 mod editor;
 mod extension;
+#[cfg(test)]
+mod git_fixture;
+mod git_status;
 mod handlers;
 mod highlight;
 mod http_client;
 mod ipc;
+mod land;
 mod markdown;
+mod mcp;
+mod overlap;
 mod perf;
 mod pi;
+mod portable;
 mod project;
+mod stage;
 mod summary;
 mod theme;
 mod tmux;
@@ -29,7 +39,7 @@ mod usage;
 mod worktree;
 
 use anyhow::{Context, Result};
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use crossterm::{
     cursor::SetCursorStyle,
     event::{
@@ -66,11 +76,23 @@ struct Cli {
 enum Commands {
     /// Upgrade amf to the latest release
     Upgrade,
+    /// Run AMF headless: serve automation and hook notifications without
+    /// the dashboard. A dashboard started later takes over from it.
+    Daemon,
+    /// Serve AMF automation to agents as a Model Context Protocol server
+    /// over stdio
+    Mcp,
     /// Run machine-friendly automation actions against a running AMF instance
     Automation {
         #[command(subcommand)]
         command: AutomationCommands,
     },
+    /// Export the project store to portable JSON, or import it on another
+    /// machine
+    Store {
+        #[command(subcommand)]
+        command: StoreCommands,
+    },
     /// Send a notification to the running AMF instance via the
     /// IPC socket. Reads JSON from stdin. Used by hook scripts.
     #[command(hide = true)]
@@ -130,6 +152,285 @@ enum AutomationCommands {
         #[arg(long, default_value_t = 120000)]
         timeout_ms: u64,
     },
+    /// Paste a prompt into a feature's agent session from JSON input
+    SendPrompt {
+        /// Read request JSON from a file. Omit or pass `-` to read stdin.
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Override the JSON payload and perform validation only.
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+        /// Timeout in milliseconds while waiting for AMF to reply.
+        #[arg(long, default_value_t = 120000)]
+        timeout_ms: u64,
+    },
+    /// Start a stopped feature
+    StartFeature {
+        #[command(flatten)]
+        target: FeatureTargetArgs,
+        /// Answer for a prompted `on_start` lifecycle hook.
+        #[arg(long)]
+        hook_choice: Option<String>,
+    },
+    /// Stop a running feature and kill its tmux session
+    StopFeature {
+        #[command(flatten)]
+        target: FeatureTargetArgs,
+        /// Answer for a prompted `on_stop` lifecycle hook.
+        #[arg(long)]
+        hook_choice: Option<String>,
+    },
+    /// Mark a feature as ready (or not ready with `--not-ready`)
+    MarkReady {
+        #[command(flatten)]
+        target: FeatureTargetArgs,
+        /// Clear the ready flag instead of setting it.
+        #[arg(long, default_value_t = false)]
+        not_ready: bool,
+    },
+    /// Set or clear a feature's display nickname
+    Rename {
+        #[command(flatten)]
+        target: FeatureTargetArgs,
+        /// New nickname. Omit to clear the current one.
+        #[arg(long)]
+        nickname: Option<String>,
+    },
+    /// Delete a feature, its tmux session, and its worktree
+    DeleteFeature {
+        #[command(flatten)]
+        target: FeatureTargetArgs,
+    },
+    /// Archive a feature: remove its worktree and tmux session but keep
+    /// its branch and metadata
+    Archive {
+        #[command(flatten)]
+        target: FeatureTargetArgs,
+    },
+    /// Restore an archived feature and relaunch its sessions
+    Restore {
+        #[command(flatten)]
+        target: FeatureTargetArgs,
+    },
+    /// Replace a feature's tags
+    Tag {
+        #[command(flatten)]
+        target: FeatureTargetArgs,
+        /// Tag to set. Repeat for several tags; omit to clear them all.
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+    /// Rebase the features stacked on a feature onto its current branch
+    Restack {
+        #[command(flatten)]
+        target: FeatureTargetArgs,
+    },
+    /// Land a ready feature on its base branch
+    Land {
+        #[command(flatten)]
+        target: FeatureTargetArgs,
+        /// How to land: merge, squash, or rebase.
+        #[arg(long, value_parser = parse_land_strategy, default_value = "merge")]
+        strategy: land::LandStrategy,
+    },
+    /// Fork a feature into a new worktree from JSON input
+    ForkFeature {
+        /// Read request JSON from a file. Omit or pass `-` to read stdin.
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Override the JSON payload and perform validation only.
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+        /// Timeout in milliseconds while waiting for AMF to reply.
+        #[arg(long, default_value_t = 120000)]
+        timeout_ms: u64,
+    },
+    /// Add a built-in or custom session to a feature
+    AddSession {
+        #[command(flatten)]
+        target: FeatureTargetArgs,
+        /// Built-in session kind: terminal, nvim, claude, opencode, or codex.
+        #[arg(long, value_parser = parse_session_kind, conflicts_with = "custom", required_unless_present = "custom")]
+        kind: Option<project::SessionKind>,
+        /// Name of a custom session from the extension config.
+        #[arg(long)]
+        custom: Option<String>,
+    },
+    /// Block until a feature's agent goes idle or asks for input
+    WaitForIdle {
+        /// AMF project that owns the feature.
+        #[arg(long)]
+        project: String,
+        /// Feature name inside the project.
+        #[arg(long)]
+        feature: String,
+        /// Session id, label, or tmux window to wait on instead of the whole feature.
+        #[arg(long)]
+        session: Option<String>,
+        /// Give up after this many milliseconds.
+        #[arg(long, default_value_t = 600000)]
+        timeout_ms: u64,
+        /// How long the agent must stay idle before the wait ends.
+        #[arg(long, default_value_t = 3000)]
+        settle_ms: u64,
+    },
+    /// Print a feature's diff against its base branch as JSON
+    Diff {
+        /// AMF project that owns the feature.
+        #[arg(long)]
+        project: String,
+        /// Feature name inside the project.
+        #[arg(long)]
+        feature: String,
+        /// Report per-file stats only, without patch text.
+        #[arg(long, default_value_t = false)]
+        no_patch: bool,
+        /// Timeout in milliseconds while waiting for AMF to reply.
+        #[arg(long, default_value_t = 120000)]
+        timeout_ms: u64,
+    },
+    /// Print pending input requests and diff reviews as JSON
+    Inputs {
+        /// Only include inputs from this project.
+        #[arg(long)]
+        project: Option<String>,
+        /// Only include inputs from this feature.
+        #[arg(long)]
+        feature: Option<String>,
+        /// Timeout in milliseconds while waiting for AMF to reply.
+        #[arg(long, default_value_t = 120000)]
+        timeout_ms: u64,
+    },
+    /// Approve, reject, or dismiss a pending input
+    ResolveInput {
+        /// The input's `request_id`, as printed by `amf automation inputs`.
+        #[arg(long)]
+        input_id: String,
+        /// approve, reject, or dismiss.
+        #[arg(long, value_parser = parse_input_decision)]
+        decision: automation::InputDecision,
+        /// Feedback sent to the agent with an approval or rejection.
+        #[arg(long)]
+        reason: Option<String>,
+        /// Validate and preview without changing AMF state.
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+        /// Timeout in milliseconds while waiting for AMF to reply.
+        #[arg(long, default_value_t = 120000)]
+        timeout_ms: u64,
+    },
+    /// Stream dashboard events as newline-delimited JSON until AMF exits
+    Watch {
+        /// Only print events for this project.
+        #[arg(long)]
+        project: Option<String>,
+    },
+    /// Print projects, features, and sessions known to the running AMF instance as JSON
+    List {
+        /// Only include features from this project.
+        #[arg(long)]
+        project: Option<String>,
+        /// Only include features carrying this tag.
+        #[arg(long)]
+        tag: Option<String>,
+        /// Timeout in milliseconds while waiting for AMF to reply.
+        #[arg(long, default_value_t = 120000)]
+        timeout_ms: u64,
+    },
+    /// Print the automation protocol version and supported actions of the running AMF instance
+    Hello {
+        /// Timeout in milliseconds while waiting for AMF to reply.
+        #[arg(long, default_value_t = 5000)]
+        timeout_ms: u64,
+    },
+    /// Print the JSON Schema of automation requests and replies
+    Schema {
+        /// Only print the request schema for this action, e.g. `create_feature`.
+        action: Option<String>,
+        /// Print the action's reply schema instead of its request schema.
+        #[arg(long, default_value_t = false, requires = "action")]
+        response: bool,
+    },
+    /// Check a request payload against its schema without a running AMF instance
+    Validate {
+        /// Action the payload is for. Defaults to the payload's `action` field.
+        action: Option<String>,
+        /// Read request JSON from a file. Omit or pass `-` to read stdin.
+        #[arg(long)]
+        file: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum StoreCommands {
+    /// Write projects, features, sessions, bookmarks and feature presets
+    /// as a versioned JSON document
+    Export {
+        /// Write to a file instead of stdout.
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+    /// Merge a `store export` document into this machine's store
+    Import {
+        /// Read the export from a file. Omit or pass `-` to read stdin.
+        #[arg(long)]
+        file: Option<PathBuf>,
+        /// Rewrite paths starting with OLD to start with NEW. Repeatable.
+        #[arg(long = "map", value_name = "OLD=NEW")]
+        mappings: Vec<portable::PathMapping>,
+        /// Recreate missing worktrees from their branches instead of
+        /// skipping those features.
+        #[arg(long, default_value_t = false)]
+        recreate_worktrees: bool,
+        /// Report what would be imported without saving anything.
+        #[arg(long, default_value_t = false)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Args, Debug)]
+struct FeatureTargetArgs {
+    /// AMF project that owns the feature.
+    #[arg(long)]
+    project: String,
+    /// Feature name inside the project.
+    #[arg(long)]
+    feature: String,
+    /// Validate and preview without changing AMF state.
+    #[arg(long, default_value_t = false)]
+    dry_run: bool,
+    /// Timeout in milliseconds while waiting for AMF to reply.
+    #[arg(long, default_value_t = 120000)]
+    timeout_ms: u64,
+}
+
+impl FeatureTargetArgs {
+    fn lifecycle_request(
+        &self,
+        hook_choice: Option<String>,
+    ) -> automation::FeatureLifecycleRequest {
+        automation::FeatureLifecycleRequest {
+            project_name: self.project.clone(),
+            feature_name: self.feature.clone(),
+            hook_choice,
+            dry_run: self.dry_run,
+        }
+    }
+}
+
+fn parse_session_kind(value: &str) -> std::result::Result<project::SessionKind, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_ascii_lowercase()))
+        .map_err(|_| format!("unknown session kind '{value}'"))
+}
+
+fn parse_land_strategy(value: &str) -> std::result::Result<land::LandStrategy, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_ascii_lowercase()))
+        .map_err(|_| format!("unknown strategy '{value}'; expected merge, squash, or rebase"))
+}
+
+fn parse_input_decision(value: &str) -> std::result::Result<automation::InputDecision, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_ascii_lowercase()))
+        .map_err(|_| format!("unknown decision '{value}'; expected approve, reject, or dismiss"))
 }
 
 fn main() -> Result<()> {
@@ -144,10 +445,37 @@ fn main() -> Result<()> {
         return upgrade::upgrade();
     }
 
+    if let Some(Commands::Daemon) = cli.command {
+        return daemon::run();
+    }
+
+    if let Some(Commands::Mcp) = cli.command {
+        return mcp::run();
+    }
+
     if let Some(Commands::Automation { command }) = cli.command {
         return run_automation_command(command);
     }
 
+    if let Some(Commands::Store { command }) = cli.command {
+        return match command {
+            StoreCommands::Export { output } => portable::run_export(output.as_deref()),
+            StoreCommands::Import {
+                file,
+                mappings,
+                recreate_worktrees,
+                dry_run,
+            } => portable::run_import(
+                file.as_deref(),
+                portable::ImportOptions {
+                    mappings,
+                    recreate_worktrees,
+                    dry_run,
+                },
+            ),
+        };
+    }
+
     if let Some(Commands::Notify) = cli.command {
         use std::io::Read;
         let mut payload = String::new();
@@ -199,6 +527,20 @@ fn main() -> Result<()> {
         std::process::exit(1);
     }
 
+    // A headless daemon owns the socket; ask it to save and step aside so
+    // the store is loaded fresh below. It is restarted when the TUI exits.
+    let resume_daemon = match daemon::take_over(&ipc::socket_path()) {
+        Ok(took_over) => took_over,
+        Err(e) => {
+            debug::log_to_file(
+                debug::LogLevel::Warn,
+                "daemon",
+                &format!("Could not take over from running AMF instance: {e}"),
+            );
+            false
+        }
+    };
+
     debug::install_panic_hook();
 
     enable_raw_mode()?;
@@ -276,6 +618,10 @@ fn main() -> Result<()> {
     )?;
     terminal.show_cursor()?;
 
+    if resume_daemon && let Err(e) = daemon::spawn_detached() {
+        eprintln!("Warning: failed to restart amf daemon: {e}");
+    }
+
     if let Some(session) = &should_switch {
         TmuxManager::attach_session(session)?;
     }
@@ -373,6 +719,79 @@ fn read_json_input(file: Option<&PathBuf>) -> Result<String> {
     Ok(payload.to_string())
 }
 
+fn send_automation_request(payload: &serde_json::Value, timeout_ms: u64) -> Result<()> {
+    let reply = automation::send_request(payload, Duration::from_millis(timeout_ms))?;
+    println!(
+        "{}",
+        serde_json::to_string_pretty(&reply).unwrap_or_else(|_| "{}".to_string())
+    );
+    Ok(())
+}
+
+fn print_automation_schema(action: Option<&str>, response: bool) -> Result<()> {
+    let schema = match action {
+        Some(action) => {
+            let action = action.replace('-', "_");
+            let contract = automation::action_contract(&action)
+                .with_context(|| format!("Unknown automation action '{action}'"))?;
+            if response {
+                contract.response.to_value()
+            } else {
+                contract.request.to_value()
+            }
+        }
+        None => automation::automation_schema_document(),
+    };
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
+
+fn validate_automation_payload(action: Option<&str>, file: Option<&PathBuf>) -> Result<()> {
+    let payload: serde_json::Value =
+        serde_json::from_str(&read_json_input(file)?).context("Payload is not valid JSON")?;
+    let action = action
+        .or_else(|| payload.get("action").and_then(|a| a.as_str()))
+        .context("Pass the action to validate against or add an `action` field to the payload")?
+        .replace('-', "_");
+
+    let errors = automation::validate_request(&action, &payload);
+    let report = serde_json::json!({
+        "action": action,
+        "ok": errors.is_empty(),
+        "errors": errors,
+    });
+    println!("{}", serde_json::to_string_pretty(&report)?);
+    if !errors.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn watch_automation_events(project: Option<&str>) -> Result<()> {
+    use std::io::{BufRead, Write};
+
+    let reader = ipc::subscribe(&ipc::socket_path())?;
+    let mut stdout = io::stdout().lock();
+    for line in reader.lines() {
+        let line = line.context("Failed to read from AMF event stream")?;
+        let Ok(event) = serde_json::from_str::<serde_json::Value>(&line) else {
+            continue;
+        };
+        if event["type"] != automation::AUTOMATION_EVENT_TYPE {
+            continue;
+        }
+        if let Some(project) = project
+            && event["project_name"] != project
+        {
+            continue;
+        }
+        writeln!(stdout, "{line}")?;
+        stdout.flush()?;
+    }
+
+    anyhow::bail!("AMF closed the event stream")
+}
+
 fn run_automation_command(command: AutomationCommands) -> Result<()> {
     match command {
         AutomationCommands::CreateProject {
@@ -387,14 +806,7 @@ fn run_automation_command(command: AutomationCommands) -> Result<()> {
                 request.dry_run = true;
             }
 
-            let socket = ipc::socket_path();
-            let outbound = serde_json::to_string(&request.ipc_payload())?;
-            let reply = ipc::send_wait(&socket, &outbound, Duration::from_millis(timeout_ms))?;
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&reply).unwrap_or_else(|_| "{}".to_string())
-            );
-            Ok(())
+            send_automation_request(&request.ipc_payload(), timeout_ms)
         }
         AutomationCommands::CreateFeature {
             file,
@@ -408,14 +820,7 @@ fn run_automation_command(command: AutomationCommands) -> Result<()> {
                 request.dry_run = true;
             }
 
-            let socket = ipc::socket_path();
-            let outbound = serde_json::to_string(&request.ipc_payload())?;
-            let reply = ipc::send_wait(&socket, &outbound, Duration::from_millis(timeout_ms))?;
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&reply).unwrap_or_else(|_| "{}".to_string())
-            );
-            Ok(())
+            send_automation_request(&request.ipc_payload(), timeout_ms)
         }
         AutomationCommands::CreateBatchFeatures {
             file,
@@ -430,15 +835,213 @@ fn run_automation_command(command: AutomationCommands) -> Result<()> {
                 request.dry_run = true;
             }
 
-            let socket = ipc::socket_path();
-            let outbound = serde_json::to_string(&request.ipc_payload())?;
-            let reply = ipc::send_wait(&socket, &outbound, Duration::from_millis(timeout_ms))?;
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&reply).unwrap_or_else(|_| "{}".to_string())
-            );
-            Ok(())
+            send_automation_request(&request.ipc_payload(), timeout_ms)
+        }
+        AutomationCommands::SendPrompt {
+            file,
+            dry_run,
+            timeout_ms,
+        } => {
+            let payload = read_json_input(file.as_ref())?;
+            let mut request: automation::SendPromptRequest =
+                serde_json::from_str(&payload).context("Invalid send_prompt JSON payload")?;
+            if dry_run {
+                request.dry_run = true;
+            }
+
+            send_automation_request(&request.ipc_payload(), timeout_ms)
+        }
+        AutomationCommands::ForkFeature {
+            file,
+            dry_run,
+            timeout_ms,
+        } => {
+            let payload = read_json_input(file.as_ref())?;
+            let mut request: automation::ForkFeatureRequest =
+                serde_json::from_str(&payload).context("Invalid fork_feature JSON payload")?;
+            if dry_run {
+                request.dry_run = true;
+            }
+
+            send_automation_request(&request.ipc_payload(), timeout_ms)
+        }
+        AutomationCommands::AddSession {
+            target,
+            kind,
+            custom,
+        } => {
+            let request = automation::AddSessionRequest {
+                project_name: target.project,
+                feature_name: target.feature,
+                kind,
+                custom,
+                dry_run: target.dry_run,
+            };
+            send_automation_request(&request.ipc_payload(), target.timeout_ms)
+        }
+        AutomationCommands::WaitForIdle {
+            project,
+            feature,
+            session,
+            timeout_ms,
+            settle_ms,
+        } => {
+            let request = automation::WaitForIdleRequest {
+                project_name: project,
+                feature_name: feature,
+                session,
+                timeout_ms,
+                settle_ms,
+            };
+            // AMF replies with `timed_out` itself; leave room for that reply.
+            send_automation_request(&request.ipc_payload(), timeout_ms.saturating_add(10_000))
+        }
+        AutomationCommands::Diff {
+            project,
+            feature,
+            no_patch,
+            timeout_ms,
+        } => {
+            let request = automation::ReadDiffRequest {
+                project_name: project,
+                feature_name: feature,
+                include_patch: !no_patch,
+            };
+            send_automation_request(&request.ipc_payload(), timeout_ms)
         }
+        AutomationCommands::Inputs {
+            project,
+            feature,
+            timeout_ms,
+        } => {
+            let request = automation::ListInputsRequest {
+                project_name: project,
+                feature_name: feature,
+            };
+            send_automation_request(&request.ipc_payload(), timeout_ms)
+        }
+        AutomationCommands::ResolveInput {
+            input_id,
+            decision,
+            reason,
+            dry_run,
+            timeout_ms,
+        } => {
+            let request = automation::ResolveInputRequest {
+                input_id,
+                decision: Some(decision),
+                reason,
+                dry_run,
+            };
+            send_automation_request(&request.ipc_payload(), timeout_ms)
+        }
+        AutomationCommands::StartFeature {
+            target,
+            hook_choice,
+        } => {
+            let request = target.lifecycle_request(hook_choice);
+            send_automation_request(
+                &request.ipc_payload(automation::START_FEATURE_ACTION),
+                target.timeout_ms,
+            )
+        }
+        AutomationCommands::StopFeature {
+            target,
+            hook_choice,
+        } => {
+            let request = target.lifecycle_request(hook_choice);
+            send_automation_request(
+                &request.ipc_payload(automation::STOP_FEATURE_ACTION),
+                target.timeout_ms,
+            )
+        }
+        AutomationCommands::MarkReady { target, not_ready } => {
+            let request = automation::SetFeatureReadyRequest {
+                project_name: target.project,
+                feature_name: target.feature,
+                ready: !not_ready,
+                dry_run: target.dry_run,
+            };
+            send_automation_request(&request.ipc_payload(), target.timeout_ms)
+        }
+        AutomationCommands::Rename { target, nickname } => {
+            let request = automation::RenameFeatureRequest {
+                project_name: target.project,
+                feature_name: target.feature,
+                nickname,
+                dry_run: target.dry_run,
+            };
+            send_automation_request(&request.ipc_payload(), target.timeout_ms)
+        }
+        AutomationCommands::DeleteFeature { target } => {
+            let request = target.lifecycle_request(None);
+            send_automation_request(
+                &request.ipc_payload(automation::DELETE_FEATURE_ACTION),
+                target.timeout_ms,
+            )
+        }
+        AutomationCommands::Archive { target } => {
+            let request = target.lifecycle_request(None);
+            send_automation_request(
+                &request.ipc_payload(automation::ARCHIVE_FEATURE_ACTION),
+                target.timeout_ms,
+            )
+        }
+        AutomationCommands::Restore { target } => {
+            let request = target.lifecycle_request(None);
+            send_automation_request(
+                &request.ipc_payload(automation::RESTORE_FEATURE_ACTION),
+                target.timeout_ms,
+            )
+        }
+        AutomationCommands::Tag { target, tags } => {
+            let request = automation::SetFeatureTagsRequest {
+                project_name: target.project,
+                feature_name: target.feature,
+                tags,
+                dry_run: target.dry_run,
+            };
+            send_automation_request(&request.ipc_payload(), target.timeout_ms)
+        }
+        AutomationCommands::Restack { target } => {
+            let request = automation::RestackFeatureRequest {
+                project_name: target.project,
+                feature_name: target.feature,
+                dry_run: target.dry_run,
+            };
+            send_automation_request(&request.ipc_payload(), target.timeout_ms)
+        }
+        AutomationCommands::Land { target, strategy } => {
+            let request = automation::LandFeatureRequest {
+                project_name: target.project,
+                feature_name: target.feature,
+                strategy,
+                dry_run: target.dry_run,
+            };
+            send_automation_request(&request.ipc_payload(), target.timeout_ms)
+        }
+        AutomationCommands::Watch { project } => watch_automation_events(project.as_deref()),
+        AutomationCommands::List {
+            project,
+            tag,
+            timeout_ms,
+        } => {
+            let request = automation::ListStateRequest {
+                project_name: project,
+                tag,
+            };
+            send_automation_request(&request.ipc_payload(), timeout_ms)
+        }
+        AutomationCommands::Schema { action, response } => {
+            print_automation_schema(action.as_deref(), response)
+        }
+        AutomationCommands::Validate { action, file } => {
+            validate_automation_payload(action.as_deref(), file.as_ref())
+        }
+        AutomationCommands::Hello { timeout_ms } => send_automation_request(
+            &automation::HelloRequest::default().ipc_payload(),
+            timeout_ms,
+        ),
     }
 }
 
@@ -536,7 +1139,10 @@ fn run_loop<B: Backend>(
     let mut last_claude_usage_debug: Option<String> = None;
     // Only used when no IPC socket is available (fallback).
     let mut last_notif_scan = std::time::Instant::now();
+    let mut last_event_publish = std::time::Instant::now();
     let mut last_resize: Option<(u16, u16, String, String)> = None;
+    let mut last_overlap_scan: Option<Instant> = None;
+    let mut last_git_summary_refresh: Option<Instant> = None;
     let mut force_redraw = true;
     let startup_grace_until = Instant::now() + Duration::from_secs(3);
     let mut startup_task_spacing_until = Instant::now();
@@ -548,6 +1154,8 @@ fn run_loop<B: Backend>(
     let mut startup_opencode_plugins_pending = true;
     let mut startup_sidebar_warm_pending = true;
     const ANIMATED_REDRAW_INTERVAL: Duration = Duration::from_millis(125);
+    const OVERLAP_SCAN_INTERVAL: Duration = Duration::from_secs(30);
+    const GIT_SUMMARY_INTERVAL: Duration = Duration::from_secs(10);
 
     loop {
         let loop_state_signature = app.redraw_signature();
@@ -579,6 +1187,10 @@ fn run_loop<B: Backend>(
             app.show_error(e);
         }
 
+        if matches!(app.mode, app::AppMode::CommitMessage(_)) && app.poll_commit_draft() {
+            force_redraw = true;
+        }
+
         if matches!(app.mode, app::AppMode::SyntaxLanguagePicker(_))
             && let Err(e) = app.poll_syntax_language_picker()
         {
@@ -614,6 +1226,18 @@ fn run_loop<B: Backend>(
             }
         }
 
+        if app.overlap_scan_bg.is_some() && app.poll_overlap_scan() {
+            force_redraw = true;
+        }
+
+        if app.poll_checkpoints() {
+            force_redraw = true;
+        }
+
+        if app.git_summary_bg.is_some() && app.poll_git_summaries() {
+            force_redraw = true;
+        }
+
         // Apply the one-shot VS Code availability check when it resolves.
         if let Ok(available) = vscode_check_rx.try_recv() {
             app.vscode_available = available;
@@ -810,6 +1434,26 @@ fn run_loop<B: Backend>(
             force_redraw = true;
         }
 
+        // Diffing every worktree is slow, so overlaps are refreshed far less
+        // often than statuses and never while a scan is still running.
+        if !handled_user_events
+            && !startup_tasks_pending
+            && app.overlap_scan_bg.is_none()
+            && last_overlap_scan.is_none_or(|at| at.elapsed() >= OVERLAP_SCAN_INTERVAL)
+        {
+            app.scan_overlaps_background();
+            last_overlap_scan = Some(Instant::now());
+        }
+
+        if !handled_user_events
+            && !startup_tasks_pending
+            && app.git_summary_bg.is_none()
+            && last_git_summary_refresh.is_none_or(|at| at.elapsed() >= GIT_SUMMARY_INTERVAL)
+        {
+            app.refresh_git_summaries_background();
+            last_git_summary_refresh = Some(Instant::now());
+        }
+
         if app.ipc.is_some() {
             // Drain all buffered socket messages each iteration.
             app.drain_ipc_messages();
@@ -843,6 +1487,14 @@ fn run_loop<B: Backend>(
             force_redraw |= thinking_changed;
         }
 
+        if app.ipc.is_some() && last_event_publish.elapsed() >= Duration::from_millis(250) {
+            app.publish_automation_events();
+            last_event_publish = std::time::Instant::now();
+        }
+
+        app.poll_automation_waits();
+        app.poll_queued_prompts(Instant::now());
+
         let summary_poll_started_at = Instant::now();
         if let Err(e) = app.poll_summary_result() {
             app.show_error(e);
@@ -0,0 +1,519 @@
+//! `amf mcp`: a stdio Model Context Protocol server that lets agents drive
+//! the running AMF instance. Each tool call is forwarded as an automation
+//! request over the IPC socket, so the server holds no state of its own.
+
+use std::io::{BufRead, Write};
+use std::time::Duration;
+
+use anyhow::Result;
+use schemars::JsonSchema;
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::automation::{
+    self, CREATE_FEATURE_ACTION, FORK_FEATURE_ACTION, LIST_STATE_ACTION, READ_DIFF_ACTION,
+    SEND_PROMPT_ACTION,
+};
+use crate::debug::{LogLevel, log_to_file};
+
+/// MCP revisions this server speaks, newest first. A client asking for
+/// another revision is answered with the newest one.
+const PROTOCOL_VERSIONS: &[&str] = &["2025-06-18", "2025-03-26", "2024-11-05"];
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(120);
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+struct Tool {
+    name: &'static str,
+    description: &'static str,
+    /// Automation action the tool's arguments are sent as.
+    action: &'static str,
+}
+
+const TOOLS: &[Tool] = &[
+    Tool {
+        name: "list_features",
+        description: "List AMF projects and their features with status, branch, worktree path, thinking state, latest summary, and pending inputs.",
+        action: LIST_STATE_ACTION,
+    },
+    Tool {
+        name: "read_summary",
+        description: "Read one feature's latest summary, status, thinking state, and pending inputs.",
+        action: LIST_STATE_ACTION,
+    },
+    Tool {
+        name: "read_diff",
+        description: "Read a feature's changes against its base branch, including uncommitted and untracked files.",
+        action: READ_DIFF_ACTION,
+    },
+    Tool {
+        name: "create_feature",
+        description: "Create a feature worktree in an AMF project and start an agent in it, optionally with a first prompt.",
+        action: CREATE_FEATURE_ACTION,
+    },
+    Tool {
+        name: "fork_feature",
+        description: "Fork a feature into a new worktree branched from it, copying its uncommitted changes and transcript context.",
+        action: FORK_FEATURE_ACTION,
+    },
+    Tool {
+        name: "send_prompt",
+        description: "Send a prompt to a feature's agent session.",
+        action: SEND_PROMPT_ACTION,
+    },
+];
+
+/// Arguments of `read_summary`.
+#[derive(Debug, Deserialize, JsonSchema)]
+#[schemars(deny_unknown_fields)]
+struct FeatureTarget {
+    #[schemars(length(min = 1))]
+    project_name: String,
+    #[schemars(length(min = 1))]
+    feature_name: String,
+}
+
+pub fn run() -> Result<()> {
+    let mut server = McpServer::new(|payload| automation::send_request(payload, REQUEST_TIMEOUT));
+    let stdin = std::io::stdin();
+    let mut stdout = std::io::stdout().lock();
+
+    for line in stdin.lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(reply) = server.handle_line(&line) {
+            writeln!(stdout, "{reply}")?;
+            stdout.flush()?;
+        }
+    }
+    Ok(())
+}
+
+/// Answers JSON-RPC messages one line at a time. `send` delivers an
+/// automation request and returns AMF's reply.
+pub struct McpServer<F> {
+    send: F,
+}
+
+impl<F> McpServer<F>
+where
+    F: FnMut(&Value) -> Result<Value>,
+{
+    pub fn new(send: F) -> Self {
+        Self { send }
+    }
+
+    /// Handle one line from the client. Returns the reply to write back,
+    /// or `None` for notifications.
+    pub fn handle_line(&mut self, line: &str) -> Option<Value> {
+        let message: Value = match serde_json::from_str(line) {
+            Ok(message) => message,
+            Err(err) => {
+                return Some(error_reply(
+                    Value::Null,
+                    PARSE_ERROR,
+                    format!("Parse error: {err}"),
+                ));
+            }
+        };
+
+        let id = message.get("id").cloned();
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            // Replies to requests this server never sends.
+            return id.map(|id| error_reply(id, INVALID_REQUEST, "Missing method".to_string()));
+        };
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        let result = match method {
+            "initialize" => Ok(initialize(&params)),
+            "ping" => Ok(json!({})),
+            "tools/list" => Ok(json!({ "tools": tool_list() })),
+            "tools/call" => self.call_tool(&params),
+            _ => Err((METHOD_NOT_FOUND, format!("Method '{method}' not found"))),
+        };
+
+        // Notifications such as `notifications/initialized` get no reply.
+        let id = id?;
+        Some(match result {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_reply(id, code, message),
+        })
+    }
+
+    fn call_tool(&mut self, params: &Value) -> std::result::Result<Value, (i64, String)> {
+        let name = params.get("name").and_then(Value::as_str).unwrap_or("");
+        let tool = TOOLS
+            .iter()
+            .find(|tool| tool.name == name)
+            .ok_or_else(|| (INVALID_PARAMS, format!("Unknown tool '{name}'")))?;
+        let arguments = match params.get("arguments") {
+            None | Some(Value::Null) => json!({}),
+            Some(arguments) => arguments.clone(),
+        };
+
+        let errors = match tool.name {
+            "read_summary" => serde_json::from_value::<FeatureTarget>(arguments.clone())
+                .err()
+                .map(|err| vec![err.to_string()])
+                .unwrap_or_default(),
+            _ => automation::validate_request(tool.action, &arguments),
+        };
+        if !errors.is_empty() {
+            return Ok(tool_result(errors.join("\n"), true));
+        }
+
+        if tool.name == "read_summary" {
+            return Ok(self.read_summary(&arguments));
+        }
+
+        let mut payload = arguments;
+        payload["type"] = json!(automation::AUTOMATION_REQUEST_TYPE);
+        payload["action"] = json!(tool.action);
+        Ok(match self.send_automation(&payload) {
+            Ok(reply) => tool_result(pretty(&reply), false),
+            Err(message) => tool_result(message, true),
+        })
+    }
+
+    fn read_summary(&mut self, arguments: &Value) -> Value {
+        let project_name = arguments["project_name"].as_str().unwrap_or_default();
+        let feature_name = arguments["feature_name"].as_str().unwrap_or_default();
+        let request = automation::ListStateRequest {
+            project_name: Some(project_name.to_string()),
+            ..Default::default()
+        };
+
+        let reply = match self.send_automation(&request.ipc_payload()) {
+            Ok(reply) => reply,
+            Err(message) => return tool_result(message, true),
+        };
+        let feature = reply["projects"]
+            .as_array()
+            .into_iter()
+            .flatten()
+            .flat_map(|project| project["features"].as_array().into_iter().flatten())
+            .find(|feature| feature["name"] == feature_name);
+
+        match feature {
+            Some(feature) => tool_result(
+                pretty(&json!({
+                    "project_name": project_name,
+                    "feature_name": feature_name,
+                    "status": feature["status"],
+                    "thinking": feature["thinking"],
+                    "ready": feature["ready"],
+                    "tags": feature["tags"],
+                    "summary": feature["summary"],
+                    "summary_updated_at": feature["summary_updated_at"],
+                    "pending_inputs": feature["pending_inputs"],
+                })),
+                false,
+            ),
+            None => tool_result(
+                format!("Feature '{feature_name}' not found in '{project_name}'"),
+                true,
+            ),
+        }
+    }
+
+    /// Send one automation request. Failed replies come back as their
+    /// error text so the agent sees why.
+    fn send_automation(&mut self, payload: &Value) -> std::result::Result<Value, String> {
+        match (self.send)(payload) {
+            Ok(reply) if reply["ok"] == true => Ok(reply),
+            Ok(reply) => Err(pretty(&reply)),
+            Err(err) => {
+                log_to_file(
+                    LogLevel::Warn,
+                    "mcp",
+                    &format!("Automation request failed: {err}"),
+                );
+                Err(format!(
+                    "Could not reach AMF: {err}. Is the dashboard or `amf daemon` running?"
+                ))
+            }
+        }
+    }
+}
+
+fn initialize(params: &Value) -> Value {
+    let requested = params.get("protocolVersion").and_then(Value::as_str);
+    let version = requested
+        .filter(|version| PROTOCOL_VERSIONS.contains(version))
+        .unwrap_or(PROTOCOL_VERSIONS[0]);
+    json!({
+        "protocolVersion": version,
+        "capabilities": { "tools": {} },
+        "serverInfo": { "name": "amf", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+fn tool_list() -> Vec<Value> {
+    TOOLS
+        .iter()
+        .map(|tool| {
+            let schema = match tool.name {
+                "read_summary" => schemars::schema_for!(FeatureTarget),
+                _ => automation::action_contract(tool.action)
+                    .map(|contract| contract.request)
+                    .unwrap_or_else(|| schemars::json_schema!({ "type": "object" })),
+            };
+            let mut schema = schema.to_value();
+            if let Some(obj) = schema.as_object_mut() {
+                obj.remove("$schema");
+                obj.remove("title");
+            }
+            json!({
+                "name": tool.name,
+                "description": tool.description,
+                "inputSchema": schema,
+            })
+        })
+        .collect()
+}
+
+fn tool_result(text: String, is_error: bool) -> Value {
+    json!({
+        "content": [{ "type": "text", "text": text }],
+        "isError": is_error,
+    })
+}
+
+fn error_reply(id: Value, code: i64, message: String) -> Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "error": { "code": code, "message": message },
+    })
+}
+
+fn pretty(value: &Value) -> String {
+    serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    type Sent = Rc<RefCell<Vec<Value>>>;
+
+    /// A server whose automation requests are recorded and answered with
+    /// `reply`.
+    fn server_with_reply(reply: Value) -> (McpServer<impl FnMut(&Value) -> Result<Value>>, Sent) {
+        let sent = Rc::new(RefCell::new(Vec::new()));
+        let log = Rc::clone(&sent);
+        let server = McpServer::new(move |payload: &Value| {
+            log.borrow_mut().push(payload.clone());
+            Ok(reply.clone())
+        });
+        (server, sent)
+    }
+
+    fn call(server: &mut McpServer<impl FnMut(&Value) -> Result<Value>>, line: Value) -> Value {
+        server
+            .handle_line(&line.to_string())
+            .expect("request got no reply")
+    }
+
+    #[test]
+    fn initialize_and_tools_list_follow_the_handshake() {
+        let (mut server, sent) = server_with_reply(json!({}));
+
+        let reply = call(
+            &mut server,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "initialize",
+                "params": { "protocolVersion": "2024-11-05", "capabilities": {} },
+            }),
+        );
+        assert_eq!(reply["id"], 1);
+        assert_eq!(reply["result"]["protocolVersion"], "2024-11-05");
+        assert_eq!(reply["result"]["serverInfo"]["name"], "amf");
+        assert!(reply["result"]["capabilities"]["tools"].is_object());
+
+        let initialized = json!({ "jsonrpc": "2.0", "method": "notifications/initialized" });
+        assert!(server.handle_line(&initialized.to_string()).is_none());
+
+        let reply = call(
+            &mut server,
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" }),
+        );
+        let tools = reply["result"]["tools"].as_array().unwrap();
+        let names: Vec<_> = tools
+            .iter()
+            .map(|tool| tool["name"].as_str().unwrap())
+            .collect();
+        assert_eq!(
+            names,
+            [
+                "list_features",
+                "read_summary",
+                "read_diff",
+                "create_feature",
+                "fork_feature",
+                "send_prompt"
+            ]
+        );
+        for tool in tools {
+            assert_eq!(tool["inputSchema"]["type"], "object", "{}", tool["name"]);
+        }
+        // The description promises a first prompt, so the schema must take one.
+        assert!(tools[3]["inputSchema"]["properties"]["prompt"].is_object());
+        assert!(sent.borrow().is_empty());
+    }
+
+    #[test]
+    fn tool_calls_are_forwarded_as_automation_requests() {
+        let (mut server, sent) = server_with_reply(json!({
+            "type": "automation-result",
+            "action": "send_prompt",
+            "ok": true,
+        }));
+
+        let reply = call(
+            &mut server,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 3,
+                "method": "tools/call",
+                "params": {
+                    "name": "send_prompt",
+                    "arguments": {
+                        "project_name": "my-repo",
+                        "feature_name": "login-fix",
+                        "prompt": "Run the tests",
+                    },
+                },
+            }),
+        );
+
+        assert_eq!(reply["result"]["isError"], false);
+        let sent = sent.borrow();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0]["type"], "automation");
+        assert_eq!(sent[0]["action"], "send_prompt");
+        assert_eq!(sent[0]["prompt"], "Run the tests");
+    }
+
+    #[test]
+    fn invalid_arguments_and_failed_replies_are_tool_errors() {
+        let (mut server, sent) = server_with_reply(json!({
+            "type": "automation-result",
+            "action": "read_diff",
+            "ok": false,
+            "code": "feature_not_found",
+            "error": "Feature 'missing' not found in 'my-repo'",
+        }));
+
+        let reply = call(
+            &mut server,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 4,
+                "method": "tools/call",
+                "params": { "name": "create_feature", "arguments": { "branch": "x" } },
+            }),
+        );
+        assert_eq!(reply["result"]["isError"], true);
+        let text = reply["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(
+            text.contains("project_name: missing required field"),
+            "got: {text}"
+        );
+        assert!(sent.borrow().is_empty());
+
+        let reply = call(
+            &mut server,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 5,
+                "method": "tools/call",
+                "params": {
+                    "name": "read_diff",
+                    "arguments": { "project_name": "my-repo", "feature_name": "missing" },
+                },
+            }),
+        );
+        assert_eq!(reply["result"]["isError"], true);
+        let text = reply["result"]["content"][0]["text"].as_str().unwrap();
+        assert!(text.contains("feature_not_found"), "got: {text}");
+
+        let reply = call(
+            &mut server,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 6,
+                "method": "tools/call",
+                "params": { "name": "delete_everything", "arguments": {} },
+            }),
+        );
+        assert_eq!(reply["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[test]
+    fn read_summary_picks_the_feature_out_of_list_state() {
+        let (mut server, sent) = server_with_reply(json!({
+            "type": "automation-result",
+            "action": "list_state",
+            "ok": true,
+            "projects": [{
+                "name": "my-repo",
+                "features": [
+                    { "name": "other", "status": "idle", "summary": "Not this one" },
+                    {
+                        "name": "login-fix",
+                        "status": "active",
+                        "thinking": true,
+                        "ready": false,
+                        "summary": "Fixed session expiry redirect",
+                        "pending_inputs": [],
+                    },
+                ],
+            }],
+        }));
+
+        let reply = call(
+            &mut server,
+            json!({
+                "jsonrpc": "2.0",
+                "id": 7,
+                "method": "tools/call",
+                "params": {
+                    "name": "read_summary",
+                    "arguments": { "project_name": "my-repo", "feature_name": "login-fix" },
+                },
+            }),
+        );
+
+        assert_eq!(reply["result"]["isError"], false);
+        let text = reply["result"]["content"][0]["text"].as_str().unwrap();
+        let summary: Value = serde_json::from_str(text).unwrap();
+        assert_eq!(summary["summary"], "Fixed session expiry redirect");
+        assert_eq!(summary["thinking"], true);
+        assert_eq!(sent.borrow()[0]["project_name"], "my-repo");
+    }
+
+    #[test]
+    fn malformed_lines_and_unknown_methods_get_json_rpc_errors() {
+        let (mut server, _) = server_with_reply(json!({}));
+
+        let reply = server.handle_line("{not json").unwrap();
+        assert_eq!(reply["error"]["code"], PARSE_ERROR);
+        assert!(reply["id"].is_null());
+
+        let reply = call(
+            &mut server,
+            json!({ "jsonrpc": "2.0", "id": 8, "method": "resources/list" }),
+        );
+        assert_eq!(reply["error"]["code"], METHOD_NOT_FOUND);
+    }
+}
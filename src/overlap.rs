@@ -0,0 +1,306 @@
+use std::path::PathBuf;
+
+use anyhow::{Context, Result};
+use tempfile::TempDir;
+
+use crate::diff::{DiffFile, DiffFileStatus, DiffSnapshot};
+
+/// One feature to diff when looking for overlaps.
+#[derive(Debug, Clone)]
+pub struct OverlapJob {
+    pub project_id: String,
+    pub feature_id: String,
+    pub workdir: PathBuf,
+    /// Branch of the feature this one is stacked on, used as the diff base.
+    pub parent_branch: Option<String>,
+    /// Ids of the features this one is stacked on, nearest first. Changes
+    /// shared with an ancestor are inherited, not a collision.
+    pub ancestors: Vec<String>,
+}
+
+/// A file two features both change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OverlapFile {
+    pub path: String,
+    /// Base line ranges (1-based, inclusive) that both features change.
+    /// Empty when the features edit different parts of the file.
+    pub ranges: Vec<(usize, usize)>,
+    /// The file as the left feature has it.
+    pub left: String,
+    /// The file as the right feature has it.
+    pub right: String,
+}
+
+impl OverlapFile {
+    pub fn hunks_overlap(&self) -> bool {
+        !self.ranges.is_empty()
+    }
+}
+
+/// Files two features of the same project both change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FeatureOverlap {
+    pub project_id: String,
+    pub left: String,
+    pub right: String,
+    pub files: Vec<OverlapFile>,
+}
+
+impl FeatureOverlap {
+    pub fn involves(&self, feature_id: &str) -> bool {
+        self.left == feature_id || self.right == feature_id
+    }
+
+    pub fn hunks_overlap(&self) -> bool {
+        self.files.iter().any(OverlapFile::hunks_overlap)
+    }
+
+    /// This overlap seen from `feature_id`, which becomes the left side.
+    pub fn oriented(&self, feature_id: &str) -> FeatureOverlap {
+        if self.left == feature_id {
+            return self.clone();
+        }
+        FeatureOverlap {
+            project_id: self.project_id.clone(),
+            left: self.right.clone(),
+            right: self.left.clone(),
+            files: self
+                .files
+                .iter()
+                .map(|file| OverlapFile {
+                    path: file.path.clone(),
+                    ranges: file.ranges.clone(),
+                    left: file.right.clone(),
+                    right: file.left.clone(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Diff every job against its base and pair up features of the same
+/// project that change the same files. Features whose diff cannot be
+/// loaded are skipped.
+pub fn scan(jobs: &[OverlapJob]) -> Vec<FeatureOverlap> {
+    let diffs: Vec<(&OverlapJob, DiffSnapshot)> = jobs
+        .iter()
+        .filter_map(|job| {
+            let snapshot =
+                crate::diff::load_snapshot(&job.workdir, job.parent_branch.as_deref()).ok()?;
+            Some((job, snapshot))
+        })
+        .collect();
+
+    let mut overlaps = Vec::new();
+    for (i, (left, left_diff)) in diffs.iter().enumerate() {
+        for (right, right_diff) in &diffs[i + 1..] {
+            if left.project_id != right.project_id
+                || left.ancestors.contains(&right.feature_id)
+                || right.ancestors.contains(&left.feature_id)
+            {
+                continue;
+            }
+            let files = overlapping_files(
+                &left_diff.files,
+                &right_diff.files,
+                left_diff.base_commit == right_diff.base_commit,
+            );
+            if !files.is_empty() {
+                overlaps.push(FeatureOverlap {
+                    project_id: left.project_id.clone(),
+                    left: left.feature_id.clone(),
+                    right: right.feature_id.clone(),
+                    files,
+                });
+            }
+        }
+    }
+    overlaps
+}
+
+/// Files changed on both sides, with the base lines both sides touch.
+/// Hunk line numbers only line up when both diffs were taken against the
+/// same base commit; otherwise just the files are reported.
+pub fn overlapping_files(
+    left: &[DiffFile],
+    right: &[DiffFile],
+    same_base: bool,
+) -> Vec<OverlapFile> {
+    left.iter()
+        .filter_map(|left_file| {
+            let right_file = right.iter().find(|file| file.path == left_file.path)?;
+            Some(OverlapFile {
+                path: left_file.path.clone(),
+                ranges: shared_ranges(left_file, right_file, same_base),
+                left: left_file.new_content.clone().unwrap_or_default(),
+                right: right_file.new_content.clone().unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Diff of the right feature's version of `file` against the left one's,
+/// so the viewer shows the right side as the base.
+pub fn review_file(file: &OverlapFile) -> Result<DiffFile> {
+    let dir = TempDir::new().context("Failed to create temp dir")?;
+    let original = dir.path().join("right");
+    let proposed = dir.path().join("left");
+    std::fs::write(&original, &file.right)?;
+    std::fs::write(&proposed, &file.left)?;
+    crate::diff::load_review_file(&original, &proposed, &file.path)
+}
+
+fn shared_ranges(left: &DiffFile, right: &DiffFile, same_base: bool) -> Vec<(usize, usize)> {
+    // A file both sides create collides as a whole.
+    if is_new(left) && is_new(right) {
+        return vec![(1, 1)];
+    }
+    if !same_base {
+        return Vec::new();
+    }
+
+    let mut ranges = Vec::new();
+    for a in &left.hunks {
+        for b in &right.hunks {
+            let (a_start, a_end) = base_range(a.old_start, a.old_lines);
+            let (b_start, b_end) = base_range(b.old_start, b.old_lines);
+            let start = a_start.max(b_start);
+            let end = a_end.min(b_end);
+            if start <= end && !ranges.contains(&(start, end)) {
+                ranges.push((start, end));
+            }
+        }
+    }
+    ranges.sort_unstable();
+    ranges
+}
+
+fn is_new(file: &DiffFile) -> bool {
+    matches!(
+        file.status,
+        DiffFileStatus::Added | DiffFileStatus::Untracked
+    )
+}
+
+/// Base lines a hunk replaces. A pure insertion claims the line it
+/// follows, so two insertions at the same spot still collide.
+fn base_range(start: usize, lines: usize) -> (usize, usize) {
+    let start = start.max(1);
+    (start, start + lines.max(1) - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::diff::parse_unified_diff;
+    use crate::git_fixture::{git_ok, init_repo_with_src};
+
+    fn file(patch: &str, new_content: &str) -> DiffFile {
+        let mut file = parse_unified_diff(patch).unwrap().pop().unwrap();
+        file.new_content = Some(new_content.to_string());
+        file
+    }
+
+    fn edit(path: &str, old_start: usize, old_lines: usize) -> String {
+        let removed: String = (0..old_lines).map(|i| format!("-old{i}\n")).collect();
+        format!(
+            "diff --git a/{path} b/{path}\n--- a/{path}\n+++ b/{path}\n@@ -{old_start},{old_lines} +{old_start},1 @@\n{removed}+new\n"
+        )
+    }
+
+    #[test]
+    fn overlapping_files_reports_shared_base_lines() {
+        let left = vec![
+            file(&edit("src/a.rs", 10, 5), "left a"),
+            file(&edit("src/b.rs", 1, 2), "left b"),
+            file(&edit("src/only_left.rs", 1, 1), ""),
+        ];
+        let right = vec![
+            file(&edit("src/a.rs", 12, 10), "right a"),
+            file(&edit("src/b.rs", 40, 2), "right b"),
+        ];
+
+        let files = overlapping_files(&left, &right, true);
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].path, "src/a.rs");
+        assert_eq!(files[0].ranges, vec![(12, 14)]);
+        assert_eq!(
+            (files[0].left.as_str(), files[0].right.as_str()),
+            ("left a", "right a")
+        );
+        assert_eq!(files[1].path, "src/b.rs");
+        assert!(!files[1].hunks_overlap());
+    }
+
+    fn edit_line(path: &std::path::Path, line: usize, text: &str) {
+        let content = std::fs::read_to_string(path).unwrap();
+        let mut lines: Vec<&str> = content.lines().collect();
+        lines[line - 1] = text;
+        std::fs::write(path, lines.join("\n") + "\n").unwrap();
+    }
+
+    fn job(feature_id: &str, workdir: PathBuf) -> OverlapJob {
+        OverlapJob {
+            project_id: "proj".into(),
+            feature_id: feature_id.into(),
+            workdir,
+            parent_branch: None,
+            ancestors: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn scan_only_compares_hunks_against_the_same_merge_base() {
+        let lines: String = (1..=20).map(|i| format!("line {i}\n")).collect();
+        let repo = init_repo_with_src(&lines);
+        let worktrees = TempDir::new().unwrap();
+        let a = worktrees.path().join("a");
+        let b = worktrees.path().join("b");
+        git_ok(
+            repo.path(),
+            &["worktree", "add", "-b", "a", a.to_str().unwrap()],
+        );
+        edit_line(&a.join("src.txt"), 10, "edited by a");
+
+        // `b` forks after main grew ten lines at the top, so its line 10
+        // is different text that only shares a line number with `a`'s.
+        let top: String = (1..=10).map(|i| format!("top {i}\n")).collect();
+        std::fs::write(repo.path().join("src.txt"), top + &lines).unwrap();
+        git_ok(repo.path(), &["commit", "-am", "prepend"]);
+        git_ok(
+            repo.path(),
+            &["worktree", "add", "-b", "b", b.to_str().unwrap()],
+        );
+        edit_line(&b.join("src.txt"), 10, "edited by b");
+
+        let overlaps = scan(&[job("a", a), job("b", b)]);
+
+        assert_eq!(overlaps.len(), 1);
+        assert_eq!(overlaps[0].files.len(), 1);
+        assert_eq!(overlaps[0].files[0].path, "src.txt");
+        assert!(!overlaps[0].hunks_overlap());
+    }
+
+    #[test]
+    fn oriented_swaps_sides_for_the_right_feature() {
+        let overlap = FeatureOverlap {
+            project_id: "proj".into(),
+            left: "a".into(),
+            right: "b".into(),
+            files: vec![OverlapFile {
+                path: "x".into(),
+                ranges: vec![(1, 1)],
+                left: "from a".into(),
+                right: "from b".into(),
+            }],
+        };
+
+        let flipped = overlap.oriented("b");
+
+        assert_eq!((flipped.left.as_str(), flipped.right.as_str()), ("b", "a"));
+        assert_eq!(flipped.files[0].left, "from b");
+        assert_eq!(overlap.oriented("a"), overlap);
+        assert!(overlap.involves("b") && !overlap.involves("c"));
+    }
+}
@@ -0,0 +1,613 @@
+//! `amf store export` / `amf store import`: a versioned JSON document of
+//! the project store and feature presets, used to move a setup to another
+//! machine. Import remaps absolute paths and checks every workdir, since
+//! the exported ones rarely exist as-is on the target.
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::extension::FeaturePreset;
+use crate::project::{self, CURRENT_PROJECT_STORE_VERSION, ProjectStatus, ProjectStore};
+use crate::traits::WorktreeOps;
+use crate::worktree::WorktreeManager;
+use crate::{db, ipc};
+
+/// `format` marker of an export document.
+pub const STORE_EXPORT_FORMAT: &str = "amf-store-export";
+/// Version of the export envelope. The store inside carries its own
+/// `version` (`CURRENT_PROJECT_STORE_VERSION` at export time).
+pub const STORE_EXPORT_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StoreExport {
+    pub format: String,
+    pub version: u32,
+    pub exported_at: DateTime<Utc>,
+    pub store: ProjectStore,
+    /// Global feature presets from `config.json`. Per-repo presets live
+    /// in the repo and travel with it.
+    #[serde(default)]
+    pub feature_presets: Vec<FeaturePreset>,
+}
+
+impl StoreExport {
+    pub fn new(store: &ProjectStore, feature_presets: Vec<FeaturePreset>) -> Self {
+        Self {
+            format: STORE_EXPORT_FORMAT.to_string(),
+            version: STORE_EXPORT_VERSION,
+            exported_at: Utc::now(),
+            store: store.clone(),
+            feature_presets,
+        }
+    }
+
+    pub fn parse(json: &str) -> Result<Self> {
+        let raw: serde_json::Value =
+            serde_json::from_str(json).context("Failed to parse store export")?;
+        let format = raw.get("format").and_then(|v| v.as_str()).unwrap_or("");
+        if format != STORE_EXPORT_FORMAT {
+            bail!("Not an AMF store export (expected format '{STORE_EXPORT_FORMAT}')");
+        }
+        let version = raw.get("version").and_then(|v| v.as_u64()).unwrap_or(0);
+        if version > u64::from(STORE_EXPORT_VERSION) {
+            bail!(
+                "Store export version {} is newer than this amf supports ({})",
+                version,
+                STORE_EXPORT_VERSION
+            );
+        }
+        let store_version = raw
+            .pointer("/store/version")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+        if store_version > u64::from(CURRENT_PROJECT_STORE_VERSION) {
+            bail!(
+                "Exported store is v{}, newer than this amf supports (v{})",
+                store_version,
+                CURRENT_PROJECT_STORE_VERSION
+            );
+        }
+        serde_json::from_value(raw).context("Failed to parse store export")
+    }
+}
+
+/// `OLD=NEW` path prefix rewrite given to `amf store import --map`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PathMapping {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+impl FromStr for PathMapping {
+    type Err = String;
+
+    fn from_str(value: &str) -> std::result::Result<Self, Self::Err> {
+        match value.split_once('=') {
+            Some((from, to)) if !from.is_empty() && !to.is_empty() => Ok(Self {
+                from: PathBuf::from(from),
+                to: PathBuf::from(to),
+            }),
+            _ => Err(format!("expected OLD=NEW, got '{value}'")),
+        }
+    }
+}
+
+/// Rewrite `path` with the mapping whose `from` is its longest prefix.
+fn remap(path: &Path, mappings: &[PathMapping]) -> PathBuf {
+    mappings
+        .iter()
+        .filter_map(|mapping| {
+            let rest = path.strip_prefix(&mapping.from).ok()?;
+            Some((mapping.from.components().count(), rebase(&mapping.to, rest)))
+        })
+        .max_by_key(|(depth, _)| *depth)
+        .map(|(_, path)| path)
+        .unwrap_or_else(|| path.to_path_buf())
+}
+
+fn rebase(root: &Path, rest: &Path) -> PathBuf {
+    if rest.as_os_str().is_empty() {
+        root.to_path_buf()
+    } else {
+        root.join(rest)
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct ImportOptions {
+    pub mappings: Vec<PathMapping>,
+    pub recreate_worktrees: bool,
+    pub dry_run: bool,
+}
+
+#[derive(Debug, Default)]
+pub struct ImportReport {
+    pub projects: usize,
+    pub features: usize,
+    pub recreated_worktrees: usize,
+    pub presets: usize,
+    /// One line per skipped or adjusted item.
+    pub notes: Vec<String>,
+}
+
+/// Remap the member worktrees of a multi-repo feature and recreate the
+/// missing ones if asked to. Returns whether the feature should be kept.
+fn restore_member_worktrees(
+    feature: &mut project::Feature,
+    project: &project::Project,
+    old_repo: &Path,
+    options: &ImportOptions,
+    worktree: &dyn WorktreeOps,
+    report: &mut ImportReport,
+) -> bool {
+    for member in &mut feature.repos {
+        member.repo = remap(&member.repo, &options.mappings);
+        member.workdir = match member.workdir.strip_prefix(old_repo) {
+            Ok(rest) => rebase(&project.repo, rest),
+            Err(_) => remap(&member.workdir, &options.mappings),
+        };
+    }
+
+    let missing: Vec<&project::FeatureRepo> = feature
+        .repos
+        .iter()
+        .filter(|member| !member.workdir.is_dir())
+        .collect();
+    if missing.is_empty() {
+        return true;
+    }
+    if !options.recreate_worktrees {
+        report.notes.push(format!(
+            "Skipped feature '{}/{}': worktree {} not found (use --recreate-worktrees)",
+            project.name,
+            feature.name,
+            missing[0].workdir.display()
+        ));
+        return false;
+    }
+    if options.dry_run {
+        report.recreated_worktrees += missing.len();
+        return true;
+    }
+
+    for member in missing {
+        if let Err(err) = worktree.create_at(&member.repo, &member.workdir, &feature.branch) {
+            report.notes.push(format!(
+                "Skipped feature '{}/{}': could not recreate worktree: {}",
+                project.name, feature.name, err
+            ));
+            return false;
+        }
+        report.recreated_worktrees += 1;
+    }
+    true
+}
+
+/// Merge the projects, bookmarks and harnesses of `export` into `target`.
+///
+/// Projects whose name or id already exists are skipped, as are projects
+/// whose (remapped) repo is missing. A worktree feature whose workdir is
+/// missing is recreated at that path from its branch when
+/// `recreate_worktrees` is set and skipped otherwise. Imported features
+/// start out stopped.
+pub fn merge_store(
+    target: &mut ProjectStore,
+    export: &StoreExport,
+    options: &ImportOptions,
+    worktree: &dyn WorktreeOps,
+) -> ImportReport {
+    let mut report = ImportReport::default();
+
+    for exported in &export.store.projects {
+        let mut project = exported.clone();
+        if target
+            .projects
+            .iter()
+            .any(|p| p.name == project.name || p.id == project.id)
+        {
+            report.notes.push(format!(
+                "Skipped project '{}': it already exists",
+                project.name
+            ));
+            continue;
+        }
+
+        let old_repo = project.repo.clone();
+        project.repo = remap(&old_repo, &options.mappings);
+        if !project.repo.is_dir() {
+            report.notes.push(format!(
+                "Skipped project '{}': repo {} not found (use --map OLD=NEW)",
+                project.name,
+                project.repo.display()
+            ));
+            continue;
+        }
+
+        let mut features = Vec::new();
+        for mut feature in std::mem::take(&mut project.features) {
+            feature.status = ProjectStatus::Stopped;
+            feature.pending_worktree_script = false;
+            feature.workdir = match feature.workdir.strip_prefix(&old_repo) {
+                Ok(rest) => rebase(&project.repo, rest),
+                Err(_) => remap(&feature.workdir, &options.mappings),
+            };
+
+            if feature.is_multi_repo() {
+                if restore_member_worktrees(
+                    &mut feature,
+                    &project,
+                    &old_repo,
+                    options,
+                    worktree,
+                    &mut report,
+                ) {
+                    features.push(feature);
+                }
+                continue;
+            }
+
+            // Archived features have no worktree on purpose.
+            if feature.is_archived() || feature.workdir.is_dir() {
+                features.push(feature);
+                continue;
+            }
+            if !feature.is_worktree {
+                feature.workdir = project.repo.clone();
+                features.push(feature);
+                continue;
+            }
+            if !options.recreate_worktrees {
+                report.notes.push(format!(
+                    "Skipped feature '{}/{}': worktree {} not found (use --recreate-worktrees)",
+                    project.name,
+                    feature.name,
+                    feature.workdir.display()
+                ));
+                continue;
+            }
+
+            if options.dry_run {
+                report.recreated_worktrees += 1;
+                features.push(feature);
+                continue;
+            }
+            match worktree.create_at(&project.repo, &feature.workdir, &feature.branch) {
+                Ok(path) => {
+                    feature.workdir = path;
+                    report.recreated_worktrees += 1;
+                    features.push(feature);
+                }
+                Err(err) => report.notes.push(format!(
+                    "Skipped feature '{}/{}': could not recreate worktree: {}",
+                    project.name, feature.name, err
+                )),
+            }
+        }
+
+        // A stacked feature whose parent was skipped is no longer stacked.
+        let ids: HashSet<String> = features.iter().map(|f| f.id.clone()).collect();
+        for feature in &mut features {
+            if feature
+                .parent_id
+                .as_ref()
+                .is_some_and(|parent| !ids.contains(parent))
+            {
+                feature.parent_id = None;
+            }
+        }
+
+        report.projects += 1;
+        report.features += features.len();
+        project.features = features;
+        target.projects.push(project);
+    }
+
+    for bookmark in &export.store.session_bookmarks {
+        let exists = target
+            .projects
+            .iter()
+            .filter(|p| p.id == bookmark.project_id)
+            .flat_map(|p| p.features.iter())
+            .filter(|f| f.id == bookmark.feature_id)
+            .any(|f| f.sessions.iter().any(|s| s.id == bookmark.session_id));
+        if exists && !target.session_bookmarks.contains(bookmark) {
+            target.session_bookmarks.push(bookmark.clone());
+        }
+    }
+
+    for harness in &export.store.available_harnesses {
+        target.add_harness(harness.clone());
+    }
+
+    report
+}
+
+/// Append the presets whose name is not taken yet; returns how many.
+pub fn merge_presets(target: &mut Vec<FeaturePreset>, incoming: &[FeaturePreset]) -> usize {
+    let mut added = 0;
+    for preset in incoming {
+        if !target.iter().any(|existing| existing.name == preset.name) {
+            target.push(preset.clone());
+            added += 1;
+        }
+    }
+    added
+}
+
+fn open_db() -> Result<db::AmfDb> {
+    db::AmfDb::open_or_seed(&project::db_path(), &project::global_db_path())
+}
+
+pub fn run_export(output: Option<&Path>) -> Result<()> {
+    let store = open_db()?.load_store()?;
+    let presets = crate::extension::load_global_extension_config().feature_presets;
+    let json = serde_json::to_string_pretty(&StoreExport::new(&store, presets))?;
+    match output {
+        Some(path) if path.as_os_str() != "-" => {
+            std::fs::write(path, json + "\n")
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            eprintln!(
+                "Exported {} project(s) to {}",
+                store.projects.len(),
+                path.display()
+            );
+        }
+        _ => println!("{json}"),
+    }
+    Ok(())
+}
+
+pub fn run_import(file: Option<&Path>, options: ImportOptions) -> Result<()> {
+    use std::io::Read;
+
+    if !options.dry_run && ipc::is_live(&ipc::socket_path()) {
+        bail!(
+            "AMF is running; quit the dashboard and stop `amf daemon` before importing, \
+             or it will overwrite the imported store"
+        );
+    }
+
+    let mut json = String::new();
+    match file {
+        Some(path) if path.as_os_str() != "-" => {
+            json = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+        }
+        _ => {
+            std::io::stdin()
+                .read_to_string(&mut json)
+                .context("Failed to read store export from stdin")?;
+        }
+    }
+    let export = StoreExport::parse(&json)?;
+
+    let db = open_db()?;
+    let mut store = db.load_store()?;
+    let mut report = merge_store(&mut store, &export, &options, &WorktreeManager);
+
+    let mut presets = crate::extension::load_global_extension_config().feature_presets;
+    report.presets = merge_presets(&mut presets, &export.feature_presets);
+
+    if !options.dry_run {
+        db.save_store(&store)?;
+        if report.presets > 0 {
+            write_global_feature_presets(&presets)?;
+        }
+    }
+
+    for note in &report.notes {
+        println!("{note}");
+    }
+    println!(
+        "{} {} project(s), {} feature(s), {} preset(s); recreated {} worktree(s)",
+        if options.dry_run {
+            "Would import"
+        } else {
+            "Imported"
+        },
+        report.projects,
+        report.features,
+        report.presets,
+        report.recreated_worktrees
+    );
+    Ok(())
+}
+
+/// Replace `extension.feature_presets` in `config.json`, keeping every
+/// other setting as written.
+fn write_global_feature_presets(presets: &[FeaturePreset]) -> Result<()> {
+    let path = project::amf_config_dir().join("config.json");
+    let mut config: serde_json::Value = std::fs::read_to_string(&path)
+        .ok()
+        .and_then(|data| serde_json::from_str(&data).ok())
+        .unwrap_or_else(|| serde_json::json!({}));
+    config["extension"]["feature_presets"] = serde_json::to_value(presets)?;
+    std::fs::write(&path, serde_json::to_string_pretty(&config)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::{AgentKind, Feature, Project, SessionBookmark, VibeMode};
+    use crate::traits::MockWorktreeOps;
+    use tempfile::TempDir;
+
+    fn feature(name: &str, workdir: PathBuf, is_worktree: bool) -> Feature {
+        let mut feature = Feature::new(
+            name.to_string(),
+            name.to_string(),
+            workdir,
+            is_worktree,
+            VibeMode::default(),
+            false,
+            false,
+            AgentKind::Claude,
+            false,
+        );
+        feature.status = ProjectStatus::Idle;
+        feature.add_session(project::SessionKind::Claude);
+        feature
+    }
+
+    fn empty_store() -> ProjectStore {
+        ProjectStore {
+            version: CURRENT_PROJECT_STORE_VERSION,
+            projects: Vec::new(),
+            session_bookmarks: Vec::new(),
+            available_harnesses: Vec::new(),
+            extra: Default::default(),
+        }
+    }
+
+    #[test]
+    fn path_mapping_parses_and_prefers_longest_prefix() {
+        let mappings: Vec<PathMapping> = ["/home/ana=/Users/ana", "/home/ana/src=/code"]
+            .iter()
+            .map(|m| m.parse().unwrap())
+            .collect();
+        assert_eq!(
+            remap(Path::new("/home/ana/src/app"), &mappings),
+            PathBuf::from("/code/app")
+        );
+        assert_eq!(
+            remap(Path::new("/home/ana/notes"), &mappings),
+            PathBuf::from("/Users/ana/notes")
+        );
+        assert_eq!(
+            remap(Path::new("/home/ana"), &mappings),
+            PathBuf::from("/Users/ana")
+        );
+        assert_eq!(
+            remap(Path::new("/srv/other"), &mappings),
+            PathBuf::from("/srv/other")
+        );
+        assert!("no-equals".parse::<PathMapping>().is_err());
+    }
+
+    #[test]
+    fn export_roundtrips_and_rejects_newer_versions() {
+        let mut store = empty_store();
+        store.projects.push(Project::new(
+            "app".to_string(),
+            PathBuf::from("/home/ana/src/app"),
+            true,
+            AgentKind::Claude,
+        ));
+        let json = serde_json::to_string(&StoreExport::new(&store, Vec::new())).unwrap();
+        let parsed = StoreExport::parse(&json).unwrap();
+        assert_eq!(parsed.store.projects[0].name, "app");
+
+        let mut raw: serde_json::Value = serde_json::from_str(&json).unwrap();
+        raw["store"]["version"] = serde_json::json!(CURRENT_PROJECT_STORE_VERSION + 1);
+        let err = StoreExport::parse(&raw.to_string()).unwrap_err();
+        assert!(err.to_string().contains("newer"), "got: {err}");
+
+        let err = StoreExport::parse(r#"{"format":"other"}"#).unwrap_err();
+        assert!(err.to_string().contains("Not an AMF store export"));
+    }
+
+    #[test]
+    fn merge_remaps_repo_and_skips_or_recreates_missing_worktrees() {
+        let repo = TempDir::new().unwrap();
+        let kept_dir = repo.path().join(".worktrees").join("kept");
+        std::fs::create_dir_all(&kept_dir).unwrap();
+        let gone_dir = repo.path().join(".worktrees").join("gone");
+
+        let old_repo = PathBuf::from("/home/ana/src/app");
+        let mut project =
+            Project::new("app".to_string(), old_repo.clone(), true, AgentKind::Claude);
+        project.features = vec![
+            feature("main", old_repo.clone(), false),
+            feature("kept", old_repo.join(".worktrees/kept"), true),
+            feature("gone", old_repo.join(".worktrees/gone"), true),
+        ];
+        project.features[2].parent_id = Some(project.features[1].id.clone());
+        let bookmark = SessionBookmark {
+            project_id: project.id.clone(),
+            feature_id: project.features[1].id.clone(),
+            session_id: project.features[1].sessions[0].id.clone(),
+        };
+        let mut exported = empty_store();
+        exported.projects.push(project);
+        exported.session_bookmarks.push(bookmark.clone());
+        exported.available_harnesses.push(AgentKind::Codex);
+        let export = StoreExport::new(&exported, Vec::new());
+        let mut options = ImportOptions {
+            mappings: vec![PathMapping {
+                from: old_repo.clone(),
+                to: repo.path().to_path_buf(),
+            }],
+            ..Default::default()
+        };
+
+        let mut target = empty_store();
+        let report = merge_store(&mut target, &export, &options, &MockWorktreeOps::new());
+
+        assert_eq!(report.projects, 1);
+        assert_eq!(report.features, 2);
+        assert_eq!(report.notes.len(), 1);
+        assert!(report.notes[0].contains("'app/gone'"), "{:?}", report.notes);
+        let imported = &target.projects[0];
+        assert_eq!(imported.repo, repo.path());
+        assert_eq!(imported.features[0].workdir, repo.path());
+        assert_eq!(imported.features[1].workdir, kept_dir);
+        assert!(
+            imported
+                .features
+                .iter()
+                .all(|f| f.status == ProjectStatus::Stopped)
+        );
+        assert_eq!(target.session_bookmarks, vec![bookmark]);
+        assert_eq!(target.available_harnesses, vec![AgentKind::Codex]);
+
+        // A second import skips the existing project; with
+        // --recreate-worktrees the missing worktree comes back.
+        let report = merge_store(&mut target, &export, &options, &MockWorktreeOps::new());
+        assert_eq!(report.projects, 0);
+        assert!(report.notes[0].contains("already exists"));
+
+        let mut worktree = MockWorktreeOps::new();
+        let expected = gone_dir.clone();
+        worktree
+            .expect_create_at()
+            .withf(move |_, path, branch| path == expected && branch == "gone")
+            .times(1)
+            .returning(|_, path, _| Ok(path.to_path_buf()));
+        options.recreate_worktrees = true;
+        let mut target = empty_store();
+        let report = merge_store(&mut target, &export, &options, &worktree);
+        assert_eq!(report.features, 3);
+        assert_eq!(report.recreated_worktrees, 1);
+        let gone = &target.projects[0].features[2];
+        assert_eq!(gone.workdir, gone_dir);
+        assert_eq!(
+            gone.parent_id,
+            Some(target.projects[0].features[1].id.clone())
+        );
+    }
+
+    #[test]
+    fn merge_presets_keeps_existing_names() {
+        let preset = |name: &str, agent: AgentKind| FeaturePreset {
+            name: name.to_string(),
+            agent,
+            ..Default::default()
+        };
+        let mut target = vec![preset("bugfix", AgentKind::Claude)];
+        let added = merge_presets(
+            &mut target,
+            &[
+                preset("bugfix", AgentKind::Codex),
+                preset("spike", AgentKind::Codex),
+            ],
+        );
+        assert_eq!(added, 1);
+        assert_eq!(target.len(), 2);
+        assert_eq!(target[0].agent, AgentKind::Claude);
+    }
+}
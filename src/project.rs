@@ -1,5 +1,6 @@
 use anyhow::{Context, Result, bail};
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::env;
@@ -12,7 +13,7 @@ use crate::worktree::WorktreeManager;
 
 pub(crate) const CURRENT_PROJECT_STORE_VERSION: u32 = 5;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ProjectStatus {
     Active,
@@ -30,7 +31,7 @@ impl std::fmt::Display for ProjectStatus {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum SessionKind {
     Claude,
@@ -43,7 +44,7 @@ pub enum SessionKind {
     Custom,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum AgentKind {
     #[default]
@@ -131,7 +132,7 @@ impl FeatureSession {
     }
 }
 
-#[derive(Debug, Clone, Serialize, PartialEq, Default)]
+#[derive(Debug, Clone, Serialize, PartialEq, Default, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum VibeMode {
     #[default]
@@ -216,6 +217,25 @@ fn is_false(value: &bool) -> bool {
     !*value
 }
 
+/// A worktree a multi-repo feature owns in one project's repository.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureRepo {
+    pub project_id: String,
+    pub repo: PathBuf,
+    pub workdir: PathBuf,
+}
+
+impl FeatureRepo {
+    /// Directory name of the worktree inside the feature's parent
+    /// directory, which is the project name at creation time.
+    pub fn label(&self) -> String {
+        self.workdir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| self.workdir.display().to_string())
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct Feature {
     pub id: String,
@@ -251,6 +271,27 @@ pub struct Feature {
     pub summary_updated_at: Option<DateTime<Utc>>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub nickname: Option<String>,
+    /// Id of the feature whose branch this one is stacked on.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    /// Free-form labels, normalized by [`normalize_tags`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    /// Multi-line notes on why the feature exists and what is left.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// When the feature was archived. Archived features keep their branch
+    /// and metadata but have no worktree or tmux session.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_at: Option<DateTime<Utc>>,
+    /// `.claude/latest-prompt.txt` from the removed worktree, written back
+    /// on restore.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archived_prompt: Option<String>,
+    /// Member worktrees of a multi-repo feature, one per project. `workdir`
+    /// is then the shared parent directory the agent runs in.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub repos: Vec<FeatureRepo>,
 }
 
 #[derive(Deserialize)]
@@ -286,6 +327,18 @@ struct FeatureDe {
     summary_updated_at: Option<DateTime<Utc>>,
     #[serde(default)]
     nickname: Option<String>,
+    #[serde(default)]
+    parent_id: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(default)]
+    notes: Option<String>,
+    #[serde(default)]
+    archived_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    archived_prompt: Option<String>,
+    #[serde(default)]
+    repos: Vec<FeatureRepo>,
 }
 
 impl<'de> Deserialize<'de> for Feature {
@@ -317,6 +370,12 @@ impl<'de> Deserialize<'de> for Feature {
             summary: feature.summary,
             summary_updated_at: feature.summary_updated_at,
             nickname: feature.nickname,
+            parent_id: feature.parent_id,
+            tags: feature.tags,
+            notes: feature.notes,
+            archived_at: feature.archived_at,
+            archived_prompt: feature.archived_prompt,
+            repos: feature.repos,
         })
     }
 }
@@ -358,6 +417,12 @@ impl Feature {
             summary: None,
             summary_updated_at: None,
             nickname: None,
+            parent_id: None,
+            tags: Vec::new(),
+            notes: None,
+            archived_at: None,
+            archived_prompt: None,
+            repos: Vec::new(),
         }
     }
 
@@ -482,6 +547,69 @@ impl Feature {
         self.sessions.push(session);
         self.sessions.last_mut().unwrap()
     }
+
+    pub fn is_archived(&self) -> bool {
+        self.archived_at.is_some()
+    }
+
+    pub fn is_multi_repo(&self) -> bool {
+        !self.repos.is_empty()
+    }
+
+    /// Worktree directories of the member repos; empty for a single-repo
+    /// feature.
+    pub fn member_workdirs(&self) -> Vec<PathBuf> {
+        self.repos.iter().map(|repo| repo.workdir.clone()).collect()
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t == tag)
+    }
+
+    /// First non-blank line of the notes, trimmed.
+    pub fn notes_headline(&self) -> Option<&str> {
+        self.notes
+            .as_deref()?
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+    }
+}
+
+/// Trims and lowercases `tags`, strips a leading `#`, and drops empty
+/// entries and duplicates, keeping the first occurrence. Tags cannot
+/// contain whitespace, `,`, or `:` so they stay usable in `tag:` queries.
+pub fn normalize_tags<I, S>(tags: I) -> Result<Vec<String>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut normalized: Vec<String> = Vec::new();
+    for tag in tags {
+        let tag = tag.as_ref().trim();
+        let tag = tag.strip_prefix('#').unwrap_or(tag).to_lowercase();
+        if tag.is_empty() {
+            continue;
+        }
+        if tag
+            .chars()
+            .any(|c| c.is_whitespace() || c == ',' || c == ':')
+        {
+            bail!(
+                "Invalid tag '{}': tags cannot contain spaces, ',' or ':'",
+                tag
+            );
+        }
+        if !normalized.contains(&tag) {
+            normalized.push(tag);
+        }
+    }
+    Ok(normalized)
+}
+
+/// Parses tags typed as a comma- or space-separated list.
+pub fn parse_tags(input: &str) -> Result<Vec<String>> {
+    normalize_tags(input.split(|c: char| c == ',' || c.is_whitespace()))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -511,6 +639,41 @@ impl Project {
             is_git,
         }
     }
+
+    /// The feature `feature` is stacked on, if it is still in this project.
+    pub fn parent_of(&self, feature: &Feature) -> Option<&Feature> {
+        let parent_id = feature.parent_id.as_deref()?;
+        self.features.iter().find(|f| f.id == parent_id)
+    }
+
+    /// Indices of the features stacked directly on `feature_id`.
+    pub fn stacked_children(&self, feature_id: &str) -> Vec<usize> {
+        self.features
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.parent_id.as_deref() == Some(feature_id))
+            .map(|(fi, _)| fi)
+            .collect()
+    }
+
+    /// Features feature `fi` is stacked on, nearest parent first.
+    pub fn ancestors(&self, fi: usize) -> Vec<&Feature> {
+        let mut ancestors = Vec::new();
+        let mut current = &self.features[fi];
+        // Bounded by the feature count so a corrupt parent cycle cannot hang.
+        while ancestors.len() < self.features.len()
+            && let Some(parent) = self.parent_of(current)
+        {
+            ancestors.push(parent);
+            current = parent;
+        }
+        ancestors
+    }
+
+    /// Number of stacked ancestors above feature `fi`.
+    pub fn stack_depth(&self, fi: usize) -> usize {
+        self.ancestors(fi).len()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -834,6 +997,12 @@ impl ProjectStore {
                             summary: None,
                             summary_updated_at: None,
                             nickname: None,
+                            parent_id: None,
+                            tags: Vec::new(),
+                            notes: None,
+                            archived_at: None,
+                            archived_prompt: None,
+                            repos: Vec::new(),
                         }
                     })
                     .collect();
@@ -907,7 +1076,14 @@ impl ProjectStore {
         if let Some(project) = self.find_project_mut(project_name)
             && let Some(idx) = project.features.iter().position(|f| f.name == feature_name)
         {
-            return Some(project.features.remove(idx));
+            let removed = project.features.remove(idx);
+            // Children of a removed feature move up to its parent.
+            for feature in &mut project.features {
+                if feature.parent_id.as_deref() == Some(removed.id.as_str()) {
+                    feature.parent_id = removed.parent_id.clone();
+                }
+            }
+            return Some(removed);
         }
         None
     }
@@ -1025,6 +1201,12 @@ mod tests {
             summary: None,
             summary_updated_at: None,
             nickname: None,
+            parent_id: None,
+            tags: Vec::new(),
+            notes: None,
+            archived_at: None,
+            archived_prompt: None,
+            repos: Vec::new(),
         }
     }
 
@@ -1378,6 +1560,111 @@ mod tests {
         assert_eq!(feat.next_window_name(&SessionKind::Claude), "claude-3");
     }
 
+    // ── Stacked features ──────────────────────────────────────
+
+    fn stacked_feature(id: &str, parent_id: Option<&str>) -> Feature {
+        Feature {
+            id: id.to_string(),
+            name: id.to_string(),
+            branch: id.to_string(),
+            parent_id: parent_id.map(str::to_string),
+            ..make_feature()
+        }
+    }
+
+    #[test]
+    fn stack_depth_counts_stacked_ancestors() {
+        let project = Project {
+            id: "proj-id".to_string(),
+            name: "my-project".to_string(),
+            repo: PathBuf::from("/tmp/test"),
+            collapsed: false,
+            features: vec![
+                stacked_feature("base", None),
+                stacked_feature("middle", Some("base")),
+                stacked_feature("top", Some("middle")),
+                stacked_feature("orphan", Some("deleted")),
+            ],
+            created_at: Utc::now(),
+            preferred_agent: AgentKind::default(),
+            is_git: true,
+        };
+
+        assert_eq!(project.stack_depth(0), 0);
+        assert_eq!(project.stack_depth(1), 1);
+        assert_eq!(project.stack_depth(2), 2);
+        assert_eq!(project.stack_depth(3), 0);
+        let ancestors: Vec<_> = project.ancestors(2).iter().map(|f| f.id.as_str()).collect();
+        assert_eq!(ancestors, vec!["middle", "base"]);
+        assert_eq!(project.stacked_children("base"), vec![1]);
+        assert_eq!(
+            project.parent_of(&project.features[2]).unwrap().id,
+            "middle"
+        );
+    }
+
+    #[test]
+    fn ancestors_stop_at_a_parent_cycle() {
+        let project = Project {
+            id: "proj-id".to_string(),
+            name: "my-project".to_string(),
+            repo: PathBuf::from("/tmp/test"),
+            collapsed: false,
+            features: vec![
+                stacked_feature("a", Some("b")),
+                stacked_feature("b", Some("a")),
+            ],
+            created_at: Utc::now(),
+            preferred_agent: AgentKind::default(),
+            is_git: true,
+        };
+
+        assert_eq!(project.ancestors(0).len(), 2);
+    }
+
+    #[test]
+    fn remove_feature_moves_stacked_children_to_its_parent() {
+        let mut store = ProjectStore {
+            version: CURRENT_PROJECT_STORE_VERSION,
+            projects: vec![Project {
+                id: "proj-id".to_string(),
+                name: "my-project".to_string(),
+                repo: PathBuf::from("/tmp/test"),
+                collapsed: false,
+                features: vec![
+                    stacked_feature("base", None),
+                    stacked_feature("middle", Some("base")),
+                    stacked_feature("top", Some("middle")),
+                ],
+                created_at: Utc::now(),
+                preferred_agent: AgentKind::default(),
+                is_git: true,
+            }],
+            session_bookmarks: vec![],
+            available_harnesses: vec![],
+            extra: HashMap::new(),
+        };
+
+        store.remove_feature("my-project", "middle").unwrap();
+
+        let features = &store.projects[0].features;
+        assert_eq!(features[1].id, "top");
+        assert_eq!(features[1].parent_id.as_deref(), Some("base"));
+    }
+
+    // ── Tags ──────────────────────────────────────────────────
+
+    #[test]
+    fn parse_tags_normalizes_and_dedupes() {
+        assert_eq!(
+            parse_tags(" Bug, #ui  bug,,Backend ").unwrap(),
+            vec!["bug", "ui", "backend"]
+        );
+        assert!(parse_tags("").unwrap().is_empty());
+        assert!(parse_tags("tag:bug").is_err());
+        assert!(normalize_tags(["needs review"]).is_err());
+    }
+
     // ── VibeMode::cli_flags ───────────────────────────────────
 
     #[test]
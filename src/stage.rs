@@ -0,0 +1,253 @@
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::{Result, bail};
+
+use crate::claude::ClaudeLauncher;
+use crate::diff::{DiffFile, DiffLineKind, git_output, git_output_with_input};
+
+/// Staged diff text sent to Claude when drafting a commit message.
+const DRAFT_PATCH_MAX_CHARS: usize = 20_000;
+
+/// Which half of the index the diff viewer's staging mode shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StageSide {
+    /// Worktree changes not in the index yet; applying a hunk stages it.
+    Unstaged,
+    /// Changes in the index; applying a hunk unstages it.
+    Staged,
+}
+
+impl StageSide {
+    pub fn toggled(self) -> Self {
+        match self {
+            StageSide::Unstaged => StageSide::Staged,
+            StageSide::Staged => StageSide::Unstaged,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StageSide::Unstaged => "unstaged",
+            StageSide::Staged => "staged",
+        }
+    }
+}
+
+pub fn load(workdir: &Path, side: StageSide) -> Result<Vec<DiffFile>> {
+    crate::diff::load_index_diff(workdir, side == StageSide::Staged)
+}
+
+/// Added and removed lines of `file` as `(hunk, line)` indices, in order.
+/// These are the positions the staging cursor moves through.
+pub fn change_lines(file: &DiffFile) -> Vec<(usize, usize)> {
+    file.hunks
+        .iter()
+        .enumerate()
+        .flat_map(|(hi, hunk)| {
+            hunk.lines
+                .iter()
+                .enumerate()
+                .filter(|(_, line)| {
+                    matches!(line.kind, DiffLineKind::Added | DiffLineKind::Removed)
+                })
+                .map(move |(li, _)| (hi, li))
+        })
+        .collect()
+}
+
+/// A patch holding hunk `hunk` of `file`, limited to the `selected` line
+/// indices when given. Unpicked changes are turned into context on the
+/// side the patch applies to and dropped on the other, so the patch still
+/// applies to the index with `git apply --cached` (reversed for
+/// [`StageSide::Staged`]).
+pub fn partial_patch(
+    file: &DiffFile,
+    hunk: usize,
+    selected: Option<&BTreeSet<usize>>,
+    side: StageSide,
+) -> Result<String> {
+    if file.is_binary {
+        bail!("{} is binary; stage the whole file instead", file.path);
+    }
+    let Some(source) = file.hunks.get(hunk) else {
+        bail!("{} has no hunk {}", file.path, hunk + 1);
+    };
+    // The index holds the old side of an unstaged diff and the new side of
+    // a staged one; unpicked lines on that side stay as context.
+    let kept_kind = match side {
+        StageSide::Unstaged => DiffLineKind::Removed,
+        StageSide::Staged => DiffLineKind::Added,
+    };
+
+    let mut body = Vec::new();
+    let mut picked = 0usize;
+    let (mut old_lines, mut new_lines) = (0usize, 0usize);
+    let mut previous_emitted = false;
+    for (index, line) in source.lines.iter().enumerate() {
+        let is_picked = selected.is_none_or(|selected| selected.contains(&index));
+        let text = match &line.kind {
+            DiffLineKind::NoNewlineMarker => {
+                if previous_emitted {
+                    body.push(line.text.clone());
+                }
+                continue;
+            }
+            DiffLineKind::Context => line.text.clone(),
+            _ if is_picked => {
+                picked += 1;
+                line.text.clone()
+            }
+            kind if *kind == kept_kind => format!(" {}", &line.text[1..]),
+            _ => {
+                previous_emitted = false;
+                continue;
+            }
+        };
+        match text.as_bytes().first() {
+            Some(b'+') => new_lines += 1,
+            Some(b'-') => old_lines += 1,
+            _ => {
+                old_lines += 1;
+                new_lines += 1;
+            }
+        }
+        body.push(text);
+        previous_emitted = true;
+    }
+    if picked == 0 {
+        bail!("No changed lines selected");
+    }
+
+    let prologue: Vec<&str> = file
+        .patch
+        .lines()
+        .take_while(|line| !line.starts_with("@@"))
+        .collect();
+    let mut patch = prologue.join("\n");
+    patch.push('\n');
+    patch.push_str(&format!(
+        "@@ -{},{} +{},{} @@\n",
+        source.old_start, old_lines, source.new_start, new_lines
+    ));
+    for line in body {
+        patch.push_str(&line);
+        patch.push('\n');
+    }
+    Ok(patch)
+}
+
+/// Stage a patch built from the unstaged diff, or unstage one built from
+/// the staged diff.
+pub fn apply(workdir: &Path, patch: &str, side: StageSide) -> Result<()> {
+    let mut args = vec!["apply", "--cached", "--recount", "--whitespace=nowarn"];
+    if side == StageSide::Staged {
+        args.push("--reverse");
+    }
+    git_output_with_input(workdir, &args, patch).map(|_| ())
+}
+
+/// Stage or unstage every change to `path`.
+pub fn apply_file(workdir: &Path, path: &str, side: StageSide) -> Result<()> {
+    let args: &[&str] = match side {
+        StageSide::Unstaged => &["add", "--all", "--", path],
+        StageSide::Staged => &["reset", "-q", "--", path],
+    };
+    git_output(workdir, args).map(|_| ())
+}
+
+/// Commit the index with `message` and return the new commit's short hash.
+pub fn commit(workdir: &Path, message: &str) -> Result<String> {
+    if message.trim().is_empty() {
+        bail!("Commit message is empty");
+    }
+    if load(workdir, StageSide::Staged)?.is_empty() {
+        bail!("Nothing is staged");
+    }
+    git_output_with_input(workdir, &["commit", "-q", "-F", "-"], message)?;
+    git_output(workdir, &["rev-parse", "--short", "HEAD"])
+}
+
+/// Ask Claude for a commit message describing what is staged.
+pub fn draft_message(workdir: &Path) -> Result<String> {
+    let staged = load(workdir, StageSide::Staged)?;
+    if staged.is_empty() {
+        bail!("Nothing is staged");
+    }
+    let mut patch: String = staged.iter().map(|file| file.patch.as_str()).collect();
+    if patch.len() > DRAFT_PATCH_MAX_CHARS {
+        let mut end = DRAFT_PATCH_MAX_CHARS;
+        while !patch.is_char_boundary(end) {
+            end -= 1;
+        }
+        patch.truncate(end);
+        patch.push_str("\n[diff truncated]\n");
+    }
+
+    let prompt = format!(
+        "Write a git commit message for the staged changes below. \
+         Use a short imperative subject line under 72 characters, then a \
+         blank line and a brief body only if the change needs explaining. \
+         Reply with the message only, no code fences or commentary.\n\n{}",
+        patch
+    );
+    let message = ClaudeLauncher::run_headless(workdir, &prompt)?;
+    let message = message.trim().trim_matches('`').trim().to_string();
+    if message.is_empty() {
+        bail!("Claude returned an empty commit message");
+    }
+    Ok(message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git_fixture::{git_ok, init_repo_with_src};
+
+    #[test]
+    fn stages_selected_lines_then_unstages_them() {
+        let repo = init_repo_with_src("a\nb\nc\nd\n");
+        std::fs::write(repo.path().join("src.txt"), "a\nB\nc\nD\n").unwrap();
+
+        let unstaged = load(repo.path(), StageSide::Unstaged).unwrap();
+        let file = &unstaged[0];
+        assert_eq!(file.hunks.len(), 1);
+        // Pick only the b -> B change: its removed and added lines.
+        let positions = change_lines(file);
+        assert_eq!(positions.len(), 4);
+        let picked: BTreeSet<usize> = positions[..2].iter().map(|(_, li)| *li).collect();
+        let patch = partial_patch(file, 0, Some(&picked), StageSide::Unstaged).unwrap();
+        apply(repo.path(), &patch, StageSide::Unstaged).unwrap();
+
+        assert_eq!(git_ok(repo.path(), &["show", ":src.txt"]), "a\nB\nc\nd");
+        let staged = load(repo.path(), StageSide::Staged).unwrap();
+        assert_eq!((staged[0].additions, staged[0].deletions), (1, 1));
+
+        let patch = partial_patch(&staged[0], 0, None, StageSide::Staged).unwrap();
+        apply(repo.path(), &patch, StageSide::Staged).unwrap();
+        assert!(load(repo.path(), StageSide::Staged).unwrap().is_empty());
+    }
+
+    #[test]
+    fn stages_part_of_an_untracked_file_and_commits_it() {
+        let repo = init_repo_with_src("a\n");
+        std::fs::write(repo.path().join("new.txt"), "one\ntwo\n").unwrap();
+
+        let unstaged = load(repo.path(), StageSide::Unstaged).unwrap();
+        let file = unstaged.iter().find(|file| file.path == "new.txt").unwrap();
+        let picked = BTreeSet::from([0]);
+        let patch = partial_patch(file, 0, Some(&picked), StageSide::Unstaged).unwrap();
+        apply(repo.path(), &patch, StageSide::Unstaged).unwrap();
+
+        assert!(commit(repo.path(), "  ").is_err());
+        let hash = commit(repo.path(), "Add new.txt\n").unwrap();
+        assert_eq!(hash, git_ok(repo.path(), &["rev-parse", "--short", "HEAD"]));
+        assert_eq!(git_ok(repo.path(), &["show", "HEAD:new.txt"]), "one");
+        assert!(commit(repo.path(), "Again").is_err());
+
+        apply_file(repo.path(), "new.txt", StageSide::Unstaged).unwrap();
+        assert_eq!(load(repo.path(), StageSide::Staged).unwrap().len(), 1);
+        apply_file(repo.path(), "new.txt", StageSide::Staged).unwrap();
+        assert!(load(repo.path(), StageSide::Staged).unwrap().is_empty());
+    }
+}
@@ -1448,6 +1448,31 @@ impl TmuxManager {
             .collect())
     }
 
+    /// List each window of a session with the command running in its
+    /// active pane, as `(window_name, pane_current_command)`.
+    pub fn list_window_commands(session: &str) -> Result<Vec<(String, String)>> {
+        let output = Self::command()
+            .args([
+                "list-windows",
+                "-t",
+                session,
+                "-F",
+                "#{window_name}\t#{pane_current_command}",
+            ])
+            .output()
+            .context("Failed to list tmux windows")?;
+
+        if !output.status.success() {
+            return Ok(Vec::new());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.split_once('\t'))
+            .map(|(window, command)| (window.to_string(), command.to_string()))
+            .collect())
+    }
+
     /// Launch Claude Code in a specific window of a session
     pub fn launch_claude(
         session: &str,
@@ -1649,6 +1674,23 @@ impl TmuxManager {
         }
     }
 
+    /// List every tmux session, not only `amf-*` ones, with its
+    /// starting directory.
+    pub fn list_session_paths() -> Result<Vec<(String, PathBuf)>> {
+        let output = Self::command()
+            .args(["list-sessions", "-F", "#{session_name}\t#{session_path}"])
+            .output();
+
+        match output {
+            Ok(o) if o.status.success() => Ok(String::from_utf8_lossy(&o.stdout)
+                .lines()
+                .filter_map(|line| line.split_once('\t'))
+                .map(|(name, path)| (name.to_string(), PathBuf::from(path)))
+                .collect()),
+            _ => Ok(Vec::new()),
+        }
+    }
+
     /// Capture the current pane content of a session's window
     pub fn capture_pane(session: &str, window: &str) -> Result<String> {
         let target = format!("{}:{}", session, window);
@@ -1939,6 +1981,14 @@ impl TmuxOps for TmuxManager {
         TmuxManager::list_sessions()
     }
 
+    fn list_session_paths(&self) -> Result<Vec<(String, PathBuf)>> {
+        TmuxManager::list_session_paths()
+    }
+
+    fn list_window_commands(&self, session: &str) -> Result<Vec<(String, String)>> {
+        TmuxManager::list_window_commands(session)
+    }
+
     fn create_session_with_window(
         &self,
         session: &str,
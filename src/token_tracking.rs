@@ -1,4 +1,5 @@
 use chrono::{DateTime, Utc};
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::collections::{HashMap, HashSet};
@@ -9,7 +10,7 @@ use std::time::{Duration, Instant};
 
 use crate::project::SessionKind;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum TokenUsageProvider {
     Claude,
@@ -17,13 +18,13 @@ pub enum TokenUsageProvider {
     Codex,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash, JsonSchema)]
 pub struct TokenUsageSource {
     pub provider: TokenUsageProvider,
     pub id: String,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, PartialEq, Eq, JsonSchema)]
 pub struct SessionTokenUsage {
     pub source: TokenUsageSource,
     pub input_tokens: u64,
@@ -112,6 +113,13 @@ impl SessionTokenTracker {
             .collect()
     }
 
+    /// Return the last usage read for `source` without touching disk.
+    pub fn cached_usage(&self, source: &TokenUsageSource) -> Option<SessionTokenUsage> {
+        self.usage_cache
+            .get(source)
+            .and_then(|entry| entry.usage.clone())
+    }
+
     pub fn discover_source(
         &mut self,
         session_kind: &SessionKind,
@@ -1,6 +1,8 @@
 use anyhow::Result;
 use std::path::{Path, PathBuf};
 
+use crate::worktree::WorktreeInfo;
+
 /// Abstraction over tmux operations, enabling mocking in tests.
 ///
 /// Methods mirror the corresponding `TmuxManager` statics. Using owned
@@ -11,6 +13,8 @@ use std::path::{Path, PathBuf};
 pub trait TmuxOps: Send + Sync {
     fn session_exists(&self, session: &str) -> bool;
     fn list_sessions(&self) -> Result<Vec<String>>;
+    fn list_session_paths(&self) -> Result<Vec<(String, PathBuf)>>;
+    fn list_window_commands(&self, session: &str) -> Result<Vec<(String, String)>>;
     fn create_session_with_window(
         &self,
         session: &str,
@@ -49,6 +53,7 @@ pub trait TmuxOps: Send + Sync {
 pub trait WorktreeOps: Send + Sync {
     fn repo_root(&self, path: &Path) -> Result<PathBuf>;
     fn create(&self, repo: &Path, name: &str, branch: &str) -> Result<PathBuf>;
+    fn create_at(&self, repo: &Path, path: &Path, branch: &str) -> Result<PathBuf>;
     fn create_from(
         &self,
         repo: &Path,
@@ -60,4 +65,7 @@ pub trait WorktreeOps: Send + Sync {
         let _ = base;
         self.create(repo, name, new_branch)
     }
+    fn list(&self, repo: &Path) -> Result<Vec<WorktreeInfo>>;
+    fn rebase(&self, workdir: &Path, onto: &str) -> Result<()>;
+    fn remove(&self, repo: &Path, worktree_path: &Path) -> Result<()>;
 }
@@ -112,6 +112,8 @@ fn build_opencode_sidebar_data(
         work_text,
         todos_text,
         summary_text,
+        git_text: sidebar_git_text(app, feature),
+        notes_text: sidebar_notes_text(feature),
     })
 }
 
@@ -149,6 +151,8 @@ fn build_claude_sidebar_data(
         work_text,
         todos_text,
         summary_text,
+        git_text: sidebar_git_text(app, feature),
+        notes_text: sidebar_notes_text(feature),
     })
 }
 
@@ -192,9 +196,25 @@ fn build_codex_sidebar_data(
         work_text,
         todos_text: None,
         summary_text,
+        git_text: sidebar_git_text(app, feature),
+        notes_text: sidebar_notes_text(feature),
     })
 }
 
+fn sidebar_git_text(app: &App, feature: &Feature) -> Option<String> {
+    app.git_summary(&feature.id)
+        .map(|summary| summary.sidebar_text(chrono::Utc::now()))
+}
+
+fn sidebar_notes_text(feature: &Feature) -> Option<String> {
+    feature
+        .notes
+        .as_deref()
+        .map(str::trim_end)
+        .filter(|notes| !notes.trim().is_empty())
+        .map(ToOwned::to_owned)
+}
+
 fn opencode_sidebar_status_text(
     activity_line: String,
     usage_line: Option<String>,
@@ -735,12 +755,24 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         return;
     }
 
-    if let AppMode::DiffViewer(state) = &app.mode {
-        draw_view_pane(frame, app, &state.from_view, false, false);
+    if let AppMode::DiffViewer(state) = &app.mode
+        && let Some(view) = &state.from_view
+    {
+        draw_view_pane(frame, app, view, false, false);
         super::dialogs::draw_diff_viewer(frame, state, &app.theme);
         return;
     }
 
+    if let AppMode::CommitMessage(state) = &app.mode
+        && let Some(view) = state.viewer.from_view.clone()
+    {
+        draw_view_pane(frame, app, &view, false, false);
+        if let AppMode::CommitMessage(state) = &mut app.mode {
+            super::dialogs::draw_commit_message_dialog(frame, state, &app.theme);
+        }
+        return;
+    }
+
     let markdown_from_view = if let AppMode::MarkdownViewer(state) = &app.mode {
         state.from_view.clone()
     } else {
@@ -898,6 +930,14 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         super::dialogs::draw_rename_feature_dialog(frame, state, &app.theme);
     }
 
+    if let AppMode::EditingFeatureTags(state) = &app.mode {
+        super::dialogs::draw_feature_tags_dialog(frame, state, &app.theme);
+    }
+
+    if let AppMode::EditingFeatureNotes(state) = &mut app.mode {
+        super::dialogs::draw_feature_notes_dialog(frame, state, &app.theme);
+    }
+
     if let AppMode::SessionConfig(state) = &app.mode {
         super::dialogs::draw_session_config_dialog(frame, state, &app.theme);
     }
@@ -958,6 +998,18 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         super::picker::draw_bookmark_picker(frame, state, &rows, &app.theme);
     }
 
+    if let AppMode::ImportPicker(state) = &app.mode {
+        super::picker::draw_import_picker(frame, state, &app.theme);
+    }
+
+    if let AppMode::DiffViewer(state) = &app.mode {
+        super::dialogs::draw_diff_viewer(frame, state, &app.theme);
+    }
+
+    if let AppMode::CommitMessage(state) = &mut app.mode {
+        super::dialogs::draw_commit_message_dialog(frame, state, &app.theme);
+    }
+
     if let AppMode::DiffReviewPrompt(state) = &app.mode {
         super::dialogs::draw_diff_review_dialog(frame, state, &app.throbber_state, &app.theme);
     }
@@ -984,6 +1036,18 @@ pub fn draw(frame: &mut Frame, app: &mut App) {
         );
     }
 
+    if let AppMode::LandingFeature(state) = &app.mode {
+        super::dialogs::draw_land_feature_dialog(frame, state, &app.theme);
+    }
+
+    if let AppMode::ReviewingOverlaps(state) = &app.mode {
+        super::dialogs::draw_overlap_dialog(frame, state, &app.theme);
+    }
+
+    if let AppMode::ReviewingCheckpoints(state) = &app.mode {
+        super::dialogs::draw_checkpoint_dialog(frame, state, &app.theme);
+    }
+
     if let AppMode::ThemePicker(state) = &app.mode {
         super::dialogs::draw_theme_picker(
             frame,
@@ -1236,6 +1300,12 @@ mod tests {
             summary: None,
             summary_updated_at: None,
             nickname: None,
+            parent_id: None,
+            tags: Vec::new(),
+            notes: None,
+            archived_at: None,
+            archived_prompt: None,
+            repos: Vec::new(),
         };
         let project = Project {
             id: "proj-1".into(),
@@ -1324,6 +1394,12 @@ mod tests {
             summary: None,
             summary_updated_at: None,
             nickname: None,
+            parent_id: None,
+            tags: Vec::new(),
+            notes: None,
+            archived_at: None,
+            archived_prompt: None,
+            repos: Vec::new(),
         };
         let project = Project {
             id: "proj-1".into(),
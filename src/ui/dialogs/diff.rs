@@ -5,17 +5,23 @@ use ratatui::{
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap},
 };
+use std::collections::BTreeSet;
 use std::path::Path;
 use unicode_width::UnicodeWidthStr;
 
 use crate::{
-    app::{DiffViewerFocus, DiffViewerLayout, DiffViewerState},
+    app::{
+        CommitMessageState, DiffViewerFocus, DiffViewerLayout, DiffViewerReturn, DiffViewerState,
+    },
     diff::{DiffFile, DiffFileStatus, DiffLine, DiffLineKind},
+    editor::VimMode,
     highlight,
+    stage::StageSide,
     theme::Theme,
 };
 
 use super::super::dashboard::centered_rect;
+use super::feature::{count_wrapped_editor_lines, editor_lines, sync_editor_scroll};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 struct StyledChunk {
@@ -38,8 +44,15 @@ pub fn draw_diff_viewer(frame: &mut Frame, state: &DiffViewerState, theme: &Them
         theme.primary.to_color()
     };
 
+    let title = match &state.return_to {
+        Some(DiffViewerReturn::Land(_)) => " Land Conflicts ",
+        Some(DiffViewerReturn::Overlaps(_)) => " Overlapping Changes ",
+        Some(DiffViewerReturn::Checkpoints(_)) => " Changes Since Checkpoint ",
+        None if state.staging.is_some() => " Stage Changes ",
+        None => " Branch Diff ",
+    };
     let block = Block::default()
-        .title(" Branch Diff ")
+        .title(title)
         .borders(Borders::ALL)
         .style(Style::default().bg(theme.effective_bg()))
         .border_style(Style::default().fg(border_color));
@@ -60,6 +73,75 @@ pub fn draw_diff_viewer(frame: &mut Frame, state: &DiffViewerState, theme: &Them
     draw_footer(frame, chunks[2], state, theme);
 }
 
+/// Commit message editor drawn over the staging view it was opened from.
+pub fn draw_commit_message_dialog(
+    frame: &mut Frame,
+    state: &mut CommitMessageState,
+    theme: &Theme,
+) {
+    draw_diff_viewer(frame, &state.viewer, theme);
+
+    let area = centered_rect(64, 45, frame.area());
+    crate::ui::draw_modal_overlay(frame, area, theme);
+
+    let title = match state.editor.vim_mode() {
+        Some(VimMode::Insert) => " Commit Message [Vim Insert] ",
+        Some(VimMode::Normal) => " Commit Message [Vim Normal] ",
+        None => " Commit Message ",
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.effective_bg()))
+        .border_style(Style::default().fg(theme.primary.to_color()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(inner);
+
+    let placeholder = if state.draft_rx.is_some() {
+        "Claude is drafting a message from the staged diff..."
+    } else {
+        "Subject line, blank line, then an optional body"
+    };
+    let text = editor_lines(&state.editor, placeholder, theme);
+    let visible_lines = chunks[0].height as usize;
+    let wrap_width = chunks[0].width as usize;
+    let total_visual_lines = count_wrapped_editor_lines(&text, wrap_width);
+    sync_editor_scroll(
+        &state.editor,
+        &mut state.scroll_offset,
+        &mut state.sync_scroll_to_cursor,
+        visible_lines,
+        wrap_width,
+        total_visual_lines,
+    );
+    let message = Paragraph::new(text)
+        .wrap(Wrap { trim: false })
+        .scroll((state.scroll_offset.min(u16::MAX as usize) as u16, 0));
+    frame.render_widget(message, chunks[0]);
+
+    let key = Style::default().fg(theme.warning.to_color());
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled("Tab", key),
+        Span::raw(" commit  "),
+        Span::styled("Ctrl+D", key),
+        Span::raw(" draft with Claude  "),
+        Span::styled("Ctrl+V", key),
+        Span::raw(if state.editor.vim_mode().is_some() {
+            " vim off  "
+        } else {
+            " vim on  "
+        }),
+        Span::styled("Ctrl+Q", key),
+        Span::raw(" back"),
+    ]));
+    frame.render_widget(hints, chunks[1]);
+}
+
 fn draw_header(frame: &mut Frame, area: Rect, state: &DiffViewerState, theme: &Theme) {
     let branch = if state.branch.is_empty() {
         "(unknown branch)"
@@ -79,6 +161,11 @@ fn draw_header(frame: &mut Frame, area: Rect, state: &DiffViewerState, theme: &T
     };
     let additions: usize = state.files.iter().map(|file| file.additions).sum();
     let deletions: usize = state.files.iter().map(|file| file.deletions).sum();
+    let (versus, base) = match state.staging.as_ref().map(|staging| staging.side) {
+        Some(StageSide::Unstaged) => ("  working tree vs  ", "index".to_string()),
+        Some(StageSide::Staged) => ("  staged vs  ", "HEAD".to_string()),
+        None => ("  vs  ", format!("{base}{commit}")),
+    };
 
     let header = Paragraph::new(vec![
         Line::from(vec![
@@ -89,9 +176,9 @@ fn draw_header(frame: &mut Frame, area: Rect, state: &DiffViewerState, theme: &T
                     .fg(theme.project_title.to_color())
                     .add_modifier(Modifier::BOLD),
             ),
-            Span::styled("  vs  ", Style::default().fg(theme.text_muted.to_color())),
+            Span::styled(versus, Style::default().fg(theme.text_muted.to_color())),
             Span::styled(
-                format!("{base}{commit}"),
+                base,
                 Style::default()
                     .fg(theme.primary.to_color())
                     .add_modifier(Modifier::BOLD),
@@ -251,6 +338,16 @@ fn draw_patch(frame: &mut Frame, area: Rect, state: &DiffViewerState, theme: &Th
     };
     let file = state.files.get(state.selected_file);
     let effective_layout = effective_layout(state);
+    let selection = state
+        .staging
+        .as_ref()
+        .zip(file)
+        .map(|(staging, file)| PatchSelection {
+            cursor: crate::stage::change_lines(file)
+                .get(staging.cursor)
+                .copied(),
+            marked: staging.marked.clone(),
+        });
     let title = file
         .map(|file| {
             if is_new_diff_file(file) {
@@ -272,11 +369,18 @@ fn draw_patch(frame: &mut Frame, area: Rect, state: &DiffViewerState, theme: &Th
             scroll: state.patch_scroll,
             include_prologue: true,
             new_file_presentation: file.map(is_new_diff_file).unwrap_or(false),
+            selection,
         },
         theme,
     );
 }
 
+/// Staging cursor and picked lines as `(hunk, line)` indices.
+pub(crate) struct PatchSelection {
+    pub cursor: Option<(usize, usize)>,
+    pub marked: BTreeSet<(usize, usize)>,
+}
+
 pub(crate) struct PatchPanelOptions {
     pub layout: DiffViewerLayout,
     pub title: String,
@@ -284,6 +388,9 @@ pub(crate) struct PatchPanelOptions {
     pub scroll: usize,
     pub include_prologue: bool,
     pub new_file_presentation: bool,
+    /// Set in staging mode; the panel then scrolls to keep the cursor in
+    /// view instead of using `scroll`.
+    pub selection: Option<PatchSelection>,
 }
 
 pub(crate) fn draw_patch_panel(
@@ -324,13 +431,20 @@ pub(crate) fn draw_patch_panel(
             frame.render_widget(Paragraph::new(lines).block(block).scroll((scroll, 0)), area);
         }
         Some(file) => {
-            let lines = patch_lines(
+            let (lines, cursor_row) = patch_lines_with_selection(
                 file,
                 area.width.saturating_sub(2),
                 theme,
                 options.include_prologue,
                 options.new_file_presentation,
+                options.selection.as_ref(),
             );
+            let scroll = match cursor_row {
+                Some(row) => {
+                    u16::try_from(row.saturating_sub(area.height as usize / 3)).unwrap_or(u16::MAX)
+                }
+                None => scroll,
+            };
             frame.render_widget(
                 Paragraph::new(lines)
                     .block(block)
@@ -364,17 +478,44 @@ fn draw_footer(frame: &mut Frame, area: Rect, state: &DiffViewerState, theme: &T
         .files
         .get(state.selected_file)
         .and_then(|file| highlight::language_install_state_for_path(Path::new(&file.path)));
-    let footer = Paragraph::new(diff_footer_lines(
-        focus,
-        layout,
-        new_file_selected,
-        syntax_status,
-        theme,
-    ))
-    .wrap(Wrap { trim: false });
+    let mut lines = diff_footer_lines(focus, layout, new_file_selected, syntax_status, theme);
+    if let Some(staging) = &state.staging {
+        lines[1] = staging_footer_line(staging.side, theme);
+    } else if state.return_to.is_none() && state.members.is_empty() {
+        lines[1].spans.extend([
+            Span::raw("  "),
+            Span::styled("S", Style::default().fg(theme.warning.to_color())),
+            Span::raw(" stage"),
+        ]);
+    }
+    let footer = Paragraph::new(lines).wrap(Wrap { trim: false });
     frame.render_widget(footer, area);
 }
 
+fn staging_footer_line(side: StageSide, theme: &Theme) -> Line<'static> {
+    let key = Style::default().fg(theme.warning.to_color());
+    let apply = match side {
+        StageSide::Unstaged => " stage  ",
+        StageSide::Staged => " unstage  ",
+    };
+    Line::from(vec![
+        Span::styled(" j/k", key),
+        Span::raw(" line  "),
+        Span::styled("PgUp/PgDn", key),
+        Span::raw(" hunk  "),
+        Span::styled("Space", key),
+        Span::raw(" pick  "),
+        Span::styled("Enter", key),
+        Span::raw(apply),
+        Span::styled("t", key),
+        Span::raw(format!(" showing:{}  ", side.label())),
+        Span::styled("c", key),
+        Span::raw(" commit  "),
+        Span::styled("S", key),
+        Span::raw(" branch diff"),
+    ])
+}
+
 fn diff_footer_lines(
     focus: &str,
     layout: &str,
@@ -451,15 +592,36 @@ fn patch_lines(
     include_prologue: bool,
     new_file_presentation: bool,
 ) -> Vec<Line<'static>> {
+    patch_lines_with_selection(
+        file,
+        width,
+        theme,
+        include_prologue,
+        new_file_presentation,
+        None,
+    )
+    .0
+}
+
+/// Unified patch lines with the staging cursor and picked lines marked in
+/// the gutter. Also returns the row the cursor's line starts on.
+fn patch_lines_with_selection(
+    file: &DiffFile,
+    width: u16,
+    theme: &Theme,
+    include_prologue: bool,
+    new_file_presentation: bool,
+    selection: Option<&PatchSelection>,
+) -> (Vec<Line<'static>>, Option<usize>) {
     let content_width = width as usize;
     if file.is_binary || file.hunks.is_empty() || content_width < 16 {
-        return raw_patch_wrapped_lines(file, content_width, theme);
+        return (raw_patch_wrapped_lines(file, content_width, theme), None);
     }
 
     let number_width = line_number_width(file);
     let gutter_width = number_width * 2 + 4;
     if content_width <= gutter_width + 4 {
-        return raw_patch_wrapped_lines(file, content_width, theme);
+        return (raw_patch_wrapped_lines(file, content_width, theme), None);
     }
     let text_width = content_width - gutter_width;
     let highlights = file_highlights(file);
@@ -475,6 +637,7 @@ fn patch_lines(
     };
 
     let mut lines = Vec::new();
+    let mut cursor_row = None;
     if include_prologue {
         for meta in patch_prologue(file) {
             lines.extend(wrap_gutter_line(
@@ -503,7 +666,8 @@ fn patch_lines(
 
         let mut old_line = hunk.old_start;
         let mut new_line = hunk.new_start;
-        for diff_line in &hunk.lines {
+        for (line_idx, diff_line) in hunk.lines.iter().enumerate() {
+            let row = lines.len();
             match diff_line.kind {
                 DiffLineKind::Context => {
                     lines.extend(wrap_gutter_line(
@@ -566,10 +730,43 @@ fn patch_lines(
                     ));
                 }
             }
+            if let Some(selection) = selection
+                && let Some(line) = lines.get_mut(row)
+            {
+                let position = (idx, line_idx);
+                let is_cursor = selection.cursor == Some(position);
+                if is_cursor {
+                    cursor_row = Some(row);
+                }
+                mark_gutter(line, is_cursor, selection.marked.contains(&position), theme);
+            }
         }
     }
 
-    lines
+    (lines, cursor_row)
+}
+
+/// Replace the `│ ` gutter separator with the staging cursor and pick
+/// markers.
+fn mark_gutter(line: &mut Line<'static>, is_cursor: bool, is_marked: bool, theme: &Theme) {
+    if !is_cursor && !is_marked {
+        return;
+    }
+    let Some(separator) = line.spans.get_mut(4) else {
+        return;
+    };
+    let marker = format!(
+        "{}{}",
+        if is_cursor { "▶" } else { "│" },
+        if is_marked { "●" } else { " " }
+    );
+    *separator = Span::styled(
+        marker,
+        separator
+            .style
+            .fg(theme.warning.to_color())
+            .add_modifier(Modifier::BOLD),
+    );
 }
 
 fn side_by_side_lines(
@@ -736,6 +933,9 @@ fn format_hunk_range(start: usize, len: usize) -> String {
 }
 
 fn effective_layout(state: &DiffViewerState) -> DiffViewerLayout {
+    if state.staging.is_some() {
+        return DiffViewerLayout::Unified;
+    }
     state
         .files
         .get(state.selected_file)
@@ -11,11 +11,13 @@ use ratatui::{
 use unicode_width::UnicodeWidthStr;
 
 use crate::app::{
-    CreateFeatureState, CreateFeatureStep, DeleteStage, DeletingFeatureState, ForkFeatureState,
-    ForkFeatureStep, PromptAnalysis, SteeringPromptState,
+    CheckpointDialogState, CreateFeatureState, CreateFeatureStep, DeleteStage,
+    DeletingFeatureState, FeatureNotesState, ForkFeatureState, ForkFeatureStep, LandFeatureState,
+    OverlapDialogState, PromptAnalysis, SteeringPromptState,
 };
 use crate::editor::{TextEditor, VimMode};
 use crate::extension::FeaturePreset;
+use crate::land::LandStrategy;
 use crate::project::{AgentKind, VibeMode};
 use crate::theme::Theme;
 
@@ -950,7 +952,11 @@ pub fn draw_steering_prompt_dialog(
     .wrap(Wrap { trim: false });
     frame.render_widget(summary, chunks[0]);
 
-    let prompt_text = steering_editor_lines(&state.editor, theme);
+    let prompt_text = editor_lines(
+        &state.editor,
+        "Describe the task, then add boundaries, validation, and watch-outs.",
+        theme,
+    );
     let prompt_title = match state.editor.vim_mode() {
         Some(VimMode::Insert) => " Prompt To Inject [Vim Insert] ",
         Some(VimMode::Normal) => " Prompt To Inject [Vim Normal] ",
@@ -964,12 +970,19 @@ pub fn draw_steering_prompt_dialog(
     let prompt_inner = prompt_block.inner(chunks[1]);
     let visible_lines = prompt_inner.height as usize;
     let mut wrap_width = prompt_inner.width as usize;
-    let mut total_visual_lines = count_wrapped_steering_lines(&prompt_text, wrap_width);
+    let mut total_visual_lines = count_wrapped_editor_lines(&prompt_text, wrap_width);
     if total_visual_lines > visible_lines && wrap_width > 1 {
         wrap_width -= 1;
-        total_visual_lines = count_wrapped_steering_lines(&prompt_text, wrap_width);
+        total_visual_lines = count_wrapped_editor_lines(&prompt_text, wrap_width);
     }
-    sync_steering_prompt_scroll(state, visible_lines, wrap_width, total_visual_lines);
+    sync_editor_scroll(
+        &state.editor,
+        &mut state.scroll_offset,
+        &mut state.sync_scroll_to_cursor,
+        visible_lines,
+        wrap_width,
+        total_visual_lines,
+    );
 
     let prompt = Paragraph::new(prompt_text)
         .block(prompt_block)
@@ -1081,7 +1094,11 @@ pub fn draw_steering_prompt_dialog(
     frame.render_widget(hints, chunks[6]);
 }
 
-fn steering_editor_lines(editor: &TextEditor, theme: &Theme) -> Vec<Line<'static>> {
+pub(super) fn editor_lines(
+    editor: &TextEditor,
+    placeholder: &str,
+    theme: &Theme,
+) -> Vec<Line<'static>> {
     if editor.text().is_empty() {
         return vec![
             Line::from(Span::styled(
@@ -1089,7 +1106,7 @@ fn steering_editor_lines(editor: &TextEditor, theme: &Theme) -> Vec<Line<'static
                 Style::default().fg(theme.primary.to_color()),
             )),
             Line::from(Span::styled(
-                "Describe the task, then add boundaries, validation, and watch-outs.",
+                placeholder.to_string(),
                 Style::default().fg(theme.text_muted.to_color()),
             )),
         ];
@@ -1127,27 +1144,29 @@ fn char_col_to_byte_idx(text: &str, char_col: usize) -> usize {
         .unwrap_or(text.len())
 }
 
-fn sync_steering_prompt_scroll(
-    state: &mut SteeringPromptState,
+pub(super) fn sync_editor_scroll(
+    editor: &TextEditor,
+    scroll_offset: &mut usize,
+    sync_scroll_to_cursor: &mut bool,
     visible_lines: usize,
     wrap_width: usize,
     total_visual_lines: usize,
 ) {
-    if state.sync_scroll_to_cursor && visible_lines > 0 && wrap_width > 0 {
-        let cursor_row = steering_cursor_visual_row(&state.editor, wrap_width);
-        if cursor_row < state.scroll_offset {
-            state.scroll_offset = cursor_row;
-        } else if cursor_row >= state.scroll_offset.saturating_add(visible_lines) {
-            state.scroll_offset = cursor_row + 1 - visible_lines;
+    if *sync_scroll_to_cursor && visible_lines > 0 && wrap_width > 0 {
+        let cursor_row = editor_cursor_visual_row(editor, wrap_width);
+        if cursor_row < *scroll_offset {
+            *scroll_offset = cursor_row;
+        } else if cursor_row >= scroll_offset.saturating_add(visible_lines) {
+            *scroll_offset = cursor_row + 1 - visible_lines;
         }
-        state.sync_scroll_to_cursor = false;
+        *sync_scroll_to_cursor = false;
     }
 
     let max_scroll = total_visual_lines.saturating_sub(visible_lines);
-    state.scroll_offset = state.scroll_offset.min(max_scroll);
+    *scroll_offset = (*scroll_offset).min(max_scroll);
 }
 
-fn count_wrapped_steering_lines(lines: &[Line<'static>], width: usize) -> usize {
+pub(super) fn count_wrapped_editor_lines(lines: &[Line<'static>], width: usize) -> usize {
     if width == 0 {
         return 0;
     }
@@ -1165,7 +1184,7 @@ fn count_wrapped_steering_lines(lines: &[Line<'static>], width: usize) -> usize
         .sum()
 }
 
-fn steering_cursor_visual_row(editor: &TextEditor, width: usize) -> usize {
+fn editor_cursor_visual_row(editor: &TextEditor, width: usize) -> usize {
     if width == 0 {
         return 0;
     }
@@ -1194,6 +1213,98 @@ fn steering_cursor_visual_row(editor: &TextEditor, width: usize) -> usize {
     wrapped_before_cursor + UnicodeWidthStr::width(cursor_prefix).div_euclid(width)
 }
 
+pub fn draw_feature_notes_dialog(frame: &mut Frame, state: &mut FeatureNotesState, theme: &Theme) {
+    let area = centered_rect(70, 60, frame.area());
+    crate::ui::draw_modal_overlay(frame, area, theme);
+
+    let title = match state.editor.vim_mode() {
+        Some(VimMode::Insert) => format!(" Notes ({}) [Vim Insert] ", state.feature_name),
+        Some(VimMode::Normal) => format!(" Notes ({}) [Vim Normal] ", state.feature_name),
+        None => format!(" Notes ({}) ", state.feature_name),
+    };
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.effective_bg()))
+        .border_style(Style::default().fg(theme.primary.to_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(3), Constraint::Length(1)])
+        .split(inner);
+
+    let notes_text = editor_lines(
+        &state.editor,
+        "Why does this feature exist? What is left to do?",
+        theme,
+    );
+    let visible_lines = chunks[0].height as usize;
+    let mut wrap_width = chunks[0].width as usize;
+    let mut total_visual_lines = count_wrapped_editor_lines(&notes_text, wrap_width);
+    if total_visual_lines > visible_lines && wrap_width > 1 {
+        wrap_width -= 1;
+        total_visual_lines = count_wrapped_editor_lines(&notes_text, wrap_width);
+    }
+    sync_editor_scroll(
+        &state.editor,
+        &mut state.scroll_offset,
+        &mut state.sync_scroll_to_cursor,
+        visible_lines,
+        wrap_width,
+        total_visual_lines,
+    );
+
+    let notes = Paragraph::new(notes_text)
+        .wrap(Wrap { trim: false })
+        .scroll((state.scroll_offset.min(u16::MAX as usize) as u16, 0));
+    frame.render_widget(notes, chunks[0]);
+
+    if total_visual_lines > visible_lines {
+        let scrollbar = Scrollbar::default()
+            .orientation(ScrollbarOrientation::VerticalRight)
+            .begin_symbol(Some("↑"))
+            .end_symbol(Some("↓"));
+        let mut scrollbar_state = ScrollbarState::new(total_visual_lines)
+            .position(state.scroll_offset)
+            .viewport_content_length(visible_lines);
+        frame.render_stateful_widget(scrollbar, chunks[0], &mut scrollbar_state);
+    }
+
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(
+            if matches!(state.editor.vim_mode(), Some(VimMode::Normal)) {
+                "i / a / o"
+            } else {
+                "Esc"
+            },
+            Style::default().fg(theme.warning.to_color()),
+        ),
+        Span::raw(
+            if matches!(state.editor.vim_mode(), Some(VimMode::Normal)) {
+                " edit  "
+            } else {
+                " normal  "
+            },
+        ),
+        Span::styled("Ctrl+J/K", Style::default().fg(theme.warning.to_color())),
+        Span::raw(" scroll  "),
+        Span::styled("Ctrl+V", Style::default().fg(theme.warning.to_color())),
+        Span::raw(if state.editor.vim_mode().is_some() {
+            " vim off  "
+        } else {
+            " vim on  "
+        }),
+        Span::styled("Tab", Style::default().fg(theme.warning.to_color())),
+        Span::raw(" save  "),
+        Span::styled("Ctrl+Q", Style::default().fg(theme.warning.to_color())),
+        Span::raw(" close"),
+    ]));
+    frame.render_widget(hints, chunks[1]);
+}
+
 pub fn draw_delete_feature_confirm(
     frame: &mut Frame,
     project_name: &str,
@@ -1407,7 +1518,13 @@ pub fn draw_fork_feature_dialog(
     crate::ui::draw_modal_overlay(frame, area, theme);
 
     let source_name = &state.source_branch;
-    let title = format!(" Fork Feature: {} ", source_name);
+    let title = match &state.checkpoint {
+        Some(checkpoint) => format!(
+            " Fork Feature: {} @ checkpoint #{} ",
+            source_name, checkpoint.number
+        ),
+        None => format!(" Fork Feature: {} ", source_name),
+    };
     let block = Block::default()
         .title(title)
         .borders(Borders::ALL)
@@ -1531,3 +1648,298 @@ pub fn draw_fork_feature_dialog(
     };
     frame.render_widget(hints, chunks[6]);
 }
+
+pub fn draw_land_feature_dialog(frame: &mut Frame, state: &LandFeatureState, theme: &Theme) {
+    let area = centered_rect(60, 50, frame.area());
+    crate::ui::draw_modal_overlay(frame, area, theme);
+
+    let preview = &state.preview;
+    let has_conflicts = !preview.conflicts.is_empty();
+    let border_color = if has_conflicts {
+        theme.danger.to_color()
+    } else {
+        theme.primary.to_color()
+    };
+    let block = Block::default()
+        .title(format!(" Land Feature: {} ", state.feature_name))
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.effective_bg()))
+        .border_style(Style::default().fg(border_color));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let muted = Style::default().fg(theme.text_muted.to_color());
+    let key = Style::default().fg(theme.warning.to_color());
+    let mut lines = vec![
+        Line::from(vec![
+            Span::styled(" Branch ", muted),
+            Span::styled(
+                preview.branch.as_str(),
+                Style::default()
+                    .fg(theme.project_title.to_color())
+                    .add_modifier(Modifier::BOLD),
+            ),
+            Span::styled("  into  ", muted),
+            Span::styled(
+                preview.target.as_str(),
+                Style::default()
+                    .fg(theme.primary.to_color())
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(Span::styled(
+            format!(" {} commit(s) ahead of {}", preview.ahead, preview.base_ref),
+            muted,
+        )),
+        Line::from(""),
+    ];
+
+    if let Some(landed) = &state.landed {
+        let short: String = landed.commit.chars().take(12).collect();
+        lines.push(Line::from(Span::styled(
+            format!(" Landed on {} at {}", landed.target, short),
+            Style::default()
+                .fg(theme.success.to_color())
+                .add_modifier(Modifier::BOLD),
+        )));
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled(" a", key),
+            Span::raw(" archive  "),
+            Span::styled("d", key),
+            Span::raw(" delete  "),
+            Span::styled("Esc", key),
+            Span::raw(" keep the feature"),
+        ]));
+    } else {
+        if has_conflicts {
+            lines.push(Line::from(Span::styled(
+                format!(
+                    " Trial merge conflicts in {} file(s):",
+                    preview.conflicts.len()
+                ),
+                Style::default()
+                    .fg(theme.danger.to_color())
+                    .add_modifier(Modifier::BOLD),
+            )));
+            for file in &preview.conflicts {
+                lines.push(Line::from(Span::styled(
+                    format!("   {}", file.path),
+                    Style::default().fg(theme.text.to_color()),
+                )));
+            }
+        } else {
+            lines.push(Line::from(Span::styled(
+                " Trial merge is clean",
+                Style::default().fg(theme.success.to_color()),
+            )));
+        }
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(" Strategy:", muted)));
+        for (i, strategy) in LandStrategy::ALL.iter().enumerate() {
+            let is_selected = i == state.selected;
+            let marker = if is_selected { ">" } else { " " };
+            let style = if is_selected {
+                Style::default()
+                    .fg(theme.primary.to_color())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                muted
+            };
+            lines.push(Line::from(Span::styled(
+                format!("   {} {}", marker, strategy.label()),
+                style,
+            )));
+        }
+        lines.push(Line::from(""));
+        let mut hints = vec![
+            Span::styled(" j/k", key),
+            Span::raw(" strategy  "),
+            Span::styled("Enter", key),
+            Span::raw(" land  "),
+        ];
+        if has_conflicts {
+            hints.push(Span::styled("v", key));
+            hints.push(Span::raw(" view conflicts  "));
+        }
+        hints.push(Span::styled("Esc", key));
+        hints.push(Span::raw(" cancel"));
+        lines.push(Line::from(hints));
+    }
+
+    frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+}
+
+pub fn draw_overlap_dialog(frame: &mut Frame, state: &OverlapDialogState, theme: &Theme) {
+    let area = centered_rect(60, 60, frame.area());
+    crate::ui::draw_modal_overlay(frame, area, theme);
+
+    let hunks_overlap = state
+        .entries
+        .iter()
+        .flat_map(|entry| &entry.files)
+        .any(|file| file.hunks_overlap());
+    let border_color = if hunks_overlap {
+        theme.danger.to_color()
+    } else {
+        theme.warning.to_color()
+    };
+    let block = Block::default()
+        .title(format!(" Overlapping Changes: {} ", state.feature_name))
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.effective_bg()))
+        .border_style(Style::default().fg(border_color));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let muted = Style::default().fg(theme.text_muted.to_color());
+    let key = Style::default().fg(theme.warning.to_color());
+    let mut lines = vec![
+        Line::from(Span::styled(
+            " Other features changing the same files:",
+            muted,
+        )),
+        Line::from(""),
+    ];
+    let mut selected_line = 0;
+    let mut index = 0;
+    for entry in &state.entries {
+        lines.push(Line::from(Span::styled(
+            format!(" {}", entry.feature_name),
+            Style::default()
+                .fg(theme.project_title.to_color())
+                .add_modifier(Modifier::BOLD),
+        )));
+        for file in &entry.files {
+            let is_selected = index == state.selected;
+            if is_selected {
+                selected_line = lines.len();
+            }
+            let marker = if is_selected { ">" } else { " " };
+            let path_style = if is_selected {
+                Style::default()
+                    .fg(theme.primary.to_color())
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(theme.text.to_color())
+            };
+            let detail = if file.hunks_overlap() {
+                let ranges: Vec<String> = file
+                    .ranges
+                    .iter()
+                    .map(|(start, end)| {
+                        if start == end {
+                            start.to_string()
+                        } else {
+                            format!("{}-{}", start, end)
+                        }
+                    })
+                    .collect();
+                Span::styled(
+                    format!("  lines {}", ranges.join(", ")),
+                    Style::default().fg(theme.danger.to_color()),
+                )
+            } else {
+                Span::styled("  different lines", muted)
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("   {} {}", marker, file.path), path_style),
+                detail,
+            ]));
+            index += 1;
+        }
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(" j/k", key),
+        Span::raw(" navigate  "),
+        Span::styled("Enter", key),
+        Span::raw(" side-by-side diff  "),
+        Span::styled("Esc", key),
+        Span::raw(" close"),
+    ]));
+
+    let height = inner.height as usize;
+    let scroll = (selected_line + 1).saturating_sub(height);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), inner);
+}
+
+pub fn draw_checkpoint_dialog(frame: &mut Frame, state: &CheckpointDialogState, theme: &Theme) {
+    let area = centered_rect(60, 60, frame.area());
+    crate::ui::draw_modal_overlay(frame, area, theme);
+
+    let block = Block::default()
+        .title(format!(" Checkpoints: {} ", state.feature_name))
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.effective_bg()))
+        .border_style(Style::default().fg(theme.primary.to_color()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let muted = Style::default().fg(theme.text_muted.to_color());
+    let key = Style::default().fg(theme.warning.to_color());
+    let mut lines = Vec::new();
+    if state.checkpoints.is_empty() {
+        lines.push(Line::from(Span::styled(
+            " No checkpoints yet. AMF takes one whenever the agent stops or a",
+            muted,
+        )));
+        lines.push(Line::from(Span::styled(
+            " prompt is submitted and the worktree changed.",
+            muted,
+        )));
+    } else {
+        lines.push(Line::from(Span::styled(
+            " Worktree snapshots, newest first:",
+            muted,
+        )));
+    }
+    lines.push(Line::from(""));
+
+    let now = chrono::Utc::now();
+    let mut selected_line = 0;
+    for (index, checkpoint) in state.checkpoints.iter().enumerate() {
+        let is_selected = index == state.selected;
+        if is_selected {
+            selected_line = lines.len();
+        }
+        let marker = if is_selected { ">" } else { " " };
+        let label_style = if is_selected {
+            Style::default()
+                .fg(theme.primary.to_color())
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(theme.text.to_color())
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(" {} #{:<4}", marker, checkpoint.number),
+                label_style,
+            ),
+            Span::styled(
+                format!(
+                    "{:>4} ago  ",
+                    crate::git_status::compact_age(checkpoint.created_at, now)
+                ),
+                muted,
+            ),
+            Span::styled(checkpoint.reason.clone(), label_style),
+            Span::styled(format!("  {}", checkpoint.short_commit()), muted),
+        ]));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(vec![
+        Span::styled(" Enter", key),
+        Span::raw(" changes since  "),
+        Span::styled("r", key),
+        Span::raw(" restore  "),
+        Span::styled("f", key),
+        Span::raw(" fork from it  "),
+        Span::styled("Esc", key),
+        Span::raw(" close"),
+    ]));
+
+    let height = inner.height as usize;
+    let scroll = (selected_line + 1).saturating_sub(height);
+    frame.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), inner);
+}
@@ -41,18 +41,26 @@ fn draw_help_at(frame: &mut Frame, area: Rect, scroll_offset: usize, theme: &The
         ("n", "Create new feature"),
         ("B", "Create batch features"),
         ("O", "Open AMF settings project"),
+        ("I", "Import worktrees / tmux sessions"),
         ("d", "Delete project/feature/session"),
         ("D", "View debug log"),
         ("P", "Open syntax parser picker"),
         ("T", "Theme picker"),
         ("c", "Start feature (create tmux)"),
         ("x", "Stop feature / remove session"),
+        ("X", "Archive / restore feature"),
         ("r", "Rename session/feature"),
+        ("t", "Edit feature tags"),
+        ("e", "Edit feature notes"),
         ("R", "Refresh statuses"),
         ("u", "Preferred harness / worktree config"),
         ("F", "Fork feature (new branch)"),
-        ("f", "Cycle session filter"),
+        ("U", "Restack features built on this one"),
+        ("f", "Cycle session/tag filter"),
         ("y", "Toggle mark feature as ready"),
+        ("L", "Land ready feature on its base"),
+        ("C", "Show overlapping changes"),
+        ("K", "Checkpoints: diff / restore / fork"),
         ("Z", "Generate session summary"),
         ("i", "Input requests picker"),
         ("/", "Search and jump to item"),
@@ -104,7 +112,7 @@ fn draw_help_at(frame: &mut Frame, area: Rect, scroll_offset: usize, theme: &The
         ("Ctrl+Q", "Exit view"),
         ("Ctrl+Space", "Open leader command menu"),
         ("s", "Steering coach"),
-        ("d", "Diff viewer"),
+        ("d", "Diff viewer (S: stage and commit)"),
         ("m", "Markdown file picker/viewer"),
         ("b", "Show/hide sidebar"),
         ("v", "Expand/collapse todos"),
@@ -16,10 +16,12 @@ pub use batch_creation::draw_create_batch_features_dialog;
 pub use browse::draw_browse_path_dialog;
 pub use harness::draw_harness_setup_dialog;
 pub use debug::draw_debug_log;
-pub use diff::draw_diff_viewer;
+pub use diff::{draw_commit_message_dialog, draw_diff_viewer};
 pub use feature::{
-    draw_confirm_supervibe_dialog, draw_create_feature_dialog, draw_delete_feature_confirm,
-    draw_deleting_feature_dialog, draw_fork_feature_dialog, draw_steering_prompt_dialog,
+    draw_checkpoint_dialog, draw_confirm_supervibe_dialog, draw_create_feature_dialog,
+    draw_delete_feature_confirm, draw_deleting_feature_dialog, draw_feature_notes_dialog,
+    draw_fork_feature_dialog, draw_land_feature_dialog, draw_overlap_dialog,
+    draw_steering_prompt_dialog,
 };
 pub use help::draw_help;
 pub use hooks::{
@@ -30,7 +32,7 @@ pub use markdown::draw_markdown_viewer;
 pub use project::{draw_create_project_dialog, draw_delete_project_confirm};
 pub use search::draw_search_dialog;
 pub use session::{
-    draw_project_agent_config_dialog, draw_rename_feature_dialog, draw_rename_session_dialog,
-    draw_session_config_dialog,
+    draw_feature_tags_dialog, draw_project_agent_config_dialog, draw_rename_feature_dialog,
+    draw_rename_session_dialog, draw_session_config_dialog,
 };
 pub use theme::draw_theme_picker;
@@ -7,7 +7,8 @@ use ratatui::{
 };
 
 use crate::app::{
-    ProjectAgentConfigState, RenameFeatureState, RenameSessionState, SessionConfigState,
+    EditFeatureTagsState, ProjectAgentConfigState, RenameFeatureState, RenameSessionState,
+    SessionConfigState,
 };
 use crate::theme::Theme;
 
@@ -65,6 +66,38 @@ pub fn draw_rename_feature_dialog(frame: &mut Frame, state: &RenameFeatureState,
     frame.render_widget(name_field, chunks[0]);
 }
 
+pub fn draw_feature_tags_dialog(frame: &mut Frame, state: &EditFeatureTagsState, theme: &Theme) {
+    let area = centered_rect(50, 25, frame.area());
+    crate::ui::draw_modal_overlay(frame, area, theme);
+
+    let block = Block::default()
+        .title(" Feature Tags ")
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.effective_bg()))
+        .border_style(Style::default().fg(theme.primary.to_color()));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(0)])
+        .split(inner);
+
+    let tags_field = Paragraph::new(Line::from(vec![
+        Span::styled(" Tags: ", Style::default().fg(theme.primary.to_color())),
+        Span::styled(&state.input, Style::default().fg(theme.text.to_color())),
+        Span::styled("\u{2588}", Style::default().fg(theme.primary.to_color())),
+    ]));
+    frame.render_widget(tags_field, chunks[0]);
+
+    let hint = Paragraph::new(Line::from(Span::styled(
+        " Separate tags with commas or spaces. Empty clears.",
+        Style::default().fg(theme.text_muted.to_color()),
+    )));
+    frame.render_widget(hint, chunks[1]);
+}
+
 pub fn draw_session_config_dialog(frame: &mut Frame, state: &SessionConfigState, theme: &Theme) {
     let header_lines = vec![
         Line::from(vec![
@@ -4,7 +4,7 @@ use chrono::{DateTime, Utc};
 use ratatui::{
     Frame,
     layout::Rect,
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, List, ListItem, Paragraph},
 };
@@ -28,6 +28,47 @@ fn format_age(dt: DateTime<Utc>) -> String {
     }
 }
 
+/// Compact git state: `*` uncommitted files, `↑`/`↓` commits ahead of and
+/// behind the base, `⇡` unpushed commits, then the last commit's age.
+fn git_summary_spans(
+    summary: &crate::git_status::GitSummary,
+    muted: Color,
+    theme: &Theme,
+) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    if summary.dirty > 0 {
+        spans.push(Span::styled(
+            format!(" *{}", summary.dirty),
+            Style::default().fg(theme.warning.to_color()),
+        ));
+    }
+    if summary.ahead > 0 {
+        spans.push(Span::styled(
+            format!(" ↑{}", summary.ahead),
+            Style::default().fg(theme.success.to_color()),
+        ));
+    }
+    if summary.behind > 0 {
+        spans.push(Span::styled(
+            format!(" ↓{}", summary.behind),
+            Style::default().fg(theme.danger.to_color()),
+        ));
+    }
+    if let Some(unpushed) = summary.unpushed.filter(|n| *n > 0) {
+        spans.push(Span::styled(
+            format!(" ⇡{}", unpushed),
+            Style::default().fg(theme.info.to_color()),
+        ));
+    }
+    if let Some(at) = summary.last_commit {
+        spans.push(Span::styled(
+            format!(" {}", crate::git_status::compact_age(at, Utc::now())),
+            Style::default().fg(muted),
+        ));
+    }
+    spans
+}
+
 pub fn rainbow_spans(text: &str, theme: &Theme) -> Vec<Span<'static>> {
     let colors = [
         theme.danger.to_color(),
@@ -49,6 +90,23 @@ pub fn rainbow_spans(text: &str, theme: &Theme) -> Vec<Span<'static>> {
         .collect()
 }
 
+/// Picks a stable badge color for `tag` so the same tag looks the same
+/// on every feature and across restarts.
+fn tag_color(tag: &str, theme: &Theme) -> Color {
+    let colors = [
+        theme.info.to_color(),
+        theme.success.to_color(),
+        theme.secondary.to_color(),
+        theme.warning.to_color(),
+        theme.primary.to_color(),
+        theme.danger.to_color(),
+    ];
+    let hash = tag.bytes().fold(0usize, |hash, b| {
+        hash.wrapping_mul(31).wrapping_add(b as usize)
+    });
+    colors[hash % colors.len()]
+}
+
 fn shorten_path(path: &Path) -> String {
     if let Some(home) = dirs::home_dir()
         && let Ok(rest) = path.strip_prefix(&home)
@@ -112,6 +170,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
             let absolute_idx = start + idx;
             let is_selected = match (&app.selection, item) {
                 (Selection::Project(a), VisibleItem::Project(b)) => a == b,
+                (Selection::ArchivedFeatures(a), VisibleItem::ArchivedFeatures(b)) => a == b,
                 (Selection::Feature(a1, a2), VisibleItem::Feature(b1, b2)) => a1 == b1 && a2 == b2,
                 (Selection::Session(a1, a2, a3), VisibleItem::Session(b1, b2, b3)) => {
                     a1 == b1 && a2 == b2 && a3 == b3
@@ -153,21 +212,46 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
 
                     Line::from(spans)
                 }
+                VisibleItem::ArchivedFeatures(pi) => {
+                    let project = &app.store.projects[*pi];
+                    let archived = project.features.iter().filter(|f| f.is_archived()).count();
+                    let collapse_icon = if app.is_archive_expanded(*pi) {
+                        "v"
+                    } else {
+                        ">"
+                    };
+                    let label_style = if is_selected {
+                        Style::default().fg(muted).add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(muted)
+                    };
+                    Line::from(vec![
+                        Span::styled("  └─", Style::default().fg(muted)),
+                        Span::styled(format!(" {} ", collapse_icon), Style::default().fg(muted)),
+                        Span::styled(format!("Archived ({})", archived), label_style),
+                    ])
+                }
                 VisibleItem::Feature(pi, fi) => {
                     let project = &app.store.projects[*pi];
                     let feature = &project.features[*fi];
                     let is_last_feature = !visible[absolute_idx + 1..].iter().any(|i| {
                         matches!(
                             i,
-                            VisibleItem::Feature(p, _)
+                            VisibleItem::Feature(p, _) | VisibleItem::ArchivedFeatures(p)
                                 if *p == *pi
                         )
                     });
 
-                    let connector = if is_last_feature {
-                        "  └─"
-                    } else {
-                        "  ├─"
+                    let connector = match (feature.is_archived(), is_last_feature) {
+                        (false, true) => "  └─",
+                        (false, false) => "  ├─",
+                        (true, true) => "     └─",
+                        (true, false) => "     ├─",
+                    };
+                    let stack_indent = match project.stack_depth(*fi) {
+                        _ if feature.is_archived() => String::new(),
+                        0 => String::new(),
+                        depth => format!("{} ↳", "  ".repeat(depth - 1)),
                     };
 
                     let is_waiting_for_input = app.is_feature_waiting_for_input(&feature.name);
@@ -309,6 +393,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                     let display_name = feature.nickname.as_ref().unwrap_or(&feature.name);
                     let mut line_spans = vec![
                         Span::styled(connector, Style::default().fg(muted)),
+                        Span::styled(stack_indent, Style::default().fg(muted)),
                         status_dot,
                         Span::styled(format!("{} ", collapse_icon), Style::default().fg(muted)),
                         Span::styled(display_name, name_style),
@@ -321,6 +406,27 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                                 .add_modifier(Modifier::BOLD),
                         ));
                     }
+                    if feature.is_multi_repo() {
+                        let labels: Vec<String> =
+                            feature.repos.iter().map(|repo| repo.label()).collect();
+                        line_spans.push(Span::styled(
+                            format!(" [{}]", labels.join("+")),
+                            Style::default().fg(theme.info.to_color()),
+                        ));
+                    }
+                    // Red when both features edit the same lines, yellow when
+                    // they only touch the same files.
+                    if let Some(hunks_overlap) = app.feature_overlap_kind(*pi, *fi) {
+                        let color = if hunks_overlap {
+                            theme.danger.to_color()
+                        } else {
+                            theme.warning.to_color()
+                        };
+                        line_spans.push(Span::styled(
+                            " [conflict]",
+                            Style::default().fg(color).add_modifier(Modifier::BOLD),
+                        ));
+                    }
                     if feature.nickname.is_some() {
                         line_spans.push(Span::styled(
                             format!(" ({})", feature.branch),
@@ -334,6 +440,15 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                             Style::default().fg(theme.mode_review.to_color()),
                         ));
                     }
+                    for tag in &feature.tags {
+                        line_spans.push(Span::raw(" "));
+                        line_spans.push(Span::styled(
+                            format!(" {} ", tag),
+                            Style::default()
+                                .fg(theme.effective_bg())
+                                .bg(tag_color(tag, &theme)),
+                        ));
+                    }
                     if is_being_deleted {
                         line_spans.push(Span::styled(
                             " [deleting...]",
@@ -363,6 +478,9 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                         Style::default().fg(theme.warning.to_color()),
                     ));
                     line_spans.push(Span::styled(badge, Style::default().fg(muted)));
+                    if let Some(summary) = app.git_summary(&feature.id) {
+                        line_spans.extend(git_summary_spans(summary, muted, &theme));
+                    }
                     if has_pending_input {
                         line_spans.push(Span::styled(
                             " ?",
@@ -389,7 +507,33 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                             Style::default().fg(theme.warning.to_color()),
                         ));
                     }
-                    Line::from(line_spans)
+                    let main_line = Line::from(line_spans);
+
+                    if let Some(headline) = feature.notes_headline() {
+                        let notes_vert = match (feature.is_archived(), is_last_feature) {
+                            (false, false) => "  │ ",
+                            (true, false) => "     │ ",
+                            (_, true) => "    ",
+                        };
+                        let notes_line = Line::from(vec![
+                            Span::styled(notes_vert, Style::default().fg(muted)),
+                            Span::raw("  ".repeat(project.stack_depth(*fi) + 1)),
+                            Span::styled(
+                                format!("✎ {}", headline),
+                                Style::default()
+                                    .fg(theme.text_muted.to_color())
+                                    .add_modifier(Modifier::ITALIC),
+                            ),
+                        ]);
+                        return if is_selected {
+                            ListItem::new(vec![main_line, notes_line])
+                                .style(Style::default().bg(theme.effective_selection_bg()))
+                        } else {
+                            ListItem::new(vec![main_line, notes_line])
+                        };
+                    }
+
+                    main_line
                 }
                 VisibleItem::Session(pi, fi, si) => {
                     let project = &app.store.projects[*pi];
@@ -399,7 +543,7 @@ pub fn draw(frame: &mut Frame, app: &mut App, area: Rect) {
                     let is_last_feature = !visible[absolute_idx + 1..].iter().any(|i| {
                         matches!(
                             i,
-                            VisibleItem::Feature(p, _)
+                            VisibleItem::Feature(p, _) | VisibleItem::ArchivedFeatures(p)
                                 if *p == *pi
                         )
                     });
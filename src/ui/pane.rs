@@ -45,6 +45,8 @@ pub(crate) struct AgentSidebarData {
     pub work_text: Option<String>,
     pub todos_text: Option<String>,
     pub summary_text: String,
+    pub git_text: Option<String>,
+    pub notes_text: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -408,6 +410,8 @@ fn draw_agent_sidebar(
         work_text: None,
         todos_text: None,
         summary_text: String::new(),
+        git_text: None,
+        notes_text: None,
     };
     let data = data.unwrap_or(&fallback);
     let sections_with_content = sidebar_sections(data, inner.width);
@@ -466,6 +470,14 @@ fn sidebar_sections<'a>(data: &'a AgentSidebarData, section_width: u16) -> Vec<S
         });
     }
 
+    if let Some(git_text) = data.git_text.as_deref() {
+        sections.push(SidebarSection {
+            title: "Git",
+            body: git_text,
+            constraint: Constraint::Length(sidebar_section_height(git_text, section_width, 2, 4)),
+        });
+    }
+
     let is_opencode = matches!(data.agent_kind, SessionKind::Opencode);
 
     if let Some(work_text) = data.work_text.as_deref() {
@@ -514,6 +526,13 @@ fn sidebar_sections<'a>(data: &'a AgentSidebarData, section_width: u16) -> Vec<S
             )),
         });
     }
+    if let Some(notes_text) = data.notes_text.as_deref() {
+        sections.push(SidebarSection {
+            title: "Notes",
+            body: notes_text,
+            constraint: Constraint::Length(sidebar_section_height(notes_text, section_width, 1, 8)),
+        });
+    }
 
     sections
 }
@@ -534,6 +553,8 @@ fn sidebar_section_color(title: &str, theme: &Theme) -> Color {
         "Work" => theme.primary.to_color(),
         "Todos" => theme.success.to_color(),
         "Summary" => theme.info.to_color(),
+        "Notes" => theme.text_muted.to_color(),
+        "Git" => theme.project_title.to_color(),
         _ => theme.border.to_color(),
     }
 }
@@ -997,6 +1018,8 @@ mod tests {
             work_text: Some("State: running tool\nTool: cargo test".into()),
             todos_text: None,
             summary_text: "Codex sidebar ready.".into(),
+            git_text: None,
+            notes_text: None,
         };
 
         let sections = sidebar_sections(&sidebar, 30);
@@ -1020,6 +1043,8 @@ mod tests {
             ),
             todos_text: None,
             summary_text: "Codex sidebar ready.".into(),
+            git_text: None,
+        notes_text: None,
         };
 
         let sections = sidebar_sections(&sidebar, 30);
@@ -1031,6 +1056,47 @@ mod tests {
         assert!(matches!(work.constraint, Constraint::Length(height) if height > 4));
     }
 
+    #[test]
+    fn notes_section_follows_other_sections_and_shows_full_text() {
+        let sidebar = AgentSidebarData {
+            agent_kind: crate::project::SessionKind::Claude,
+            status_text: "Idle".into(),
+            prompt_text: String::new(),
+            work_text: None,
+            todos_text: None,
+            summary_text: "Sidebar ready.".into(),
+            git_text: None,
+            notes_text: Some("Why: flaky login\nTODO: add tests\nTODO: docs".into()),
+        };
+
+        let sections = sidebar_sections(&sidebar, 30);
+        let notes = sections.last().unwrap();
+
+        assert_eq!(notes.title, "Notes");
+        assert_eq!(notes.body, "Why: flaky login\nTODO: add tests\nTODO: docs");
+        assert!(matches!(notes.constraint, Constraint::Length(5)));
+    }
+
+    #[test]
+    fn git_section_sits_under_status() {
+        let sidebar = AgentSidebarData {
+            agent_kind: crate::project::SessionKind::Claude,
+            status_text: "Idle".into(),
+            prompt_text: "Preview: Continue".into(),
+            work_text: None,
+            todos_text: None,
+            summary_text: String::new(),
+            git_text: Some("Clean\n1 ahead, 0 behind main\nNo upstream".into()),
+            notes_text: None,
+        };
+
+        let sections = sidebar_sections(&sidebar, 30);
+        let titles: Vec<&str> = sections.iter().map(|section| section.title).collect();
+
+        assert_eq!(titles, vec!["Status", "Git", "Prompt"]);
+        assert!(matches!(sections[1].constraint, Constraint::Length(5)));
+    }
+
     #[test]
     fn prompt_section_height_is_compact() {
         assert_eq!(prompt_section_height("Preview: Continue", 30), 3);
@@ -1064,6 +1130,8 @@ mod tests {
             work_text: None,
             todos_text: None,
             summary_text: "Sidebar ready.".into(),
+            git_text: None,
+            notes_text: None,
         };
 
         terminal
@@ -1104,6 +1172,8 @@ mod tests {
             work_text: None,
             todos_text: None,
             summary_text: "Codex sidebar ready.".into(),
+            git_text: None,
+            notes_text: None,
         };
 
         terminal
@@ -1142,6 +1212,8 @@ mod tests {
             work_text: Some("State: running tool\nTool: cargo test".into()),
             todos_text: None,
             summary_text: "Codex sidebar ready.".into(),
+            git_text: None,
+            notes_text: None,
         };
 
         terminal
@@ -1199,6 +1271,8 @@ mod tests {
             work_text: Some("State: running tool\nTool: cargo test".into()),
             todos_text: None,
             summary_text: "Codex sidebar ready.".into(),
+            git_text: None,
+            notes_text: None,
         };
 
         terminal
@@ -1240,6 +1314,8 @@ mod tests {
             work_text: Some("State: running tool\nTool: cargo test".into()),
             todos_text: None,
             summary_text: "Codex sidebar ready.".into(),
+            git_text: None,
+            notes_text: None,
         };
 
         terminal
@@ -1285,6 +1361,8 @@ mod tests {
             ),
             todos_text: None,
             summary_text: "Small summary.".into(),
+            git_text: None,
+        notes_text: None,
         };
 
         terminal
@@ -1323,6 +1401,8 @@ mod tests {
             work_text: Some("State: waiting for input\nRequest: Need approval.".into()),
             todos_text: None,
             summary_text: "Codex sidebar ready.".into(),
+            git_text: None,
+            notes_text: None,
         };
 
         terminal
@@ -1361,6 +1441,8 @@ mod tests {
             work_text: Some("State: waiting for input\nRequest: Need approval.".into()),
             todos_text: None,
             summary_text: String::new(),
+            git_text: None,
+            notes_text: None,
         };
 
         terminal
@@ -1399,6 +1481,8 @@ mod tests {
             work_text: Some("State: waiting for input\nRequest: Need approval.".into()),
             todos_text: None,
             summary_text: "Codex sidebar ready.".into(),
+            git_text: None,
+            notes_text: None,
         };
 
         terminal
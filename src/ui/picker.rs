@@ -9,8 +9,9 @@ use ratatui::{
 
 use crate::app::{
     BookmarkPickerState, ClaudeSessionPickerState, CodexSessionPickerState, CommandAction,
-    CommandPickerState, MarkdownFilePickerState, OpencodeSessionPickerState, PendingInput,
-    SessionPickerState, SessionSwitcherState, SyntaxLanguagePickerState, SyntaxOperationAction,
+    CommandPickerState, ImportPickerState, MarkdownFilePickerState, OpencodeSessionPickerState,
+    PendingInput, SessionPickerState, SessionSwitcherState, SyntaxLanguagePickerState,
+    SyntaxOperationAction,
 };
 use crate::project::SessionKind;
 use crate::theme::Theme;
@@ -733,6 +734,103 @@ pub fn draw_bookmark_picker(
     frame.render_widget(hints, chunks[1]);
 }
 
+pub fn draw_import_picker(frame: &mut Frame, state: &ImportPickerState, theme: &Theme) {
+    let area = centered_rect(70, 50, frame.area());
+    crate::ui::draw_modal_overlay(frame, area, theme);
+
+    let title = format!(
+        " Import into {} ({}) ",
+        state.project_name,
+        state.candidates.len()
+    );
+    let block = Block::default()
+        .title(title)
+        .borders(Borders::ALL)
+        .style(Style::default().bg(theme.effective_bg()))
+        .border_style(Style::default().fg(theme.primary.to_color()));
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(2)])
+        .split(inner);
+
+    let items: Vec<ListItem> = state
+        .candidates
+        .iter()
+        .enumerate()
+        .map(|(i, candidate)| {
+            let is_selected = i == state.selected;
+            let checked = state.checked.get(i).copied().unwrap_or(false);
+            let source = match &candidate.tmux_session {
+                Some(session) => {
+                    let windows: Vec<&str> = candidate
+                        .windows
+                        .iter()
+                        .map(|(window, _)| window.as_str())
+                        .collect();
+                    format!("tmux {}: {}", session, windows.join(", "))
+                }
+                None => format!("worktree {}", candidate.workdir.display()),
+            };
+            let line = Line::from(vec![
+                Span::styled(
+                    if is_selected { "  > " } else { "    " },
+                    Style::default().fg(theme.warning.to_color()),
+                ),
+                Span::styled(
+                    if checked { "[x] " } else { "[ ] " },
+                    Style::default().fg(theme.success.to_color()),
+                ),
+                Span::styled(
+                    candidate.name.clone(),
+                    if is_selected {
+                        Style::default()
+                            .fg(theme.text.to_color())
+                            .add_modifier(Modifier::BOLD)
+                    } else {
+                        Style::default().fg(theme.text.to_color())
+                    },
+                ),
+                Span::styled(
+                    format!("  {}", source),
+                    Style::default().fg(theme.text_muted.to_color()),
+                ),
+            ]);
+
+            if is_selected {
+                ListItem::new(line).style(Style::default().bg(theme.effective_selection_bg()))
+            } else {
+                ListItem::new(line)
+            }
+        })
+        .collect();
+
+    frame.render_widget(List::new(items), chunks[0]);
+
+    let hints = Paragraph::new(Line::from(vec![
+        Span::styled(
+            "  j/k or \u{2191}/\u{2193}",
+            Style::default().fg(theme.warning.to_color()),
+        ),
+        Span::styled(
+            " navigate  ",
+            Style::default().fg(theme.text_muted.to_color()),
+        ),
+        Span::styled("Space", Style::default().fg(theme.warning.to_color())),
+        Span::styled(" check  ", Style::default().fg(theme.text_muted.to_color())),
+        Span::styled("Enter", Style::default().fg(theme.warning.to_color())),
+        Span::styled(
+            " import  ",
+            Style::default().fg(theme.text_muted.to_color()),
+        ),
+        Span::styled("Esc", Style::default().fg(theme.warning.to_color())),
+        Span::styled(" cancel", Style::default().fg(theme.text_muted.to_color())),
+    ]));
+    frame.render_widget(hints, chunks[1]);
+}
+
 pub fn draw_session_switcher(
     frame: &mut Frame,
     state: &SessionSwitcherState,
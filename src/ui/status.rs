@@ -89,6 +89,10 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
                 Selection::Feature(pi, fi) => app.feature_is_pending_worktree_script(pi, fi),
                 _ => false,
             };
+            let has_overlaps = match app.selection {
+                Selection::Feature(pi, fi) => app.feature_overlap_kind(pi, fi).is_some(),
+                _ => false,
+            };
             if on_session {
                 let mut spans = filter_spans;
                 spans.extend(vec![
@@ -140,6 +144,8 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
                     Span::raw(" stop  "),
                     Span::styled("y", key_style()),
                     Span::raw(" ready  "),
+                    Span::styled("L", key_style()),
+                    Span::raw(" land  "),
                     Span::styled("f", key_style()),
                     Span::raw(" filter  "),
                     Span::styled("s", key_style()),
@@ -151,6 +157,10 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
                     Span::styled("d", key_style()),
                     Span::raw(" delete  "),
                 ]);
+                if has_overlaps {
+                    spans.push(Span::styled("C", key_style()));
+                    spans.push(Span::raw(" conflicts  "));
+                }
                 if !app.active_extension.custom_sessions.is_empty() {
                     spans.push(Span::styled("p", key_style()));
                     spans.push(Span::raw(" sessions  "));
@@ -207,11 +217,39 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
             Span::styled("Ctrl+Q", key_style()),
             Span::raw(" close"),
         ]),
+        AppMode::EditingFeatureNotes(state) => Line::from(vec![
+            Span::styled("Tab", key_style()),
+            Span::raw(" save  "),
+            Span::styled(
+                if matches!(state.editor.vim_mode(), Some(VimMode::Normal)) {
+                    "i / a / o"
+                } else {
+                    "Esc"
+                },
+                key_style(),
+            ),
+            Span::raw(
+                if matches!(state.editor.vim_mode(), Some(VimMode::Normal)) {
+                    " edit  "
+                } else {
+                    " normal  "
+                },
+            ),
+            Span::styled("Ctrl+V", key_style()),
+            Span::raw(if state.editor.vim_mode().is_some() {
+                " vim off  "
+            } else {
+                " vim on  "
+            }),
+            Span::styled("Ctrl+Q", key_style()),
+            Span::raw(" close"),
+        ]),
         AppMode::CreatingProject(_)
         | AppMode::CreatingFeature(_)
         | AppMode::CreatingBatchFeatures(_)
         | AppMode::RenamingSession(_)
         | AppMode::RenamingFeature(_)
+        | AppMode::EditingFeatureTags(_)
         | AppMode::BrowsingPath(_) => Line::from(vec![
             Span::styled("Enter", key_style()),
             Span::raw(" confirm  "),
@@ -253,6 +291,16 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
             Span::styled("Esc", key_style()),
             Span::raw(" cancel"),
         ]),
+        AppMode::ImportPicker(_) => Line::from(vec![
+            Span::styled("j/k or \u{2191}/\u{2193}", key_style()),
+            Span::raw(" navigate  "),
+            Span::styled("Space", key_style()),
+            Span::raw(" check  "),
+            Span::styled("Enter", key_style()),
+            Span::raw(" import  "),
+            Span::styled("Esc", key_style()),
+            Span::raw(" cancel"),
+        ]),
         AppMode::ConfirmingOpencodeSession { .. }
         | AppMode::ConfirmingClaudeSession { .. }
         | AppMode::ConfirmingCodexSession { .. } => Line::from(vec![
@@ -385,6 +433,28 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
             ]);
             Line::from(spans)
         }
+        AppMode::DiffViewer(state) if state.staging.is_some() => Line::from(vec![
+            Span::styled(" Space", key_style()),
+            Span::raw(" pick  "),
+            Span::styled("Enter", key_style()),
+            Span::raw(" stage/unstage  "),
+            Span::styled("t", key_style()),
+            Span::raw(" staged/unstaged  "),
+            Span::styled("c", key_style()),
+            Span::raw(" commit  "),
+            Span::styled("S", key_style()),
+            Span::raw(" branch diff  "),
+            Span::styled("Esc", key_style()),
+            Span::raw(" close"),
+        ]),
+        AppMode::CommitMessage(_) => Line::from(vec![
+            Span::styled(" Tab", key_style()),
+            Span::raw(" commit  "),
+            Span::styled("Ctrl+D", key_style()),
+            Span::raw(" draft  "),
+            Span::styled("Ctrl+Q", key_style()),
+            Span::raw(" back"),
+        ]),
         AppMode::DiffViewer(_) => {
             let mut spans = vec![Span::styled(" Tab", key_style()), Span::raw(" focus  ")];
             if app.diff_viewer_selected_file_is_new() {
@@ -430,6 +500,44 @@ pub fn draw(frame: &mut Frame, app: &App, area: Rect) {
             Span::styled("Esc", key_style()),
             Span::raw(" cancel"),
         ]),
+        AppMode::LandingFeature(state) if state.landed.is_some() => Line::from(vec![
+            Span::styled(" a", key_style()),
+            Span::raw(" archive  "),
+            Span::styled("d", key_style()),
+            Span::raw(" delete  "),
+            Span::styled("Esc", key_style()),
+            Span::raw(" keep"),
+        ]),
+        AppMode::LandingFeature(_) => Line::from(vec![
+            Span::styled(" j/k", key_style()),
+            Span::raw(" strategy  "),
+            Span::styled("Enter", key_style()),
+            Span::raw(" land  "),
+            Span::styled("v", key_style()),
+            Span::raw(" conflicts  "),
+            Span::styled("Esc", key_style()),
+            Span::raw(" cancel"),
+        ]),
+        AppMode::ReviewingOverlaps(_) => Line::from(vec![
+            Span::styled(" j/k", key_style()),
+            Span::raw(" navigate  "),
+            Span::styled("Enter", key_style()),
+            Span::raw(" side-by-side diff  "),
+            Span::styled("Esc", key_style()),
+            Span::raw(" close"),
+        ]),
+        AppMode::ReviewingCheckpoints(_) => Line::from(vec![
+            Span::styled(" j/k", key_style()),
+            Span::raw(" navigate  "),
+            Span::styled("Enter", key_style()),
+            Span::raw(" diff  "),
+            Span::styled("r", key_style()),
+            Span::raw(" restore  "),
+            Span::styled("f", key_style()),
+            Span::raw(" fork  "),
+            Span::styled("Esc", key_style()),
+            Span::raw(" close"),
+        ]),
         AppMode::ThemePicker(_) => Line::from(vec![
             Span::styled(" j/k", key_style()),
             Span::raw(" navigate  "),
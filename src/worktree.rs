@@ -6,6 +6,10 @@ use std::sync::mpsc::{self, Receiver};
 
 use crate::traits::WorktreeOps;
 
+/// Directories AMF writes into worktrees (prompts, notifications, plugin
+/// state). Their untracked contents are not the user's work.
+const AMF_WORKTREE_DIRS: &[&str] = &[".claude", ".amf", ".opencode", ".codex"];
+
 pub struct WorktreeManager;
 
 pub struct SpawnedWorktreeCommand {
@@ -87,10 +91,16 @@ impl WorktreeManager {
 
     /// Create a new worktree for a branch
     pub fn create(repo: &Path, name: &str, branch: &str) -> Result<PathBuf> {
-        let worktree_dir = repo.join(".worktrees");
-        std::fs::create_dir_all(&worktree_dir)?;
+        Self::create_at(repo, &repo.join(".worktrees").join(name), branch)
+    }
 
-        let worktree_path = worktree_dir.join(name);
+    /// Create a worktree of `repo` for `branch` at an arbitrary path,
+    /// creating the branch when it does not exist yet.
+    pub fn create_at(repo: &Path, worktree_path: &Path, branch: &str) -> Result<PathBuf> {
+        if let Some(parent) = worktree_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let worktree_path = worktree_path.to_path_buf();
 
         if worktree_path.exists() {
             bail!("Worktree path already exists: {}", worktree_path.display());
@@ -242,6 +252,30 @@ impl WorktreeManager {
         Ok(worktree_path)
     }
 
+    /// Rebase the branch checked out in `workdir` onto `onto`.
+    /// Runs `git rebase --fork-point --autostash {onto}`, so commits `onto`
+    /// carried before it was rewritten are dropped rather than replayed.
+    /// A rebase that stops on conflicts is aborted, leaving the worktree
+    /// as it was.
+    pub fn rebase(workdir: &Path, onto: &str) -> Result<()> {
+        let output = Command::new("git")
+            .args(["rebase", "--fork-point", "--autostash", onto])
+            .current_dir(workdir)
+            .output()
+            .context("Failed to run git rebase")?;
+
+        if !output.status.success() {
+            let _ = Command::new("git")
+                .args(["rebase", "--abort"])
+                .current_dir(workdir)
+                .output();
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("git rebase onto '{}' failed: {}", onto, stderr.trim());
+        }
+
+        Ok(())
+    }
+
     /// Copy uncommitted changes from source worktree to destination worktree
     pub fn copy_uncommitted_changes(source: &Path, dest: &Path) -> Result<()> {
         let output = Command::new("git")
@@ -291,6 +325,49 @@ impl WorktreeManager {
         Ok(())
     }
 
+    /// Whether tracked files in `path` have uncommitted changes. Untracked
+    /// files are ignored since AMF itself drops some into every worktree.
+    pub fn has_tracked_changes(path: &Path) -> Result<bool> {
+        let output = Command::new("git")
+            .args(["status", "--porcelain", "--untracked-files=no"])
+            .current_dir(path)
+            .output()
+            .context("Failed to get git status")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("git status failed: {}", stderr.trim());
+        }
+
+        Ok(!output.stdout.is_empty())
+    }
+
+    /// Untracked, non-ignored files in `path`, leaving out the directories
+    /// AMF itself writes into every worktree.
+    pub fn untracked_files(path: &Path) -> Result<Vec<String>> {
+        let output = Command::new("git")
+            .args(["ls-files", "--others", "--exclude-standard", "-z"])
+            .current_dir(path)
+            .output()
+            .context("Failed to list untracked files")?;
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            bail!("git ls-files failed: {}", stderr.trim());
+        }
+
+        Ok(String::from_utf8_lossy(&output.stdout)
+            .split('\0')
+            .filter(|file| !file.is_empty())
+            .filter(|file| {
+                !AMF_WORKTREE_DIRS
+                    .iter()
+                    .any(|dir| file.starts_with(&format!("{dir}/")))
+            })
+            .map(str::to_string)
+            .collect())
+    }
+
     /// Remove a worktree
     pub fn remove(repo: &Path, worktree_path: &Path) -> Result<()> {
         let output = Command::new("git")
@@ -426,6 +503,10 @@ impl WorktreeOps for WorktreeManager {
         WorktreeManager::create(repo, name, branch)
     }
 
+    fn create_at(&self, repo: &Path, path: &Path, branch: &str) -> Result<PathBuf> {
+        WorktreeManager::create_at(repo, path, branch)
+    }
+
     fn create_from(
         &self,
         repo: &Path,
@@ -435,6 +516,18 @@ impl WorktreeOps for WorktreeManager {
     ) -> Result<PathBuf> {
         WorktreeManager::create_from(repo, name, new_branch, base)
     }
+
+    fn list(&self, repo: &Path) -> Result<Vec<WorktreeInfo>> {
+        WorktreeManager::list(repo)
+    }
+
+    fn rebase(&self, workdir: &Path, onto: &str) -> Result<()> {
+        WorktreeManager::rebase(workdir, onto)
+    }
+
+    fn remove(&self, repo: &Path, worktree_path: &Path) -> Result<()> {
+        WorktreeManager::remove(repo, worktree_path)
+    }
 }
 
 #[cfg(test)]